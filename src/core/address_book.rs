@@ -0,0 +1,117 @@
+use web3::types::Address;
+
+use super::{csv, eth_chain::EthChain, eth_utils};
+
+const CSV_HEADER: [&str; 5] = ["name", "address", "ens", "chains", "notes"];
+const CSV_CHAINS_SEPARATOR: char = ';';
+
+const ERR_EMPTY_CONTACT_NAME: &str = "Contact name can't be empty";
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Contact {
+    pub name: String,
+    pub address: Address,
+    #[serde(default)]
+    pub ens: Option<String>,
+    #[serde(default)]
+    pub chains: Vec<EthChain>, // Any chain if empty
+    #[serde(default)]
+    pub notes: String,
+}
+
+impl Contact {
+    pub fn new(name: &str, address: Address) -> Self {
+        Self {
+            name: name.to_string(),
+            address,
+            ens: None,
+            chains: Vec::new(),
+            notes: String::new(),
+        }
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(anyhow::anyhow!(ERR_EMPTY_CONTACT_NAME));
+        }
+        Ok(())
+    }
+
+    pub fn is_allowed_on(&self, chain: EthChain) -> bool {
+        self.chains.is_empty() || self.chains.contains(&chain)
+    }
+
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        self.name.to_lowercase().contains(&query)
+            || self.ens.as_ref().is_some_and(|ens| ens.to_lowercase().contains(&query))
+            || format!("{:?}", self.address).contains(&query)
+    }
+}
+
+pub fn search<'a>(contacts: &'a [Contact], query: &str) -> Vec<&'a Contact> {
+    contacts.iter().filter(|contact| contact.matches(query)).collect()
+}
+
+// Contact typed as exact name or ENS name
+pub fn resolve<'a>(contacts: &'a [Contact], name: &str) -> Option<&'a Contact> {
+    let name = name.trim();
+    contacts.iter().find(|contact| {
+        contact.name.eq_ignore_ascii_case(name) || contact.ens.as_ref().is_some_and(|ens| ens.eq_ignore_ascii_case(name))
+    })
+}
+
+pub fn to_csv(contacts: &[Contact]) -> String {
+    let mut text = csv::write_row(&CSV_HEADER);
+    for contact in contacts {
+        let chains = contact.chains.iter()
+            .map(|chain| chain.get_chain_id().to_string())
+            .collect::<Vec<_>>()
+            .join(&CSV_CHAINS_SEPARATOR.to_string());
+        text += &csv::write_row(&[
+            contact.name.clone(),
+            format!("{:?}", contact.address),
+            contact.ens.clone().unwrap_or_default(),
+            chains,
+            contact.notes.clone(),
+        ]);
+    }
+    text
+}
+
+pub fn from_csv(text: &str) -> anyhow::Result<Vec<Contact>> {
+    let mut contacts = Vec::new();
+    for (index, row) in csv::parse(text)?.into_iter().enumerate() {
+        if index == 0 && row.first().is_some_and(|field| field == CSV_HEADER[0]) {
+            continue;
+        }
+
+        let field = |column: usize| row.get(column).map(|field| field.trim()).unwrap_or_default();
+        let address = eth_utils::str_to_eth_address(field(1))
+            .map_err(|err| anyhow::anyhow!("Row {}: {}", index + 1, err))?;
+        let chains = field(3).split(CSV_CHAINS_SEPARATOR)
+            .filter(|chain_id| !chain_id.trim().is_empty())
+            .map(|chain_id| chain_id.trim().parse::<u64>().ok().and_then(EthChain::from_chain_id)
+                .ok_or_else(|| anyhow::anyhow!("Row {}: unknown chain id {}", index + 1, chain_id)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let contact = Contact {
+            name: field(0).to_string(),
+            address,
+            ens: Some(field(2).to_string()).filter(|ens| !ens.is_empty()),
+            chains,
+            notes: field(4).to_string(),
+        };
+        contact.validate().map_err(|err| anyhow::anyhow!("Row {}: {}", index + 1, err))?;
+        contacts.push(contact);
+    }
+    Ok(contacts)
+}
+
+pub fn from_json(text: &str) -> anyhow::Result<Vec<Contact>> {
+    let contacts: Vec<Contact> = serde_json::from_str(text)?;
+    for contact in &contacts {
+        contact.validate()?;
+    }
+    Ok(contacts)
+}
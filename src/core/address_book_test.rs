@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::Address;
+    use crate::core::{address_book::{self, Contact}, eth_chain::EthChain};
+
+    fn contacts() -> Vec<Contact> {
+        vec![
+            Contact {
+                ens: Some("alice.eth".to_string()),
+                chains: vec![EthChain::EthereumMainnet, EthChain::OptimismMainnet],
+                notes: "Rent, monthly".to_string(),
+                ..Contact::new("Alice", Address::from_low_u64_be(0xa11ce))
+            },
+            Contact::new("Bob", Address::from_low_u64_be(0xb0b)),
+        ]
+    }
+
+    #[test_case("ali", &["Alice"]; "name")]
+    #[test_case("ALICE.ETH", &["Alice"]; "ens")]
+    #[test_case("b0b", &["Bob"]; "address")]
+    #[test_case("", &["Alice", "Bob"]; "empty")]
+    #[test_case("carol", &[]; "nothing")]
+    fn test_search(query: &str, expected: &[&str]) {
+        let contacts = contacts();
+        let found = address_book::search(&contacts, query).iter().map(|contact| contact.name.clone()).collect::<Vec<_>>();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_resolve_and_chains() {
+        let contacts = contacts();
+
+        let alice = address_book::resolve(&contacts, "alice.eth").unwrap();
+        assert_eq!(alice.name, "Alice");
+        assert!(alice.is_allowed_on(EthChain::OptimismMainnet));
+        assert!(!alice.is_allowed_on(EthChain::ArbitrumMainnet));
+
+        let bob = address_book::resolve(&contacts, " bob ").unwrap();
+        assert!(bob.is_allowed_on(EthChain::ArbitrumMainnet));
+
+        assert!(address_book::resolve(&contacts, "bo").is_none());
+    }
+
+    #[test]
+    fn test_csv_roundtrip() -> anyhow::Result<()> {
+        let csv = address_book::to_csv(&contacts());
+        assert!(csv.starts_with("name,address,ens,chains,notes\n"));
+        assert!(csv.contains(",1;10,\"Rent, monthly\"\n"));
+
+        assert_eq!(address_book::from_csv(&csv)?, contacts());
+        Ok(())
+    }
+
+    #[test_case("Carol,0x123,,,"; "invalid address")]
+    #[test_case(",0x0000000000000000000000000000000000000001,,,"; "empty name")]
+    #[test_case("Carol,0x0000000000000000000000000000000000000001,,56,"; "unknown chain")]
+    fn test_csv_errors(csv: &str) {
+        assert!(address_book::from_csv(csv).is_err());
+    }
+
+    #[test]
+    fn test_json_roundtrip() -> anyhow::Result<()> {
+        let json = serde_json::to_string(&contacts())?;
+        assert_eq!(address_book::from_json(&json)?, contacts());
+
+        // Optional fields may be omitted
+        let json = r#"[{"name": "Dan", "address": "0x0000000000000000000000000000000000000001"}]"#;
+        assert_eq!(address_book::from_json(json)?, vec![Contact::new("Dan", Address::from_low_u64_be(1))]);
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use web3::types::{Address, U256};
+
+use super::{amount::{Amount, ETH_DECIMALS}, erc20::{self, TokenApproval}, eth_chain::EthChain, transaction::TransactionRequest};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Allowance {
+    pub chain: EthChain,
+    pub currency: String,
+    pub contract_address: Address,
+    pub spender: Address,
+    pub amount: U256,
+    pub decimals: u16,
+}
+
+impl Allowance {
+    // NOTE: dapps approve the max value or something close to it, anything beyond 128 bits never runs out
+    pub fn is_unlimited(&self) -> bool {
+        self.amount > U256::from(u128::MAX)
+    }
+
+    pub fn amount_str(&self) -> String {
+        if self.is_unlimited() {
+            return "Unlimited".to_string();
+        }
+        format!("{} {}", Amount::new(self.amount, self.decimals), self.currency)
+    }
+
+    // Revoking is approving zero, sent to the token contract without any ETH
+    pub fn revoke_request(&self, owner: Address) -> anyhow::Result<TransactionRequest> {
+        Ok(TransactionRequest {
+            from: owner,
+            to: self.contract_address,
+            amount: Amount::zero(ETH_DECIMALS),
+            currency: "ETH".to_string(),
+            chain: self.chain,
+            fees: None,
+            data: Some(erc20::encode_approve(self.spender, U256::zero())?),
+        })
+    }
+}
+
+// Token and spender pairs ever approved, allowances have to be queried since transfers spend them silently
+pub fn approved_pairs(approvals: &[TokenApproval]) -> Vec<(Address, Address)> {
+    let mut pairs = Vec::new();
+    for approval in approvals {
+        let pair = (approval.contract_address, approval.spender);
+        if !pairs.contains(&pair) {
+            pairs.push(pair);
+        }
+    }
+    pairs
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{Address, U256};
+    use crate::core::{allowance::{self, Allowance}, erc20::{self, TokenApproval}, eth_chain::EthChain};
+
+    fn allowance(amount: U256) -> Allowance {
+        Allowance {
+            chain: EthChain::EthereumMainnet,
+            currency: "USDC".to_string(),
+            contract_address: Address::from_low_u64_be(0xc0),
+            spender: Address::from_low_u64_be(0x5e11),
+            amount,
+            decimals: 6,
+        }
+    }
+
+    #[test_case(U256::MAX, true, "Unlimited"; "max")]
+    #[test_case(U256::MAX >> 1, true, "Unlimited"; "half max")]
+    #[test_case(U256::from(u128::MAX), false, "340282366920938463463374607431768.211455 USDC"; "u128 max")]
+    #[test_case(U256::from(1_500_000), false, "1.5 USDC"; "limited")]
+    fn test_allowance_amount(amount: U256, unlimited: bool, expected: &str) {
+        let allowance = allowance(amount);
+        assert_eq!(allowance.is_unlimited(), unlimited);
+        assert_eq!(allowance.amount_str(), expected);
+    }
+
+    #[test]
+    fn test_revoke_request() -> anyhow::Result<()> {
+        let owner = Address::from_low_u64_be(1);
+        let allowance = allowance(U256::MAX);
+
+        let request = allowance.revoke_request(owner)?;
+        assert_eq!(request.from, owner);
+        assert_eq!(request.to, allowance.contract_address);
+        assert!(request.amount.is_zero());
+        assert_eq!(request.chain, EthChain::EthereumMainnet);
+        assert_eq!(request.data, Some(erc20::encode_approve(allowance.spender, U256::zero())?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_approved_pairs() {
+        let approval = |contract: u64, spender: u64| TokenApproval {
+            contract_address: Address::from_low_u64_be(contract),
+            owner: Address::from_low_u64_be(1),
+            spender: Address::from_low_u64_be(spender),
+            value: U256::one(),
+            block_number: None,
+        };
+        let approvals = vec![approval(10, 20), approval(10, 21), approval(10, 20), approval(11, 20)];
+
+        assert_eq!(allowance::approved_pairs(&approvals), vec![
+            (Address::from_low_u64_be(10), Address::from_low_u64_be(20)),
+            (Address::from_low_u64_be(10), Address::from_low_u64_be(21)),
+            (Address::from_low_u64_be(11), Address::from_low_u64_be(20)),
+        ]);
+    }
+}
@@ -0,0 +1,179 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+use web3::types::U256;
+
+pub const ETH_DECIMALS: u16 = 18;
+pub const GWEI_DECIMALS: u16 = 9;
+pub const USD_DECIMALS: u16 = 2;
+
+const ERR_INVALID_AMOUNT: &str = "Invalid amount";
+const ERR_AMOUNT_OVERFLOW: &str = "Amount is too large";
+
+// Exact token amount, `raw` is the on-chain integer value with `decimals` digits after the point
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "AmountRepr")]
+pub struct Amount {
+    pub raw: U256,
+    pub decimals: u16,
+}
+
+// NOTE: records written before the exact amounts stored plain f64 numbers
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum AmountRepr {
+    Exact { raw: U256, decimals: u16 },
+    Legacy(f64),
+}
+
+impl TryFrom<AmountRepr> for Amount {
+    type Error = String;
+
+    fn try_from(repr: AmountRepr) -> Result<Self, Self::Error> {
+        match repr {
+            AmountRepr::Exact { raw, decimals } => Ok(Amount::new(raw, decimals)),
+            AmountRepr::Legacy(value) => Amount::from_f64(value, ETH_DECIMALS).map_err(|err| err.to_string()),
+        }
+    }
+}
+
+impl Amount {
+    pub fn new(raw: U256, decimals: u16) -> Self {
+        Self { raw, decimals }
+    }
+
+    pub fn zero(decimals: u16) -> Self {
+        Self::new(U256::zero(), decimals)
+    }
+
+    pub fn from_wei(wei: U256) -> Self {
+        Self::new(wei, ETH_DECIMALS)
+    }
+
+    // User input, must fit into `decimals` without losing digits
+    pub fn parse(text: &str, decimals: u16) -> anyhow::Result<Self> {
+        text.parse::<Amount>()?.to_decimals(decimals).map(|raw| Self::new(raw, decimals))
+    }
+
+    // Uses the shortest representation of the float, digits beyond `decimals` are cut
+    pub fn from_f64(value: f64, decimals: u16) -> anyhow::Result<Self> {
+        if !value.is_finite() || value < 0.0 {
+            return Err(anyhow::anyhow!(ERR_INVALID_AMOUNT));
+        }
+        let amount = value.to_string().parse::<Amount>()?;
+        amount.rescale(decimals).ok_or_else(|| anyhow::anyhow!(ERR_AMOUNT_OVERFLOW))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.raw.is_zero()
+    }
+
+    // Raw value with other decimals, fails instead of dropping digits
+    pub fn to_decimals(self, decimals: u16) -> anyhow::Result<U256> {
+        let rescaled = self.rescale(decimals).ok_or_else(|| anyhow::anyhow!(ERR_AMOUNT_OVERFLOW))?;
+        if rescaled != self {
+            return Err(anyhow::anyhow!("Amount has more than {} decimals", decimals));
+        }
+        Ok(rescaled.raw)
+    }
+
+    // Truncates when the decimals go down, None on overflow
+    pub fn rescale(&self, decimals: u16) -> Option<Self> {
+        let raw = match decimals.cmp(&self.decimals) {
+            Ordering::Equal => self.raw,
+            Ordering::Greater => self.raw.checked_mul(pow10(decimals - self.decimals)?)?,
+            Ordering::Less => pow10(self.decimals - decimals).map_or(U256::zero(), |divisor| self.raw / divisor),
+        };
+        Some(Self::new(raw, decimals))
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let decimals = self.decimals.max(other.decimals);
+        let raw = self.rescale(decimals)?.raw.checked_add(other.rescale(decimals)?.raw)?;
+        Some(Self::new(raw, decimals))
+    }
+
+    // For prices and charts only, never for values that get signed
+    pub fn to_f64(self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::MAX)
+    }
+
+    fn split(&self) -> (U256, String) {
+        if self.decimals == 0 {
+            return (self.raw, String::new());
+        }
+        match pow10(self.decimals) {
+            Some(divisor) => (self.raw / divisor, format!("{:0>width$}", (self.raw % divisor).to_string(), width = self.decimals as usize)),
+            None => (U256::zero(), format!("{:0>width$}", self.raw.to_string(), width = self.decimals as usize)),
+        }
+    }
+}
+
+fn pow10(exponent: u16) -> Option<U256> {
+    U256::from(10).checked_pow(exponent.into())
+}
+
+impl FromStr for Amount {
+    type Err = anyhow::Error;
+
+    // Keeps as many decimals as typed
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+            return Err(anyhow::anyhow!(ERR_INVALID_AMOUNT));
+        }
+
+        let digits = format!("{}{}", integer, fraction);
+        let digits = digits.trim_start_matches('0');
+        let raw = if digits.is_empty() {
+            U256::zero()
+        } else {
+            U256::from_dec_str(digits).map_err(|_| anyhow::anyhow!(ERR_AMOUNT_OVERFLOW))?
+        };
+        let decimals = u16::try_from(fraction.len()).map_err(|_| anyhow::anyhow!(ERR_INVALID_AMOUNT))?;
+        Ok(Self::new(raw, decimals))
+    }
+}
+
+// Exact by default, `{:.N}` cuts to N decimals instead of rounding
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (integer, fraction) = self.split();
+        let fraction = match f.precision() {
+            Some(precision) => format!("{:0<precision$}", &fraction[..fraction.len().min(precision)]),
+            None => fraction.trim_end_matches('0').to_string(),
+        };
+        if fraction.is_empty() {
+            write!(f, "{}", integer)
+        } else {
+            write!(f, "{}.{}", integer, fraction)
+        }
+    }
+}
+
+// NOTE: amounts are compared by value, whatever their decimals
+impl PartialEq for Amount {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Amount {}
+
+impl PartialOrd for Amount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Amount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let decimals = self.decimals.max(other.decimals);
+        match (self.rescale(decimals), other.rescale(decimals)) {
+            (Some(value), Some(other)) => value.raw.cmp(&other.raw),
+            // Only the side with fewer decimals can overflow
+            (None, _) => Ordering::Greater,
+            (_, None) => Ordering::Less,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::U256;
+    use crate::core::amount::{Amount, ETH_DECIMALS};
+
+    #[test_case("1.5", 6, "1500000")]
+    #[test_case(" 0.25 ", 18, "250000000000000000")]
+    #[test_case("42", 0, "42")]
+    #[test_case(".5", 1, "5")]
+    #[test_case("7.", 2, "700")]
+    #[test_case("123456789012345678901234567890.123456789012345678", 18, "123456789012345678901234567890123456789012345678")]
+    fn test_parse(text: &str, decimals: u16, raw: &str) -> anyhow::Result<()> {
+        let amount = Amount::parse(text, decimals)?;
+        assert_eq!(amount.raw, U256::from_dec_str(raw)?);
+        assert_eq!(amount.decimals, decimals);
+        Ok(())
+    }
+
+    #[test_case("", 18, "Invalid amount")]
+    #[test_case(".", 18, "Invalid amount")]
+    #[test_case("-1", 18, "Invalid amount")]
+    #[test_case("1e5", 18, "Invalid amount")]
+    #[test_case("1.2.3", 18, "Invalid amount")]
+    #[test_case("1.0000001", 6, "Amount has more than 6 decimals")]
+    #[test_case("1000000000000000000000000000000000000000000000000000000000000000000000000000000", 0, "Amount is too large")]
+    fn test_parse_errors(text: &str, decimals: u16, expected: &str) {
+        assert_eq!(Amount::parse(text, decimals).unwrap_err().to_string(), expected);
+    }
+
+    #[test_case("1500000", 6, "1.5", "1.500000", "1.50")]
+    #[test_case("1", 18, "0.000000000000000001", "0.000000", "0.00")]
+    #[test_case("1999999999999999999", 18, "1.999999999999999999", "1.999999", "1.99")]
+    #[test_case("42", 0, "42", "42.000000", "42.00")]
+    fn test_display(raw: &str, decimals: u16, exact: &str, six: &str, two: &str) -> anyhow::Result<()> {
+        let amount = Amount::new(U256::from_dec_str(raw)?, decimals);
+        assert_eq!(amount.to_string(), exact);
+        assert_eq!(format!("{:.6}", amount), six);
+        assert_eq!(format!("{:.2}", amount), two);
+        Ok(())
+    }
+
+    #[test]
+    fn test_wei_above_u128() {
+        let amount = Amount::from_wei(U256::MAX);
+        assert_eq!(amount.to_string(), "115792089237316195423570985008687907853269984665640564039457.584007913129639935");
+        assert_eq!(Amount::parse(&amount.to_string(), ETH_DECIMALS).unwrap(), amount);
+    }
+
+    #[test]
+    fn test_compare_and_add() -> anyhow::Result<()> {
+        let usdc = Amount::parse("1.5", 6)?;
+        let dai = Amount::parse("1.5", 18)?;
+        assert_eq!(usdc, dai);
+        assert!(Amount::parse("1.500001", 6)? > dai);
+
+        let sum = usdc.checked_add(&Amount::parse("0.000000000000000001", 18)?).unwrap();
+        assert_eq!(sum.decimals, 18);
+        assert_eq!(sum.to_string(), "1.500000000000000001");
+        assert_eq!(Amount::from_wei(U256::MAX).checked_add(&Amount::from_wei(1.into())), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rescale() -> anyhow::Result<()> {
+        let amount = Amount::parse("1.234567", 6)?;
+        assert_eq!(amount.rescale(2).unwrap().to_string(), "1.23");
+        assert_eq!(amount.rescale(18).unwrap().raw, U256::from(1_234_567_000_000_000_000u128));
+        assert_eq!(amount.to_decimals(18)?, U256::from(1_234_567_000_000_000_000u128));
+        assert!(amount.to_decimals(2).is_err());
+        Ok(())
+    }
+
+    #[test_case(0.1, "0.1")]
+    #[test_case(1.0, "1")]
+    #[test_case(0.000001, "0.000001")]
+    #[test_case(1e-20, "0")]
+    #[test_case(123456.789, "123456.789")]
+    fn test_from_f64(value: f64, expected: &str) -> anyhow::Result<()> {
+        assert_eq!(Amount::from_f64(value, ETH_DECIMALS)?.to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_f64_parses_back() -> anyhow::Result<()> {
+        // Converted USD to ETH amount has more digits than the currency keeps
+        let amount = Amount::from_f64(1.0 / 2637.13, ETH_DECIMALS)?;
+        assert_eq!(Amount::parse(&amount.to_string(), ETH_DECIMALS)?, amount);
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde() -> anyhow::Result<()> {
+        let amount = Amount::parse("2.5", 6)?;
+        let json = serde_json::to_string(&amount)?;
+        assert_eq!(json, r#"{"raw":"0x2625a0","decimals":6}"#);
+
+        let back: Amount = serde_json::from_str(&json)?;
+        assert_eq!((back.raw, back.decimals), (amount.raw, amount.decimals));
+
+        // Records written as floats are still readable
+        let legacy: Amount = serde_json::from_str("0.01")?;
+        assert_eq!((legacy.raw, legacy.decimals), (U256::exp10(16), ETH_DECIMALS));
+        assert!(serde_json::from_str::<Amount>("-1.0").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_f64() -> anyhow::Result<()> {
+        assert_eq!(Amount::parse("2.5", 18)?.to_f64(), 2.5);
+        assert_eq!(Amount::zero(6).to_f64(), 0.0);
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use std::collections::HashMap;
 
-use super::eth_chain::EthChain;
+use super::{amount::Amount, eth_chain::EthChain};
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct BalanceValue {
-    pub value: f64,
+    pub value: Amount,
     pub usd_value: f64,
 }
 
@@ -16,7 +17,7 @@ pub struct Balance {
 pub type Balances = Vec<Balance>;
 
 impl BalanceValue {
-    pub fn new(value: f64, usd_value: f64) -> Self {
+    pub fn new(value: Amount, usd_value: f64) -> Self {
         Self { value, usd_value }
     }
 
@@ -26,7 +27,7 @@ impl BalanceValue {
 }
 
 impl Balance {
-    pub fn new(currency: &str, chain: EthChain, value: f64, usd_value: f64) -> Self {
+    pub fn new(currency: &str, chain: EthChain, value: Amount, usd_value: f64) -> Self {
         let mut chain_values = HashMap::new();
         chain_values.insert(chain, BalanceValue::new(value, usd_value));
         Self { currency: currency.to_string(), chain_values }
@@ -38,8 +39,9 @@ impl Balance {
     }
 
     pub fn summary(&self) -> BalanceValue {
-        self.chain_values.values().fold(BalanceValue::new(0.0, 0.0), |acc, v| {
-            BalanceValue::new(acc.value + v.value, acc.usd_value + v.usd_value)
+        // NOTE: chains may use different decimals for the same token, the sum keeps the largest
+        self.chain_values.values().fold(BalanceValue::new(Amount::zero(0), 0.0), |acc, v| {
+            BalanceValue::new(acc.value.checked_add(&v.value).unwrap_or(acc.value), acc.usd_value + v.usd_value)
         })
     }
 
@@ -1,16 +1,20 @@
 #[cfg(test)]
 mod tests {
-    use super::super::{balance::Balance, eth_chain::EthChain};
+    use super::super::{amount::Amount, balance::{Balance, BalanceValue}, eth_chain::EthChain};
+
+    fn amount(text: &str, decimals: u16) -> Amount {
+        Amount::parse(text, decimals).unwrap()
+    }
 
     #[test]
     fn test_extend_balances() {
         let balances = vec![
-            Balance::new("ETH", EthChain::ArbitrumMainnet, 0.5, 1300.0),
-            Balance::new("USDC", EthChain::ArbitrumMainnet, 50.0, 50.0),
+            Balance::new("ETH", EthChain::ArbitrumMainnet, amount("0.5", 18), 1300.0),
+            Balance::new("USDC", EthChain::ArbitrumMainnet, amount("50", 6), 50.0),
         ];
         let new_balances = vec![
-            Balance::new("ETH", EthChain::OptimismMainnet, 0.25, 0.0),
-            Balance::new("DAI", EthChain::OptimismMainnet, 100.0, 100.0),
+            Balance::new("ETH", EthChain::OptimismMainnet, amount("0.25", 18), 0.0),
+            Balance::new("DAI", EthChain::OptimismMainnet, amount("100", 18), 100.0),
         ];
         let extended_balances = Balance::extend_balances(balances, &new_balances);
         assert_eq!(extended_balances.len(), 3);
@@ -18,19 +22,29 @@ mod tests {
         assert_eq!(extended_balances[0].currency, "ETH");
         assert_eq!(extended_balances[0].chain_values.len(), 2);
         let summary = extended_balances[0].summary();
-        assert_eq!(summary.value, 0.75);
+        assert_eq!(summary.value, amount("0.75", 18));
         assert_eq!(summary.usd_value, 1300.0);
 
         assert_eq!(extended_balances[1].currency, "USDC");
         assert_eq!(extended_balances[1].chain_values.len(), 1);
         let summary = extended_balances[1].summary();
-        assert_eq!(summary.value, 50.0);
+        assert_eq!(summary.value, amount("50", 6));
         assert_eq!(summary.usd_value, 50.0);
 
         assert_eq!(extended_balances[2].currency, "DAI");
         assert_eq!(extended_balances[2].chain_values.len(), 1);
         let summary = extended_balances[2].summary();
-        assert_eq!(summary.value, 100.0);
+        assert_eq!(summary.value, amount("100", 18));
         assert_eq!(summary.usd_value, 100.0);
     }
+
+    #[test]
+    fn test_summary_with_mixed_decimals() {
+        let mut balance = Balance::new("USDC", EthChain::EthereumMainnet, amount("1.5", 6), 1.5);
+        balance.chain_values.insert(EthChain::ArbitrumMainnet, BalanceValue::new(amount("0.000000000000000001", 18), 0.0));
+
+        let summary = balance.summary();
+        assert_eq!(summary.value.decimals, 18);
+        assert_eq!(summary.value.to_string(), "1.500000000000000001");
+    }
 }
@@ -0,0 +1,161 @@
+use web3::types::{Address, H256, U256};
+
+use super::{amount::Amount, balance::Balances, csv, eth_chain::EthChain, eth_utils, transaction::{TransactionFees, TransactionRequest}};
+
+const CSV_HEADER: [&str; 4] = ["address", "amount", "currency", "chain"];
+const RESULT_CSV_HEADER: [&str; 7] = ["address", "amount", "currency", "chain", "status", "tx_hash", "error"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchPayment {
+    pub row: usize,
+    pub to: Address,
+    pub amount: Amount,
+    pub currency: String,
+    pub chain: EthChain,
+}
+
+// Parsed CSV row, invalid rows are kept to be shown to the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRow {
+    pub row: usize,
+    pub payment: Result<BatchPayment, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchTotal {
+    pub chain: EthChain,
+    pub currency: String,
+    pub amount: Amount,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchEstimate {
+    pub fees: Vec<TransactionFees>,         // Per payment
+    pub chain_fees: Vec<(EthChain, U256)>,  // Combined max cost per chain
+    pub shortfalls: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult {
+    pub payment: BatchPayment,
+    pub result: Result<H256, String>,
+}
+
+impl BatchPayment {
+    pub fn to_request(&self, from: Address) -> TransactionRequest {
+        TransactionRequest {
+            from,
+            to: self.to,
+            amount: self.amount,
+            currency: self.currency.clone(),
+            chain: self.chain,
+            fees: None,
+            data: None,
+        }
+    }
+}
+
+impl BatchEstimate {
+    pub fn is_ready(&self) -> bool {
+        self.shortfalls.is_empty() && self.fees.iter().all(|fees| matches!(fees, TransactionFees::Estimated { .. }))
+    }
+}
+
+// Chain column accepts either the chain id or the display name
+pub fn parse_chain(value: &str) -> Option<EthChain> {
+    let value = value.trim();
+    if let Ok(chain_id) = value.parse::<u64>() {
+        return EthChain::from_chain_id(chain_id);
+    }
+    super::eth_chain::MAINNET_CHAINS.iter().chain(super::eth_chain::TESTNET_CHAINS.iter())
+        .find(|chain| chain.get_display_name().eq_ignore_ascii_case(value))
+        .copied()
+}
+
+fn parse_payment(row: usize, fields: &[String]) -> Result<BatchPayment, String> {
+    let field = |column: usize| fields.get(column).map(|field| field.trim()).unwrap_or_default();
+    if fields.len() < CSV_HEADER.len() {
+        return Err(format!("expected {} columns, got {}", CSV_HEADER.len(), fields.len()));
+    }
+
+    let to = eth_utils::str_to_eth_address(field(0)).map_err(|err| err.to_string())?;
+    let amount = field(1).parse::<Amount>().ok()
+        .filter(|amount| !amount.is_zero())
+        .ok_or_else(|| format!("invalid amount {}", field(1)))?;
+    let currency = field(2).to_uppercase();
+    if currency.is_empty() {
+        return Err("missing currency".to_string());
+    }
+    let chain = parse_chain(field(3)).ok_or_else(|| format!("unknown chain {}", field(3)))?;
+
+    Ok(BatchPayment { row, to, amount, currency, chain })
+}
+
+pub fn parse_csv(text: &str) -> anyhow::Result<Vec<BatchRow>> {
+    let mut rows = Vec::new();
+    for (index, fields) in csv::parse(text)?.into_iter().enumerate() {
+        if index == 0 && fields.first().is_some_and(|field| field.trim().eq_ignore_ascii_case(CSV_HEADER[0])) {
+            continue;
+        }
+        let row = index + 1;
+        rows.push(BatchRow { row, payment: parse_payment(row, &fields) });
+    }
+    Ok(rows)
+}
+
+// Sums per chain and currency, in order of the first appearance
+pub fn totals(payments: &[BatchPayment]) -> Vec<BatchTotal> {
+    let mut totals: Vec<BatchTotal> = Vec::new();
+    for payment in payments {
+        match totals.iter_mut().find(|total| total.chain == payment.chain && total.currency == payment.currency) {
+            Some(total) => total.amount = total.amount.checked_add(&payment.amount).unwrap_or(total.amount),
+            None => totals.push(BatchTotal {
+                chain: payment.chain,
+                currency: payment.currency.clone(),
+                amount: payment.amount,
+            }),
+        }
+    }
+    totals
+}
+
+// Combined fees are paid in ETH, so they are added to the ETH total of each chain
+pub fn find_shortfalls(totals: &[BatchTotal], chain_fees: &[(EthChain, U256)], balances: &Balances) -> Vec<String> {
+    let mut required = totals.to_vec();
+    for (chain, fee) in chain_fees {
+        let fee = eth_utils::wei_to_eth(*fee);
+        match required.iter_mut().find(|total| total.chain == *chain && total.currency == "ETH") {
+            Some(total) => total.amount = total.amount.checked_add(&fee).unwrap_or(total.amount),
+            None => required.push(BatchTotal { chain: *chain, currency: "ETH".to_string(), amount: fee }),
+        }
+    }
+
+    required.iter().filter_map(|total| {
+        let available = balances.iter()
+            .find(|balance| balance.currency == total.currency)
+            .and_then(|balance| balance.chain_values.get(&total.chain))
+            .map_or(Amount::zero(0), |value| value.value);
+        (available < total.amount).then(|| format!("Not enough {} on {}: {:.6} required, {:.6} available",
+            total.currency, total.chain.get_display_name(), total.amount, available))
+    }).collect()
+}
+
+pub fn results_to_csv(results: &[BatchResult]) -> String {
+    let mut text = csv::write_row(&RESULT_CSV_HEADER);
+    for result in results {
+        let (status, hash, error) = match &result.result {
+            Ok(hash) => ("sent", format!("{:?}", hash), String::new()),
+            Err(error) => ("failed", String::new(), error.clone()),
+        };
+        text += &csv::write_row(&[
+            format!("{:?}", result.payment.to),
+            result.payment.amount.to_string(),
+            result.payment.currency.clone(),
+            result.payment.chain.get_chain_id().to_string(),
+            status.to_string(),
+            hash,
+            error,
+        ]);
+    }
+    text
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{Address, H256};
+    use crate::core::{amount::Amount, balance::Balance, batch::{self, BatchPayment, BatchResult, BatchTotal}, eth_chain::EthChain, eth_utils};
+
+    const ALICE: &str = "0x00000000000000000000000000000000000a11ce";
+
+    fn amount(text: &str) -> Amount {
+        text.parse().unwrap()
+    }
+
+    #[test_case("1", Some(EthChain::EthereumMainnet); "chain id")]
+    #[test_case("optimism sepolia", Some(EthChain::OptimismSepolia); "display name")]
+    #[test_case("56", None; "unknown chain id")]
+    #[test_case("solana", None; "unknown name")]
+    fn test_parse_chain(value: &str, expected: Option<EthChain>) {
+        assert_eq!(batch::parse_chain(value), expected);
+    }
+
+    #[test]
+    fn test_parse_csv() -> anyhow::Result<()> {
+        let text = format!("address,amount,currency,chain\n\
+            {ALICE},0.5,eth,1\n\
+            0xdeadbeef,1,ETH,1\n\
+            {ALICE},0,USDC,10\n\
+            {ALICE},10,USDC,56\n\
+            {ALICE},10\n");
+        let rows = batch::parse_csv(&text)?;
+        assert_eq!(rows.len(), 5);
+
+        let payment = rows[0].payment.as_ref().unwrap();
+        assert_eq!(payment.row, 2);
+        assert_eq!(payment.to, Address::from_low_u64_be(0xa11ce));
+        assert_eq!(payment.amount, "0.5".parse()?);
+        assert_eq!(payment.currency, "ETH");
+        assert_eq!(payment.chain, EthChain::EthereumMainnet);
+
+        assert!(rows[1].payment.is_err());
+        assert_eq!(rows[2].payment, Err("invalid amount 0".to_string()));
+        assert_eq!(rows[3].payment, Err("unknown chain 56".to_string()));
+        assert_eq!(rows[4].payment, Err("expected 4 columns, got 2".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_totals_and_results() {
+        let payment = |value: &str, currency: &str, chain: EthChain| BatchPayment {
+            row: 1,
+            to: Address::from_low_u64_be(0xa11ce),
+            amount: amount(value),
+            currency: currency.to_string(),
+            chain,
+        };
+        let payments = vec![
+            payment("1.0", "ETH", EthChain::EthereumMainnet),
+            payment("2.5", "USDC", EthChain::EthereumMainnet),
+            payment("0.5", "ETH", EthChain::EthereumMainnet),
+            payment("1.0", "ETH", EthChain::OptimismMainnet),
+        ];
+
+        let totals = batch::totals(&payments);
+        assert_eq!(totals.len(), 3);
+        assert_eq!((totals[0].chain, totals[0].currency.as_str(), totals[0].amount), (EthChain::EthereumMainnet, "ETH", amount("1.5")));
+        assert_eq!((totals[1].chain, totals[1].currency.as_str(), totals[1].amount), (EthChain::EthereumMainnet, "USDC", amount("2.5")));
+        assert_eq!((totals[2].chain, totals[2].currency.as_str(), totals[2].amount), (EthChain::OptimismMainnet, "ETH", amount("1")));
+
+        let results = vec![
+            BatchResult { payment: payments[0].clone(), result: Ok(H256::from_low_u64_be(1)) },
+            BatchResult { payment: payments[1].clone(), result: Err("Not enough funds, sorry".to_string()) },
+        ];
+        let text = batch::results_to_csv(&results);
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "address,amount,currency,chain,status,tx_hash,error");
+        assert_eq!(lines[1], format!("{ALICE},1,ETH,1,sent,{:?},", H256::from_low_u64_be(1)));
+        assert_eq!(lines[2], format!("{ALICE},2.5,USDC,1,failed,,\"Not enough funds, sorry\""));
+    }
+
+    #[test]
+    fn test_find_shortfalls() {
+        let totals = vec![
+            BatchTotal { chain: EthChain::EthereumMainnet, currency: "ETH".to_string(), amount: amount("1") },
+            BatchTotal { chain: EthChain::EthereumMainnet, currency: "USDC".to_string(), amount: amount("100") },
+        ];
+        let chain_fees = vec![
+            (EthChain::EthereumMainnet, eth_utils::eth_to_wei(&amount("0.01")).unwrap()),
+            (EthChain::OptimismMainnet, eth_utils::eth_to_wei(&amount("0.001")).unwrap()),
+        ];
+        let balances = vec![
+            Balance::new("ETH", EthChain::EthereumMainnet, amount("1.005"), 0.0),
+            Balance::new("USDC", EthChain::EthereumMainnet, amount("150"), 150.0),
+        ];
+
+        let shortfalls = batch::find_shortfalls(&totals, &chain_fees, &balances);
+        assert_eq!(shortfalls, vec![
+            "Not enough ETH on Ethereum Mainnet: 1.010000 required, 1.005000 available".to_string(),
+            "Not enough ETH on Optimism Mainnet: 0.001000 required, 0.000000 available".to_string(),
+        ]);
+    }
+}
@@ -0,0 +1,297 @@
+use web3::{ethabi, types::{Address, Log, H256, U256, U64}};
+
+use super::{amount::Amount, eth_chain::EthChain, transaction::TransactionRequest};
+
+const OP_BRIDGE_ABI: &[u8] = include_bytes!("../../abi/op_bridge.json");
+const ARBITRUM_BRIDGE_ABI: &[u8] = include_bytes!("../../abi/arbitrum_bridge.json");
+
+// NOTE: OP stack predeploys, see https://docs.optimism.io/stack/smart-contracts
+const OP_L2_STANDARD_BRIDGE: &str = "0x4200000000000000000000000000000000000010";
+const OP_L2_TO_L1_MESSAGE_PASSER: &str = "0x4200000000000000000000000000000000000016";
+const OP_LEGACY_ERC20_ETH: &str = "0xDeadDeAddeAddEAddeadDEaDDEAdDeaDDeAD0000";
+// NOTE: Arbitrum precompile, see https://docs.arbitrum.io/build-decentralized-apps/precompiles/reference
+const ARB_SYS: &str = "0x0000000000000000000000000000000000000064";
+
+// Gas for relaying the ETH on the other side, the value the official SDK uses
+const MIN_GAS_LIMIT: u32 = 200_000;
+
+// NOTE: Arbitrum assertions are confirmed after ~6.4 days on mainnet and within an hour on Sepolia
+const ARBITRUM_CHALLENGE_PERIOD_SECONDS: i64 = 7 * 24 * 60 * 60;
+const ARBITRUM_TESTNET_CHALLENGE_PERIOD_SECONDS: i64 = 60 * 60;
+
+const ERR_NOT_L2_CHAIN: &str = "Chain has no canonical bridge";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rollup {
+    OpStack,
+    Arbitrum,
+}
+
+// Canonical bridge contracts on L1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BridgeContracts {
+    pub l1_chain: EthChain,
+    pub deposit: Address,               // OP L1StandardBridge or Arbitrum Inbox
+    pub portal: Option<Address>,        // OP OptimismPortal
+    pub state_oracle: Option<Address>,  // OP DisputeGameFactory
+    pub outbox: Option<Address>,        // Arbitrum Outbox
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum WithdrawalMessage {
+    OpStack { withdrawal_hash: H256 },
+    Arbitrum { position: U256 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum WithdrawalStatus {
+    Initiated,      // Waiting for the L2 state to be posted on L1
+    Provable,       // OP only, has to be proven on L1
+    Proven,         // OP only, waiting for the challenge period
+    Finalizable,    // Has to be finalized on L1
+    Finalized,
+    Failed,         // L2 transaction failed
+}
+
+// L1 view of a withdrawal, queried by the tracker
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WithdrawalProgress {
+    pub state_posted: bool,
+    pub proven_at: Option<i64>,
+    pub challenge_period: i64,
+    // OP only, when the proven dispute game was resolved for its claim and how long it takes to be final
+    pub game_resolved_at: Option<i64>,
+    pub game_finality_delay: i64,
+    pub finalized: bool,
+}
+
+// Withdrawals take days and several L1 actions, so they are kept in the vault until finalized
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Withdrawal {
+    pub tx_hash: H256,
+    pub chain: EthChain,
+    pub account: Address,
+    pub amount: Amount,
+    pub initiated_at: i64,
+    pub l2_block: Option<U64>,
+    pub message: Option<WithdrawalMessage>,
+    pub status: WithdrawalStatus,
+    #[serde(default)]
+    pub finalizable_at: Option<i64>,
+}
+
+impl Rollup {
+    pub fn for_chain(chain: EthChain) -> Option<Self> {
+        match chain {
+            EthChain::EthereumMainnet | EthChain::EthereumSepolia => None,
+            EthChain::OptimismMainnet | EthChain::OptimismSepolia => Some(Rollup::OpStack),
+            EthChain::ArbitrumMainnet | EthChain::ArbitrumSepolia => Some(Rollup::Arbitrum),
+        }
+    }
+}
+
+impl BridgeContracts {
+    // NOTE from https://docs.optimism.io/chain/addresses and https://docs.arbitrum.io/for-devs/useful-addresses
+    pub fn for_chain(l2_chain: EthChain) -> Option<Self> {
+        let address = |address: &str| address.parse::<Address>().unwrap();
+        let contracts = match l2_chain {
+            EthChain::EthereumMainnet | EthChain::EthereumSepolia => return None,
+            EthChain::OptimismMainnet => BridgeContracts {
+                l1_chain: EthChain::EthereumMainnet,
+                deposit: address("0x99C9fc46f92E8a1c0deC1b1747d010903E884bE1"),
+                portal: Some(address("0xbEb5Fc579115071764c7423A4f12eDde41f106Ed")),
+                state_oracle: Some(address("0xe5965Ab5962eDc7477C8520243A95517CD252fA9")),
+                outbox: None,
+            },
+            EthChain::OptimismSepolia => BridgeContracts {
+                l1_chain: EthChain::EthereumSepolia,
+                deposit: address("0xFBb0621E0B23b5478B630BD55a5f21f67730B0F1"),
+                portal: Some(address("0x16Fc5058F25648194471939df75CF27A2fdC48BC")),
+                state_oracle: Some(address("0x05F9613aDB30026FFd634f38e5C4dFd30a197Fa1")),
+                outbox: None,
+            },
+            EthChain::ArbitrumMainnet => BridgeContracts {
+                l1_chain: EthChain::EthereumMainnet,
+                deposit: address("0x4Dbd4fc535Ac27206064B68FfCf827b0A60BAB3f"),
+                portal: None,
+                state_oracle: None,
+                outbox: Some(address("0x0B9857ae2D4A3DBe74ffE1d7DF045bb7F96E4840")),
+            },
+            EthChain::ArbitrumSepolia => BridgeContracts {
+                l1_chain: EthChain::EthereumSepolia,
+                deposit: address("0xaAe29B0366299461418F5324a79Afc425BE5ae21"),
+                portal: None,
+                state_oracle: None,
+                outbox: Some(address("0x65f07C7D521164a4d5DaC6eB8Fac8DA067A3B78F")),
+            },
+        };
+        Some(contracts)
+    }
+}
+
+impl WithdrawalStatus {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            WithdrawalStatus::Initiated => "Initiated",
+            WithdrawalStatus::Provable => "Ready to prove",
+            WithdrawalStatus::Proven => "Proven",
+            WithdrawalStatus::Finalizable => "Ready to finalize",
+            WithdrawalStatus::Finalized => "Finalized",
+            WithdrawalStatus::Failed => "Failed",
+        }
+    }
+}
+
+impl Withdrawal {
+    pub fn new(tx_hash: H256, chain: EthChain, account: Address, amount: Amount, initiated_at: i64) -> Self {
+        Self {
+            tx_hash,
+            chain,
+            account,
+            amount,
+            initiated_at,
+            l2_block: None,
+            message: None,
+            status: WithdrawalStatus::Initiated,
+            finalizable_at: None,
+        }
+    }
+
+    pub fn is_tracked(&self) -> bool {
+        !matches!(self.status, WithdrawalStatus::Finalized | WithdrawalStatus::Failed)
+    }
+
+    pub fn needs_action(&self) -> bool {
+        matches!(self.status, WithdrawalStatus::Provable | WithdrawalStatus::Finalizable)
+    }
+
+    // What the user has to do next, proving and finalizing happen on L1
+    pub fn action_str(&self) -> Option<String> {
+        let l1_chain = BridgeContracts::for_chain(self.chain)?.l1_chain;
+        let action = match self.status {
+            WithdrawalStatus::Provable => "prove",
+            WithdrawalStatus::Finalizable => "finalize",
+            _ => return None,
+        };
+        Some(format!("Withdrawal of {} ETH from {} is ready, {} it on {}",
+            self.amount, self.chain.get_display_name(), action, l1_chain.get_display_name()))
+    }
+
+    // Returns true if the status has changed
+    pub fn apply_progress(&mut self, progress: &WithdrawalProgress, now: i64) -> bool {
+        let original = (self.status, self.finalizable_at);
+        if progress.finalized {
+            self.status = WithdrawalStatus::Finalized;
+            return original != (self.status, self.finalizable_at);
+        }
+
+        match Rollup::for_chain(self.chain) {
+            Some(Rollup::OpStack) => match progress.proven_at {
+                Some(proven_at) => {
+                    // NOTE: the portal also waits for the dispute game, the estimate holds until it is resolved
+                    let game_final_at = progress.game_resolved_at.map(|resolved_at| resolved_at + progress.game_finality_delay);
+                    let finalizable_at = (proven_at + progress.challenge_period).max(game_final_at.unwrap_or_default());
+                    self.finalizable_at = Some(finalizable_at);
+                    self.status = if game_final_at.is_some() && now >= finalizable_at {
+                        WithdrawalStatus::Finalizable
+                    } else {
+                        WithdrawalStatus::Proven
+                    };
+                },
+                None if progress.state_posted => self.status = WithdrawalStatus::Provable,
+                None => self.status = WithdrawalStatus::Initiated,
+            },
+            Some(Rollup::Arbitrum) => {
+                // NOTE: no proving step, the outbox accepts the message once its assertion is confirmed
+                let finalizable_at = self.initiated_at + progress.challenge_period;
+                self.finalizable_at = Some(finalizable_at);
+                self.status = if now >= finalizable_at { WithdrawalStatus::Finalizable } else { WithdrawalStatus::Initiated };
+            },
+            None => {},
+        }
+        original != (self.status, self.finalizable_at)
+    }
+}
+
+pub fn arbitrum_challenge_period(chain: EthChain) -> i64 {
+    if chain.is_test_network() {
+        ARBITRUM_TESTNET_CHALLENGE_PERIOD_SECONDS
+    } else {
+        ARBITRUM_CHALLENGE_PERIOD_SECONDS
+    }
+}
+
+// Deposits are credited to the same account on L2 within minutes
+pub fn deposit_request(account: Address, l2_chain: EthChain, amount: Amount) -> anyhow::Result<TransactionRequest> {
+    let contracts = BridgeContracts::for_chain(l2_chain).ok_or_else(|| anyhow::anyhow!(ERR_NOT_L2_CHAIN))?;
+    let data = match Rollup::for_chain(l2_chain) {
+        Some(Rollup::OpStack) => ethabi::Contract::load(OP_BRIDGE_ABI)?.function("depositETH")?.encode_input(&[
+            ethabi::Token::Uint(MIN_GAS_LIMIT.into()),
+            ethabi::Token::Bytes(Vec::new()),
+        ])?,
+        _ => ethabi::Contract::load(ARBITRUM_BRIDGE_ABI)?.function("depositEth")?.encode_input(&[])?,
+    };
+    Ok(TransactionRequest {
+        from: account,
+        to: contracts.deposit,
+        amount,
+        currency: "ETH".to_string(),
+        chain: contracts.l1_chain,
+        fees: None,
+        data: Some(data),
+    })
+}
+
+pub fn withdrawal_request(account: Address, l2_chain: EthChain, amount: Amount) -> anyhow::Result<TransactionRequest> {
+    let (to, data) = match Rollup::for_chain(l2_chain) {
+        Some(Rollup::OpStack) => {
+            let value = super::eth_utils::eth_to_wei(&amount)?;
+            let data = ethabi::Contract::load(OP_BRIDGE_ABI)?.function("withdraw")?.encode_input(&[
+                ethabi::Token::Address(OP_LEGACY_ERC20_ETH.parse()?),
+                ethabi::Token::Uint(value),
+                ethabi::Token::Uint(MIN_GAS_LIMIT.into()),
+                ethabi::Token::Bytes(Vec::new()),
+            ])?;
+            (OP_L2_STANDARD_BRIDGE, data)
+        },
+        Some(Rollup::Arbitrum) => {
+            let data = ethabi::Contract::load(ARBITRUM_BRIDGE_ABI)?.function("withdrawEth")?.encode_input(&[
+                ethabi::Token::Address(account),
+            ])?;
+            (ARB_SYS, data)
+        },
+        None => return Err(anyhow::anyhow!(ERR_NOT_L2_CHAIN)),
+    };
+    Ok(TransactionRequest {
+        from: account,
+        to: to.parse()?,
+        amount,
+        currency: "ETH".to_string(),
+        chain: l2_chain,
+        fees: None,
+        data: Some(data),
+    })
+}
+
+// Finds the L2 to L1 message in the logs of the withdrawal transaction
+pub fn decode_withdrawal_message(chain: EthChain, logs: &[Log]) -> anyhow::Result<Option<WithdrawalMessage>> {
+    let (abi, event, emitter) = match Rollup::for_chain(chain) {
+        Some(Rollup::OpStack) => (OP_BRIDGE_ABI, "MessagePassed", OP_L2_TO_L1_MESSAGE_PASSER),
+        Some(Rollup::Arbitrum) => (ARBITRUM_BRIDGE_ABI, "L2ToL1Tx", ARB_SYS),
+        None => return Err(anyhow::anyhow!(ERR_NOT_L2_CHAIN)),
+    };
+    let emitter: Address = emitter.parse()?;
+    let event = ethabi::Contract::load(abi)?.event(event)?.clone();
+
+    for log in logs.iter().filter(|log| log.address == emitter && log.topics.first() == Some(&event.signature())) {
+        let parsed = event.parse_log(ethabi::RawLog { topics: log.topics.clone(), data: log.data.0.clone() })?;
+        let param = |name: &str| parsed.params.iter().find(|param| param.name == name).map(|param| param.value.clone());
+        let message = match (param("withdrawalHash"), param("position")) {
+            (Some(ethabi::Token::FixedBytes(hash)), _) => WithdrawalMessage::OpStack { withdrawal_hash: H256::from_slice(&hash) },
+            (_, Some(ethabi::Token::Uint(position))) => WithdrawalMessage::Arbitrum { position },
+            _ => continue,
+        };
+        return Ok(Some(message));
+    }
+    Ok(None)
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::{ethabi, signing::keccak256, types::{Address, Log, H256, U256}};
+    use crate::core::{
+        bridge::{self, BridgeContracts, Withdrawal, WithdrawalMessage, WithdrawalProgress, WithdrawalStatus},
+        eth_chain::EthChain
+    };
+
+    const NOW: i64 = 1_700_000_000;
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn log(address: &str, signature: &str, topics: Vec<H256>, data: Vec<ethabi::Token>) -> Log {
+        let mut all_topics = vec![H256::from(keccak256(signature.as_bytes()))];
+        all_topics.extend(topics);
+        Log {
+            address: address.parse().unwrap(),
+            topics: all_topics,
+            data: ethabi::encode(&data).into(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        }
+    }
+
+    #[test_case(EthChain::OptimismMainnet, EthChain::EthereumMainnet, "b1a1a882")]
+    #[test_case(EthChain::ArbitrumSepolia, EthChain::EthereumSepolia, "439370b1")]
+    fn test_deposit_request(l2_chain: EthChain, l1_chain: EthChain, selector: &str) -> anyhow::Result<()> {
+        let account = Address::from_low_u64_be(1);
+        let request = bridge::deposit_request(account, l2_chain, "0.5".parse()?)?;
+
+        assert_eq!(request.chain, l1_chain);
+        assert_eq!(request.to, BridgeContracts::for_chain(l2_chain).unwrap().deposit);
+        assert_eq!(request.amount, "0.5".parse()?);
+        assert_eq!(hex::encode(&request.data.unwrap()[..4]), selector);
+        assert!(bridge::deposit_request(account, EthChain::EthereumMainnet, "0.5".parse()?).is_err());
+        Ok(())
+    }
+
+    #[test_case(EthChain::OptimismSepolia, "0x4200000000000000000000000000000000000010", "32b7006d")]
+    #[test_case(EthChain::ArbitrumMainnet, "0x0000000000000000000000000000000000000064", "25e16063")]
+    fn test_withdrawal_request(l2_chain: EthChain, to: &str, selector: &str) -> anyhow::Result<()> {
+        let request = bridge::withdrawal_request(Address::from_low_u64_be(1), l2_chain, "1.5".parse()?)?;
+
+        assert_eq!(request.chain, l2_chain);
+        assert_eq!(request.to, to.parse()?);
+        assert_eq!(request.amount, "1.5".parse()?);
+        assert_eq!(hex::encode(&request.data.unwrap()[..4]), selector);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_withdrawal_message() -> anyhow::Result<()> {
+        let withdrawal_hash = H256::from_low_u64_be(77);
+        let message_passed = log(
+            "0x4200000000000000000000000000000000000016",
+            "MessagePassed(uint256,address,address,uint256,uint256,bytes,bytes32)",
+            vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2), H256::from_low_u64_be(3)],
+            vec![
+                ethabi::Token::Uint(1000.into()),
+                ethabi::Token::Uint(200_000.into()),
+                ethabi::Token::Bytes(Vec::new()),
+                ethabi::Token::FixedBytes(withdrawal_hash.as_bytes().to_vec()),
+            ],
+        );
+        let l2_to_l1 = log(
+            "0x0000000000000000000000000000000000000064",
+            "L2ToL1Tx(address,address,uint256,uint256,uint256,uint256,uint256,uint256,bytes)",
+            vec![H256::from_low_u64_be(2), H256::from_low_u64_be(5), H256::from_low_u64_be(42)],
+            vec![
+                ethabi::Token::Address(Address::from_low_u64_be(2)),
+                ethabi::Token::Uint(1.into()),
+                ethabi::Token::Uint(2.into()),
+                ethabi::Token::Uint(3.into()),
+                ethabi::Token::Uint(1000.into()),
+                ethabi::Token::Bytes(Vec::new()),
+            ],
+        );
+
+        assert_eq!(bridge::decode_withdrawal_message(EthChain::OptimismMainnet, &[l2_to_l1.clone(), message_passed.clone()])?,
+            Some(WithdrawalMessage::OpStack { withdrawal_hash }));
+        assert_eq!(bridge::decode_withdrawal_message(EthChain::ArbitrumMainnet, &[message_passed, l2_to_l1])?,
+            Some(WithdrawalMessage::Arbitrum { position: U256::from(42) }));
+        assert_eq!(bridge::decode_withdrawal_message(EthChain::OptimismMainnet, &[])?, None);
+        Ok(())
+    }
+
+    #[test_case(WithdrawalProgress::default(), WithdrawalStatus::Initiated, None; "waiting for state")]
+    #[test_case(WithdrawalProgress { state_posted: true, ..Default::default() }, WithdrawalStatus::Provable, None; "provable")]
+    #[test_case(WithdrawalProgress { proven_at: Some(NOW - DAY), challenge_period: 7 * DAY, ..Default::default() },
+        WithdrawalStatus::Proven, Some(NOW + 6 * DAY); "proven")]
+    #[test_case(WithdrawalProgress { proven_at: Some(NOW - 7 * DAY), challenge_period: 7 * DAY, ..Default::default() },
+        WithdrawalStatus::Proven, Some(NOW); "game not resolved")]
+    #[test_case(WithdrawalProgress { proven_at: Some(NOW - 7 * DAY), challenge_period: 7 * DAY,
+        game_resolved_at: Some(NOW - 3 * DAY), game_finality_delay: 3 * DAY + 1, ..Default::default() },
+        WithdrawalStatus::Proven, Some(NOW + 1); "game not final")]
+    #[test_case(WithdrawalProgress { proven_at: Some(NOW - 7 * DAY), challenge_period: 7 * DAY,
+        game_resolved_at: Some(NOW - 4 * DAY), game_finality_delay: 3 * DAY, ..Default::default() },
+        WithdrawalStatus::Finalizable, Some(NOW); "finalizable")]
+    #[test_case(WithdrawalProgress { finalized: true, ..Default::default() }, WithdrawalStatus::Finalized, None; "finalized")]
+    fn test_op_withdrawal_progress(progress: WithdrawalProgress, status: WithdrawalStatus, finalizable_at: Option<i64>) {
+        let mut withdrawal = Withdrawal::new(H256::zero(), EthChain::OptimismMainnet, Address::zero(), Default::default(), NOW - 2 * DAY);
+
+        assert_eq!(withdrawal.apply_progress(&progress, NOW), status != WithdrawalStatus::Initiated);
+        assert_eq!(withdrawal.status, status);
+        assert_eq!(withdrawal.finalizable_at, finalizable_at);
+        assert!(!withdrawal.apply_progress(&progress, NOW));
+        assert_eq!(withdrawal.needs_action(), withdrawal.action_str().is_some());
+    }
+
+    #[test]
+    fn test_arbitrum_withdrawal_progress() {
+        let mut withdrawal = Withdrawal::new(H256::zero(), EthChain::ArbitrumMainnet, Address::zero(), "2".parse().unwrap(), NOW);
+        let progress = WithdrawalProgress { challenge_period: bridge::arbitrum_challenge_period(withdrawal.chain), ..Default::default() };
+
+        withdrawal.apply_progress(&progress, NOW + DAY);
+        assert_eq!(withdrawal.status, WithdrawalStatus::Initiated);
+        assert_eq!(withdrawal.finalizable_at, Some(NOW + 7 * DAY));
+
+        withdrawal.apply_progress(&progress, NOW + 7 * DAY);
+        assert_eq!(withdrawal.status, WithdrawalStatus::Finalizable);
+        assert_eq!(withdrawal.action_str().unwrap(), "Withdrawal of 2 ETH from Arbitrum Mainnet is ready, finalize it on Ethereum Mainnet");
+
+        withdrawal.apply_progress(&WithdrawalProgress { finalized: true, ..progress }, NOW + 8 * DAY);
+        assert!(!withdrawal.is_tracked());
+    }
+}
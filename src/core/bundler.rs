@@ -0,0 +1,52 @@
+use web3::types::{Address, H256};
+
+use super::smart_account::{UserOperation, UserOperationGas, UserOperationReceipt};
+
+const ERR_UNSUPPORTED_ENTRY_POINT: &str = "The bundler doesn't support the EntryPoint";
+
+// ERC-4337 bundler JSON-RPC, served by a separate endpoint than the node
+#[derive(Clone)]
+pub struct Bundler<T: web3::Transport> {
+    transport: T,
+    pub entry_point: Address,
+}
+
+impl<T: web3::Transport> Bundler<T> {
+    pub fn new(transport: T, entry_point: Address) -> Self {
+        Self { transport, entry_point }
+    }
+
+    pub async fn verify_entry_point(&self) -> anyhow::Result<()> {
+        let response = self.transport.execute("eth_supportedEntryPoints", vec![]).await?;
+        let entry_points: Vec<Address> = serde_json::from_value(response)?;
+        if !entry_points.contains(&self.entry_point) {
+            return Err(anyhow::anyhow!(ERR_UNSUPPORTED_ENTRY_POINT));
+        }
+        Ok(())
+    }
+
+    // Simulated with the dummy signature, the operation must not be signed yet
+    pub async fn estimate_user_operation_gas(&self, operation: &UserOperation) -> anyhow::Result<UserOperationGas> {
+        let response = self.transport.execute("eth_estimateUserOperationGas", vec![
+            serde_json::to_value(operation)?,
+            serde_json::to_value(self.entry_point)?,
+        ]).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    pub async fn send_user_operation(&self, operation: &UserOperation) -> anyhow::Result<H256> {
+        let response = self.transport.execute("eth_sendUserOperation", vec![
+            serde_json::to_value(operation)?,
+            serde_json::to_value(self.entry_point)?,
+        ]).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+
+    // None until the operation is included in a block
+    pub async fn get_user_operation_receipt(&self, user_op_hash: H256) -> anyhow::Result<Option<UserOperationReceipt>> {
+        let response = self.transport.execute("eth_getUserOperationReceipt", vec![
+            serde_json::to_value(user_op_hash)?,
+        ]).await?;
+        Ok(serde_json::from_value(response)?)
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use test_case::test_case;
+    use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::TcpListener};
+    use web3::{transports::Http, types::{Address, H256, U256}};
+    use crate::core::{
+        bundler::Bundler,
+        fees::GasPrices,
+        smart_account::{self, SmartAccount, UserOperation, UserOperationGas}
+    };
+
+    // Local bundler answering the expected calls in order, the received requests are kept for assertions
+    struct MockBundler {
+        url: String,
+        requests: Arc<Mutex<Vec<serde_json::Value>>>,
+    }
+
+    impl MockBundler {
+        async fn start(responses: Vec<(&'static str, serde_json::Value)>) -> anyhow::Result<Self> {
+            let listener = TcpListener::bind("127.0.0.1:0").await?;
+            let url = format!("http://{}", listener.local_addr()?);
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let responses = Arc::new(Mutex::new(responses.into_iter()));
+
+            let received = requests.clone();
+            tokio::spawn(async move {
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    let received = received.clone();
+                    let responses = responses.clone();
+                    tokio::spawn(async move {
+                        while let Some(request) = read_request(&mut stream).await {
+                            let (method, response) = responses.lock().unwrap().next().expect("Unexpected request");
+                            assert_eq!(request["method"], method);
+                            let mut body = serde_json::json!({ "jsonrpc": "2.0", "id": request["id"] });
+                            body.as_object_mut().unwrap().extend(response.as_object().unwrap().clone());
+                            received.lock().unwrap().push(request);
+
+                            let body = body.to_string();
+                            let head = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n", body.len());
+                            if stream.write_all(format!("{}{}", head, body).as_bytes()).await.is_err() {
+                                break;
+                            }
+                        }
+                    });
+                }
+            });
+            Ok(Self { url, requests })
+        }
+
+        fn bundler(&self) -> anyhow::Result<Bundler<Http>> {
+            Ok(Bundler::new(Http::new(&self.url)?, smart_account::ENTRY_POINT.parse()?))
+        }
+
+        fn params(&self, index: usize) -> serde_json::Value {
+            self.requests.lock().unwrap()[index]["params"].clone()
+        }
+    }
+
+    async fn read_request(stream: &mut tokio::net::TcpStream) -> Option<serde_json::Value> {
+        let mut data = Vec::new();
+        let mut buffer = [0u8; 4096];
+        loop {
+            if let Some(end) = data.windows(4).position(|window| window == b"\r\n\r\n") {
+                let head = String::from_utf8_lossy(&data[..end]).to_lowercase();
+                let length = head.lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .and_then(|length| length.trim().parse::<usize>().ok())?;
+                if data.len() >= end + 4 + length {
+                    return serde_json::from_slice(&data[end + 4..end + 4 + length]).ok();
+                }
+            }
+            let read = stream.read(&mut buffer).await.ok()?;
+            if read == 0 {
+                return None;
+            }
+            data.extend_from_slice(&buffer[..read]);
+        }
+    }
+
+    fn result(value: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({ "result": value })
+    }
+
+    fn operation() -> anyhow::Result<UserOperation> {
+        let account = SmartAccount::new(Address::from_low_u64_be(1), Address::from_low_u64_be(2));
+        let call_data = smart_account::encode_execute(Address::from_low_u64_be(3), 1_000.into(), Vec::new())?;
+        UserOperation::new(&account, U256::zero(), false, call_data, GasPrices::with_priority_fee(10.into(), 1.into()))
+    }
+
+    #[test_case(smart_account::ENTRY_POINT, true)]
+    #[test_case("0x0000000071727De22E5E9d8BAf0edAc6f37da032", false)]
+    #[tokio::test]
+    async fn test_verify_entry_point(supported: &str, expected: bool) -> anyhow::Result<()> {
+        let mock = MockBundler::start(vec![("eth_supportedEntryPoints", result(serde_json::json!([supported])))]).await?;
+        assert_eq!(mock.bundler()?.verify_entry_point().await.is_ok(), expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_estimate_user_operation_gas() -> anyhow::Result<()> {
+        let mock = MockBundler::start(vec![("eth_estimateUserOperationGas", result(serde_json::json!({
+            "preVerificationGas": "0xb4e8",
+            "verificationGasLimit": "0x5f5e1",
+            "callGasLimit": "0x3a98",
+        })))]).await?;
+
+        let mut operation = operation()?;
+        let gas = mock.bundler()?.estimate_user_operation_gas(&operation).await?;
+        assert_eq!(gas, UserOperationGas {
+            pre_verification_gas: 0xb4e8.into(),
+            verification_gas_limit: 0x5f5e1.into(),
+            call_gas_limit: 0x3a98.into(),
+        });
+        operation.apply_gas(&gas);
+        assert_eq!(operation.max_cost(), U256::from(0xb4e8 + 0x5f5e1 + 0x3a98) * operation.max_fee_per_gas);
+
+        // The operation goes out as the bundler expects it, along with the EntryPoint
+        let params = mock.params(0);
+        assert_eq!(params[0]["sender"], "0x0000000000000000000000000000000000000002");
+        assert!(params[0]["initCode"].as_str().unwrap().starts_with(&smart_account::SIMPLE_ACCOUNT_FACTORY.to_lowercase()));
+        assert_eq!(params[0]["paymasterAndData"], "0x");
+        assert_eq!(params[1], smart_account::ENTRY_POINT.to_lowercase());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_and_track_user_operation() -> anyhow::Result<()> {
+        let user_op_hash = H256::from_low_u64_be(0x4337);
+        let transaction_hash = H256::from_low_u64_be(0x1234);
+        let mock = MockBundler::start(vec![
+            ("eth_sendUserOperation", result(serde_json::to_value(user_op_hash)?)),
+            ("eth_getUserOperationReceipt", result(serde_json::Value::Null)),
+            ("eth_getUserOperationReceipt", result(serde_json::json!({
+                "userOpHash": user_op_hash,
+                "success": true,
+                "actualGasUsed": "0x1",
+                "receipt": { "transactionHash": transaction_hash, "blockNumber": "0x10" },
+            }))),
+        ]).await?;
+        let bundler = mock.bundler()?;
+
+        let operation = operation()?;
+        assert_eq!(bundler.send_user_operation(&operation).await?, user_op_hash);
+        assert_eq!(mock.params(0)[0], serde_json::to_value(&operation)?);
+
+        // Pending first, then included
+        assert!(bundler.get_user_operation_receipt(user_op_hash).await?.is_none());
+        let receipt = bundler.get_user_operation_receipt(user_op_hash).await?.unwrap();
+        assert!(receipt.success);
+        assert_eq!(receipt.user_op_hash, user_op_hash);
+        assert_eq!(receipt.receipt.transaction_hash, transaction_hash);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejected_user_operation() -> anyhow::Result<()> {
+        let mock = MockBundler::start(vec![("eth_estimateUserOperationGas", serde_json::json!({
+            "error": { "code": -32500, "message": "AA21 didn't pay prefund" }
+        }))]).await?;
+
+        let err = mock.bundler()?.estimate_user_operation_gas(&operation()?).await.unwrap_err();
+        assert!(err.to_string().contains("AA21 didn't pay prefund"));
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use web3::{ethabi::{self, token::{LenientTokenizer, Tokenizer}, StateMutability}, types::{Address, U256}};
+
+use super::eth_chain::EthChain;
+
+// Bundled ABIs, used to decode calldata before signing
+const KNOWN_ABIS: [&[u8]; 7] = [
+    include_bytes!("../../abi/erc20_transfer.json"),
+    include_bytes!("../../abi/erc20_approve.json"),
+    include_bytes!("../../abi/erc721.json"),
+    include_bytes!("../../abi/erc1155.json"),
+    include_bytes!("../../abi/uniswap_v2_router.json"),
+    include_bytes!("../../abi/uniswap_v3_router.json"),
+    include_bytes!("../../abi/safe.json"),
+];
+
+const ERR_ARGUMENTS_COUNT: &str = "Wrong number of arguments";
+
+// Contract saved in the vault with its ABI, so it can be reused without the file
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SavedContract {
+    pub name: String,
+    pub chain: EthChain,
+    pub address: Address,
+    pub abi: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedCall {
+    pub function: String,
+    pub args: Vec<String>,
+}
+
+pub fn load_abi(json: &str) -> anyhow::Result<ethabi::Contract> {
+    Ok(ethabi::Contract::load(json.as_bytes())?)
+}
+
+// Sorted by name, overloads by the number of inputs
+pub fn functions(abi: &ethabi::Contract) -> Vec<ethabi::Function> {
+    let mut functions = abi.functions().cloned().collect::<Vec<_>>();
+    functions.sort_by(|a, b| a.name.cmp(&b.name).then(a.inputs.len().cmp(&b.inputs.len())));
+    functions
+}
+
+// NOTE: old ABIs only have the `constant` flag
+#[allow(deprecated)]
+pub fn is_view(function: &ethabi::Function) -> bool {
+    matches!(function.state_mutability, StateMutability::View | StateMutability::Pure) || function.constant == Some(true)
+}
+
+pub fn is_payable(function: &ethabi::Function) -> bool {
+    function.state_mutability == StateMutability::Payable
+}
+
+pub fn function_label(function: &ethabi::Function) -> String {
+    let inputs = function.inputs.iter()
+        .map(|param| if param.name.is_empty() { param.kind.to_string() } else { format!("{} {}", param.kind, param.name) })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mutability = match function.state_mutability {
+        StateMutability::Pure => " pure",
+        StateMutability::View => " view",
+        StateMutability::NonPayable => "",
+        StateMutability::Payable => " payable",
+    };
+    format!("{}({}){}", function.name, inputs, mutability)
+}
+
+pub fn param_placeholder(param: &ethabi::Param) -> String {
+    if param.name.is_empty() {
+        format!("Enter {}", param.kind)
+    } else {
+        format!("Enter {} ({})", param.name, param.kind)
+    }
+}
+
+// Arguments are typed as text, arrays as [a,b] and tuples as (a,b)
+pub fn encode_call(function: &ethabi::Function, args: &[String]) -> anyhow::Result<Vec<u8>> {
+    if args.len() != function.inputs.len() {
+        return Err(anyhow::anyhow!(ERR_ARGUMENTS_COUNT));
+    }
+
+    let tokens = function.inputs.iter().zip(args).map(|(param, arg)| {
+        LenientTokenizer::tokenize(&param.kind, arg.trim()).map_err(|_| {
+            let name = if param.name.is_empty() { "argument" } else { param.name.as_str() };
+            anyhow::anyhow!("Invalid {}: expected {}", name, param.kind)
+        })
+    }).collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(function.encode_input(&tokens)?)
+}
+
+pub fn decode_output(function: &ethabi::Function, data: &[u8]) -> anyhow::Result<Vec<String>> {
+    let tokens = function.decode_output(data)?;
+    Ok(function.outputs.iter().zip(tokens).map(|(param, token)| {
+        if param.name.is_empty() {
+            format_token(&token)
+        } else {
+            format!("{}: {}", param.name, format_token(&token))
+        }
+    }).collect())
+}
+
+pub fn decode_call(abi: &ethabi::Contract, data: &[u8]) -> Option<DecodedCall> {
+    let (selector, input) = (data.get(..4)?, data.get(4..)?);
+    abi.functions().find_map(|function| {
+        if function.short_signature() != selector {
+            return None;
+        }
+        let tokens = function.decode_input(input).ok()?;
+        Some(DecodedCall {
+            function: function.name.clone(),
+            args: function.inputs.iter().zip(tokens).map(|(param, token)| {
+                if param.name.is_empty() {
+                    format_token(&token)
+                } else {
+                    format!("{}: {}", param.name, format_token(&token))
+                }
+            }).collect(),
+        })
+    })
+}
+
+// User-saved ABIs go first, they know the contract better than the standards
+pub fn decode_known_call(data: &[u8], saved_abis: &[ethabi::Contract]) -> Option<DecodedCall> {
+    saved_abis.iter().find_map(|abi| decode_call(abi, data)).or_else(|| {
+        KNOWN_ABIS.iter()
+            .filter_map(|abi| ethabi::Contract::load(*abi).ok())
+            .find_map(|abi| decode_call(&abi, data))
+    })
+}
+
+pub fn format_token(token: &ethabi::Token) -> String {
+    let join = |tokens: &[ethabi::Token]| tokens.iter().map(format_token).collect::<Vec<_>>().join(", ");
+    match token {
+        ethabi::Token::Address(address) => format!("{:?}", address),
+        ethabi::Token::Bytes(bytes) | ethabi::Token::FixedBytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        ethabi::Token::Uint(value) => value.to_string(),
+        ethabi::Token::Int(value) => format_int(*value),
+        ethabi::Token::Bool(value) => value.to_string(),
+        ethabi::Token::String(value) => value.clone(),
+        ethabi::Token::Array(tokens) | ethabi::Token::FixedArray(tokens) => format!("[{}]", join(tokens)),
+        ethabi::Token::Tuple(tokens) => format!("({})", join(tokens)),
+    }
+}
+
+// Two's complement
+fn format_int(value: U256) -> String {
+    if value.bit(255) {
+        format!("-{}", (!value).overflowing_add(U256::one()).0)
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::{ethabi, types::{Address, U256}};
+    use crate::core::contract;
+
+    const ABI: &str = r#"[
+        {"type":"function","name":"transfer","stateMutability":"nonpayable",
+         "inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],
+         "outputs":[{"name":"","type":"bool"}]},
+        {"type":"function","name":"balanceOf","stateMutability":"view",
+         "inputs":[{"name":"owner","type":"address"}],
+         "outputs":[{"name":"balance","type":"uint256"}]},
+        {"type":"function","name":"deposit","stateMutability":"payable","inputs":[],"outputs":[]},
+        {"type":"function","name":"delta","stateMutability":"pure","inputs":[],
+         "outputs":[{"name":"value","type":"int256"},{"name":"ids","type":"uint8[]"}]},
+        {"type":"event","name":"Transfer","anonymous":false,"inputs":[]}
+    ]"#;
+
+    #[test]
+    fn test_functions() -> anyhow::Result<()> {
+        let abi = contract::load_abi(ABI)?;
+        let functions = contract::functions(&abi);
+
+        let labels = functions.iter().map(contract::function_label).collect::<Vec<_>>();
+        assert_eq!(labels, vec![
+            "balanceOf(address owner) view",
+            "delta() pure",
+            "deposit() payable",
+            "transfer(address to, uint256 amount)",
+        ]);
+        assert_eq!(functions.iter().map(contract::is_view).collect::<Vec<_>>(), vec![true, true, false, false]);
+        assert_eq!(functions.iter().map(contract::is_payable).collect::<Vec<_>>(), vec![false, false, true, false]);
+        assert_eq!(contract::param_placeholder(&functions[0].inputs[0]), "Enter owner (address)");
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_call() -> anyhow::Result<()> {
+        let abi = contract::load_abi(ABI)?;
+        let transfer = abi.function("transfer")?;
+        let to = Address::from_low_u64_be(0xbeef);
+
+        let data = contract::encode_call(transfer, &[format!("{:?}", to), " 1000 ".to_string()])?;
+        let expected = transfer.encode_input(&[ethabi::Token::Address(to), ethabi::Token::Uint(1000.into())])?;
+        assert_eq!(data, expected);
+        Ok(())
+    }
+
+    #[test_case(&["0x01", "1000"], "Invalid to: expected address"; "bad address")]
+    #[test_case(&["0x000000000000000000000000000000000000beef", "abc"], "Invalid amount: expected uint256"; "bad amount")]
+    #[test_case(&["0x000000000000000000000000000000000000beef"], "Wrong number of arguments"; "missing argument")]
+    fn test_encode_call_errors(args: &[&str], expected: &str) -> anyhow::Result<()> {
+        let abi = contract::load_abi(ABI)?;
+        let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let err = contract::encode_call(abi.function("transfer")?, &args).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_output() -> anyhow::Result<()> {
+        let abi = contract::load_abi(ABI)?;
+
+        let data = ethabi::encode(&[ethabi::Token::Uint(42.into())]);
+        assert_eq!(contract::decode_output(abi.function("balanceOf")?, &data)?, vec!["balance: 42"]);
+
+        let data = ethabi::encode(&[
+            ethabi::Token::Int(U256::MAX - 4),
+            ethabi::Token::Array(vec![ethabi::Token::Uint(1.into()), ethabi::Token::Uint(2.into())]),
+        ]);
+        assert_eq!(contract::decode_output(abi.function("delta")?, &data)?, vec!["value: -5", "ids: [1, 2]"]);
+
+        let data = ethabi::encode(&[ethabi::Token::Bool(true)]);
+        assert_eq!(contract::decode_output(abi.function("transfer")?, &data)?, vec!["true"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_known_call() -> anyhow::Result<()> {
+        let to = Address::from_low_u64_be(0xbeef);
+
+        // ERC-20 approve from the bundled ABIs
+        let data = crate::core::erc20::encode_approve(to, 5.into())?;
+        let decoded = contract::decode_known_call(&data, &[]).unwrap();
+        assert_eq!(decoded, contract::DecodedCall {
+            function: "approve".to_string(),
+            args: vec![format!("_spender: {:?}", to), "_value: 5".to_string()],
+        });
+
+        // Saved ABIs win over the bundled ones
+        let abi = contract::load_abi(ABI)?;
+        let data = contract::encode_call(abi.function("transfer")?, &[format!("{:?}", to), "7".to_string()])?;
+        let decoded = contract::decode_known_call(&data, std::slice::from_ref(&abi)).unwrap();
+        assert_eq!(decoded.args, vec![format!("to: {:?}", to), "amount: 7".to_string()]);
+        let decoded = contract::decode_known_call(&data, &[]).unwrap();
+        assert_eq!(decoded.args, vec![format!("_to: {:?}", to), "_value: 7".to_string()]);
+
+        assert_eq!(contract::decode_known_call(&[0xde, 0xad, 0xbe, 0xef], std::slice::from_ref(&abi)), None);
+        assert_eq!(contract::decode_known_call(&[0x01], &[abi]), None);
+        Ok(())
+    }
+}
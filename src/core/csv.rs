@@ -0,0 +1,61 @@
+// Minimal RFC 4180 reading and writing, enough for imports and exports
+const ERR_UNTERMINATED_QUOTE: &str = "Unterminated quoted field";
+
+pub fn write_row<S: AsRef<str>>(fields: &[S]) -> String {
+    let fields = fields.iter().map(|field| {
+        let field = field.as_ref();
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }).collect::<Vec<_>>();
+    format!("{}\n", fields.join(","))
+}
+
+// Rows of fields, empty lines are skipped
+pub fn parse(text: &str) -> anyhow::Result<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                },
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' => {},
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                if row.len() > 1 || !row[0].is_empty() {
+                    rows.push(std::mem::take(&mut row));
+                } else {
+                    row.clear();
+                }
+            },
+            _ => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(anyhow::anyhow!(ERR_UNTERMINATED_QUOTE));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
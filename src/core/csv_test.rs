@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use crate::core::csv;
+
+    #[test_case(&["a", "b"], "a,b\n"; "plain")]
+    #[test_case(&["a,b", "c"], "\"a,b\",c\n"; "comma")]
+    #[test_case(&["say \"hi\"", ""], "\"say \"\"hi\"\"\",\n"; "quotes")]
+    #[test_case(&["multi\nline"], "\"multi\nline\"\n"; "new line")]
+    fn test_write_row(fields: &[&str], expected: &str) {
+        assert_eq!(csv::write_row(fields), expected);
+    }
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        let text = "name,notes\r\nAlice,\"likes, commas\"\n\nBob,\"say \"\"hi\"\"\nbye\"\nCarol,";
+
+        let rows = csv::parse(text)?;
+        assert_eq!(rows, vec![
+            vec!["name", "notes"],
+            vec!["Alice", "likes, commas"],
+            vec!["Bob", "say \"hi\"\nbye"],
+            vec!["Carol", ""],
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_roundtrip() -> anyhow::Result<()> {
+        let fields = ["a\"b", "c,d", "e\nf", ""];
+        assert_eq!(csv::parse(&csv::write_row(&fields))?, vec![fields.to_vec()]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_unterminated_quote() {
+        assert!(csv::parse("a,\"b").is_err());
+    }
+}
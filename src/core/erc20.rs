@@ -0,0 +1,118 @@
+use web3::{ethabi, types::{Address, Log, H256, U256, U64}};
+
+const ERC20_TRANSFER_ABI: &[u8] = include_bytes!("../../abi/erc20_transfer.json");
+const ERC20_APPROVE_ABI: &[u8] = include_bytes!("../../abi/erc20_approve.json");
+
+const ERR_INVALID_TRANSFER_LOG: &str = "Log is not an ERC-20 transfer";
+const ERR_INVALID_APPROVAL_LOG: &str = "Log is not an ERC-20 approval";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenTransfer {
+    pub contract_address: Address,
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    pub transaction_hash: Option<H256>,
+    pub block_number: Option<U64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenApproval {
+    pub contract_address: Address,
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub block_number: Option<U64>,
+}
+
+// Token movement requested by transfer, transferFrom or approve calldata
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenCall {
+    pub function: String,
+    pub recipient: Address,
+    pub value: U256,
+}
+
+pub fn encode_transfer(to: Address, amount: U256) -> anyhow::Result<Vec<u8>> {
+    let contract = ethabi::Contract::load(ERC20_TRANSFER_ABI)?;
+    let data = contract.function("transfer")?
+        .encode_input(&[ethabi::Token::Address(to), ethabi::Token::Uint(amount)])?;
+    Ok(data)
+}
+
+// NOTE: an approval lets the spender move the tokens, so it counts as paying them
+pub fn decode_token_call(data: &[u8]) -> Option<TokenCall> {
+    let (selector, input) = (data.get(..4)?, data.get(4..)?);
+    let transfer = ethabi::Contract::load(ERC20_TRANSFER_ABI).ok()?;
+    let approve = ethabi::Contract::load(ERC20_APPROVE_ABI).ok()?;
+    let function = [transfer.function("transfer").ok()?, transfer.function("transferFrom").ok()?, approve.function("approve").ok()?]
+        .into_iter()
+        .find(|function| function.short_signature() == selector)?;
+
+    let mut tokens = function.decode_input(input).ok()?.into_iter().rev();
+    let value = tokens.next()?.into_uint()?;
+    let recipient = tokens.next()?.into_address()?;
+    Some(TokenCall { function: function.name.clone(), recipient, value })
+}
+
+pub fn transfer_event_topic() -> anyhow::Result<H256> {
+    let contract = ethabi::Contract::load(ERC20_TRANSFER_ABI)?;
+    Ok(contract.event("Transfer")?.signature())
+}
+
+pub fn decode_transfer_log(log: &Log) -> anyhow::Result<TokenTransfer> {
+    let contract = ethabi::Contract::load(ERC20_TRANSFER_ABI)?;
+    let parsed = contract.event("Transfer")?.parse_log(ethabi::RawLog {
+        topics: log.topics.clone(),
+        data: log.data.0.clone(),
+    })?;
+
+    let param = |index: usize| parsed.params.get(index).map(|param| param.value.clone());
+    match (param(0), param(1), param(2)) {
+        (Some(ethabi::Token::Address(from)), Some(ethabi::Token::Address(to)), Some(ethabi::Token::Uint(value))) => {
+            Ok(TokenTransfer {
+                contract_address: log.address,
+                from,
+                to,
+                value,
+                transaction_hash: log.transaction_hash,
+                block_number: log.block_number,
+            })
+        },
+        _ => Err(anyhow::anyhow!(ERR_INVALID_TRANSFER_LOG)),
+    }
+}
+
+pub fn encode_approve(spender: Address, amount: U256) -> anyhow::Result<Vec<u8>> {
+    let contract = ethabi::Contract::load(ERC20_APPROVE_ABI)?;
+    let data = contract.function("approve")?
+        .encode_input(&[ethabi::Token::Address(spender), ethabi::Token::Uint(amount)])?;
+    Ok(data)
+}
+
+pub fn approval_event_topic() -> anyhow::Result<H256> {
+    let contract = ethabi::Contract::load(ERC20_APPROVE_ABI)?;
+    Ok(contract.event("Approval")?.signature())
+}
+
+pub fn decode_approval_log(log: &Log) -> anyhow::Result<TokenApproval> {
+    let contract = ethabi::Contract::load(ERC20_APPROVE_ABI)?;
+    let parsed = contract.event("Approval")?.parse_log(ethabi::RawLog {
+        topics: log.topics.clone(),
+        data: log.data.0.clone(),
+    })?;
+
+    let param = |index: usize| parsed.params.get(index).map(|param| param.value.clone());
+    match (param(0), param(1), param(2)) {
+        (Some(ethabi::Token::Address(owner)), Some(ethabi::Token::Address(spender)), Some(ethabi::Token::Uint(value))) => {
+            Ok(TokenApproval {
+                contract_address: log.address,
+                owner,
+                spender,
+                value,
+                block_number: log.block_number,
+            })
+        },
+        _ => Err(anyhow::anyhow!(ERR_INVALID_APPROVAL_LOG)),
+    }
+}
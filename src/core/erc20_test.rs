@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use web3::types::{Address, Log, H256, U256};
+    use crate::core::erc20;
+
+    #[test]
+    fn test_encode_transfer() -> anyhow::Result<()> {
+        let to = Address::from_low_u64_be(132);
+        let amount = U256::exp10(18);
+
+        let data = erc20::encode_transfer(to, amount)?;
+        assert_eq!(data.len(), 4 + 32 + 32);
+        assert_eq!(hex::encode(&data[..4]), "a9059cbb");
+        assert_eq!(&data[16..36], to.as_bytes());
+        assert_eq!(U256::from_big_endian(&data[36..68]), amount);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_transfer_log() -> anyhow::Result<()> {
+        let from = Address::from_low_u64_be(1);
+        let to = Address::from_low_u64_be(2);
+        let amount = U256::exp10(6);
+        let log = Log {
+            address: Address::from_low_u64_be(3),
+            topics: vec![erc20::transfer_event_topic()?, H256::from(from), H256::from(to)],
+            data: web3::types::Bytes({
+                let mut data = [0u8; 32];
+                amount.to_big_endian(&mut data);
+                data.to_vec()
+            }),
+            block_hash: None,
+            block_number: Some(7.into()),
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+
+        let transfer = erc20::decode_transfer_log(&log)?;
+        assert_eq!(hex::encode(erc20::transfer_event_topic()?), "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+        assert_eq!(transfer.contract_address, Address::from_low_u64_be(3));
+        assert_eq!(transfer.from, from);
+        assert_eq!(transfer.to, to);
+        assert_eq!(transfer.value, amount);
+        assert_eq!(transfer.block_number, Some(7.into()));
+
+        // Approval log doesn't match the transfer signature
+        let approval = Log { topics: vec![H256::from_low_u64_be(1), H256::from(from), H256::from(to)], ..log };
+        assert!(erc20::decode_transfer_log(&approval).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_approve() -> anyhow::Result<()> {
+        let spender = Address::from_low_u64_be(0x5e11);
+
+        let data = erc20::encode_approve(spender, U256::zero())?;
+        assert_eq!(data.len(), 4 + 32 + 32);
+        assert_eq!(hex::encode(&data[..4]), "095ea7b3");
+        assert_eq!(&data[16..36], spender.as_bytes());
+        assert!(U256::from_big_endian(&data[36..68]).is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_approval_log() -> anyhow::Result<()> {
+        let owner = Address::from_low_u64_be(1);
+        let spender = Address::from_low_u64_be(2);
+        let log = Log {
+            address: Address::from_low_u64_be(3),
+            topics: vec![erc20::approval_event_topic()?, H256::from(owner), H256::from(spender)],
+            data: web3::types::Bytes(vec![0xff; 32]),
+            block_hash: None,
+            block_number: Some(9.into()),
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+
+        let approval = erc20::decode_approval_log(&log)?;
+        assert_eq!(hex::encode(erc20::approval_event_topic()?), "8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+        assert_eq!(approval.contract_address, Address::from_low_u64_be(3));
+        assert_eq!(approval.owner, owner);
+        assert_eq!(approval.spender, spender);
+        assert_eq!(approval.value, U256::MAX);
+        assert_eq!(approval.block_number, Some(9.into()));
+
+        let transfer = Log { topics: vec![erc20::transfer_event_topic()?, H256::from(owner), H256::from(spender)], ..log };
+        assert!(erc20::decode_approval_log(&transfer).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_token_call() -> anyhow::Result<()> {
+        let recipient = Address::from_low_u64_be(0xb0b);
+        let call = erc20::decode_token_call(&erc20::encode_transfer(recipient, 5.into())?).unwrap();
+        assert_eq!((call.function.as_str(), call.recipient, call.value), ("transfer", recipient, 5.into()));
+        let call = erc20::decode_token_call(&erc20::encode_approve(recipient, U256::MAX)?).unwrap();
+        assert_eq!((call.function.as_str(), call.recipient, call.value), ("approve", recipient, U256::MAX));
+
+        // transferFrom(from, to, value) pays the second address
+        let mut data = hex::decode("23b872dd")?;
+        data.extend(web3::ethabi::encode(&[
+            web3::ethabi::Token::Address(Address::from_low_u64_be(1)),
+            web3::ethabi::Token::Address(recipient),
+            web3::ethabi::Token::Uint(9.into()),
+        ]));
+        let call = erc20::decode_token_call(&data).unwrap();
+        assert_eq!((call.function.as_str(), call.recipient, call.value), ("transferFrom", recipient, 9.into()));
+
+        assert!(erc20::decode_token_call(&[]).is_none());
+        assert!(erc20::decode_token_call(&data[..40]).is_none());
+        assert!(erc20::decode_token_call(&hex::decode("095ea7b4")?).is_none());
+        Ok(())
+    }
+}
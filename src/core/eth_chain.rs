@@ -37,18 +37,24 @@ impl EthChain {
         TESTNET_CHAINS.contains(self)
     }
 
-    #[allow(dead_code)]
+    // NOTE from https://chainlist.org
     pub fn get_chain_id(&self) -> u64 {
         match self {
             EthChain::EthereumMainnet => 1,
-            EthChain::EthereumSepolia => 2,
+            EthChain::EthereumSepolia => 11155111,
             EthChain::OptimismMainnet => 10,
-            EthChain::OptimismSepolia => 11,
+            EthChain::OptimismSepolia => 11155420,
             EthChain::ArbitrumMainnet => 42161,
-            EthChain::ArbitrumSepolia => 421611,
+            EthChain::ArbitrumSepolia => 421614,
         }
     }
 
+    pub fn from_chain_id(chain_id: u64) -> Option<EthChain> {
+        MAINNET_CHAINS.iter().chain(TESTNET_CHAINS.iter())
+            .find(|chain| chain.get_chain_id() == chain_id)
+            .copied()
+    }
+
     pub fn finalize_endpoint_url(&self, endpoint_url: &str) -> String {
         let chain_name = match self {
             EthChain::EthereumMainnet => "mainnet",
@@ -61,6 +67,14 @@ impl EthChain {
         format!("https://{}.{}", chain_name, endpoint_url)
     }
 
+    // First block to scan for token transfers, none of the listed tokens existed before it
+    pub fn get_history_start_block(&self) -> u64 {
+        match self {
+            EthChain::EthereumMainnet => 4_000_000,
+            _ => 0,
+        }
+    }
+
     // NOTE from https://docs.chain.link/data-feeds/price-feeds/addresses
     pub fn get_chainlink_contract_address(&self) -> web3::types::Address {
         match self {
@@ -3,20 +3,27 @@ mod tests {
     use test_case::test_case;
     use super::super::eth_chain::EthChain;
 
-    #[test_case(EthChain::EthereumMainnet, false)]
-    #[test_case(EthChain::EthereumSepolia, true)]
-    #[test_case(EthChain::OptimismMainnet, false)]
-    #[test_case(EthChain::OptimismSepolia, true)]
-    #[test_case(EthChain::ArbitrumMainnet, false)]
-    #[test_case(EthChain::ArbitrumSepolia, true)]
-    fn test_chain_utility(chain: EthChain, is_test_net: bool) -> anyhow::Result<()> {
+    #[test_case(EthChain::EthereumMainnet, false, 1)]
+    #[test_case(EthChain::EthereumSepolia, true, 11155111)]
+    #[test_case(EthChain::OptimismMainnet, false, 10)]
+    #[test_case(EthChain::OptimismSepolia, true, 11155420)]
+    #[test_case(EthChain::ArbitrumMainnet, false, 42161)]
+    #[test_case(EthChain::ArbitrumSepolia, true, 421614)]
+    fn test_chain_utility(chain: EthChain, is_test_net: bool, chain_id: u64) -> anyhow::Result<()> {
         let endpoint_url = "test";
 
         assert!(chain.finalize_endpoint_url(&endpoint_url).starts_with("https://"));
         assert!(!chain.get_display_name().is_empty());
         assert_eq!(chain.is_test_network(), is_test_net);
+        assert_eq!(chain.get_chain_id(), chain_id);
+        assert_eq!(EthChain::from_chain_id(chain_id), Some(chain));
         assert!(chain.get_chainlink_contract_address().to_string().starts_with("0x"));
 
         Ok(())
     }
+
+    #[test]
+    fn test_unknown_chain_id() {
+        assert_eq!(EthChain::from_chain_id(56), None);
+    }
 }
@@ -1,20 +1,22 @@
 use web3::types::{Address, U256};
 
+use super::amount::{Amount, ETH_DECIMALS, GWEI_DECIMALS};
+
 pub const ERR_INVALID_ADDRESS_LENGTH: &str = "Invalid address length. Ethereum address must be 42 characters long.";
 pub const ERR_INVALID_ADDRESS_PREFIX: &str = "Ethereum address must start with '0x'.";
 pub const ERR_INVALID_ADDRESS: &str = "Invalid Ethereum address format. It must be a valid hexadecimal string.";
 
-pub fn wei_to_eth(wei: U256) -> f64 {
-    let ether = web3::types::U256::exp10(18); // 1 Ether = 10^18 Wei
-    let eth_value = wei.as_u128() as f64 / ether.as_u128() as f64;
-    eth_value
+pub fn wei_to_eth(wei: U256) -> Amount {
+    Amount::from_wei(wei)
+}
+
+// NOTE: fails on amounts with more than 18 decimals rather than dropping them
+pub fn eth_to_wei(eth: &Amount) -> anyhow::Result<U256> {
+    eth.to_decimals(ETH_DECIMALS)
 }
 
-#[allow(dead_code)]
-pub fn eth_to_wei(eth: f64) -> U256 {
-    let ether = web3::types::U256::exp10(18); // 1 Ether = 10^18 Wei
-    let wei_value = (eth * ether.as_u128() as f64) as u128;
-    U256::from(wei_value)
+pub fn gwei_to_wei(gwei: &str) -> anyhow::Result<U256> {
+    Ok(Amount::parse(gwei, GWEI_DECIMALS)?.raw)
 }
 
 pub fn str_to_eth_address(address: &str) -> anyhow::Result<Address> {
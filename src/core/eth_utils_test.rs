@@ -4,17 +4,26 @@ mod tests {
     use test_case::test_case;
     use crate::core::eth_utils;
 
-    #[test_case(1_000_000_000_000, 0.000001)]
-    #[test_case(1_000_000_000_000_000_000, 1.0)]
-    #[test_case(1_000_000_000_000_000_000_000, 1000.0)]
-    fn test_wei_to_eth_and_back(wei: u128, eth: f64) {
+    #[test_case(1_000_000_000_000, "0.000001")]
+    #[test_case(1_000_000_000_000_000_000, "1")]
+    #[test_case(1_000_000_000_000_000_000_000, "1000")]
+    #[test_case(u128::MAX, "340282366920938463463.374607431768211455")]
+    fn test_wei_to_eth_and_back(wei: u128, eth: &str) -> anyhow::Result<()> {
         let wei = U256::from(wei);
 
         let eth_back = eth_utils::wei_to_eth(wei);
-        assert_eq!(eth_back, eth);
+        assert_eq!(eth_back.to_string(), eth);
 
-        let wei_back = eth_utils::eth_to_wei(eth_back);
+        let wei_back = eth_utils::eth_to_wei(&eth.parse()?)?;
         assert_eq!(wei_back, wei);
+        Ok(())
+    }
+
+    #[test_case("1.5", Some(1_500_000_000))]
+    #[test_case("0.000000001", Some(1))]
+    #[test_case("0.0000000001", None)]
+    fn test_gwei_to_wei(gwei: &str, wei: Option<u64>) {
+        assert_eq!(eth_utils::gwei_to_wei(gwei).ok(), wei.map(U256::from));
     }
 
     #[test_case("0x0000000000000000000000000000000000000084", Ok(web3::types::Address::from_low_u64_be(132)))]
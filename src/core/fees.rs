@@ -0,0 +1,170 @@
+use web3::types::{FeeHistory, U256};
+
+pub const FEE_HISTORY_BLOCKS: u64 = 10;
+pub const FEE_HISTORY_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+
+// NOTE: doubling the base fee keeps the transaction valid for ~6 consecutive full blocks
+const BASE_FEE_MULTIPLIER: u64 = 2;
+
+// NOTE: nodes accept a replacement only if both fees are bumped by at least 10%
+const REPLACEMENT_FEE_BUMP_PERCENT: u64 = 10;
+
+const ERR_EMPTY_FEE_HISTORY: &str = "Fee history has no base fee data";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeeTier {
+    Slow,
+    Normal,
+    Fast,
+    Custom { max_priority_fee_per_gas: U256 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasPrices {
+    pub base_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+}
+
+// Cost of posting the transaction data to L1 on rollups
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum L1DataFee {
+    #[default]
+    None,
+    Charged(U256),      // OP stack: wei charged on top of the L2 gas
+    IncludedGas(U256),  // Arbitrum: gas units already included in the gas limit
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasFees {
+    pub gas_limit: U256,
+    pub prices: GasPrices,
+    pub l1_data_fee: L1DataFee,
+}
+
+impl FeeTier {
+    fn percentile_index(&self) -> Option<usize> {
+        match self {
+            FeeTier::Slow => Some(0),
+            FeeTier::Normal => Some(1),
+            FeeTier::Fast => Some(2),
+            FeeTier::Custom { .. } => None,
+        }
+    }
+}
+
+impl GasPrices {
+    pub fn with_priority_fee(base_fee_per_gas: U256, max_priority_fee_per_gas: U256) -> Self {
+        Self {
+            base_fee_per_gas,
+            max_priority_fee_per_gas,
+            max_fee_per_gas: base_fee_per_gas * BASE_FEE_MULTIPLIER + max_priority_fee_per_gas,
+        }
+    }
+
+    pub fn from_fee_history(history: &FeeHistory, tier: FeeTier) -> anyhow::Result<Self> {
+        // NOTE: the last base fee is the one predicted for the next block
+        let base_fee_per_gas = *history.base_fee_per_gas.last()
+            .ok_or_else(|| anyhow::anyhow!(ERR_EMPTY_FEE_HISTORY))?;
+
+        let max_priority_fee_per_gas = match tier {
+            FeeTier::Custom { max_priority_fee_per_gas } => max_priority_fee_per_gas,
+            _ => {
+                let index = tier.percentile_index().unwrap_or_default();
+                let rewards = history.reward.as_ref()
+                    .map(|rewards| rewards.iter().filter_map(|block| block.get(index).copied()).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                if rewards.is_empty() {
+                    U256::zero()
+                } else {
+                    rewards.iter().fold(U256::zero(), |acc, reward| acc + reward) / rewards.len()
+                }
+            }
+        };
+
+        Ok(Self::with_priority_fee(base_fee_per_gas, max_priority_fee_per_gas))
+    }
+
+    // Prices for a transaction replacing one sent with `original` prices
+    pub fn bump_for_replacement(&self, original: &GasPrices) -> Self {
+        let bump = |value: U256| value + (value * REPLACEMENT_FEE_BUMP_PERCENT + 99) / 100;
+        let max_priority_fee_per_gas = self.max_priority_fee_per_gas.max(bump(original.max_priority_fee_per_gas));
+        let max_fee_per_gas = self.max_fee_per_gas
+            .max(bump(original.max_fee_per_gas))
+            .max(self.base_fee_per_gas + max_priority_fee_per_gas);
+
+        Self {
+            base_fee_per_gas: self.base_fee_per_gas,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+        }
+    }
+}
+
+impl GasFees {
+    pub fn new(gas_limit: U256, prices: GasPrices) -> Self {
+        Self { gas_limit, prices, l1_data_fee: L1DataFee::None }
+    }
+
+    pub fn with_l1_data_fee(mut self, l1_data_fee: L1DataFee) -> Self {
+        self.l1_data_fee = l1_data_fee;
+        self
+    }
+
+    // Expected cost if the base fee stays the same
+    pub fn min_cost(&self) -> U256 {
+        self.l2_min_cost() + self.l1_min_cost()
+    }
+
+    // Upper bound the transaction can be charged
+    pub fn max_cost(&self) -> U256 {
+        self.l2_max_cost() + self.l1_max_cost()
+    }
+
+    pub fn l2_min_cost(&self) -> U256 {
+        self.l2_gas_limit() * (self.prices.base_fee_per_gas + self.prices.max_priority_fee_per_gas)
+    }
+
+    pub fn l2_max_cost(&self) -> U256 {
+        self.l2_gas_limit() * self.prices.max_fee_per_gas
+    }
+
+    pub fn l1_min_cost(&self) -> U256 {
+        match self.l1_data_fee {
+            L1DataFee::None => U256::zero(),
+            L1DataFee::Charged(fee) => fee,
+            L1DataFee::IncludedGas(gas) => self.l1_gas(gas) * (self.prices.base_fee_per_gas + self.prices.max_priority_fee_per_gas),
+        }
+    }
+
+    pub fn l1_max_cost(&self) -> U256 {
+        match self.l1_data_fee {
+            L1DataFee::None => U256::zero(),
+            L1DataFee::Charged(fee) => fee,
+            L1DataFee::IncludedGas(gas) => self.l1_gas(gas) * self.prices.max_fee_per_gas,
+        }
+    }
+
+    pub fn has_l1_data_fee(&self) -> bool {
+        self.l1_data_fee != L1DataFee::None
+    }
+
+    // Paid on top of the gas, so it has to travel with a transaction signed offline
+    pub fn charged_l1_fee(&self) -> U256 {
+        match self.l1_data_fee {
+            L1DataFee::Charged(fee) => fee,
+            _ => U256::zero(),
+        }
+    }
+
+    fn l1_gas(&self, gas: U256) -> U256 {
+        gas.min(self.gas_limit)
+    }
+
+    fn l2_gas_limit(&self) -> U256 {
+        match self.l1_data_fee {
+            L1DataFee::IncludedGas(gas) => self.gas_limit - self.l1_gas(gas),
+            _ => self.gas_limit,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{BlockNumber, FeeHistory, U256};
+    use crate::core::fees::{FeeTier, GasFees, GasPrices, L1DataFee};
+
+    fn test_fee_history() -> FeeHistory {
+        FeeHistory {
+            oldest_block: BlockNumber::Number(18000000.into()),
+            base_fee_per_gas: vec![90.into(), 95.into(), 100.into()],
+            gas_used_ratio: vec![0.4, 0.6],
+            reward: Some(vec![
+                vec![1.into(), 4.into(), 10.into()],
+                vec![3.into(), 6.into(), 20.into()],
+            ]),
+        }
+    }
+
+    #[test_case(FeeTier::Slow, 2, 202)]
+    #[test_case(FeeTier::Normal, 5, 205)]
+    #[test_case(FeeTier::Fast, 15, 215)]
+    #[test_case(FeeTier::Custom { max_priority_fee_per_gas: 7.into() }, 7, 207)]
+    fn test_gas_prices_from_fee_history(tier: FeeTier, priority_fee: u64, max_fee: u64) -> anyhow::Result<()> {
+        let prices = GasPrices::from_fee_history(&test_fee_history(), tier)?;
+        assert_eq!(prices.base_fee_per_gas, U256::from(100));
+        assert_eq!(prices.max_priority_fee_per_gas, U256::from(priority_fee));
+        assert_eq!(prices.max_fee_per_gas, U256::from(max_fee));
+        Ok(())
+    }
+
+    #[test]
+    fn test_gas_prices_without_rewards() -> anyhow::Result<()> {
+        let mut history = test_fee_history();
+        history.reward = None;
+
+        let prices = GasPrices::from_fee_history(&history, FeeTier::Fast)?;
+        assert_eq!(prices.max_priority_fee_per_gas, U256::zero());
+
+        history.base_fee_per_gas.clear();
+        assert!(GasPrices::from_fee_history(&history, FeeTier::Fast).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_gas_fees_cost_range() {
+        let prices = GasPrices::with_priority_fee(100.into(), 5.into());
+        let fees = GasFees::new(21000.into(), prices);
+        assert_eq!(fees.min_cost(), U256::from(21000 * 105));
+        assert_eq!(fees.max_cost(), U256::from(21000 * 205));
+    }
+
+    #[test_case(L1DataFee::None, 21000 * 105, 0, 21000 * 205; "no data fee")]
+    #[test_case(L1DataFee::Charged(5000.into()), 21000 * 105, 5000, 21000 * 205 + 5000; "charged on top")]
+    #[test_case(L1DataFee::IncludedGas(1000.into()), 20000 * 105, 1000 * 105, 21000 * 205; "included in gas limit")]
+    #[test_case(L1DataFee::IncludedGas(50000.into()), 0, 21000 * 105, 21000 * 205; "capped by gas limit")]
+    fn test_gas_fees_l1_data_fee(l1_data_fee: L1DataFee, l2_min_cost: u64, l1_min_cost: u64, max_cost: u64) {
+        let prices = GasPrices::with_priority_fee(100.into(), 5.into());
+        let fees = GasFees::new(21000.into(), prices).with_l1_data_fee(l1_data_fee);
+        assert_eq!(fees.l2_min_cost(), U256::from(l2_min_cost));
+        assert_eq!(fees.l1_min_cost(), U256::from(l1_min_cost));
+        assert_eq!(fees.min_cost(), U256::from(l2_min_cost + l1_min_cost));
+        assert_eq!(fees.max_cost(), U256::from(max_cost));
+    }
+
+    #[test_case(100, 5, 10, 1, 110, 6)]
+    #[test_case(100, 50, 300, 60, 300, 60)]
+    #[test_case(100, 5, 201, 3, 201, 6)]
+    fn test_bump_for_replacement(
+        original_max_fee: u64,
+        original_priority_fee: u64,
+        current_max_fee: u64,
+        current_priority_fee: u64,
+        max_fee: u64,
+        priority_fee: u64
+    ) {
+        let original = GasPrices {
+            base_fee_per_gas: 50.into(),
+            max_priority_fee_per_gas: original_priority_fee.into(),
+            max_fee_per_gas: original_max_fee.into(),
+        };
+        let current = GasPrices {
+            base_fee_per_gas: 50.into(),
+            max_priority_fee_per_gas: current_priority_fee.into(),
+            max_fee_per_gas: current_max_fee.into(),
+        };
+
+        let bumped = current.bump_for_replacement(&original);
+        assert_eq!(bumped.max_fee_per_gas, U256::from(max_fee));
+        assert_eq!(bumped.max_priority_fee_per_gas, U256::from(priority_fee));
+        assert!(bumped.max_fee_per_gas >= original.max_fee_per_gas * 11 / 10);
+    }
+}
@@ -0,0 +1,207 @@
+use web3::types::{Address, H256};
+
+use super::{
+    amount::Amount, csv, eth_chain::EthChain,
+    transaction::{TransactionResult, TransactionStatus},
+    transaction_label::TransactionLabel
+};
+
+const CSV_HEADER: [&str; 16] = [
+    "date", "chain", "hash", "status", "direction", "counterparty", "token", "amount", "fee",
+    "amount_usd", "fee_usd", "eth_usd_rate", "unpriced", "category", "tags", "note",
+];
+const CSV_TAGS_SEPARATOR: &str = ";";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+// NOTE: wrapped ETH has no feed of its own, it is valued with the ETH rate
+const ETH_PRICED_CURRENCIES: [&str; 2] = ["ETH", "WETH"];
+
+const ERR_INVALID_DATE: &str = "Dates should be YYYY-MM-DD";
+const ERR_INVALID_DATE_RANGE: &str = "Start date is after the end date";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => ExportFormat::Json,
+            _ => ExportFormat::Csv,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    In,
+    Out,
+    #[serde(rename = "self")]
+    SelfTransfer,
+}
+
+impl Direction {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            Direction::In => "in",
+            Direction::Out => "out",
+            Direction::SelfTransfer => "self",
+        }
+    }
+}
+
+// Dates are inclusive days in UTC, any chain if empty
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryFilter {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub chains: Vec<EthChain>,
+}
+
+impl HistoryFilter {
+    pub fn new(from: &str, to: &str, chains: Vec<EthChain>) -> anyhow::Result<Self> {
+        let from = parse_day_start(from)?;
+        let to = parse_day_start(to)?.map(|day| day + SECONDS_PER_DAY - 1);
+        if from.zip(to).is_some_and(|(from, to)| from > to) {
+            return Err(anyhow::anyhow!(ERR_INVALID_DATE_RANGE));
+        }
+        Ok(Self { from, to, chains })
+    }
+
+    pub fn includes_chain(&self, chain: EthChain) -> bool {
+        self.chains.is_empty() || self.chains.contains(&chain)
+    }
+
+    pub fn includes_time(&self, timestamp: i64) -> bool {
+        self.from.is_none_or(|from| timestamp >= from) && self.to.is_none_or(|to| timestamp <= to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct HistoryRecord {
+    pub date: String,
+    pub chain: EthChain,
+    pub hash: H256,
+    pub status: String,
+    pub direction: Direction,
+    pub counterparty: Option<Address>,
+    pub token: String,
+    pub amount: String,
+    pub fee: String,
+    pub amount_usd: Option<f64>,
+    pub fee_usd: Option<f64>,
+    pub eth_usd_rate: Option<f64>,
+    pub unpriced: bool,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub note: String,
+    #[serde(skip)]
+    pub timestamp: i64,
+}
+
+impl HistoryRecord {
+    // Only mined transactions belong to the history, the fee is only paid by the sender
+    // Rows missing a fiat value because the rate at the block is unknown are marked unpriced
+    pub fn new(
+        account: Address,
+        transaction: &TransactionResult,
+        timestamp: i64,
+        eth_usd_rate: Option<f64>,
+        currency_usd_rate: Option<f64>,
+        label: Option<&TransactionLabel>,
+    ) -> Self {
+        let direction = match (transaction.from == Some(account), transaction.to == Some(account)) {
+            (true, true) => Direction::SelfTransfer,
+            (true, false) => Direction::Out,
+            _ => Direction::In,
+        };
+        let counterparty = match direction {
+            Direction::Out => transaction.to,
+            Direction::In => transaction.from,
+            Direction::SelfTransfer => Some(account),
+        };
+
+        // NOTE: a failed transaction moves nothing, but its fee is still paid
+        let failed = transaction.status == TransactionStatus::Failed;
+        let amount = if failed { Amount::zero(transaction.amount.decimals) } else { transaction.amount };
+        let fee = if direction == Direction::In { Amount::zero(transaction.fee.decimals) } else { transaction.fee };
+
+        let amount_usd = currency_usd_rate.map(|rate| round_cents(amount.to_f64() * rate));
+        let fee_usd = eth_usd_rate.map(|rate| round_cents(fee.to_f64() * rate));
+        let label = label.cloned().unwrap_or_default();
+
+        Self {
+            date: chrono::DateTime::from_timestamp(timestamp, 0)
+                .map(|date| date.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+                .unwrap_or_default(),
+            chain: transaction.chain,
+            hash: transaction.hash,
+            status: if failed { "failed" } else { "confirmed" }.to_string(),
+            direction,
+            counterparty,
+            token: transaction.currency.clone(),
+            amount: amount.to_string(),
+            fee: fee.to_string(),
+            amount_usd,
+            fee_usd,
+            eth_usd_rate,
+            unpriced: amount_usd.is_none() || fee_usd.is_none(),
+            category: label.category,
+            tags: label.tags,
+            note: label.note,
+            timestamp,
+        }
+    }
+}
+
+pub fn is_eth_priced(currency: &str) -> bool {
+    ETH_PRICED_CURRENCIES.contains(&currency)
+}
+
+pub fn to_csv(records: &[HistoryRecord]) -> String {
+    let optional = |value: Option<f64>| value.map(|value| value.to_string()).unwrap_or_default();
+
+    let mut text = csv::write_row(&CSV_HEADER);
+    for record in records {
+        text.push_str(&csv::write_row(&[
+            record.date.clone(),
+            record.chain.get_display_name().to_string(),
+            format!("{:?}", record.hash),
+            record.status.clone(),
+            record.direction.get_display_name().to_string(),
+            record.counterparty.map(|address| format!("{:?}", address)).unwrap_or_default(),
+            record.token.clone(),
+            record.amount.clone(),
+            record.fee.clone(),
+            optional(record.amount_usd),
+            optional(record.fee_usd),
+            optional(record.eth_usd_rate),
+            record.unpriced.to_string(),
+            record.category.clone(),
+            record.tags.join(CSV_TAGS_SEPARATOR),
+            record.note.clone(),
+        ]));
+    }
+    text
+}
+
+pub fn to_json(records: &[HistoryRecord]) -> anyhow::Result<String> {
+    Ok(serde_json::to_string_pretty(records)?)
+}
+
+fn parse_day_start(text: &str) -> anyhow::Result<Option<i64>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let date = chrono::NaiveDate::parse_from_str(text, DATE_FORMAT)
+        .map_err(|_| anyhow::anyhow!(ERR_INVALID_DATE))?;
+    Ok(date.and_hms_opt(0, 0, 0).map(|time| time.and_utc().timestamp()))
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
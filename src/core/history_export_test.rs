@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{Address, H256};
+    use crate::core::{
+        amount::Amount, eth_chain::EthChain,
+        history_export::{self, Direction, ExportFormat, HistoryFilter, HistoryRecord},
+        transaction::{TransactionResult, TransactionStatus},
+        transaction_label::TransactionLabel
+    };
+
+    // 2024-03-01T12:00:00Z
+    const TIMESTAMP: i64 = 1_709_294_400;
+
+    fn transaction(from: u64, to: u64, amount: &str, currency: &str, status: TransactionStatus) -> TransactionResult {
+        TransactionResult {
+            hash: H256::from_low_u64_be(1),
+            block_number: Some(100.into()),
+            from: Some(Address::from_low_u64_be(from)),
+            to: Some(Address::from_low_u64_be(to)),
+            amount: amount.parse().unwrap(),
+            currency: currency.to_string(),
+            fee: Amount::parse("0.001", 18).unwrap(),
+            chain: EthChain::OptimismMainnet,
+            status,
+            nonce: None,
+            gas_used: None,
+            confirmations: 10,
+            replaces: None,
+            replaced_by: None,
+            swap: None,
+        }
+    }
+
+    #[test_case("", "", None, None; "open range")]
+    #[test_case("2024-03-01", "2024-03-01", Some(1_709_251_200), Some(1_709_337_599); "single day")]
+    #[test_case(" 2024-03-02 ", "", Some(1_709_337_600), None; "from only")]
+    fn test_history_filter(from: &str, to: &str, expected_from: Option<i64>, expected_to: Option<i64>) {
+        let filter = HistoryFilter::new(from, to, vec![EthChain::OptimismMainnet]).unwrap();
+        assert_eq!((filter.from, filter.to), (expected_from, expected_to));
+        assert!(filter.includes_chain(EthChain::OptimismMainnet));
+        assert!(!filter.includes_chain(EthChain::EthereumMainnet));
+    }
+
+    #[test]
+    fn test_history_filter_errors() {
+        assert!(HistoryFilter::new("01.03.2024", "", Vec::new()).is_err());
+        assert!(HistoryFilter::new("2024-03-02", "2024-03-01", Vec::new()).is_err());
+
+        let filter = HistoryFilter::new("2024-03-01", "2024-03-01", Vec::new()).unwrap();
+        assert!(filter.includes_chain(EthChain::ArbitrumMainnet));
+        assert!(filter.includes_time(TIMESTAMP));
+        assert!(!filter.includes_time(TIMESTAMP + 24 * 60 * 60));
+    }
+
+    #[test_case(transaction(1, 2, "1.5", "ETH", TransactionStatus::Successed), Some(2000.0), Direction::Out, ("1.5", "0.001"), (Some(3000.0), Some(2.0)); "outgoing")]
+    #[test_case(transaction(2, 1, "100", "USDC", TransactionStatus::Successed), Some(0.9999), Direction::In, ("100", "0"), (Some(99.99), Some(0.0)); "incoming token")]
+    #[test_case(transaction(2, 1, "1000", "PEPE", TransactionStatus::Successed), None, Direction::In, ("1000", "0"), (None, Some(0.0)); "unpriced token")]
+    #[test_case(transaction(1, 1, "1", "WETH", TransactionStatus::Failed), Some(2000.0), Direction::SelfTransfer, ("0", "0.001"), (Some(0.0), Some(2.0)); "failed to self")]
+    fn test_history_record(transaction: TransactionResult, currency_usd_rate: Option<f64>, direction: Direction, amounts: (&str, &str), fiat: (Option<f64>, Option<f64>)) {
+        let account = Address::from_low_u64_be(1);
+        let record = HistoryRecord::new(account, &transaction, TIMESTAMP, Some(2000.0), currency_usd_rate, None);
+
+        assert_eq!(record.date, "2024-03-01T12:00:00Z");
+        assert_eq!(record.direction, direction);
+        assert_eq!((record.amount.as_str(), record.fee.as_str()), amounts);
+        assert_eq!((record.amount_usd, record.fee_usd), fiat);
+        assert_eq!(record.unpriced, currency_usd_rate.is_none());
+    }
+
+    #[test]
+    fn test_export() -> anyhow::Result<()> {
+        let account = Address::from_low_u64_be(1);
+        let label = TransactionLabel::new("Rent, March", "home, monthly", "Housing");
+        let transaction = transaction(1, 2, "0.25", "ETH", TransactionStatus::Successed);
+        let records = vec![
+            HistoryRecord::new(account, &transaction, TIMESTAMP, Some(3456.78), Some(3456.78), Some(&label)),
+            HistoryRecord::new(account, &transaction, TIMESTAMP, None, None, None),
+        ];
+
+        let csv = history_export::to_csv(&records);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "date,chain,hash,status,direction,counterparty,token,amount,fee,amount_usd,fee_usd,eth_usd_rate,unpriced,category,tags,note");
+        assert_eq!(lines[1], format!("2024-03-01T12:00:00Z,Optimism Mainnet,{:?},confirmed,out,{:?},ETH,0.25,0.001,864.2,3.46,3456.78,false,Housing,home;monthly,\"Rent, March\"",
+            transaction.hash, Address::from_low_u64_be(2)));
+        assert!(lines[2].ends_with(",ETH,0.25,0.001,,,,true,,,"));
+
+        let json: serde_json::Value = serde_json::from_str(&history_export::to_json(&records)?)?;
+        assert_eq!(json[0]["direction"], "out");
+        assert_eq!(json[0]["tags"], serde_json::json!(["home", "monthly"]));
+        assert_eq!(json[1]["amount_usd"], serde_json::Value::Null);
+        assert_eq!(json[1]["unpriced"], true);
+        assert!(json[0].get("timestamp").is_none());
+        Ok(())
+    }
+
+    #[test_case("history.JSON", ExportFormat::Json)]
+    #[test_case("history.csv", ExportFormat::Csv)]
+    #[test_case("history", ExportFormat::Csv)]
+    fn test_export_format(path: &str, expected: ExportFormat) {
+        assert_eq!(ExportFormat::from_path(std::path::Path::new(path)), expected);
+    }
+}
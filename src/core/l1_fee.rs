@@ -0,0 +1,58 @@
+use rlp::RlpStream;
+use web3::types::{Address, TransactionParameters};
+
+use super::eth_chain::EthChain;
+
+// NOTE from https://docs.optimism.io/stack/smart-contracts#gaspriceoracle
+pub const GAS_PRICE_ORACLE_ADDRESS: &str = "0x420000000000000000000000000000000000000F";
+// NOTE from https://docs.arbitrum.io/build-decentralized-apps/nodeinterface/overview
+pub const NODE_INTERFACE_ADDRESS: &str = "0x00000000000000000000000000000000000000C8";
+
+const EIP1559_TRANSACTION_PREFIX: u8 = 0x02;
+
+// Rollups post every transaction to L1, which is paid on top of the L2 execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L1FeeOracle {
+    OpStack,    // GasPriceOracle predeploy
+    Arbitrum,   // NodeInterface precompile
+}
+
+impl L1FeeOracle {
+    pub fn for_chain(chain: EthChain) -> Option<Self> {
+        match chain {
+            EthChain::EthereumMainnet | EthChain::EthereumSepolia => None,
+            EthChain::OptimismMainnet | EthChain::OptimismSepolia => Some(L1FeeOracle::OpStack),
+            EthChain::ArbitrumMainnet | EthChain::ArbitrumSepolia => Some(L1FeeOracle::Arbitrum),
+        }
+    }
+
+    pub fn get_contract_address(&self) -> Address {
+        match self {
+            L1FeeOracle::OpStack => GAS_PRICE_ORACLE_ADDRESS,
+            L1FeeOracle::Arbitrum => NODE_INTERFACE_ADDRESS,
+        }
+        .parse()
+        .unwrap()
+    }
+}
+
+// Unsigned EIP-1559 payload as `GasPriceOracle.getL1Fee` expects it, the oracle pads for the signature
+pub fn encode_unsigned_transaction(transaction: &TransactionParameters, chain_id: u64) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(9);
+    stream.append(&chain_id);
+    stream.append(&transaction.nonce.unwrap_or_default());
+    stream.append(&transaction.max_priority_fee_per_gas.unwrap_or_default());
+    stream.append(&transaction.max_fee_per_gas.unwrap_or_default());
+    stream.append(&transaction.gas);
+    match transaction.to {
+        Some(to) => stream.append(&to),
+        None => stream.append(&""),
+    };
+    stream.append(&transaction.value);
+    stream.append(&transaction.data.0);
+    stream.begin_list(0); // Access list
+
+    let mut encoded = vec![EIP1559_TRANSACTION_PREFIX];
+    encoded.extend_from_slice(&stream.out());
+    encoded
+}
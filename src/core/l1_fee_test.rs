@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{Address, TransactionParameters};
+    use crate::core::{eth_chain::EthChain, l1_fee::{self, L1FeeOracle}};
+
+    #[test_case(EthChain::EthereumMainnet, None)]
+    #[test_case(EthChain::OptimismSepolia, Some(L1FeeOracle::OpStack))]
+    #[test_case(EthChain::ArbitrumMainnet, Some(L1FeeOracle::Arbitrum))]
+    fn test_oracle_for_chain(chain: EthChain, expected: Option<L1FeeOracle>) {
+        assert_eq!(L1FeeOracle::for_chain(chain), expected);
+    }
+
+    #[test]
+    fn test_encode_transfer() {
+        let transaction = TransactionParameters {
+            to: Some(Address::from_low_u64_be(1)),
+            gas: 21000.into(),
+            max_priority_fee_per_gas: Some(1.into()),
+            max_fee_per_gas: Some(2.into()),
+            ..Default::default()
+        };
+
+        let expected = format!("02df0a80010282520894{}018080c0", "00".repeat(19));
+        assert_eq!(hex::encode(l1_fee::encode_unsigned_transaction(&transaction, 10)), expected);
+    }
+
+    #[test]
+    fn test_encode_long_data() {
+        let transaction = TransactionParameters {
+            to: Some(Address::from_low_u64_be(1)),
+            nonce: Some(300.into()),
+            value: 1_000_000_000u64.into(),
+            data: vec![0xab; 60].into(),
+            ..Default::default()
+        };
+
+        let encoded = hex::encode(l1_fee::encode_unsigned_transaction(&transaction, 42161));
+        // Both the list of 101 bytes and the data of 60 bytes take a long length prefix
+        assert!(encoded.starts_with("02f86582a4b182012c8080830186a0"));
+        assert!(encoded.ends_with(&format!("843b9aca00b83c{}c0", "ab".repeat(60))));
+    }
+}
@@ -1,5 +1,9 @@
+pub mod amount;
+mod amount_test;
 pub mod eth_utils;
 mod eth_utils_test;
+pub mod csv;
+mod csv_test;
 pub mod key_pair;
 mod key_pair_test;
 pub mod seed_phrase;
@@ -8,9 +12,52 @@ pub mod eth_chain;
 mod eth_chain_test;
 pub mod token;
 mod token_test;
+pub mod address_book;
+mod address_book_test;
+pub mod payment_uri;
+mod payment_uri_test;
+pub mod erc20;
+mod erc20_test;
 pub mod balance;
 mod balance_test;
+pub mod fees;
+mod fees_test;
+pub mod l1_fee;
+mod l1_fee_test;
+pub mod revert;
+mod revert_test;
 pub mod transaction;
+mod transaction_test;
+pub mod transaction_label;
+mod transaction_label_test;
+pub mod history_export;
+mod history_export_test;
+pub mod nonce;
+mod nonce_test;
+pub mod unsigned_transaction;
+mod unsigned_transaction_test;
+pub mod batch;
+mod batch_test;
+pub mod allowance;
+mod allowance_test;
+pub mod nft;
+mod nft_test;
+pub mod contract;
+mod contract_test;
+pub mod review;
+mod review_test;
+pub mod policy;
+mod policy_test;
+pub mod bridge;
+mod bridge_test;
+pub mod swap;
+mod swap_test;
+pub mod safe;
+mod safe_test;
+pub mod smart_account;
+mod smart_account_test;
+pub mod bundler;
+mod bundler_test;
 pub mod provider;
 pub mod provider_eth;
 mod provider_test;
@@ -0,0 +1,160 @@
+use web3::{ethabi, types::{Address, Log, H256, U256}};
+
+use super::{amount::{Amount, ETH_DECIMALS}, eth_chain::EthChain, transaction::TransactionRequest};
+
+const ERC721_ABI: &[u8] = include_bytes!("../../abi/erc721.json");
+const ERC1155_ABI: &[u8] = include_bytes!("../../abi/erc1155.json");
+
+const ERR_NOT_NFT_TRANSFER_LOG: &str = "Log is not an NFT transfer";
+const ERR_ERC721_AMOUNT: &str = "ERC-721 token can only be transferred as a whole";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum NftStandard {
+    Erc721,
+    Erc1155,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NftTransfer {
+    pub standard: NftStandard,
+    pub contract_address: Address,
+    pub from: Address,
+    pub to: Address,
+    pub token_id: U256,
+    pub amount: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NftHolding {
+    pub chain: EthChain,
+    pub standard: NftStandard,
+    pub contract_address: Address,
+    pub collection: Option<String>,
+    pub token_id: U256,
+    pub amount: U256,
+    pub metadata_uri: Option<String>,
+}
+
+impl NftStandard {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            NftStandard::Erc721 => "ERC-721",
+            NftStandard::Erc1155 => "ERC-1155",
+        }
+    }
+}
+
+impl NftHolding {
+    pub fn collection_str(&self) -> String {
+        self.collection.clone().unwrap_or_else(|| format!("{:?}", self.contract_address))
+    }
+
+    pub fn apply_transfer(&mut self, account: Address, transfer: &NftTransfer) {
+        if transfer.to == account {
+            self.amount += transfer.amount;
+        }
+        if transfer.from == account {
+            self.amount = self.amount.saturating_sub(transfer.amount);
+        }
+    }
+
+    // Sent to the collection contract, ETH amount is always zero
+    pub fn transfer_request(&self, owner: Address, to: Address, amount: U256) -> anyhow::Result<TransactionRequest> {
+        Ok(TransactionRequest {
+            from: owner,
+            to: self.contract_address,
+            amount: Amount::zero(ETH_DECIMALS),
+            currency: "ETH".to_string(),
+            chain: self.chain,
+            fees: None,
+            data: Some(encode_safe_transfer(self.standard, owner, to, self.token_id, amount)?),
+        })
+    }
+}
+
+// NOTE: ERC-721 shares the Transfer signature with ERC-20, but has the token id indexed
+pub fn erc721_transfer_topic() -> anyhow::Result<H256> {
+    let contract = ethabi::Contract::load(ERC721_ABI)?;
+    Ok(contract.event("Transfer")?.signature())
+}
+
+pub fn erc1155_transfer_topics() -> anyhow::Result<Vec<H256>> {
+    let contract = ethabi::Contract::load(ERC1155_ABI)?;
+    Ok(vec![contract.event("TransferSingle")?.signature(), contract.event("TransferBatch")?.signature()])
+}
+
+pub fn decode_transfer_log(log: &Log) -> anyhow::Result<Vec<NftTransfer>> {
+    let topic = log.topics.first().copied().unwrap_or_default();
+    let raw_log = ethabi::RawLog { topics: log.topics.clone(), data: log.data.0.clone() };
+    let transfer = |standard: NftStandard, from: Address, to: Address, token_id: U256, amount: U256| NftTransfer {
+        standard,
+        contract_address: log.address,
+        from,
+        to,
+        token_id,
+        amount,
+    };
+
+    if topic == erc721_transfer_topic()? && log.topics.len() == 4 {
+        let contract = ethabi::Contract::load(ERC721_ABI)?;
+        let parsed = contract.event("Transfer")?.parse_log(raw_log)?;
+        let param = |index: usize| parsed.params.get(index).map(|param| param.value.clone());
+        return match (param(0), param(1), param(2)) {
+            (Some(ethabi::Token::Address(from)), Some(ethabi::Token::Address(to)), Some(ethabi::Token::Uint(token_id))) =>
+                Ok(vec![transfer(NftStandard::Erc721, from, to, token_id, U256::one())]),
+            _ => Err(anyhow::anyhow!(ERR_NOT_NFT_TRANSFER_LOG)),
+        };
+    }
+
+    let contract = ethabi::Contract::load(ERC1155_ABI)?;
+    let event = match erc1155_transfer_topics()?.iter().position(|other| *other == topic) {
+        Some(0) => contract.event("TransferSingle")?,
+        Some(_) => contract.event("TransferBatch")?,
+        None => return Err(anyhow::anyhow!(ERR_NOT_NFT_TRANSFER_LOG)),
+    };
+    let parsed = event.parse_log(raw_log)?;
+    let param = |index: usize| parsed.params.get(index).map(|param| param.value.clone());
+    match (param(1), param(2), param(3), param(4)) {
+        (Some(ethabi::Token::Address(from)), Some(ethabi::Token::Address(to)), Some(ethabi::Token::Uint(id)), Some(ethabi::Token::Uint(value))) =>
+            Ok(vec![transfer(NftStandard::Erc1155, from, to, id, value)]),
+        (Some(ethabi::Token::Address(from)), Some(ethabi::Token::Address(to)), Some(ethabi::Token::Array(ids)), Some(ethabi::Token::Array(values))) => {
+            Ok(ids.into_iter().zip(values).filter_map(|(id, value)| match (id, value) {
+                (ethabi::Token::Uint(id), ethabi::Token::Uint(value)) => Some(transfer(NftStandard::Erc1155, from, to, id, value)),
+                _ => None,
+            }).collect())
+        },
+        _ => Err(anyhow::anyhow!(ERR_NOT_NFT_TRANSFER_LOG)),
+    }
+}
+
+pub fn encode_safe_transfer(standard: NftStandard, from: Address, to: Address, token_id: U256, amount: U256) -> anyhow::Result<Vec<u8>> {
+    let data = match standard {
+        NftStandard::Erc721 => {
+            if amount != U256::one() {
+                return Err(anyhow::anyhow!(ERR_ERC721_AMOUNT));
+            }
+            ethabi::Contract::load(ERC721_ABI)?.function("safeTransferFrom")?.encode_input(&[
+                ethabi::Token::Address(from),
+                ethabi::Token::Address(to),
+                ethabi::Token::Uint(token_id),
+            ])?
+        },
+        NftStandard::Erc1155 => {
+            ethabi::Contract::load(ERC1155_ABI)?.function("safeTransferFrom")?.encode_input(&[
+                ethabi::Token::Address(from),
+                ethabi::Token::Address(to),
+                ethabi::Token::Uint(token_id),
+                ethabi::Token::Uint(amount),
+                ethabi::Token::Bytes(Vec::new()),
+            ])?
+        },
+    };
+    Ok(data)
+}
+
+// ERC-1155 clients substitute {id} with the lowercase hex id padded to 64 chars
+pub fn expand_metadata_uri(uri: &str, token_id: U256) -> String {
+    let mut id = [0u8; 32];
+    token_id.to_big_endian(&mut id);
+    uri.replace("{id}", &hex::encode(id))
+}
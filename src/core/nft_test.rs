@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use web3::{ethabi, types::{Address, Bytes, Log, H256, U256}};
+    use crate::core::{eth_chain::EthChain, nft::{self, NftHolding, NftStandard}};
+
+    fn log(topics: Vec<H256>, data: Vec<u8>) -> Log {
+        Log {
+            address: Address::from_low_u64_be(0xc0),
+            topics,
+            data: Bytes(data),
+            block_hash: None,
+            block_number: Some(5.into()),
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        }
+    }
+
+    #[test]
+    fn test_decode_erc721_transfer() -> anyhow::Result<()> {
+        let from = Address::from_low_u64_be(1);
+        let to = Address::from_low_u64_be(2);
+        let token_id = H256::from_low_u64_be(42);
+
+        let transfers = nft::decode_transfer_log(&log(
+            vec![nft::erc721_transfer_topic()?, H256::from(from), H256::from(to), token_id], Vec::new()))?;
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].standard, NftStandard::Erc721);
+        assert_eq!(transfers[0].contract_address, Address::from_low_u64_be(0xc0));
+        assert_eq!((transfers[0].from, transfers[0].to), (from, to));
+        assert_eq!(transfers[0].token_id, U256::from(42));
+        assert_eq!(transfers[0].amount, U256::one());
+
+        // ERC-20 transfer has the value in data instead of the indexed token id
+        let erc20 = log(vec![nft::erc721_transfer_topic()?, H256::from(from), H256::from(to)], vec![0; 32]);
+        assert!(nft::decode_transfer_log(&erc20).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_erc1155_transfers() -> anyhow::Result<()> {
+        let operator = H256::from_low_u64_be(9);
+        let from = Address::from_low_u64_be(1);
+        let to = Address::from_low_u64_be(2);
+        let topics = nft::erc1155_transfer_topics()?;
+
+        let single = ethabi::encode(&[ethabi::Token::Uint(7.into()), ethabi::Token::Uint(3.into())]);
+        let transfers = nft::decode_transfer_log(&log(vec![topics[0], operator, H256::from(from), H256::from(to)], single))?;
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].standard, NftStandard::Erc1155);
+        assert_eq!((transfers[0].from, transfers[0].to), (from, to));
+        assert_eq!((transfers[0].token_id, transfers[0].amount), (U256::from(7), U256::from(3)));
+
+        let batch = ethabi::encode(&[
+            ethabi::Token::Array(vec![ethabi::Token::Uint(7.into()), ethabi::Token::Uint(8.into())]),
+            ethabi::Token::Array(vec![ethabi::Token::Uint(1.into()), ethabi::Token::Uint(2.into())]),
+        ]);
+        let transfers = nft::decode_transfer_log(&log(vec![topics[1], operator, H256::from(from), H256::from(to)], batch))?;
+        assert_eq!(transfers.iter().map(|transfer| (transfer.token_id.as_u64(), transfer.amount.as_u64())).collect::<Vec<_>>(),
+            vec![(7, 1), (8, 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_safe_transfer() -> anyhow::Result<()> {
+        let from = Address::from_low_u64_be(1);
+        let to = Address::from_low_u64_be(2);
+
+        let data = nft::encode_safe_transfer(NftStandard::Erc721, from, to, 42.into(), U256::one())?;
+        assert_eq!(hex::encode(&data[..4]), "42842e0e");
+        assert_eq!(data.len(), 4 + 3 * 32);
+        assert!(nft::encode_safe_transfer(NftStandard::Erc721, from, to, 42.into(), 2.into()).is_err());
+
+        let data = nft::encode_safe_transfer(NftStandard::Erc1155, from, to, 42.into(), 5.into())?;
+        assert_eq!(hex::encode(&data[..4]), "f242432a");
+        assert_eq!(U256::from_big_endian(&data[100..132]), U256::from(5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_transfers_and_request() -> anyhow::Result<()> {
+        let account = Address::from_low_u64_be(1);
+        let other = Address::from_low_u64_be(2);
+        let mut holding = NftHolding {
+            chain: EthChain::EthereumMainnet,
+            standard: NftStandard::Erc1155,
+            contract_address: Address::from_low_u64_be(0xc0),
+            collection: None,
+            token_id: 7.into(),
+            amount: U256::zero(),
+            metadata_uri: None,
+        };
+        let contract_address = holding.contract_address;
+        let transfer = |from: Address, to: Address, amount: u64| nft::NftTransfer {
+            standard: NftStandard::Erc1155,
+            contract_address,
+            from,
+            to,
+            token_id: 7.into(),
+            amount: amount.into(),
+        };
+
+        holding.apply_transfer(account, &transfer(other, account, 5));
+        holding.apply_transfer(account, &transfer(account, other, 2));
+        holding.apply_transfer(account, &transfer(account, account, 1));
+        assert_eq!(holding.amount, U256::from(3));
+        assert_eq!(holding.collection_str(), format!("{:?}", holding.contract_address));
+
+        let request = holding.transfer_request(account, other, 2.into())?;
+        assert_eq!(request.to, holding.contract_address);
+        assert!(request.amount.is_zero());
+        assert_eq!(request.data, Some(nft::encode_safe_transfer(NftStandard::Erc1155, account, other, 7.into(), 2.into())?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_metadata_uri() {
+        assert_eq!(nft::expand_metadata_uri("ipfs://meta/{id}.json", 0x2a.into()),
+            format!("ipfs://meta/{}2a.json", "0".repeat(62)));
+        assert_eq!(nft::expand_metadata_uri("https://meta/42", 0x2a.into()), "https://meta/42");
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use web3::types::{Address, U256};
+
+use super::eth_chain::EthChain;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonceGap {
+    pub chain: EthChain,
+    pub nonce: U256,
+}
+
+// Hands out sequential nonces per account and chain, so queued sends don't collide
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    next_nonces: HashMap<(Address, EthChain), U256>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Reconcile with the node's pending nonce, which wins if someone else sent from this account
+    pub fn reconcile(&mut self, account: Address, chain: EthChain, pending_nonce: U256) -> U256 {
+        let next_nonce = self.next_nonces.entry((account, chain)).or_insert(pending_nonce);
+        if *next_nonce < pending_nonce {
+            *next_nonce = pending_nonce;
+        }
+        *next_nonce
+    }
+
+    pub fn take_nonce(&mut self, account: Address, chain: EthChain, pending_nonce: U256) -> U256 {
+        let nonce = self.reconcile(account, chain, pending_nonce);
+        self.next_nonces.insert((account, chain), nonce + 1);
+        nonce
+    }
+
+    // Give back a nonce which never made it to the chain, only the latest one can be reused
+    pub fn release(&mut self, account: Address, chain: EthChain, nonce: U256) {
+        if let Some(next_nonce) = self.next_nonces.get_mut(&(account, chain)) {
+            if *next_nonce == nonce + 1 {
+                *next_nonce = nonce;
+            }
+        }
+    }
+}
+
+// Nonces missing between the confirmed account nonce and our latest pending transaction
+pub fn find_nonce_gaps(chain: EthChain, account_nonce: U256, pending_nonces: &[U256]) -> Vec<NonceGap> {
+    let Some(max_nonce) = pending_nonces.iter().max() else {
+        return Vec::new();
+    };
+
+    let mut gaps = Vec::new();
+    let mut nonce = account_nonce;
+    while nonce < *max_nonce {
+        if !pending_nonces.contains(&nonce) {
+            gaps.push(NonceGap { chain, nonce });
+        }
+        nonce += U256::one();
+    }
+    gaps
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{Address, U256};
+    use crate::core::{eth_chain::EthChain, nonce::{find_nonce_gaps, NonceGap, NonceManager}};
+
+    const CHAIN: EthChain = EthChain::EthereumSepolia;
+
+    #[test]
+    fn test_sequential_nonces() {
+        let mut manager = NonceManager::new();
+        let account = Address::from_low_u64_be(1);
+
+        // The node doesn't see the first transaction yet, but the second still gets the next nonce
+        assert_eq!(manager.take_nonce(account, CHAIN, 5.into()), 5.into());
+        assert_eq!(manager.take_nonce(account, CHAIN, 5.into()), 6.into());
+        assert_eq!(manager.take_nonce(account, CHAIN, 6.into()), 7.into());
+
+        // Other accounts and chains are independent
+        assert_eq!(manager.take_nonce(Address::from_low_u64_be(2), CHAIN, 0.into()), 0.into());
+        assert_eq!(manager.take_nonce(account, EthChain::EthereumMainnet, 1.into()), 1.into());
+    }
+
+    #[test]
+    fn test_reconcile_with_pending_nonce() {
+        let mut manager = NonceManager::new();
+        let account = Address::from_low_u64_be(1);
+
+        assert_eq!(manager.take_nonce(account, CHAIN, 2.into()), 2.into());
+        // Sent from another wallet meanwhile
+        assert_eq!(manager.reconcile(account, CHAIN, 10.into()), 10.into());
+        assert_eq!(manager.take_nonce(account, CHAIN, 3.into()), 10.into());
+    }
+
+    #[test]
+    fn test_release_nonce() {
+        let mut manager = NonceManager::new();
+        let account = Address::from_low_u64_be(1);
+
+        let first = manager.take_nonce(account, CHAIN, 0.into());
+        let second = manager.take_nonce(account, CHAIN, 0.into());
+
+        // Only the latest nonce can be reused
+        manager.release(account, CHAIN, first);
+        assert_eq!(manager.reconcile(account, CHAIN, 0.into()), 2.into());
+        manager.release(account, CHAIN, second);
+        assert_eq!(manager.reconcile(account, CHAIN, 0.into()), 1.into());
+    }
+
+    #[test_case(5, &[], &[]; "no pending")]
+    #[test_case(5, &[5, 6, 7], &[]; "no gaps")]
+    #[test_case(5, &[6, 7], &[5]; "first dropped")]
+    #[test_case(5, &[5, 8], &[6, 7]; "several dropped")]
+    fn test_find_nonce_gaps(account_nonce: u64, pending: &[u64], expected: &[u64]) {
+        let pending = pending.iter().map(|nonce| U256::from(*nonce)).collect::<Vec<_>>();
+        let expected = expected.iter().map(|nonce| NonceGap { chain: CHAIN, nonce: (*nonce).into() }).collect::<Vec<_>>();
+
+        assert_eq!(find_nonce_gaps(CHAIN, account_nonce.into(), &pending), expected);
+    }
+}
@@ -0,0 +1,116 @@
+use std::{fmt, str::FromStr};
+use web3::types::{Address, U256};
+
+use super::{amount::Amount, eth_chain::EthChain, eth_utils};
+
+const SCHEME: &str = "ethereum:";
+const PAY_PREFIX: &str = "pay-";
+const TRANSFER_FUNCTION: &str = "transfer";
+
+const ERR_NOT_PAYMENT_URI: &str = "Not an ethereum: payment URI";
+const ERR_NO_RECIPIENT: &str = "Token transfer without a recipient address";
+const ERR_INVALID_NUMBER: &str = "Invalid number in the payment URI";
+
+// EIP-681 payment request, ETH goes to the target address and tokens through transfer(address,uint256)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentRequest {
+    pub recipient: Address,
+    pub chain: Option<EthChain>,
+    // ERC-20 contract, None for ETH
+    pub token: Option<Address>,
+    // Raw value in wei or token units
+    pub amount: Option<U256>,
+}
+
+impl PaymentRequest {
+    pub fn is_payment_uri(text: &str) -> bool {
+        text.trim().to_lowercase().starts_with(SCHEME)
+    }
+}
+
+impl fmt::Display for PaymentRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chain = self.chain.map(|chain| format!("@{}", chain.get_chain_id())).unwrap_or_default();
+        match self.token {
+            Some(token) => {
+                write!(f, "{}{:?}{}/{}?address={:?}", SCHEME, token, chain, TRANSFER_FUNCTION, self.recipient)?;
+                if let Some(amount) = self.amount {
+                    write!(f, "&uint256={}", amount)?;
+                }
+                Ok(())
+            },
+            None => {
+                write!(f, "{}{:?}{}", SCHEME, self.recipient, chain)?;
+                if let Some(amount) = self.amount {
+                    write!(f, "?value={}", amount)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl FromStr for PaymentRequest {
+    type Err = anyhow::Error;
+
+    // NOTE: unknown parameters such as gas limits are ignored, the wallet estimates them itself
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if !Self::is_payment_uri(text) {
+            return Err(anyhow::anyhow!(ERR_NOT_PAYMENT_URI));
+        }
+        let text = &text[SCHEME.len()..];
+        let text = text.strip_prefix(PAY_PREFIX).unwrap_or(text);
+
+        let (path, query) = text.split_once('?').unwrap_or((text, ""));
+        let (target, function) = match path.split_once('/') {
+            Some((target, function)) => (target, Some(function)),
+            None => (path, None),
+        };
+        let (target, chain) = match target.split_once('@') {
+            Some((target, chain_id)) => {
+                let chain_id = chain_id.parse::<u64>().map_err(|_| anyhow::anyhow!("Invalid chain id {}", chain_id))?;
+                let chain = EthChain::from_chain_id(chain_id).ok_or_else(|| anyhow::anyhow!("Unsupported chain id {}", chain_id))?;
+                (target, Some(chain))
+            },
+            None => (target, None),
+        };
+        let target = eth_utils::str_to_eth_address(target)?;
+        let parameters = query.split('&')
+            .filter_map(|parameter| parameter.split_once('='))
+            .collect::<Vec<_>>();
+        let parameter = |name: &str| parameters.iter().find(|(key, _)| *key == name).map(|(_, value)| *value);
+
+        match function {
+            None => Ok(Self {
+                recipient: target,
+                chain,
+                token: None,
+                amount: parameter("value").map(parse_number).transpose()?,
+            }),
+            Some(TRANSFER_FUNCTION) => Ok(Self {
+                recipient: eth_utils::str_to_eth_address(parameter("address").ok_or_else(|| anyhow::anyhow!(ERR_NO_RECIPIENT))?)?,
+                chain,
+                token: Some(target),
+                amount: parameter("uint256").map(parse_number).transpose()?,
+            }),
+            Some(function) => Err(anyhow::anyhow!("Unsupported payment function {}", function)),
+        }
+    }
+}
+
+// Integer with an optional exponent, e.g. 2.014e18, the result must be whole
+fn parse_number(text: &str) -> anyhow::Result<U256> {
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<u16>().map_err(|_| anyhow::anyhow!(ERR_INVALID_NUMBER))?),
+        None => (text, 0),
+    };
+    let mantissa = mantissa.parse::<Amount>().map_err(|_| anyhow::anyhow!(ERR_INVALID_NUMBER))?;
+    if exponent >= mantissa.decimals {
+        let multiplier = U256::from(10).checked_pow((exponent - mantissa.decimals).into())
+            .ok_or_else(|| anyhow::anyhow!(ERR_INVALID_NUMBER))?;
+        mantissa.raw.checked_mul(multiplier).ok_or_else(|| anyhow::anyhow!(ERR_INVALID_NUMBER))
+    } else {
+        Amount::new(mantissa.raw, mantissa.decimals - exponent).to_decimals(0)
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{Address, U256};
+    use crate::core::{eth_chain::EthChain, payment_uri::PaymentRequest};
+
+    const RECIPIENT: &str = "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359";
+    const USDC: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+
+    fn recipient() -> Address {
+        RECIPIENT.parse().unwrap()
+    }
+
+    #[test]
+    fn test_format() {
+        let request = PaymentRequest { recipient: recipient(), chain: None, token: None, amount: None };
+        assert_eq!(request.to_string(), format!("ethereum:{}", RECIPIENT));
+
+        let eth = PaymentRequest { chain: Some(EthChain::OptimismMainnet), amount: Some(U256::exp10(16)), ..request.clone() };
+        assert_eq!(eth.to_string(), format!("ethereum:{}@10?value=10000000000000000", RECIPIENT));
+
+        let token = PaymentRequest {
+            chain: Some(EthChain::EthereumMainnet),
+            token: Some(USDC.parse().unwrap()),
+            amount: Some(1_500_000.into()),
+            ..request
+        };
+        assert_eq!(token.to_string(), format!("ethereum:{}@1/transfer?address={}&uint256=1500000", USDC, RECIPIENT));
+    }
+
+    #[test_case(None, None, None)]
+    #[test_case(Some(EthChain::ArbitrumSepolia), None, Some(1))]
+    #[test_case(Some(EthChain::EthereumMainnet), Some(USDC), Some(1_500_000))]
+    #[test_case(None, Some(USDC), None)]
+    fn test_round_trip(chain: Option<EthChain>, token: Option<&str>, amount: Option<u64>) -> anyhow::Result<()> {
+        let request = PaymentRequest {
+            recipient: recipient(),
+            chain,
+            token: token.map(|token| token.parse()).transpose()?,
+            amount: amount.map(U256::from),
+        };
+        assert_eq!(request.to_string().parse::<PaymentRequest>()?, request);
+        Ok(())
+    }
+
+    // NOTE: examples from EIP-681
+    #[test_case("ethereum:0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359?value=2.014e18", None, None, Some("2014000000000000000"))]
+    #[test_case("ethereum:pay-0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359@11155111?value=1e16&gas=21000", Some(EthChain::EthereumSepolia), None, Some("10000000000000000"))]
+    #[test_case("ethereum:0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606EB48/transfer?address=0xFB6916095ca1df60bB79Ce92cE3Ea74c37c5d359&uint256=1", None, Some(USDC), Some("1"))]
+    #[test_case("  ETHEREUM:0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359  ", None, None, None)]
+    fn test_parse(uri: &str, chain: Option<EthChain>, token: Option<&str>, amount: Option<&str>) -> anyhow::Result<()> {
+        let request: PaymentRequest = uri.parse()?;
+        assert_eq!(request.recipient, recipient());
+        assert_eq!(request.chain, chain);
+        assert_eq!(request.token, token.map(|token| token.parse()).transpose()?);
+        assert_eq!(request.amount, amount.map(U256::from_dec_str).transpose()?);
+        Ok(())
+    }
+
+    #[test_case("0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359" ; "bare address")]
+    #[test_case("bitcoin:1BoatSLRHtKNngkdXEeobR76b53LETtpyT" ; "other scheme")]
+    #[test_case("ethereum:vitalik.eth" ; "ens name")]
+    #[test_case("ethereum:0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359@999" ; "unsupported chain")]
+    #[test_case("ethereum:0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359?value=1.5" ; "fractional wei")]
+    #[test_case("ethereum:0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359?value=-1" ; "negative value")]
+    #[test_case("ethereum:0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48/transfer?uint256=1" ; "transfer without recipient")]
+    #[test_case("ethereum:0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48/approve?address=0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359" ; "other function")]
+    fn test_parse_invalid(uri: &str) {
+        assert!(uri.parse::<PaymentRequest>().is_err());
+    }
+
+    #[test]
+    fn test_is_payment_uri() {
+        assert!(PaymentRequest::is_payment_uri("ethereum:0x0"));
+        assert!(!PaymentRequest::is_payment_uri(RECIPIENT));
+    }
+}
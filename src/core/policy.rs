@@ -0,0 +1,192 @@
+use web3::types::{Address, U256};
+
+use super::{amount::Amount, erc20, eth_chain::EthChain, eth_utils};
+
+// Daily limits are checked over the last 24 hours, not the calendar day
+pub const DAILY_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+
+const ERR_EMPTY_LIMIT_CURRENCY: &str = "Limit currency can't be empty";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum LimitUnit {
+    Token,
+    Usd,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SpendingLimit {
+    pub currency: String,
+    pub unit: LimitUnit,
+    pub per_transaction: Option<Amount>,
+    pub daily: Option<Amount>,
+}
+
+// Vault guardrails, an empty policy allows everything
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SpendingPolicy {
+    pub limits: Vec<SpendingLimit>,
+    pub recipients: Vec<Address>,   // Any recipient if empty
+    pub chains: Vec<EthChain>,      // Any chain if empty
+    pub cooling_off_hours: u64,     // Disabled if zero
+}
+
+// Outgoing value of a sent transaction, counted against the daily limits
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PolicySpend {
+    pub timestamp: i64,
+    pub chain: EthChain,
+    pub currency: String,
+    pub amount: Amount,
+    pub usd_value: Option<f64>,
+}
+
+// What a signed call pays and to whom
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyTransfer {
+    pub recipient: Address,
+    pub currency: String,
+    pub amount: Amount,
+}
+
+impl LimitUnit {
+    pub fn get_display_name(&self) -> &str {
+        match self {
+            LimitUnit::Token => "Token",
+            LimitUnit::Usd => "USD",
+        }
+    }
+}
+
+impl SpendingLimit {
+    pub fn value_str(&self, value: &Amount) -> String {
+        match self.unit {
+            LimitUnit::Token => format!("{} {}", value, self.currency),
+            LimitUnit::Usd => format!("{} USD", value),
+        }
+    }
+
+    fn check(&self, spend: &PolicySpend, history: &[PolicySpend]) -> anyhow::Result<()> {
+        let history = history.iter().filter(|other| other.currency == spend.currency);
+        match self.unit {
+            LimitUnit::Token => {
+                if let Some(limit) = &self.per_transaction {
+                    if spend.amount > *limit {
+                        return Err(anyhow::anyhow!("Amount exceeds the per-transaction limit of {}", self.value_str(limit)));
+                    }
+                }
+                if let Some(limit) = &self.daily {
+                    let spent = history.fold(Amount::zero(0), |acc, other| acc.checked_add(&other.amount).unwrap_or(acc));
+                    if spent.checked_add(&spend.amount).is_none_or(|total| total > *limit) {
+                        return Err(anyhow::anyhow!("Amount exceeds the daily limit of {}, already spent {} in the last 24 hours",
+                            self.value_str(limit), self.value_str(&spent)));
+                    }
+                }
+            },
+            LimitUnit::Usd => {
+                let usd_value = spend.usd_value.ok_or_else(||
+                    anyhow::anyhow!("USD price of {} is unknown, the spending limit can't be checked", spend.currency))?;
+                if let Some(limit) = &self.per_transaction {
+                    if usd_value > limit.to_f64() {
+                        return Err(anyhow::anyhow!("Amount of {:.2} USD exceeds the per-transaction limit of {}",
+                            usd_value, self.value_str(limit)));
+                    }
+                }
+                if let Some(limit) = &self.daily {
+                    // NOTE: spends without a known price count as zero
+                    let spent = history.filter_map(|other| other.usd_value).sum::<f64>();
+                    if spent + usd_value > limit.to_f64() {
+                        return Err(anyhow::anyhow!("Amount of {:.2} USD exceeds the daily limit of {}, already spent {:.2} USD in the last 24 hours",
+                            usd_value, self.value_str(limit), spent));
+                    }
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for SpendingLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let per_transaction = self.per_transaction.as_ref()
+            .map_or("no per-transaction limit".to_string(), |value| format!("{} per transaction", self.value_str(value)));
+        let daily = self.daily.as_ref()
+            .map_or("no daily limit".to_string(), |value| format!("{} daily", self.value_str(value)));
+        write!(f, "{}: {}, {}", self.currency, per_transaction, daily)
+    }
+}
+
+impl SpendingPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.limits.is_empty() && self.recipients.is_empty() && self.chains.is_empty() && self.cooling_off_hours == 0
+    }
+
+    pub fn has_usd_limits(&self, currency: &str) -> bool {
+        self.limits.iter().any(|limit| limit.currency == currency && limit.unit == LimitUnit::Usd)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (index, limit) in self.limits.iter().enumerate() {
+            if limit.currency.trim().is_empty() {
+                return Err(anyhow::anyhow!(ERR_EMPTY_LIMIT_CURRENCY));
+            }
+            if limit.per_transaction.is_none() && limit.daily.is_none() {
+                return Err(anyhow::anyhow!("Limit for {} has no values", limit.currency));
+            }
+            if self.limits[..index].iter().any(|other| other.currency == limit.currency && other.unit == limit.unit) {
+                return Err(anyhow::anyhow!("Duplicated {} limit for {}", limit.unit.get_display_name(), limit.currency));
+            }
+        }
+        Ok(())
+    }
+
+    // Adds the limit or replaces the one with the same currency and unit
+    pub fn set_limit(&mut self, limit: SpendingLimit) {
+        match self.limits.iter_mut().find(|other| other.currency == limit.currency && other.unit == limit.unit) {
+            Some(other) => *other = limit,
+            None => self.limits.push(limit),
+        }
+    }
+
+    // `history` holds the spends of the last 24 hours, `recipient_first_seen` is None for known recipients
+    pub fn check(&self, spend: &PolicySpend, recipient: Address, history: &[PolicySpend], recipient_first_seen: Option<i64>) -> anyhow::Result<()> {
+        if !self.chains.is_empty() && !self.chains.contains(&spend.chain) {
+            return Err(anyhow::anyhow!("{} is not allowed by the spending policy", spend.chain.get_display_name()));
+        }
+        if !self.recipients.is_empty() && !self.recipients.contains(&recipient) {
+            return Err(anyhow::anyhow!("Recipient {:?} is not in the spending policy allowlist", recipient));
+        }
+
+        // NOTE: calls without value, like revoking an allowance, don't wait for the cooling-off
+        if let Some(first_seen) = recipient_first_seen.filter(|_| self.cooling_off_hours > 0 && !spend.amount.is_zero()) {
+            let remaining = first_seen + self.cooling_off_hours as i64 * 3600 - spend.timestamp;
+            if remaining > 0 {
+                let minutes = (remaining + 59) / 60;
+                return Err(anyhow::anyhow!("Recipient {:?} is new, transfers are allowed in {}h {}m",
+                    recipient, minutes / 60, minutes % 60));
+            }
+        }
+
+        let history = history.iter()
+            .filter(|other| other.timestamp > spend.timestamp - DAILY_WINDOW_SECONDS)
+            .cloned()
+            .collect::<Vec<_>>();
+        for limit in self.limits.iter().filter(|limit| limit.currency == spend.currency) {
+            limit.check(spend, &history)?;
+        }
+        Ok(())
+    }
+}
+
+// ERC-20 calls are checked as the tokens they move, `token` looks up the symbol and decimals of the contract.
+// NOTE: other contract calls can't be decoded, only the contract allowlist and the attached ETH apply to them
+pub fn decode_transfer<F>(to: Address, value: U256, data: &[u8], token: F) -> PolicyTransfer
+where F: Fn(Address) -> Option<(String, u16)> {
+    match erc20::decode_token_call(data) {
+        Some(call) => {
+            // Unknown tokens can't match any limit, the recipient is still checked
+            let (currency, decimals) = token(to).unwrap_or_else(|| (format!("{:?}", to), 0));
+            PolicyTransfer { recipient: call.recipient, currency, amount: Amount::new(call.value, decimals) }
+        },
+        None => PolicyTransfer { recipient: to, currency: "ETH".to_string(), amount: eth_utils::wei_to_eth(value) },
+    }
+}
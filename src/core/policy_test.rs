@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{Address, U256};
+    use crate::core::{
+        amount::Amount,
+        erc20,
+        eth_chain::EthChain,
+        policy::{self, LimitUnit, PolicySpend, PolicyTransfer, SpendingLimit, SpendingPolicy, DAILY_WINDOW_SECONDS}
+    };
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn amount(text: &str) -> Option<Amount> {
+        Some(text.parse().unwrap())
+    }
+
+    fn spend(currency: &str, value: &str, usd_value: Option<f64>, timestamp: i64) -> PolicySpend {
+        PolicySpend {
+            timestamp,
+            chain: EthChain::EthereumMainnet,
+            currency: currency.to_string(),
+            amount: value.parse().unwrap(),
+            usd_value,
+        }
+    }
+
+    fn policy() -> SpendingPolicy {
+        SpendingPolicy {
+            limits: vec![
+                SpendingLimit { currency: "ETH".to_string(), unit: LimitUnit::Token, per_transaction: amount("1"), daily: amount("2.5") },
+                SpendingLimit { currency: "USDC".to_string(), unit: LimitUnit::Usd, per_transaction: None, daily: amount("1000") },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test_case("ETH", "0.5", Some(1000.0), Ok(()); "at the daily limit")]
+    #[test_case("ETH", "1.000000000000000001", None, Err("Amount exceeds the per-transaction limit of 1 ETH"); "per transaction")]
+    #[test_case("ETH", "0.6", None, Err("Amount exceeds the daily limit of 2.5 ETH, already spent 2 ETH in the last 24 hours"); "daily")]
+    #[test_case("USDC", "600", Some(600.0), Err("Amount of 600.00 USD exceeds the daily limit of 1000 USD, already spent 500.00 USD in the last 24 hours"); "daily usd")]
+    #[test_case("USDC", "400", None, Err("USD price of USDC is unknown, the spending limit can't be checked"); "unknown price")]
+    #[test_case("DAI", "1000000", None, Ok(()); "no limit")]
+    fn test_limits(currency: &str, value: &str, usd_value: Option<f64>, expected: Result<(), &str>) {
+        let history = vec![
+            spend("ETH", "1.5", Some(3000.0), NOW - 3600),
+            spend("ETH", "0.5", None, NOW - 60),
+            spend("ETH", "10", None, NOW - DAILY_WINDOW_SECONDS), // Out of the window
+            spend("USDC", "500", Some(500.0), NOW - 60),
+        ];
+
+        let result = policy().check(&spend(currency, value, usd_value, NOW), Address::zero(), &history, None);
+        assert_eq!(result.map_err(|err| err.to_string()), expected.map_err(|err| err.to_string()));
+    }
+
+    #[test]
+    fn test_allowlists() {
+        let allowed = Address::from_low_u64_be(1);
+        let policy = SpendingPolicy {
+            recipients: vec![allowed],
+            chains: vec![EthChain::OptimismMainnet],
+            ..Default::default()
+        };
+        let mut transfer = spend("ETH", "1", None, NOW);
+
+        let err = policy.check(&transfer, allowed, &[], None).unwrap_err();
+        assert_eq!(err.to_string(), "Ethereum Mainnet is not allowed by the spending policy");
+
+        transfer.chain = EthChain::OptimismMainnet;
+        assert!(policy.check(&transfer, allowed, &[], None).is_ok());
+        let err = policy.check(&transfer, Address::from_low_u64_be(2), &[], None).unwrap_err();
+        assert_eq!(err.to_string(), format!("Recipient {:?} is not in the spending policy allowlist", Address::from_low_u64_be(2)));
+    }
+
+    #[test_case("1", Some(NOW - 3600), Err("transfers are allowed in 23h 0m"); "new recipient")]
+    #[test_case("1", Some(NOW - 30), Err("transfers are allowed in 24h 0m"); "just seen")]
+    #[test_case("1", Some(NOW - DAILY_WINDOW_SECONDS), Ok(()); "cooled off")]
+    #[test_case("1", None, Ok(()); "known recipient")]
+    #[test_case("0", Some(NOW), Ok(()); "call without value")]
+    fn test_cooling_off(value: &str, first_seen: Option<i64>, expected: Result<(), &str>) {
+        let policy = SpendingPolicy { cooling_off_hours: 24, ..Default::default() };
+        let result = policy.check(&spend("ETH", value, None, NOW), Address::zero(), &[], first_seen);
+        match expected {
+            Ok(()) => assert!(result.is_ok()),
+            Err(message) => assert!(result.unwrap_err().to_string().ends_with(message)),
+        }
+    }
+
+    #[test]
+    fn test_validate_and_set_limit() {
+        let mut policy = policy();
+        assert!(policy.validate().is_ok());
+        assert!(!policy.is_empty());
+        assert!(policy.has_usd_limits("USDC"));
+        assert!(!policy.has_usd_limits("ETH"));
+
+        policy.set_limit(SpendingLimit { currency: "ETH".to_string(), unit: LimitUnit::Token, per_transaction: None, daily: amount("5") });
+        assert_eq!(policy.limits.len(), 2);
+        assert_eq!(policy.limits[0].to_string(), "ETH: no per-transaction limit, 5 ETH daily");
+
+        policy.limits.push(policy.limits[0].clone());
+        assert_eq!(policy.validate().unwrap_err().to_string(), "Duplicated Token limit for ETH");
+
+        policy.limits.pop();
+        policy.limits.push(SpendingLimit { currency: "DAI".to_string(), unit: LimitUnit::Usd, per_transaction: None, daily: None });
+        assert_eq!(policy.validate().unwrap_err().to_string(), "Limit for DAI has no values");
+        assert!(SpendingPolicy::default().is_empty());
+    }
+
+    #[test]
+    fn test_decode_transfer() -> anyhow::Result<()> {
+        let usdc = Address::from_low_u64_be(0xc);
+        let recipient = Address::from_low_u64_be(0xb0b);
+        let token = |contract: Address| (contract == usdc).then(|| ("USDC".to_string(), 6));
+        let transfer = |recipient: Address, currency: &str, amount: &str| PolicyTransfer {
+            recipient, currency: currency.to_string(), amount: amount.parse().unwrap(),
+        };
+
+        // Plain ETH and undecoded calls pay the ETH value to the target
+        assert_eq!(policy::decode_transfer(recipient, U256::exp10(18), &[], token), transfer(recipient, "ETH", "1"));
+        assert_eq!(policy::decode_transfer(usdc, U256::exp10(17), &[0xde, 0xad, 0xbe, 0xef], token), transfer(usdc, "ETH", "0.1"));
+
+        // Token calls pay the tokens to the recipient or the spender
+        let data = erc20::encode_transfer(recipient, 2_500_000.into())?;
+        assert_eq!(policy::decode_transfer(usdc, U256::zero(), &data, token), transfer(recipient, "USDC", "2.5"));
+        let data = erc20::encode_approve(recipient, 1_000_000.into())?;
+        assert_eq!(policy::decode_transfer(usdc, U256::zero(), &data, token), transfer(recipient, "USDC", "1"));
+
+        // Unknown tokens keep the raw amount under the contract address
+        let other = Address::from_low_u64_be(0xd);
+        let data = erc20::encode_transfer(recipient, 7.into())?;
+        assert_eq!(policy::decode_transfer(other, U256::zero(), &data, token), transfer(recipient, &format!("{:?}", other), "7"));
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
 use web3::Web3;
 
 use super::eth_chain::EthChain;
@@ -6,11 +7,29 @@ use super::eth_chain::EthChain;
 pub struct Provider<T: web3::Transport> {
     pub web3: Web3<T>,
     pub chain: EthChain,
+    chain_id_verified: Arc<AtomicBool>,
 }
 
 impl<T: web3::Transport> Provider<T> {
     pub fn new(transport: T, chain: EthChain) -> anyhow::Result<Self> {
         let web3 = Web3::new(transport);
-        Ok(Self { web3, chain })
+        Ok(Self { web3, chain, chain_id_verified: Arc::new(AtomicBool::new(false)) })
+    }
+
+    // eth_chainId handshake, performed once per provider before anything gets signed
+    pub async fn verify_chain_id(&self) -> anyhow::Result<()> {
+        if self.chain_id_verified.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let endpoint_chain_id = self.web3.eth().chain_id().await?;
+        let expected_chain_id = self.chain.get_chain_id();
+        if endpoint_chain_id != expected_chain_id.into() {
+            return Err(anyhow::anyhow!(
+                "Endpoint for {} serves chain id {}, expected {}", self.chain, endpoint_chain_id, expected_chain_id));
+        }
+
+        self.chain_id_verified.store(true, Ordering::Relaxed);
+        Ok(())
     }
 }
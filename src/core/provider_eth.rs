@@ -4,13 +4,42 @@ use web3::{
     types::*,
 };
 
-use super::{balance::{Balance, Balances}, eth_utils, provider::Provider, token::{Token, TokenList}, transaction::TransactionFees};
+use super::{
+    amount::Amount,
+    balance::{Balance, Balances},
+    bridge::{self, BridgeContracts, Withdrawal, WithdrawalMessage, WithdrawalProgress},
+    erc20,
+    eth_utils,
+    nft::{self, NftStandard},
+    fees::{FeeTier, GasFees, GasPrices, L1DataFee, FEE_HISTORY_BLOCKS, FEE_HISTORY_PERCENTILES},
+    l1_fee::{self, L1FeeOracle},
+    provider::Provider,
+    token::{Token, TokenList},
+    transaction::{TransactionFees, EIP1559_TRANSACTION_TYPE}
+};
 
 const ETH: &str = "ETH";
+const CHAINLINK_DECIMALS: i32 = 8;
+const CHAINLINK_PHASE_OFFSET: usize = 64;
+
+const ERR_NO_BLOCK_FOUND: &str = "No block found";
+const ERR_NO_PRICE_ROUND: &str = "No price round before the timestamp";
+const ERR_INVALID_SECONDS: &str = "Bridge contract returned an out of range time";
+
+// GameStatus of the OP dispute games, IN_PROGRESS is 0
+const GAME_STATUS_CHALLENGER_WINS: u8 = 1;
+const GAME_STATUS_DEFENDER_WINS: u8 = 2;
 
 const CHAINLINK_ABI: &[u8] = include_bytes!("../../abi/chainlink.json");
 const ERC20_BALANCE_ABI: &[u8] = include_bytes!("../../abi/erc20_balance.json");
 const ERC20_TOKENS_ABI: &[u8] = include_bytes!("../../abi/erc20_tokens.json");
+const ERC20_APPROVE_ABI: &[u8] = include_bytes!("../../abi/erc20_approve.json");
+const ERC721_ABI: &[u8] = include_bytes!("../../abi/erc721.json");
+const ERC1155_ABI: &[u8] = include_bytes!("../../abi/erc1155.json");
+const GAS_PRICE_ORACLE_ABI: &[u8] = include_bytes!("../../abi/gas_price_oracle.json");
+const OP_BRIDGE_ABI: &[u8] = include_bytes!("../../abi/op_bridge.json");
+const ARBITRUM_BRIDGE_ABI: &[u8] = include_bytes!("../../abi/arbitrum_bridge.json");
+const NODE_INTERFACE_ABI: &[u8] = include_bytes!("../../abi/node_interface.json");
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -22,6 +51,27 @@ struct PriceFeedData {
     answered_in_round: U256,
 }
 
+impl PriceFeedData {
+    fn rate(&self) -> f64 {
+        self.answer as f64 / 10f64.powi(CHAINLINK_DECIMALS)
+    }
+}
+
+async fn query_price_feed<T, P>(contract: &Contract<T>, function: &str, params: P) -> anyhow::Result<PriceFeedData>
+where T: web3::Transport, P: web3::contract::tokens::Tokenize {
+    contract
+        .query(function, params, None, Options::default(), None)
+        .await
+        .map(|(round_id, answer, started_at, updated_at, answered_in_round)| PriceFeedData {
+            round_id,
+            answer,
+            started_at,
+            updated_at,
+            answered_in_round,
+        })
+        .map_err(Into::into)
+}
+
 impl<T: web3::Transport> Provider<T> {
     #[allow(dead_code)]
     pub async fn get_token_metadata(&self, contract_address: Address) -> anyhow::Result<Token> {
@@ -41,33 +91,80 @@ impl<T: web3::Transport> Provider<T> {
     }
 
     pub async fn get_eth_usd_rate(&self) -> anyhow::Result<f64> {
-        let contrcat_address = self.chain.get_chainlink_contract_address();
-        let contract = Contract::from_json(self.web3.eth(), contrcat_address, CHAINLINK_ABI)?;
+        self.get_usd_rate(self.chain.get_chainlink_contract_address()).await
+    }
 
-        let result: PriceFeedData = contract
-        .query("latestRoundData", (), None, Options::default(), None)
-        .await
-        .map(|(round_id, answer, started_at, updated_at, answered_in_round)| PriceFeedData {
-            round_id,
-            answer,
-            started_at,
-            updated_at,
-            answered_in_round,
-        })?;
+    // Latest rate of a Chainlink USD feed
+    pub async fn get_usd_rate(&self, feed: Address) -> anyhow::Result<f64> {
+        let contract = Contract::from_json(self.web3.eth(), feed, CHAINLINK_ABI)?;
+
+        let result = query_price_feed(&contract, "latestRoundData", ()).await?;
+        Ok(result.rate())
+    }
+
+    pub async fn get_eth_usd_rate_at(&self, timestamp: u64) -> anyhow::Result<f64> {
+        self.get_usd_rate_at(self.chain.get_chainlink_contract_address(), timestamp).await
+    }
 
-        Ok(result.answer as f64 / 10f64.powi(8))
+    // Rate of the last round updated at or before the timestamp
+    // NOTE: round ids are `phase << 64 | aggregator round`, phases are searched from the latest one back
+    pub async fn get_usd_rate_at(&self, feed: Address, timestamp: u64) -> anyhow::Result<f64> {
+        let contract = Contract::from_json(self.web3.eth(), feed, CHAINLINK_ABI)?;
+        let timestamp = U256::from(timestamp);
+
+        let latest = query_price_feed(&contract, "latestRoundData", ()).await?;
+        if latest.updated_at <= timestamp {
+            return Ok(latest.rate());
+        }
+        let mut phase = (latest.round_id >> CHAINLINK_PHASE_OFFSET).low_u64();
+        let mut last_round = latest.round_id.low_u64();
+
+        while phase > 0 {
+            // Binary search below the last round, which was updated after the timestamp
+            let (mut low, mut high) = (1, last_round);
+            let mut found = None;
+            while low < high {
+                let middle = low + (high - low) / 2;
+                let round_id = (U256::from(phase) << CHAINLINK_PHASE_OFFSET) | U256::from(middle);
+                let round = query_price_feed(&contract, "getRoundData", (round_id,)).await?;
+                if round.updated_at <= timestamp {
+                    found = Some(round);
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            }
+            if let Some(round) = found {
+                return Ok(round.rate());
+            }
+
+            phase -= 1;
+            if phase == 0 {
+                break;
+            }
+            // The previous phase ends with the latest round of its own aggregator
+            let aggregator: Address = contract
+                .query("phaseAggregators", (phase as u16,), None, Options::default(), None)
+                .await?;
+            let aggregator = Contract::from_json(self.web3.eth(), aggregator, CHAINLINK_ABI)?;
+            let phase_latest = query_price_feed(&aggregator, "latestRoundData", ()).await?;
+            if phase_latest.updated_at <= timestamp {
+                return Ok(phase_latest.rate());
+            }
+            last_round = phase_latest.round_id.low_u64();
+        }
+        Err(anyhow::anyhow!(ERR_NO_PRICE_ROUND))
     }
 
     pub async fn get_eth_balance(&self, account: Address) -> anyhow::Result<Balance> {
         let wei = self.web3.eth().balance(account, None).await?;
         let eth = eth_utils::wei_to_eth(wei);
         let eth_usd_rate = self.get_eth_usd_rate().await?;
-        Ok(Balance::new(ETH, self.chain, eth, eth_usd_rate * eth))
+        Ok(Balance::new(ETH, self.chain, eth, eth_usd_rate * eth.to_f64()))
     }
 
     pub async fn get_token_balances(&self, account: Address, tokens: &TokenList) -> anyhow::Result<Balances> {
         let mut balances = Vec::new();
-        let eth_usd_rate = self.get_eth_usd_rate().await?;
 
         for token in tokens {
             // Handle ERC-20 token balances
@@ -92,46 +189,204 @@ impl<T: web3::Transport> Provider<T> {
                 },
             };
 
-            let balance_f64 = balance.as_u128() as f64 / 10f64.powi(token_chain_data.decimals as i32);
-            let balance = Balance::new(&token.symbol, self.chain ,balance_f64, balance_f64 * eth_usd_rate);
+            // NOTE: tokens without a price feed are valued at zero
+            let usd_rate = match token_chain_data.price_feed {
+                Some(feed) => self.get_usd_rate(feed).await.unwrap_or_else(|err| {
+                    log::warn!("Failed to get price of token {} on {}: {}", token.symbol, self.chain, err);
+                    0.0
+                }),
+                None => 0.0,
+            };
+            let balance = Amount::new(balance, token_chain_data.decimals);
+            let balance = Balance::new(&token.symbol, self.chain, balance, balance.to_f64() * usd_rate);
             balances.push(balance);
         }
         Ok(balances)
     }
 
-    pub async fn estimate_transaction_fees(&self, transaction: TransactionParameters, from: Address) -> anyhow::Result<TransactionFees> {
-        let gas_limit = match self.web3.eth().estimate_gas(
-            CallRequest {
-                from: Some(from),
-                to: transaction.to,
-                gas: None,
-                gas_price: None,
-                value: Some(transaction.value),
-                data: Some(transaction.data),
-                ..Default::default()
-            },
-            None
-        )
-        .await {
+    pub async fn get_gas_prices(&self, tier: FeeTier) -> anyhow::Result<GasPrices> {
+        let history = self.web3.eth().fee_history(
+            FEE_HISTORY_BLOCKS.into(),
+            BlockNumber::Latest,
+            Some(FEE_HISTORY_PERCENTILES.to_vec())
+        ).await?;
+        GasPrices::from_fee_history(&history, tier)
+    }
+
+    pub async fn estimate_transaction_fees(&self, transaction: TransactionParameters, from: Address, tier: FeeTier) -> anyhow::Result<TransactionFees> {
+        let call = CallRequest {
+            from: Some(from),
+            to: transaction.to,
+            gas: None,
+            gas_price: None,
+            value: Some(transaction.value),
+            data: Some(transaction.data.clone()),
+            ..Default::default()
+        };
+
+        // Dry run before anything gets signed, so reverts are reported with their reason
+        if let Err(err) = self.web3.eth().call(call.clone(), None).await {
+            return Ok(call_error_to_fees(&err));
+        }
+        let gas_limit = match self.web3.eth().estimate_gas(call, None).await {
             Ok(gas) => gas,
-            Err(_) => {
-                // TODO: handle specific errors
-                return Ok(TransactionFees::NotEnoughFunds { currency: ETH.to_string() });
-            }
+            Err(err) => return Ok(call_error_to_fees(&err)),
+        };
+
+        let prices = match self.get_gas_prices(tier).await {
+            Ok(prices) => prices,
+            Err(err) => return Ok(TransactionFees::RpcFailure { error: err.to_string() }),
+        };
+        let transaction = TransactionParameters {
+            gas: gas_limit,
+            max_fee_per_gas: Some(prices.max_fee_per_gas),
+            max_priority_fee_per_gas: Some(prices.max_priority_fee_per_gas),
+            ..transaction
+        };
+        let l1_data_fee = match self.estimate_l1_data_fee(&transaction, from).await {
+            Ok(l1_data_fee) => l1_data_fee,
+            Err(err) => return Ok(TransactionFees::RpcFailure { error: format!("Failed to estimate L1 data fee: {}", err) }),
+        };
+        let fees = GasFees::new(gas_limit, prices).with_l1_data_fee(l1_data_fee);
+
+        // NOTE: nodes require the balance to cover the worst case fee
+        let balance = match self.web3.eth().balance(from, None).await {
+            Ok(balance) => balance,
+            Err(err) => return Ok(call_error_to_fees(&err)),
+        };
+        if balance < transaction.value + fees.max_cost() {
+            return Ok(TransactionFees::NotEnoughFunds { currency: ETH.to_string() });
+        }
+
+        Ok(TransactionFees::Estimated { currency: ETH.to_string(), fees })
+    }
+
+    // NOTE: the nonce barely changes the encoded size, so an unknown one is left as zero
+    pub async fn estimate_l1_data_fee(&self, transaction: &TransactionParameters, from: Address) -> anyhow::Result<L1DataFee> {
+        let oracle = match L1FeeOracle::for_chain(self.chain) {
+            Some(oracle) => oracle,
+            None => return Ok(L1DataFee::None),
         };
 
-        let gas_price: U256 = self.web3.eth().gas_price().await?;
-        let wei = gas_limit * gas_price;
+        match oracle {
+            L1FeeOracle::OpStack => {
+                let contract = Contract::from_json(self.web3.eth(), oracle.get_contract_address(), GAS_PRICE_ORACLE_ABI)?;
+                let encoded = l1_fee::encode_unsigned_transaction(transaction, self.chain.get_chain_id());
+                let fee: U256 = contract
+                    .query("getL1Fee", (Bytes(encoded),), None, Options::default(), None)
+                    .await?;
+                Ok(L1DataFee::Charged(fee))
+            },
+            L1FeeOracle::Arbitrum => {
+                let contract = Contract::from_json(self.web3.eth(), oracle.get_contract_address(), NODE_INTERFACE_ABI)?;
+                let params = (
+                    transaction.to.unwrap_or_default(),
+                    transaction.to.is_none(),
+                    Bytes(transaction.data.0.clone()),
+                );
+                let options = Options::with(|options| options.value = Some(transaction.value));
+                let (_, gas_for_l1, _, _): (u64, u64, U256, U256) = contract
+                    .query("gasEstimateComponents", params, Some(from), options, None)
+                    .await?;
+                Ok(L1DataFee::IncludedGas(gas_for_l1.into()))
+            },
+        }
+    }
+
+    // Queried on L1, where withdrawals are proven and finalized
+    pub async fn get_withdrawal_progress(&self, withdrawal: &Withdrawal) -> anyhow::Result<WithdrawalProgress> {
+        let contracts = BridgeContracts::for_chain(withdrawal.chain)
+            .filter(|contracts| contracts.l1_chain == self.chain)
+            .ok_or_else(|| anyhow::anyhow!("No bridge from {} to {}", withdrawal.chain, self.chain))?;
+        let mut progress = WithdrawalProgress::default();
 
-        Ok(TransactionFees::Estimated { currency: ETH.to_string(), amount: eth_utils::wei_to_eth(wei) })
+        match (withdrawal.message, contracts.portal, contracts.state_oracle, contracts.outbox) {
+            (Some(WithdrawalMessage::OpStack { withdrawal_hash }), Some(portal), Some(state_oracle), _) => {
+                let portal = Contract::from_json(self.web3.eth(), portal, OP_BRIDGE_ABI)?;
+                progress.finalized = portal
+                    .query("finalizedWithdrawals", (withdrawal_hash,), None, Options::default(), None)
+                    .await?;
+                if progress.finalized {
+                    return Ok(progress);
+                }
+
+                // NOTE: only proofs submitted by the account itself are looked up
+                let (game, proven_at): (Address, u64) = portal
+                    .query("provenWithdrawals", (withdrawal_hash, withdrawal.account), None, Options::default(), None)
+                    .await?;
+                if proven_at > 0 {
+                    let game = Contract::from_json(self.web3.eth(), game, OP_BRIDGE_ABI)?;
+                    let game_status: u8 = game
+                        .query("status", (), None, Options::default(), None)
+                        .await?;
+                    // A lost game invalidates the proof, the withdrawal has to be proven again
+                    if game_status != GAME_STATUS_CHALLENGER_WINS {
+                        let delay: U256 = portal
+                            .query("proofMaturityDelaySeconds", (), None, Options::default(), None)
+                            .await?;
+                        progress.proven_at = Some(to_seconds(proven_at.into())?);
+                        progress.challenge_period = to_seconds(delay)?;
+
+                        if game_status == GAME_STATUS_DEFENDER_WINS {
+                            let resolved_at: u64 = game
+                                .query("resolvedAt", (), None, Options::default(), None)
+                                .await?;
+                            let finality_delay: U256 = portal
+                                .query("disputeGameFinalityDelaySeconds", (), None, Options::default(), None)
+                                .await?;
+                            progress.game_resolved_at = Some(to_seconds(resolved_at.into())?);
+                            progress.game_finality_delay = to_seconds(finality_delay)?;
+                        }
+                        return Ok(progress);
+                    }
+                }
+
+                let Some(l2_block) = withdrawal.l2_block else {
+                    return Ok(progress);
+                };
+                let factory = Contract::from_json(self.web3.eth(), state_oracle, OP_BRIDGE_ABI)?;
+                let game_count: U256 = factory
+                    .query("gameCount", (), None, Options::default(), None)
+                    .await?;
+                if game_count.is_zero() {
+                    return Ok(progress);
+                }
+                let (_, _, game): (u32, u64, Address) = factory
+                    .query("gameAtIndex", (game_count - 1,), None, Options::default(), None)
+                    .await?;
+                let game = Contract::from_json(self.web3.eth(), game, OP_BRIDGE_ABI)?;
+                let game_block: U256 = game
+                    .query("l2BlockNumber", (), None, Options::default(), None)
+                    .await?;
+                progress.state_posted = game_block >= U256::from(l2_block.as_u64());
+            },
+            (Some(WithdrawalMessage::Arbitrum { position }), _, _, Some(outbox)) => {
+                let outbox = Contract::from_json(self.web3.eth(), outbox, ARBITRUM_BRIDGE_ABI)?;
+                progress.finalized = outbox
+                    .query("isSpent", (position,), None, Options::default(), None)
+                    .await?;
+                progress.challenge_period = bridge::arbitrum_challenge_period(withdrawal.chain);
+            },
+            _ => {},
+        }
+        Ok(progress)
     }
 
-    pub async fn send_transaction(&self, transaction: TransactionParameters, sender: H160, secret_key: &SecretKey) -> anyhow::Result<H256> {
-        let nonce = self.web3.eth().transaction_count(sender, None).await?;
+    pub async fn send_transaction(&self, transaction: TransactionParameters, sender: H160, fees: GasFees, secret_key: &SecretKey) -> anyhow::Result<H256> {
+        self.verify_chain_id().await?;
+        let nonce = match transaction.nonce {
+            Some(nonce) => nonce, // Replacing transaction with the same nonce
+            None => self.get_pending_transaction_count(sender).await?,
+        };
 
         let signed = self.web3.accounts()
             .sign_transaction(TransactionParameters {
                 nonce: Some(nonce), // Explicitly set the nonce
+                chain_id: Some(self.chain.get_chain_id()), // EIP-155 replay protection
+                gas: fees.gas_limit,
+                transaction_type: Some(EIP1559_TRANSACTION_TYPE.into()),
+                max_fee_per_gas: Some(fees.prices.max_fee_per_gas),
+                max_priority_fee_per_gas: Some(fees.prices.max_priority_fee_per_gas),
                 ..transaction
             }, secret_key)
             .await?;
@@ -141,6 +396,11 @@ impl<T: web3::Transport> Provider<T> {
         Ok(tx_hash)
     }
 
+    pub async fn send_raw_transaction(&self, raw_transaction: Bytes) -> anyhow::Result<H256> {
+        self.verify_chain_id().await?;
+        Ok(self.web3.eth().send_raw_transaction(raw_transaction).await?)
+    }
+
     pub async fn get_transaction(&self, tx_hash: H256) -> anyhow::Result<Option<Transaction>> {
         match self.web3.eth().transaction(TransactionId::Hash(tx_hash)).await {
             Ok(receipt) => Ok(receipt),
@@ -148,11 +408,143 @@ impl<T: web3::Transport> Provider<T> {
         }
     }
 
-    #[allow(dead_code)]
     pub async fn get_transaction_receipt(&self, tx_hash: H256) -> anyhow::Result<Option<TransactionReceipt>> {
         match self.web3.eth().transaction_receipt(tx_hash).await {
             Ok(receipt) => Ok(receipt),
             Err(err) => Err(anyhow::anyhow!("Failed to get transaction receipt: {}", err)),
         }
     }
+
+    pub async fn get_block_number(&self) -> anyhow::Result<U64> {
+        Ok(self.web3.eth().block_number().await?)
+    }
+
+    pub async fn get_block_timestamp(&self, block_number: U64) -> anyhow::Result<u64> {
+        let block = self.web3.eth().block(BlockId::Number(BlockNumber::Number(block_number))).await?
+            .ok_or_else(|| anyhow::anyhow!(ERR_NO_BLOCK_FOUND))?;
+        Ok(block.timestamp.low_u64())
+    }
+
+    pub async fn get_transaction_count(&self, account: Address) -> anyhow::Result<U256> {
+        Ok(self.web3.eth().transaction_count(account, None).await?)
+    }
+
+    // Includes transactions still sitting in the mempool
+    pub async fn get_pending_transaction_count(&self, account: Address) -> anyhow::Result<U256> {
+        Ok(self.web3.eth().transaction_count(account, Some(BlockNumber::Pending)).await?)
+    }
+
+    // ERC-20 transfers from and to the account emitted by any of the token contracts
+    pub async fn get_transfer_logs(&self, contract_addresses: Vec<Address>, account: Address, from_block: U64, to_block: U64) -> anyhow::Result<Vec<Log>> {
+        let transfer_topic = erc20::transfer_event_topic()?;
+        let account_topic = H256::from(account);
+
+        let mut logs = Vec::new();
+        for (from, to) in [(Some(vec![account_topic]), None), (None, Some(vec![account_topic]))] {
+            let filter = FilterBuilder::default()
+                .address(contract_addresses.clone())
+                .from_block(BlockNumber::Number(from_block))
+                .to_block(BlockNumber::Number(to_block))
+                .topics(Some(vec![transfer_topic]), from, to, None)
+                .build();
+            for log in self.web3.eth().logs(filter).await? {
+                // NOTE: transfers to self match both filters
+                if !logs.iter().any(|other: &Log| other.transaction_hash == log.transaction_hash && other.log_index == log.log_index) {
+                    logs.push(log);
+                }
+            }
+        }
+        Ok(logs)
+    }
+
+    // ERC-20 approvals given by the owner on any of the token contracts
+    pub async fn get_approval_logs(&self, contract_addresses: Vec<Address>, owner: Address, from_block: U64, to_block: U64) -> anyhow::Result<Vec<Log>> {
+        let filter = FilterBuilder::default()
+            .address(contract_addresses)
+            .from_block(BlockNumber::Number(from_block))
+            .to_block(BlockNumber::Number(to_block))
+            .topics(Some(vec![erc20::approval_event_topic()?]), Some(vec![H256::from(owner)]), None, None)
+            .build();
+        Ok(self.web3.eth().logs(filter).await?)
+    }
+
+    // ERC-721 and ERC-1155 transfers from and to the account emitted by any contract
+    pub async fn get_nft_transfer_logs(&self, account: Address, from_block: U64, to_block: U64) -> anyhow::Result<Vec<Log>> {
+        let erc721_topics = Some(vec![nft::erc721_transfer_topic()?]);
+        let erc1155_topics = Some(nft::erc1155_transfer_topics()?);
+        let account_topic = Some(vec![H256::from(account)]);
+
+        let mut logs = Vec::new();
+        for (topic0, topic1, topic2, topic3) in [
+            (erc721_topics.clone(), account_topic.clone(), None, None),
+            (erc721_topics, None, account_topic.clone(), None),
+            // NOTE: ERC-1155 has the operator in the first indexed topic
+            (erc1155_topics.clone(), None, account_topic.clone(), None),
+            (erc1155_topics, None, None, account_topic),
+        ] {
+            let filter = FilterBuilder::default()
+                .from_block(BlockNumber::Number(from_block))
+                .to_block(BlockNumber::Number(to_block))
+                .topics(topic0, topic1, topic2, topic3)
+                .build();
+            for log in self.web3.eth().logs(filter).await? {
+                if !logs.iter().any(|other: &Log| other.transaction_hash == log.transaction_hash && other.log_index == log.log_index) {
+                    logs.push(log);
+                }
+            }
+        }
+        Ok(logs)
+    }
+
+    // Collection name and metadata URI, both are optional extensions of the standards
+    pub async fn get_nft_metadata(&self, standard: NftStandard, contract_address: Address, token_id: U256) -> anyhow::Result<(Option<String>, Option<String>)> {
+        match standard {
+            NftStandard::Erc721 => {
+                let contract = Contract::from_json(self.web3.eth(), contract_address, ERC721_ABI)?;
+                let name: Option<String> = contract.query("name", (), None, Options::default(), None).await.ok();
+                let uri: Option<String> = contract.query("tokenURI", (token_id,), None, Options::default(), None).await.ok();
+                Ok((name, uri))
+            },
+            NftStandard::Erc1155 => {
+                let contract = Contract::from_json(self.web3.eth(), contract_address, ERC1155_ABI)?;
+                let uri: Option<String> = contract.query("uri", (token_id,), None, Options::default(), None).await.ok();
+                Ok((None, uri.map(|uri| nft::expand_metadata_uri(&uri, token_id))))
+            },
+        }
+    }
+
+    // Read-only call, returns the raw output
+    pub async fn call_contract(&self, from: Address, contract_address: Address, data: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        let call = CallRequest {
+            from: Some(from),
+            to: Some(contract_address),
+            data: Some(Bytes(data)),
+            ..Default::default()
+        };
+        Ok(self.web3.eth().call(call, None).await?.0)
+    }
+
+    // Empty for accounts that aren't deployed yet
+    pub async fn get_code(&self, address: Address) -> anyhow::Result<Vec<u8>> {
+        Ok(self.web3.eth().code(address, None).await?.0)
+    }
+
+    pub async fn get_allowance(&self, contract_address: Address, owner: Address, spender: Address) -> anyhow::Result<U256> {
+        let contract = Contract::from_json(self.web3.eth(), contract_address, ERC20_APPROVE_ABI)?;
+        let allowance: U256 = contract.query("allowance", (owner, spender), None, Options::default(), None).await?;
+        Ok(allowance)
+    }
+}
+
+fn to_seconds(value: U256) -> anyhow::Result<i64> {
+    u64::try_from(value).ok()
+        .and_then(|value| i64::try_from(value).ok())
+        .ok_or_else(|| anyhow::anyhow!(ERR_INVALID_SECONDS))
+}
+
+fn call_error_to_fees(err: &web3::Error) -> TransactionFees {
+    match err {
+        web3::Error::Rpc(rpc_error) => TransactionFees::from_rpc_error(&rpc_error.message, rpc_error.data.as_ref(), ETH),
+        _ => TransactionFees::RpcFailure { error: err.to_string() },
+    }
 }
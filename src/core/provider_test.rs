@@ -1,9 +1,13 @@
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
-    use web3::transports::Http;
+    use web3::{signing::SecretKey, transports::{test::TestTransport, Http}, types::TransactionParameters};
     use crate::core::eth_utils;
+    use crate::core::fees::{GasFees, GasPrices, L1DataFee};
+    use crate::core::l1_fee;
+    use crate::core::bridge::{Withdrawal, WithdrawalMessage, WithdrawalProgress};
     use crate::core::token::Token;
+    use crate::core::{fees::FeeTier, transaction::TransactionFees};
     use super::super::eth_chain::EthChain;
     use super::super::provider::Provider;
 
@@ -74,4 +78,403 @@ mod tests {
 
         Ok(())
     }
+
+    #[test_case(EthChain::EthereumMainnet, "0x1", true)]
+    #[test_case(EthChain::EthereumSepolia, "0xaa36a7", true)]
+    #[test_case(EthChain::EthereumSepolia, "0x2", false)]
+    #[test_case(EthChain::ArbitrumSepolia, "0x66eee", true)]
+    #[test_case(EthChain::ArbitrumSepolia, "0x66eeb", false)]
+    #[tokio::test]
+    async fn test_verify_chain_id(chain: EthChain, endpoint_chain_id: &str, valid: bool) -> anyhow::Result<()> {
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!(endpoint_chain_id));
+
+        let provider = Provider::new(transport.clone(), chain)?;
+        assert_eq!(provider.verify_chain_id().await.is_ok(), valid);
+        transport.assert_request("eth_chainId", &[]);
+
+        // Successful handshake should be cached
+        if valid {
+            provider.verify_chain_id().await?;
+        }
+        transport.assert_no_more_requests();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_refuse_to_sign_on_chain_mismatch() -> anyhow::Result<()> {
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!("0x1"));
+
+        let provider = Provider::new(transport.clone(), EthChain::OptimismMainnet)?;
+        let secret_key = SecretKey::from_slice(&[1u8; 32])?;
+        let fees = GasFees::new(21000.into(), GasPrices::with_priority_fee(100.into(), 1.into()));
+        let transaction = TransactionParameters {
+            to: Some(web3::types::Address::from_low_u64_be(1)),
+            ..Default::default()
+        };
+
+        let result = provider.send_transaction(transaction, web3::types::Address::from_low_u64_be(2), fees, &secret_key).await;
+        assert!(result.is_err());
+
+        transport.assert_request("eth_chainId", &[]);
+        transport.assert_no_more_requests();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signed_transaction_has_chain_id() -> anyhow::Result<()> {
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!("0xaa36a7"));
+        transport.add_response(serde_json::json!("0x0"));
+        transport.add_response(serde_json::json!(format!("0x{}", "ab".repeat(32))));
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumSepolia)?;
+        let secret_key = SecretKey::from_slice(&[1u8; 32])?;
+        let fees = GasFees::new(21000.into(), GasPrices::with_priority_fee(100.into(), 1.into()));
+        let transaction = TransactionParameters {
+            to: Some(web3::types::Address::from_low_u64_be(1)),
+            ..Default::default()
+        };
+
+        provider.send_transaction(transaction, web3::types::Address::from_low_u64_be(2), fees, &secret_key).await?;
+
+        transport.assert_request("eth_chainId", &[]);
+        transport.assert_request("eth_getTransactionCount", &[
+            "\"0x0000000000000000000000000000000000000002\"".to_string(), "\"pending\"".to_string()]);
+
+        // Type-2 envelope, followed by the RLP list which starts with the chain id
+        let expected = web3::Web3::new(TestTransport::default()).accounts().sign_transaction(TransactionParameters {
+            nonce: Some(0.into()),
+            to: Some(web3::types::Address::from_low_u64_be(1)),
+            gas: 21000.into(),
+            chain_id: Some(EthChain::EthereumSepolia.get_chain_id()),
+            transaction_type: Some(2.into()),
+            max_fee_per_gas: Some(201.into()),
+            max_priority_fee_per_gas: Some(1.into()),
+            ..Default::default()
+        }, &secret_key).await?;
+        let raw_transaction = expected.raw_transaction.0.clone();
+        assert_eq!(raw_transaction[0], 2);
+        assert_eq!(raw_transaction[3..7], [0x83, 0xaa, 0x36, 0xa7]);
+
+        transport.assert_request("eth_sendRawTransaction", &[
+            format!("\"0x{}\"", hex::encode(&raw_transaction))]);
+        transport.assert_no_more_requests();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_transfer_logs() -> anyhow::Result<()> {
+        let account = web3::types::Address::from_low_u64_be(2);
+        let token = web3::types::Address::from_low_u64_be(3);
+        let log = |log_index: u64| serde_json::json!({
+            "address": format!("{:?}", token),
+            "topics": [],
+            "data": "0x",
+            "transactionHash": format!("0x{}", "ab".repeat(32)),
+            "logIndex": format!("0x{:x}", log_index),
+        });
+
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!([log(0), log(1)]));
+        transport.add_response(serde_json::json!([log(1), log(2)]));
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumSepolia)?;
+        let logs = provider.get_transfer_logs(vec![token], account, 10.into(), 20.into()).await?;
+
+        // Transfer to self is reported by both filters, but must be listed once
+        assert_eq!(logs.iter().map(|log| log.log_index.unwrap().as_u64()).collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        let transfer_topic = format!("{:?}", crate::core::erc20::transfer_event_topic()?);
+        let account_topic = format!("{:?}", web3::types::H256::from(account));
+        transport.assert_request("eth_getLogs", &[serde_json::json!({
+            "address": format!("{:?}", token),
+            "fromBlock": "0xa",
+            "toBlock": "0x14",
+            "topics": [transfer_topic, account_topic],
+        }).to_string()]);
+        transport.assert_request("eth_getLogs", &[serde_json::json!({
+            "address": format!("{:?}", token),
+            "fromBlock": "0xa",
+            "toBlock": "0x14",
+            "topics": [transfer_topic, null, account_topic],
+        }).to_string()]);
+        transport.assert_no_more_requests();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_approvals_and_allowance() -> anyhow::Result<()> {
+        let owner = web3::types::Address::from_low_u64_be(2);
+        let token = web3::types::Address::from_low_u64_be(3);
+        let spender = web3::types::Address::from_low_u64_be(4);
+
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!([]));
+        transport.add_response(serde_json::json!(format!("0x{}", "ff".repeat(32))));
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumSepolia)?;
+        let logs = provider.get_approval_logs(vec![token], owner, 0.into(), 20.into()).await?;
+        assert!(logs.is_empty());
+        let allowance = provider.get_allowance(token, owner, spender).await?;
+        assert_eq!(allowance, web3::types::U256::MAX);
+
+        let approval_topic = format!("{:?}", crate::core::erc20::approval_event_topic()?);
+        let owner_topic = format!("{:?}", web3::types::H256::from(owner));
+        transport.assert_request("eth_getLogs", &[serde_json::json!({
+            "address": format!("{:?}", token),
+            "fromBlock": "0x0",
+            "toBlock": "0x14",
+            "topics": [approval_topic, owner_topic],
+        }).to_string()]);
+        transport.assert_request("eth_call", &[serde_json::json!({
+            "data": format!("0xdd62ed3e{}{}", hex::encode(web3::types::H256::from(owner)), hex::encode(web3::types::H256::from(spender))),
+            "to": format!("{:?}", token),
+        }).to_string(), serde_json::json!("latest").to_string()]);
+        transport.assert_no_more_requests();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_nft_metadata() -> anyhow::Result<()> {
+        let contract = web3::types::Address::from_low_u64_be(5);
+        let uri = web3::ethabi::encode(&[web3::ethabi::Token::String("ipfs://meta/{id}.json".to_string())]);
+
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!(format!("0x{}", hex::encode(uri))));
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumSepolia)?;
+        let metadata = provider.get_nft_metadata(crate::core::nft::NftStandard::Erc1155, contract, 26.into()).await?;
+        assert_eq!(metadata, (None, Some(format!("ipfs://meta/{:064x}.json", 26))));
+
+        transport.assert_request("eth_call", &[serde_json::json!({
+            "data": format!("0x0e89341c{:064x}", 26),
+            "to": format!("{:?}", contract),
+        }).to_string(), serde_json::json!("latest").to_string()]);
+        transport.assert_no_more_requests();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_call_contract() -> anyhow::Result<()> {
+        let from = web3::types::Address::from_low_u64_be(1);
+        let contract = web3::types::Address::from_low_u64_be(6);
+
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!(format!("0x{:064x}", 42)));
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumSepolia)?;
+        let output = provider.call_contract(from, contract, vec![0x12, 0x34]).await?;
+        assert_eq!(web3::types::U256::from_big_endian(&output), 42.into());
+
+        transport.assert_request("eth_call", &[serde_json::json!({
+            "data": "0x1234",
+            "from": format!("{:?}", from),
+            "to": format!("{:?}", contract),
+        }).to_string(), serde_json::json!("latest").to_string()]);
+        transport.assert_no_more_requests();
+        Ok(())
+    }
+
+    #[test_case("0xde0b6b3a7640000", true; "enough balance")]
+    #[test_case("0x5208", false; "balance below max cost")]
+    #[tokio::test]
+    async fn test_estimate_transaction_fees(balance: &str, enough: bool) -> anyhow::Result<()> {
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!("0x"));
+        transport.add_response(serde_json::json!("0x5208"));
+        transport.add_response(serde_json::json!({
+            "oldestBlock": "0x1",
+            "baseFeePerGas": ["0x64", "0x64"],
+            "gasUsedRatio": [0.5],
+            "reward": [["0x1", "0x2", "0x3"]],
+        }));
+        transport.add_response(serde_json::json!(balance));
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumSepolia)?;
+        let transaction = TransactionParameters {
+            to: Some(web3::types::Address::from_low_u64_be(1)),
+            value: 1.into(),
+            ..Default::default()
+        };
+        let fees = provider.estimate_transaction_fees(transaction, web3::types::Address::from_low_u64_be(2), FeeTier::Normal).await?;
+
+        let expected = if enough {
+            TransactionFees::Estimated {
+                currency: "ETH".to_string(),
+                fees: GasFees::new(21000.into(), GasPrices::with_priority_fee(100.into(), 2.into())),
+            }
+        } else {
+            TransactionFees::NotEnoughFunds { currency: "ETH".to_string() }
+        };
+        assert_eq!(fees, expected);
+
+        // Dry run goes first
+        transport.assert_request("eth_call", &[
+            serde_json::json!({
+                "from": "0x0000000000000000000000000000000000000002",
+                "to": "0x0000000000000000000000000000000000000001",
+                "value": "0x1",
+                "data": "0x",
+            }).to_string(),
+            "\"latest\"".to_string()]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_estimate_transaction_fees_with_l1_data_fee() -> anyhow::Result<()> {
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!("0x"));
+        transport.add_response(serde_json::json!("0x5208"));
+        transport.add_response(serde_json::json!({
+            "oldestBlock": "0x1",
+            "baseFeePerGas": ["0x64", "0x64"],
+            "gasUsedRatio": [0.5],
+            "reward": [["0x1", "0x2", "0x3"]],
+        }));
+        transport.add_response(serde_json::json!(format!("0x{:064x}", 5000)));
+        transport.add_response(serde_json::json!("0xde0b6b3a7640000"));
+
+        let provider = Provider::new(transport.clone(), EthChain::OptimismSepolia)?;
+        let transaction = TransactionParameters {
+            to: Some(web3::types::Address::from_low_u64_be(1)),
+            value: 1.into(),
+            ..Default::default()
+        };
+        let fees = provider.estimate_transaction_fees(transaction, web3::types::Address::from_low_u64_be(2), FeeTier::Normal).await?;
+
+        let expected = GasFees::new(21000.into(), GasPrices::with_priority_fee(100.into(), 2.into()))
+            .with_l1_data_fee(L1DataFee::Charged(5000.into()));
+        assert_eq!(fees, TransactionFees::Estimated { currency: "ETH".to_string(), fees: expected });
+
+        // The oracle gets the transaction with the estimated gas
+        let encoded = l1_fee::encode_unsigned_transaction(&TransactionParameters {
+            to: Some(web3::types::Address::from_low_u64_be(1)),
+            value: 1.into(),
+            gas: 21000.into(),
+            max_fee_per_gas: Some(202.into()),
+            max_priority_fee_per_gas: Some(2.into()),
+            ..Default::default()
+        }, EthChain::OptimismSepolia.get_chain_id());
+        let call_data = web3::ethabi::encode(&[web3::ethabi::Token::Bytes(encoded)]);
+        transport.assert_request("eth_call", &[
+            serde_json::json!({
+                "from": "0x0000000000000000000000000000000000000002",
+                "to": "0x0000000000000000000000000000000000000001",
+                "value": "0x1",
+                "data": "0x",
+            }).to_string(),
+            "\"latest\"".to_string()]);
+        transport.assert_request("eth_estimateGas", &[
+            serde_json::json!({
+                "from": "0x0000000000000000000000000000000000000002",
+                "to": "0x0000000000000000000000000000000000000001",
+                "value": "0x1",
+                "data": "0x",
+            }).to_string()]);
+        transport.assert_request("eth_feeHistory", &[
+            "\"0xa\"".to_string(), "\"latest\"".to_string(), "[10.0,50.0,90.0]".to_string()]);
+        transport.assert_request("eth_call", &[
+            serde_json::json!({
+                "to": "0x420000000000000000000000000000000000000f",
+                "data": format!("0x49948e0e{}", hex::encode(call_data)),
+            }).to_string(),
+            "\"latest\"".to_string()]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_op_withdrawal_progress() -> anyhow::Result<()> {
+        let word = |value: u64| format!("{:064x}", value);
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!(format!("0x{}", word(0))));                // finalizedWithdrawals
+        transport.add_response(serde_json::json!(format!("0x{}{}", word(0), word(0))));     // provenWithdrawals
+        transport.add_response(serde_json::json!(format!("0x{}", word(5))));                // gameCount
+        transport.add_response(serde_json::json!(format!("0x{}{}{}", word(1), word(1_700_000_000), word(0xab))));
+        transport.add_response(serde_json::json!(format!("0x{}", word(1000))));             // l2BlockNumber
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumSepolia)?;
+        let mut withdrawal = Withdrawal::new(Default::default(), EthChain::OptimismSepolia,
+            web3::types::Address::from_low_u64_be(2), "1".parse()?, 0);
+        withdrawal.l2_block = Some(900.into());
+        withdrawal.message = Some(WithdrawalMessage::OpStack { withdrawal_hash: web3::types::H256::from_low_u64_be(7) });
+
+        // NOTE: the posted state is only known from the last response, the L2 block of the latest dispute game
+        let progress = provider.get_withdrawal_progress(&withdrawal).await?;
+        assert_eq!(progress, WithdrawalProgress { state_posted: true, ..Default::default() });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_arbitrum_withdrawal_progress() -> anyhow::Result<()> {
+        let mut transport = TestTransport::default();
+        transport.add_response(serde_json::json!(format!("0x{:064x}", 1)));
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumMainnet)?;
+        let mut withdrawal = Withdrawal::new(Default::default(), EthChain::ArbitrumMainnet,
+            web3::types::Address::from_low_u64_be(2), "1".parse()?, 0);
+        withdrawal.message = Some(WithdrawalMessage::Arbitrum { position: 42.into() });
+
+        let progress = provider.get_withdrawal_progress(&withdrawal).await?;
+        assert!(progress.finalized);
+        transport.assert_request("eth_call", &[
+            serde_json::json!({
+                "to": "0x0b9857ae2d4a3dbe74ffe1d7df045bb7f96e4840",
+                "data": format!("0x5a129efe{:064x}", 42),
+            }).to_string(),
+            "\"latest\"".to_string()]);
+
+        // Withdrawals are only followed on their own L1
+        let provider = Provider::new(TestTransport::default(), EthChain::EthereumSepolia)?;
+        assert!(provider.get_withdrawal_progress(&withdrawal).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_estimate_transaction_fees_rpc_failure() -> anyhow::Result<()> {
+        // No response for the dry run
+        let transport = TestTransport::default();
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumSepolia)?;
+        let fees = provider.estimate_transaction_fees(TransactionParameters::default(), web3::types::Address::from_low_u64_be(2), FeeTier::Normal).await?;
+
+        assert!(matches!(fees, TransactionFees::RpcFailure { .. }));
+        Ok(())
+    }
+
+    fn price_round_response(round_id: web3::types::U256, answer: u64, updated_at: u64) -> serde_json::Value {
+        serde_json::json!(format!("0x{:064x}{:064x}{:064x}{:064x}{:064x}", round_id, answer, updated_at, updated_at, round_id))
+    }
+
+    #[tokio::test]
+    async fn test_get_eth_usd_rate_at() -> anyhow::Result<()> {
+        let phase_round = |round: u64| (web3::types::U256::one() << 64) | round.into();
+        let mut transport = TestTransport::default();
+        transport.add_response(price_round_response(phase_round(4), 4000_00000000, 400));
+        transport.add_response(price_round_response(phase_round(2), 2000_00000000, 200));
+        transport.add_response(price_round_response(phase_round(3), 3000_00000000, 300));
+
+        let provider = Provider::new(transport.clone(), EthChain::EthereumMainnet)?;
+        assert_eq!(provider.get_eth_usd_rate_at(250).await?, 2000.0);
+
+        let feed = format!("{:?}", EthChain::EthereumMainnet.get_chainlink_contract_address());
+        transport.assert_request("eth_call", &[
+            serde_json::json!({ "to": feed, "data": "0xfeaf968c" }).to_string(),
+            "\"latest\"".to_string()]);
+        for round in [2, 3] {
+            transport.assert_request("eth_call", &[
+                serde_json::json!({ "to": feed, "data": format!("0x9a6fc8f5{:064x}", phase_round(round)) }).to_string(),
+                "\"latest\"".to_string()]);
+        }
+        transport.assert_no_more_requests();
+
+        // Rounds updated before the timestamp are taken as they are
+        let mut transport = TestTransport::default();
+        transport.add_response(price_round_response(phase_round(4), 4000_00000000, 400));
+        let provider = Provider::new(transport, EthChain::EthereumMainnet)?;
+        assert_eq!(provider.get_eth_usd_rate_at(500).await?, 4000.0);
+        Ok(())
+    }
 }
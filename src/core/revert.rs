@@ -0,0 +1,44 @@
+use web3::{ethabi, types::U256};
+
+// Error(string) and Panic(uint256) selectors emitted by Solidity
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+const SELECTOR_LENGTH: usize = 4;
+
+// Human readable reason from the revert data of a failed call
+pub fn decode_revert_data(data: &[u8]) -> Option<String> {
+    if data.len() < SELECTOR_LENGTH {
+        return None;
+    }
+    let (selector, payload) = data.split_at(SELECTOR_LENGTH);
+
+    if selector == ERROR_SELECTOR {
+        return match ethabi::decode(&[ethabi::ParamType::String], payload).ok()?.pop()? {
+            ethabi::Token::String(reason) => Some(reason),
+            _ => None,
+        };
+    }
+    if selector == PANIC_SELECTOR {
+        return match ethabi::decode(&[ethabi::ParamType::Uint(256)], payload).ok()?.pop()? {
+            ethabi::Token::Uint(code) => Some(panic_reason(code)),
+            _ => None,
+        };
+    }
+    Some(format!("custom error 0x{}", hex::encode(selector)))
+}
+
+fn panic_reason(code: U256) -> String {
+    let description = match code.low_u64() {
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to uninitialized function",
+        _ => "unknown panic",
+    };
+    format!("panic 0x{:x} ({})", code, description)
+}
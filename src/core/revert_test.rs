@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::{ethabi, types::U256};
+    use crate::core::revert::decode_revert_data;
+
+    fn encode(selector: &str, token: ethabi::Token) -> Vec<u8> {
+        let mut data = hex::decode(selector).unwrap();
+        data.extend(ethabi::encode(&[token]));
+        data
+    }
+
+    #[test]
+    fn test_decode_error_string() {
+        let data = encode("08c379a0", ethabi::Token::String("ERC20: transfer amount exceeds balance".to_string()));
+        assert_eq!(decode_revert_data(&data), Some("ERC20: transfer amount exceeds balance".to_string()));
+    }
+
+    #[test_case(0x01, "panic 0x1 (assertion failed)")]
+    #[test_case(0x11, "panic 0x11 (arithmetic overflow or underflow)")]
+    #[test_case(0x32, "panic 0x32 (array index out of bounds)")]
+    #[test_case(0x99, "panic 0x99 (unknown panic)")]
+    fn test_decode_panic(code: u64, expected: &str) {
+        let data = encode("4e487b71", ethabi::Token::Uint(U256::from(code)));
+        assert_eq!(decode_revert_data(&data), Some(expected.to_string()));
+    }
+
+    #[test_case("", None; "empty")]
+    #[test_case("08c3", None; "truncated selector")]
+    #[test_case("08c379a0", None; "missing reason")]
+    #[test_case("e450d38c", Some("custom error 0xe450d38c"); "custom error")]
+    fn test_decode_other(data: &str, expected: Option<&str>) {
+        assert_eq!(decode_revert_data(&hex::decode(data).unwrap()), expected.map(str::to_string));
+    }
+}
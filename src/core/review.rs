@@ -0,0 +1,41 @@
+use web3::types::U256;
+
+use super::{contract::DecodedCall, eth_utils, unsigned_transaction::UnsignedTransaction};
+
+// What the user confirms before signing, the transaction is signed exactly as reviewed
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionReview {
+    pub transaction: UnsignedTransaction,
+    pub from_name: Option<String>,
+    pub to_name: Option<String>,
+    pub eth_usd_rate: Option<f64>,
+    pub currency_usd_rate: Option<f64>,
+    pub call: Option<DecodedCall>,
+}
+
+impl TransactionReview {
+    pub fn is_native_currency(&self) -> bool {
+        self.transaction.currency == "ETH"
+    }
+
+    pub fn amount_usd(&self) -> Option<f64> {
+        self.currency_usd_rate.map(|rate| self.transaction.amount.to_f64() * rate)
+    }
+
+    pub fn max_fee(&self) -> U256 {
+        self.transaction.max_cost()
+    }
+
+    pub fn max_fee_usd(&self) -> Option<f64> {
+        self.eth_usd_rate.map(|rate| eth_utils::wei_to_eth(self.max_fee()).to_f64() * rate)
+    }
+
+    // Worst case ETH leaving the account, tokens are on top of it
+    pub fn total_eth(&self) -> U256 {
+        self.transaction.value + self.max_fee()
+    }
+
+    pub fn total_usd(&self) -> Option<f64> {
+        Some(self.amount_usd()? + self.max_fee_usd()?)
+    }
+}
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{Address, TransactionParameters};
+    use crate::core::{
+        amount::Amount,
+        eth_chain::EthChain,
+        fees::{GasFees, GasPrices},
+        review::TransactionReview,
+        unsigned_transaction::UnsignedTransaction
+    };
+
+    fn review(currency: &str, value: u64, currency_usd_rate: Option<f64>) -> TransactionReview {
+        let transaction = TransactionParameters {
+            nonce: Some(1.into()),
+            to: Some(Address::from_low_u64_be(1)),
+            value: value.into(),
+            ..Default::default()
+        };
+        // 0.001 ETH max fee
+        let fees = GasFees::new(100_000.into(), GasPrices::with_priority_fee(4_000_000_000u64.into(), 2_000_000_000u64.into()));
+        TransactionReview {
+            transaction: UnsignedTransaction::new(transaction, EthChain::EthereumMainnet, Address::from_low_u64_be(2), fees)
+                .with_transfer(Address::from_low_u64_be(1), "0.5".parse::<Amount>().unwrap(), currency),
+            from_name: None,
+            to_name: None,
+            eth_usd_rate: Some(2000.0),
+            currency_usd_rate,
+            call: None,
+        }
+    }
+
+    #[test_case("ETH", 500_000_000_000_000_000, Some(2000.0), 501_000_000_000_000_000, Some(1002.0); "eth")]
+    #[test_case("USDC", 0, Some(1.0), 1_000_000_000_000_000, Some(2.5); "token")]
+    #[test_case("XYZ", 0, None, 1_000_000_000_000_000, None; "token without price")]
+    fn test_review_totals(currency: &str, value: u64, rate: Option<f64>, total_eth: u64, total_usd: Option<f64>) {
+        let review = review(currency, value, rate);
+        assert_eq!(review.is_native_currency(), currency == "ETH");
+        assert_eq!(review.max_fee(), 1_000_000_000_000_000u64.into());
+        assert_eq!(review.max_fee_usd(), Some(2.0));
+        assert_eq!(review.total_eth(), total_eth.into());
+        assert_eq!(review.total_usd(), total_usd);
+    }
+}
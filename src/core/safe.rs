@@ -0,0 +1,297 @@
+use web3::{
+    ethabi,
+    signing::{keccak256, Key, SecretKey, SecretKeyRef},
+    types::{Address, Bytes, H256, U256},
+};
+
+use super::{amount::{Amount, ETH_DECIMALS}, eth_chain::EthChain, transaction::TransactionRequest};
+
+const SAFE_ABI: &[u8] = include_bytes!("../../abi/safe.json");
+
+// NOTE: the domain with the chain id is used since Safe 1.3.0
+const DOMAIN_SEPARATOR_TYPE: &str = "EIP712Domain(uint256 chainId,address verifyingContract)";
+const SAFE_TX_TYPE: &str = "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)";
+const EIP712_PREFIX: [u8; 2] = [0x19, 0x01];
+
+const OPERATION_CALL: u8 = 0;
+const SIGNATURE_LENGTH: usize = 65;
+const SIGNATURE_V_OFFSET: u8 = 27;
+
+const ERR_INVALID_SAFE_RESPONSE: &str = "Unexpected response, is it a Safe contract?";
+const ERR_NOT_OWNER: &str = "The account is not an owner of the Safe";
+const ERR_INVALID_SIGNATURE: &str = "Invalid signature";
+const ERR_WRONG_SIGNER: &str = "Signature doesn't belong to its owner";
+const ERR_OTHER_TRANSACTION: &str = "Signatures are for another Safe transaction";
+const ERR_THRESHOLD_NOT_MET: &str = "Not enough owner signatures to execute";
+const ERR_CHAIN_ID_MISMATCH: &str = "Safe transaction chain id doesn't match its chain";
+const ERR_UNSUPPORTED_OPERATION: &str = "Only plain calls are supported, delegate calls are rejected";
+const ERR_GAS_REFUND: &str = "Safe transactions with gas refunds are not supported";
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SavedSafe {
+    pub chain: EthChain,
+    pub address: Address,
+}
+
+// On-chain state of a Safe, read without the hosted transaction service
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafeInfo {
+    pub chain: EthChain,
+    pub address: Address,
+    pub owners: Vec<Address>,
+    pub threshold: usize,
+    pub nonce: U256,
+}
+
+impl SafeInfo {
+    pub fn is_owner(&self, account: Address) -> bool {
+        self.owners.contains(&account)
+    }
+}
+
+// Fields of the EIP-712 SafeTx struct, refunds are never used so the executor pays the gas
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTransaction {
+    pub chain: EthChain,
+    pub chain_id: u64,
+    pub safe: Address,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub operation: u8,
+    pub safe_tx_gas: U256,
+    pub base_gas: U256,
+    pub gas_price: U256,
+    pub gas_token: Address,
+    pub refund_receiver: Address,
+    pub nonce: U256,
+}
+
+impl SafeTransaction {
+    pub fn new(info: &SafeInfo, to: Address, value: U256, data: Vec<u8>) -> Self {
+        Self {
+            chain: info.chain,
+            chain_id: info.chain.get_chain_id(),
+            safe: info.address,
+            to,
+            value,
+            data: Bytes(data),
+            operation: OPERATION_CALL,
+            safe_tx_gas: U256::zero(),
+            base_gas: U256::zero(),
+            gas_price: U256::zero(),
+            gas_token: Address::zero(),
+            refund_receiver: Address::zero(),
+            nonce: info.nonce,
+        }
+    }
+
+    pub fn domain_separator(&self) -> H256 {
+        H256(keccak256(&ethabi::encode(&[
+            ethabi::Token::FixedBytes(keccak256(DOMAIN_SEPARATOR_TYPE.as_bytes()).to_vec()),
+            ethabi::Token::Uint(self.chain_id.into()),
+            ethabi::Token::Address(self.safe),
+        ])))
+    }
+
+    pub fn struct_hash(&self) -> H256 {
+        H256(keccak256(&ethabi::encode(&[
+            ethabi::Token::FixedBytes(keccak256(SAFE_TX_TYPE.as_bytes()).to_vec()),
+            ethabi::Token::Address(self.to),
+            ethabi::Token::Uint(self.value),
+            ethabi::Token::FixedBytes(keccak256(&self.data.0).to_vec()),
+            ethabi::Token::Uint(self.operation.into()),
+            ethabi::Token::Uint(self.safe_tx_gas),
+            ethabi::Token::Uint(self.base_gas),
+            ethabi::Token::Uint(self.gas_price),
+            ethabi::Token::Address(self.gas_token),
+            ethabi::Token::Address(self.refund_receiver),
+            ethabi::Token::Uint(self.nonce),
+        ])))
+    }
+
+    // The safeTxHash owners sign
+    pub fn hash(&self) -> H256 {
+        let mut message = EIP712_PREFIX.to_vec();
+        message.extend_from_slice(self.domain_separator().as_bytes());
+        message.extend_from_slice(self.struct_hash().as_bytes());
+        H256(keccak256(&message))
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.chain_id != self.chain.get_chain_id() {
+            return Err(anyhow::anyhow!(ERR_CHAIN_ID_MISMATCH));
+        }
+        // NOTE: a delegate call or a refund could move the Safe's funds without showing up as the call
+        if self.operation != OPERATION_CALL {
+            return Err(anyhow::anyhow!(ERR_UNSUPPORTED_OPERATION));
+        }
+        if !self.safe_tx_gas.is_zero() || !self.base_gas.is_zero() || !self.gas_price.is_zero()
+            || !self.gas_token.is_zero() || !self.refund_receiver.is_zero() {
+            return Err(anyhow::anyhow!(ERR_GAS_REFUND));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SafeSignature {
+    pub owner: Address,
+    pub signature: Bytes, // r, s and v with v of 27 or 28
+}
+
+impl SafeSignature {
+    pub fn sign(hash: H256, secret_key: &SecretKey) -> anyhow::Result<Self> {
+        let key = SecretKeyRef::new(secret_key);
+        let signature = key.sign(hash.as_bytes(), None)?;
+        let mut bytes = signature.r.as_bytes().to_vec();
+        bytes.extend_from_slice(signature.s.as_bytes());
+        bytes.push(signature.v as u8);
+        Ok(Self { owner: key.address(), signature: Bytes(bytes) })
+    }
+
+    pub fn recover(&self, hash: H256) -> anyhow::Result<Address> {
+        let bytes = &self.signature.0;
+        if bytes.len() != SIGNATURE_LENGTH || bytes[64] < SIGNATURE_V_OFFSET {
+            return Err(anyhow::anyhow!(ERR_INVALID_SIGNATURE));
+        }
+        let recovery_id = (bytes[64] - SIGNATURE_V_OFFSET) as i32;
+        web3::signing::recover(hash.as_bytes(), &bytes[..64], recovery_id)
+            .map_err(|_| anyhow::anyhow!(ERR_INVALID_SIGNATURE))
+    }
+
+    pub fn verify(&self, hash: H256) -> anyhow::Result<()> {
+        if self.recover(hash)? != self.owner {
+            return Err(anyhow::anyhow!(ERR_WRONG_SIGNER));
+        }
+        Ok(())
+    }
+}
+
+// Safe transaction with the owner signatures collected so far, exported and imported as JSON
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeProposal {
+    pub safe_tx_hash: H256,
+    pub transaction: SafeTransaction,
+    pub signatures: Vec<SafeSignature>,
+}
+
+impl SafeProposal {
+    pub fn new(transaction: SafeTransaction) -> Self {
+        Self { safe_tx_hash: transaction.hash(), transaction, signatures: Vec::new() }
+    }
+
+    // The hash is recomputed, so a tampered file can't pass off signatures for another transaction
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.transaction.validate()?;
+        if self.transaction.hash() != self.safe_tx_hash {
+            return Err(anyhow::anyhow!(ERR_OTHER_TRANSACTION));
+        }
+        self.signatures.iter().try_for_each(|signature| signature.verify(self.safe_tx_hash))
+    }
+
+    pub fn sign(&mut self, info: &SafeInfo, secret_key: &SecretKey) -> anyhow::Result<()> {
+        let signature = SafeSignature::sign(self.safe_tx_hash, secret_key)?;
+        if !info.is_owner(signature.owner) {
+            return Err(anyhow::anyhow!(ERR_NOT_OWNER));
+        }
+        self.add_signature(signature)
+    }
+
+    // A newer signature of the same owner replaces the previous one
+    pub fn add_signature(&mut self, signature: SafeSignature) -> anyhow::Result<()> {
+        signature.verify(self.safe_tx_hash)?;
+        self.signatures.retain(|other| other.owner != signature.owner);
+        self.signatures.push(signature);
+        Ok(())
+    }
+
+    pub fn merge(&mut self, other: &SafeProposal) -> anyhow::Result<()> {
+        if other.safe_tx_hash != self.safe_tx_hash {
+            return Err(anyhow::anyhow!(ERR_OTHER_TRANSACTION));
+        }
+        other.signatures.iter().try_for_each(|signature| self.add_signature(signature.clone()))
+    }
+
+    pub fn is_signed_by(&self, owner: Address) -> bool {
+        self.signatures.iter().any(|signature| signature.owner == owner)
+    }
+
+    // Signatures of current owners, sorted by owner as execTransaction requires
+    pub fn owner_signatures(&self, info: &SafeInfo) -> Vec<&SafeSignature> {
+        let mut signatures = self.signatures.iter()
+            .filter(|signature| info.is_owner(signature.owner))
+            .collect::<Vec<_>>();
+        signatures.sort_by_key(|signature| signature.owner);
+        signatures
+    }
+
+    pub fn is_executable(&self, info: &SafeInfo) -> bool {
+        self.transaction.nonce == info.nonce && self.owner_signatures(info).len() >= info.threshold
+    }
+}
+
+pub fn encode_getter(name: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(ethabi::Contract::load(SAFE_ABI)?.function(name)?.encode_input(&[])?)
+}
+
+pub fn decode_info(chain: EthChain, address: Address, owners: &[u8], threshold: &[u8], nonce: &[u8]) -> anyhow::Result<SafeInfo> {
+    let safe = ethabi::Contract::load(SAFE_ABI)?;
+    let output = |name: &str, data: &[u8]| -> anyhow::Result<ethabi::Token> {
+        safe.function(name)?.decode_output(data)?.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!(ERR_INVALID_SAFE_RESPONSE))
+    };
+
+    let owners = output("getOwners", owners)?.into_array()
+        .ok_or_else(|| anyhow::anyhow!(ERR_INVALID_SAFE_RESPONSE))?
+        .into_iter()
+        .filter_map(|owner| owner.into_address())
+        .collect::<Vec<_>>();
+    let threshold = output("getThreshold", threshold)?.into_uint()
+        .ok_or_else(|| anyhow::anyhow!(ERR_INVALID_SAFE_RESPONSE))?;
+    let nonce = output("nonce", nonce)?.into_uint()
+        .ok_or_else(|| anyhow::anyhow!(ERR_INVALID_SAFE_RESPONSE))?;
+    if owners.is_empty() || threshold.is_zero() || threshold > owners.len().into() {
+        return Err(anyhow::anyhow!(ERR_INVALID_SAFE_RESPONSE));
+    }
+
+    Ok(SafeInfo { chain, address, owners, threshold: threshold.as_usize(), nonce })
+}
+
+// Any account can execute once the threshold is met, it only pays the gas
+pub fn exec_request(executor: Address, info: &SafeInfo, proposal: &SafeProposal) -> anyhow::Result<TransactionRequest> {
+    proposal.validate()?;
+    if !proposal.is_executable(info) {
+        return Err(anyhow::anyhow!(ERR_THRESHOLD_NOT_MET));
+    }
+    let signatures = proposal.owner_signatures(info).into_iter()
+        .take(info.threshold)
+        .flat_map(|signature| signature.signature.0.clone())
+        .collect::<Vec<_>>();
+
+    let transaction = &proposal.transaction;
+    let data = ethabi::Contract::load(SAFE_ABI)?.function("execTransaction")?.encode_input(&[
+        ethabi::Token::Address(transaction.to),
+        ethabi::Token::Uint(transaction.value),
+        ethabi::Token::Bytes(transaction.data.0.clone()),
+        ethabi::Token::Uint(transaction.operation.into()),
+        ethabi::Token::Uint(transaction.safe_tx_gas),
+        ethabi::Token::Uint(transaction.base_gas),
+        ethabi::Token::Uint(transaction.gas_price),
+        ethabi::Token::Address(transaction.gas_token),
+        ethabi::Token::Address(transaction.refund_receiver),
+        ethabi::Token::Bytes(signatures),
+    ])?;
+
+    Ok(TransactionRequest {
+        from: executor,
+        to: transaction.safe,
+        amount: Amount::zero(ETH_DECIMALS),
+        currency: "ETH".to_string(),
+        chain: transaction.chain,
+        fees: None,
+        data: Some(data),
+    })
+}
@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::{ethabi, signing::{keccak256, Key, SecretKey, SecretKeyRef}, types::{Address, H256, U256}};
+    use crate::core::{
+        eth_chain::EthChain,
+        safe::{self, SafeInfo, SafeProposal, SafeSignature, SafeTransaction}
+    };
+
+    fn owner_key(seed: u8) -> SecretKey {
+        SecretKey::from_slice(&[seed; 32]).unwrap()
+    }
+
+    fn owner(seed: u8) -> Address {
+        SecretKeyRef::new(&owner_key(seed)).address()
+    }
+
+    fn safe_info(threshold: usize) -> SafeInfo {
+        SafeInfo {
+            chain: EthChain::EthereumSepolia,
+            address: Address::from_low_u64_be(0x5afe),
+            owners: vec![owner(1), owner(2), owner(3)],
+            threshold,
+            nonce: 7.into(),
+        }
+    }
+
+    fn proposal(info: &SafeInfo) -> SafeProposal {
+        SafeProposal::new(SafeTransaction::new(info, Address::from_low_u64_be(42), 1_000.into(), Vec::new()))
+    }
+
+    #[test]
+    fn test_type_hashes() {
+        // NOTE: constants of the Safe contracts, see SafeL2.sol
+        assert_eq!(hex::encode(keccak256(b"EIP712Domain(uint256 chainId,address verifyingContract)")),
+            "47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a79469218");
+        assert_eq!(hex::encode(keccak256(b"SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)")),
+            "bb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8");
+    }
+
+    #[test]
+    fn test_transaction_hash() {
+        let info = safe_info(2);
+        let transaction = SafeTransaction::new(&info, Address::from_low_u64_be(42), 1_000.into(), vec![1, 2, 3]);
+        assert_eq!(transaction.nonce, info.nonce);
+        assert_eq!(transaction.chain_id, 11155111);
+
+        // The hash commits to every field, including the chain and the Safe
+        let hash = transaction.hash();
+        assert_ne!(SafeTransaction { nonce: 8.into(), ..transaction.clone() }.hash(), hash);
+        assert_ne!(SafeTransaction { chain_id: 1, ..transaction.clone() }.hash(), hash);
+        assert_ne!(SafeTransaction { safe: Address::from_low_u64_be(1), ..transaction.clone() }.hash(), hash);
+        assert_ne!(SafeTransaction { data: vec![1, 2].into(), ..transaction.clone() }.hash(), hash);
+        assert_eq!(transaction.clone().hash(), hash);
+    }
+
+    #[test]
+    fn test_validate_transaction() -> anyhow::Result<()> {
+        let transaction = SafeTransaction::new(&safe_info(2), Address::from_low_u64_be(42), 1_000.into(), Vec::new());
+        transaction.validate()?;
+
+        let refund_receiver = Address::from_low_u64_be(7);
+        assert!(SafeTransaction { chain_id: 1, ..transaction.clone() }.validate().is_err());
+        assert!(SafeTransaction { operation: 1, ..transaction.clone() }.validate().is_err());
+        assert!(SafeTransaction { safe_tx_gas: 1.into(), ..transaction.clone() }.validate().is_err());
+        assert!(SafeTransaction { base_gas: 1.into(), ..transaction.clone() }.validate().is_err());
+        assert!(SafeTransaction { gas_price: 1.into(), ..transaction.clone() }.validate().is_err());
+        assert!(SafeTransaction { gas_token: refund_receiver, ..transaction.clone() }.validate().is_err());
+        assert!(SafeTransaction { refund_receiver, ..transaction.clone() }.validate().is_err());
+
+        // Imported proposals go through the same checks
+        let mut proposal = SafeProposal::new(SafeTransaction { operation: 1, ..transaction });
+        proposal.safe_tx_hash = proposal.transaction.hash();
+        assert!(proposal.validate().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign_and_merge() -> anyhow::Result<()> {
+        let info = safe_info(2);
+        let mut first = proposal(&info);
+        let mut second = first.clone();
+
+        first.sign(&info, &owner_key(1))?;
+        second.sign(&info, &owner_key(2))?;
+        assert_eq!(first.signatures[0].signature.0.len(), 65);
+        assert!([27, 28].contains(&first.signatures[0].signature.0[64]));
+        assert!(!first.is_executable(&info));
+
+        // Merging the same signature twice keeps one per owner
+        first.merge(&second)?;
+        first.merge(&second)?;
+        assert_eq!(first.signatures.len(), 2);
+        assert!(first.is_signed_by(owner(1)) && first.is_signed_by(owner(2)));
+        assert!(first.is_executable(&info));
+        first.validate()?;
+
+        // Non-owners can't sign and proposals for another transaction don't merge
+        assert!(first.sign(&info, &owner_key(9)).is_err());
+        let mut other = SafeProposal::new(SafeTransaction { nonce: 8.into(), ..first.transaction.clone() });
+        other.sign(&info, &owner_key(3))?;
+        assert!(first.merge(&other).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_signatures() -> anyhow::Result<()> {
+        let info = safe_info(1);
+        let mut proposal = proposal(&info);
+        let signature = SafeSignature::sign(proposal.safe_tx_hash, &owner_key(1))?;
+
+        // Claimed by another owner
+        let forged = SafeSignature { owner: owner(2), ..signature.clone() };
+        assert!(proposal.add_signature(forged).is_err());
+        // Signed over another hash
+        let other_hash = SafeSignature::sign(H256::from_low_u64_be(1), &owner_key(1))?;
+        assert!(proposal.add_signature(other_hash).is_err());
+        // Truncated
+        let truncated = SafeSignature { signature: signature.signature.0[..64].to_vec().into(), ..signature.clone() };
+        assert!(proposal.add_signature(truncated).is_err());
+
+        // A changed transaction no longer matches its hash
+        proposal.add_signature(signature)?;
+        proposal.transaction.value = 2_000.into();
+        assert!(proposal.validate().is_err());
+        Ok(())
+    }
+
+    #[test_case(1, 1, true)]
+    #[test_case(2, 1, false)]
+    #[test_case(2, 3, true)]
+    fn test_exec_request(threshold: usize, signers: u8, expected: bool) -> anyhow::Result<()> {
+        let info = safe_info(threshold);
+        let mut proposal = proposal(&info);
+        for seed in (1..=signers).rev() {
+            proposal.sign(&info, &owner_key(seed))?;
+        }
+
+        let executor = Address::from_low_u64_be(99);
+        let request = safe::exec_request(executor, &info, &proposal);
+        assert_eq!(request.is_ok(), expected);
+        let Ok(request) = request else {
+            return Ok(());
+        };
+        assert_eq!(request.from, executor);
+        assert_eq!(request.to, info.address);
+        assert!(request.amount.is_zero());
+
+        // Signatures are packed by ascending owner, up to the threshold
+        let data = request.data.unwrap();
+        assert_eq!(hex::encode(&data[..4]), "6a761202");
+        let function = ethabi::Contract::load(&include_bytes!("../../abi/safe.json")[..])?.function("execTransaction")?.clone();
+        let args = function.decode_input(&data[4..])?;
+        let packed = args[9].clone().into_bytes().unwrap();
+        assert_eq!(packed.len(), 65 * threshold);
+
+        let mut signers = proposal.signatures.iter().map(|signature| signature.owner).collect::<Vec<_>>();
+        signers.sort();
+        let packed_signers = packed.chunks(65).map(|chunk| {
+            let signature = SafeSignature { owner: Address::zero(), signature: chunk.to_vec().into() };
+            signature.recover(proposal.safe_tx_hash)
+        }).collect::<anyhow::Result<Vec<_>>>()?;
+        assert_eq!(packed_signers, signers[..threshold]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stale_nonce_is_not_executable() -> anyhow::Result<()> {
+        let mut info = safe_info(1);
+        let mut proposal = proposal(&info);
+        proposal.sign(&info, &owner_key(1))?;
+
+        info.nonce += U256::one();
+        assert!(!proposal.is_executable(&info));
+        assert!(safe::exec_request(Address::zero(), &info, &proposal).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_info() -> anyhow::Result<()> {
+        let chain = EthChain::EthereumMainnet;
+        let address = Address::from_low_u64_be(1);
+        let owners = ethabi::encode(&[ethabi::Token::Array(vec![ethabi::Token::Address(owner(1)), ethabi::Token::Address(owner(2))])]);
+        let uint = |value: u64| ethabi::encode(&[ethabi::Token::Uint(value.into())]);
+
+        let info = safe::decode_info(chain, address, &owners, &uint(2), &uint(5))?;
+        assert_eq!(info, SafeInfo { chain, address, owners: vec![owner(1), owner(2)], threshold: 2, nonce: 5.into() });
+
+        // Not a Safe: empty responses or a threshold above the owners
+        assert!(safe::decode_info(chain, address, &[], &[], &[]).is_err());
+        assert!(safe::decode_info(chain, address, &owners, &uint(3), &uint(5)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_proposal_json() -> anyhow::Result<()> {
+        let info = safe_info(2);
+        let mut proposal = proposal(&info);
+        proposal.sign(&info, &owner_key(1))?;
+
+        let json = serde_json::to_string(&proposal)?;
+        assert!(json.contains("\"safeTxHash\""));
+        let parsed: SafeProposal = serde_json::from_str(&json)?;
+        assert_eq!(parsed, proposal);
+        parsed.validate()?;
+        Ok(())
+    }
+}
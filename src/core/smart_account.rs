@@ -0,0 +1,208 @@
+use web3::{
+    ethabi,
+    signing::{keccak256, Key, SecretKey, SecretKeyRef},
+    types::{Address, Bytes, H256, U256},
+};
+
+use super::fees::GasPrices;
+
+const SIMPLE_ACCOUNT_ABI: &[u8] = include_bytes!("../../abi/simple_account.json");
+const ENTRY_POINT_ABI: &[u8] = include_bytes!("../../abi/entry_point.json");
+
+// NOTE from https://github.com/eth-infinitism/account-abstraction/releases/tag/v0.6.0,
+// the same addresses on every supported chain
+pub const ENTRY_POINT: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+pub const SIMPLE_ACCOUNT_FACTORY: &str = "0x9406Cc6185a346906296840746125a0E44976454";
+
+// Well-formed signature the account can't validate, bundlers simulate with it before signing
+const DUMMY_SIGNATURE: &str = "fffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c";
+const ETH_SIGNED_MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n32";
+const NONCE_KEY: u64 = 0;
+
+const ERR_INVALID_ACCOUNT_RESPONSE: &str = "Unexpected response from the account factory";
+const ERR_NOT_EXECUTE_CALL: &str = "User operation isn't a single execute call";
+
+// SimpleAccount owned by a vault key, its address is known before it is deployed.
+// NOTE: the factory and the EntryPoint are deployed deterministically, so the address is the same on every chain
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SmartAccount {
+    pub address: Address,
+    pub owner: Address,
+    pub factory: Address,
+    pub entry_point: Address,
+    pub salt: U256,
+}
+
+impl SmartAccount {
+    pub fn new(owner: Address, address: Address) -> Self {
+        Self {
+            address,
+            owner,
+            factory: SIMPLE_ACCOUNT_FACTORY.parse().unwrap(),
+            entry_point: ENTRY_POINT.parse().unwrap(),
+            salt: U256::zero(),
+        }
+    }
+
+    // Factory address followed by its call, the EntryPoint deploys the account with it
+    pub fn init_code(&self) -> anyhow::Result<Vec<u8>> {
+        let mut init_code = self.factory.as_bytes().to_vec();
+        init_code.extend(ethabi::Contract::load(SIMPLE_ACCOUNT_ABI)?.function("createAccount")?.encode_input(&[
+            ethabi::Token::Address(self.owner),
+            ethabi::Token::Uint(self.salt),
+        ])?);
+        Ok(init_code)
+    }
+}
+
+// NOTE: the factory computes the CREATE2 address itself, so the proxy bytecode doesn't have to be bundled
+pub fn encode_get_address(owner: Address, salt: U256) -> anyhow::Result<Vec<u8>> {
+    Ok(ethabi::Contract::load(SIMPLE_ACCOUNT_ABI)?.function("getAddress")?.encode_input(&[
+        ethabi::Token::Address(owner),
+        ethabi::Token::Uint(salt),
+    ])?)
+}
+
+pub fn decode_get_address(data: &[u8]) -> anyhow::Result<Address> {
+    ethabi::Contract::load(SIMPLE_ACCOUNT_ABI)?.function("getAddress")?.decode_output(data)?
+        .into_iter().next()
+        .and_then(|address| address.into_address())
+        .filter(|address| !address.is_zero())
+        .ok_or_else(|| anyhow::anyhow!(ERR_INVALID_ACCOUNT_RESPONSE))
+}
+
+pub fn encode_get_nonce(sender: Address) -> anyhow::Result<Vec<u8>> {
+    Ok(ethabi::Contract::load(ENTRY_POINT_ABI)?.function("getNonce")?.encode_input(&[
+        ethabi::Token::Address(sender),
+        ethabi::Token::Uint(NONCE_KEY.into()),
+    ])?)
+}
+
+pub fn decode_get_nonce(data: &[u8]) -> anyhow::Result<U256> {
+    ethabi::Contract::load(ENTRY_POINT_ABI)?.function("getNonce")?.decode_output(data)?
+        .into_iter().next()
+        .and_then(|nonce| nonce.into_uint())
+        .ok_or_else(|| anyhow::anyhow!(ERR_INVALID_ACCOUNT_RESPONSE))
+}
+
+pub fn encode_execute(to: Address, value: U256, data: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    Ok(ethabi::Contract::load(SIMPLE_ACCOUNT_ABI)?.function("execute")?.encode_input(&[
+        ethabi::Token::Address(to),
+        ethabi::Token::Uint(value),
+        ethabi::Token::Bytes(data),
+    ])?)
+}
+
+// Target, value and data of the call the account makes
+pub fn decode_execute(call_data: &[u8]) -> anyhow::Result<(Address, U256, Vec<u8>)> {
+    let function = ethabi::Contract::load(SIMPLE_ACCOUNT_ABI)?.function("execute")?.clone();
+    if call_data.get(..4) != Some(&function.short_signature()[..]) {
+        return Err(anyhow::anyhow!(ERR_NOT_EXECUTE_CALL));
+    }
+    match function.decode_input(&call_data[4..])?.as_slice() {
+        [ethabi::Token::Address(to), ethabi::Token::Uint(value), ethabi::Token::Bytes(data)] => Ok((*to, *value, data.clone())),
+        _ => Err(anyhow::anyhow!(ERR_NOT_EXECUTE_CALL)),
+    }
+}
+
+// EntryPoint v0.6 user operation, serialized as the bundler JSON-RPC expects it
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    pub init_code: Bytes,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub paymaster_and_data: Bytes,
+    pub signature: Bytes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGas {
+    pub pre_verification_gas: U256,
+    pub verification_gas_limit: U256,
+    pub call_gas_limit: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+    pub user_op_hash: H256,
+    pub success: bool,
+    pub receipt: UserOperationTransaction,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationTransaction {
+    pub transaction_hash: H256,
+}
+
+impl UserOperation {
+    // Gas limits are left for the bundler to estimate, the first operation also deploys the account
+    pub fn new(account: &SmartAccount, nonce: U256, deployed: bool, call_data: Vec<u8>, prices: GasPrices) -> anyhow::Result<Self> {
+        Ok(Self {
+            sender: account.address,
+            nonce,
+            init_code: Bytes(if deployed { Vec::new() } else { account.init_code()? }),
+            call_data: Bytes(call_data),
+            call_gas_limit: U256::zero(),
+            verification_gas_limit: U256::zero(),
+            pre_verification_gas: U256::zero(),
+            max_fee_per_gas: prices.max_fee_per_gas,
+            max_priority_fee_per_gas: prices.max_priority_fee_per_gas,
+            paymaster_and_data: Bytes::default(),
+            signature: Bytes(hex::decode(DUMMY_SIGNATURE)?),
+        })
+    }
+
+    pub fn apply_gas(&mut self, gas: &UserOperationGas) {
+        self.pre_verification_gas = gas.pre_verification_gas;
+        self.verification_gas_limit = gas.verification_gas_limit;
+        self.call_gas_limit = gas.call_gas_limit;
+    }
+
+    // Prefunded by the account, without a paymaster it pays all of its gas
+    pub fn max_cost(&self) -> U256 {
+        (self.call_gas_limit + self.verification_gas_limit + self.pre_verification_gas) * self.max_fee_per_gas
+    }
+
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> H256 {
+        let packed = ethabi::encode(&[
+            ethabi::Token::Address(self.sender),
+            ethabi::Token::Uint(self.nonce),
+            ethabi::Token::FixedBytes(keccak256(&self.init_code.0).to_vec()),
+            ethabi::Token::FixedBytes(keccak256(&self.call_data.0).to_vec()),
+            ethabi::Token::Uint(self.call_gas_limit),
+            ethabi::Token::Uint(self.verification_gas_limit),
+            ethabi::Token::Uint(self.pre_verification_gas),
+            ethabi::Token::Uint(self.max_fee_per_gas),
+            ethabi::Token::Uint(self.max_priority_fee_per_gas),
+            ethabi::Token::FixedBytes(keccak256(&self.paymaster_and_data.0).to_vec()),
+        ]);
+        H256(keccak256(&ethabi::encode(&[
+            ethabi::Token::FixedBytes(keccak256(&packed).to_vec()),
+            ethabi::Token::Address(entry_point),
+            ethabi::Token::Uint(chain_id.into()),
+        ])))
+    }
+
+    // SimpleAccount recovers the owner from the eth_sign digest of the operation hash
+    pub fn sign(&mut self, entry_point: Address, chain_id: u64, secret_key: &SecretKey) -> anyhow::Result<()> {
+        let mut message = ETH_SIGNED_MESSAGE_PREFIX.to_vec();
+        message.extend_from_slice(self.hash(entry_point, chain_id).as_bytes());
+        let signature = SecretKeyRef::new(secret_key).sign(&keccak256(&message), None)?;
+
+        let mut bytes = signature.r.as_bytes().to_vec();
+        bytes.extend_from_slice(signature.s.as_bytes());
+        bytes.push(signature.v as u8);
+        self.signature = Bytes(bytes);
+        Ok(())
+    }
+}
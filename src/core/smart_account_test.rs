@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use web3::{ethabi, signing::{keccak256, recover, Key, SecretKey, SecretKeyRef}, types::{Address, U256}};
+    use crate::core::{
+        fees::GasPrices,
+        smart_account::{self, SmartAccount, UserOperation, UserOperationGas}
+    };
+
+    fn owner_key() -> SecretKey {
+        SecretKey::from_slice(&[1u8; 32]).unwrap()
+    }
+
+    fn account() -> SmartAccount {
+        SmartAccount::new(SecretKeyRef::new(&owner_key()).address(), Address::from_low_u64_be(0x4337))
+    }
+
+    fn operation(deployed: bool) -> anyhow::Result<UserOperation> {
+        let call_data = smart_account::encode_execute(Address::from_low_u64_be(42), 1_000.into(), vec![1, 2, 3])?;
+        UserOperation::new(&account(), 5.into(), deployed, call_data, GasPrices::with_priority_fee(100.into(), 2.into()))
+    }
+
+    #[test]
+    fn test_init_code() -> anyhow::Result<()> {
+        let account = account();
+        let init_code = account.init_code()?;
+
+        // Factory address, then createAccount(owner, salt)
+        assert_eq!(&init_code[..20], account.factory.as_bytes());
+        assert_eq!(hex::encode(&init_code[20..24]), hex::encode(&keccak256(b"createAccount(address,uint256)")[..4]));
+        let args = ethabi::decode(&[ethabi::ParamType::Address, ethabi::ParamType::Uint(256)], &init_code[24..])?;
+        assert_eq!(args, vec![ethabi::Token::Address(account.owner), ethabi::Token::Uint(U256::zero())]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_encodings() -> anyhow::Result<()> {
+        let owner = Address::from_low_u64_be(1);
+        assert_eq!(hex::encode(&smart_account::encode_get_address(owner, U256::zero())?[..4]),
+            hex::encode(&keccak256(b"getAddress(address,uint256)")[..4]));
+        assert_eq!(hex::encode(&smart_account::encode_get_nonce(owner)?[..4]),
+            hex::encode(&keccak256(b"getNonce(address,uint192)")[..4]));
+        assert_eq!(hex::encode(&smart_account::encode_execute(owner, U256::zero(), Vec::new())?[..4]),
+            hex::encode(&keccak256(b"execute(address,uint256,bytes)")[..4]));
+
+        let address = Address::from_low_u64_be(0x4337);
+        assert_eq!(smart_account::decode_get_address(&ethabi::encode(&[ethabi::Token::Address(address)]))?, address);
+        assert_eq!(smart_account::decode_get_nonce(&ethabi::encode(&[ethabi::Token::Uint(7.into())]))?, 7.into());
+        let call_data = smart_account::encode_execute(address, 5.into(), vec![1, 2, 3])?;
+        assert_eq!(smart_account::decode_execute(&call_data)?, (address, 5.into(), vec![1, 2, 3]));
+        assert!(smart_account::decode_execute(&smart_account::encode_get_nonce(owner)?).is_err());
+        assert!(smart_account::decode_execute(&call_data[..40]).is_err());
+
+        // No factory at the address or a zero address
+        assert!(smart_account::decode_get_address(&[]).is_err());
+        assert!(smart_account::decode_get_address(&ethabi::encode(&[ethabi::Token::Address(Address::zero())])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_operation() -> anyhow::Result<()> {
+        let undeployed = operation(false)?;
+        assert_eq!(undeployed.init_code.0, account().init_code()?);
+        assert_eq!(undeployed.signature.0.len(), 65);
+        assert_eq!(undeployed.max_fee_per_gas, 202.into());
+        assert_eq!(undeployed.max_priority_fee_per_gas, 2.into());
+        assert!(undeployed.max_cost().is_zero());
+
+        let mut deployed = operation(true)?;
+        assert!(deployed.init_code.0.is_empty());
+        deployed.apply_gas(&UserOperationGas {
+            pre_verification_gas: 1.into(),
+            verification_gas_limit: 2.into(),
+            call_gas_limit: 3.into(),
+        });
+        assert_eq!(deployed.max_cost(), (6 * 202).into());
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash() -> anyhow::Result<()> {
+        let entry_point: Address = smart_account::ENTRY_POINT.parse()?;
+        let operation = operation(false)?;
+        let hash = operation.hash(entry_point, 1);
+
+        // Commits to the chain, the EntryPoint and the fields, but not to the signature
+        assert_ne!(operation.hash(entry_point, 10), hash);
+        assert_ne!(operation.hash(Address::zero(), 1), hash);
+        assert_ne!(UserOperation { nonce: 6.into(), ..operation.clone() }.hash(entry_point, 1), hash);
+        assert_ne!(UserOperation { call_gas_limit: 1.into(), ..operation.clone() }.hash(entry_point, 1), hash);
+        assert_eq!(UserOperation { signature: Vec::new().into(), ..operation.clone() }.hash(entry_point, 1), hash);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign() -> anyhow::Result<()> {
+        let entry_point: Address = smart_account::ENTRY_POINT.parse()?;
+        let mut operation = operation(true)?;
+        operation.sign(entry_point, 1, &owner_key())?;
+
+        // SimpleAccount recovers the owner from the eth_sign digest
+        let signature = operation.signature.0.clone();
+        assert_eq!(signature.len(), 65);
+        assert!([27, 28].contains(&signature[64]));
+        let mut message = b"\x19Ethereum Signed Message:\n32".to_vec();
+        message.extend_from_slice(operation.hash(entry_point, 1).as_bytes());
+        let recovered = recover(&keccak256(&message), &signature[..64], signature[64] as i32 - 27)?;
+        assert_eq!(recovered, account().owner);
+        Ok(())
+    }
+
+    #[test]
+    fn test_operation_json() -> anyhow::Result<()> {
+        let operation = operation(false)?;
+        let json = serde_json::to_value(&operation)?;
+        assert_eq!(json["nonce"], "0x5");
+        assert!(json["callData"].as_str().unwrap().starts_with("0xb61d27f6"));
+        assert_eq!(serde_json::from_value::<UserOperation>(json)?, operation);
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use web3::{ethabi, types::{Address, Log, U256}};
+
+use super::{amount::{Amount, ETH_DECIMALS}, erc20, eth_chain::EthChain, transaction::TransactionRequest};
+
+const V2_ROUTER_ABI: &[u8] = include_bytes!("../../abi/uniswap_v2_router.json");
+const V3_ROUTER_ABI: &[u8] = include_bytes!("../../abi/uniswap_v3_router.json");
+const V3_QUOTER_ABI: &[u8] = include_bytes!("../../abi/uniswap_v3_quoter.json");
+const WETH_ABI: &[u8] = include_bytes!("../../abi/weth.json");
+
+// NOTE from https://docs.uniswap.org/contracts/v3/reference/deployments, SwapRouter02 and QuoterV2
+// share their addresses on mainnet, Optimism and Arbitrum
+const V3_SWAP_ROUTER: &str = "0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45";
+const V3_QUOTER: &str = "0x61fFE014bA17989E743c5F6cB21bF9697530B21e";
+
+// SwapRouter02 constant for the router itself, the output stays there to be unwrapped
+const ROUTER_ADDRESS_THIS: u64 = 2;
+
+pub const V3_FEE_TIERS: [u32; 3] = [500, 3000, 10_000];
+pub const DEFAULT_SLIPPAGE: &str = "0.5";
+pub const SWAP_DEADLINE_SECONDS: i64 = 20 * 60;
+
+const BASIS_POINTS: u32 = 10_000;
+const V3_FEE_UNITS_PER_PERCENT: f64 = 10_000.0;
+const SLIPPAGE_DECIMALS: u16 = 2; // Percent with two decimals are basis points
+const MAX_SLIPPAGE_BPS: u32 = 5_000;
+// Part of the amount quoted for the price before the trade moves it
+const SPOT_QUOTE_DIVISOR: u64 = 1_000;
+
+const ERR_NO_SWAP_ON_CHAIN: &str = "Swaps are not available on this chain";
+const ERR_NO_ROUTER: &str = "No router for the protocol on this chain";
+const ERR_INVALID_SLIPPAGE: &str = "Slippage should be a percent between 0 and 50";
+const ERR_INVALID_QUOTE: &str = "Unexpected quote response";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SwapProtocol {
+    V2,
+    V3 { fee: u32 },
+}
+
+impl SwapProtocol {
+    pub fn get_display_name(&self) -> String {
+        match self {
+            SwapProtocol::V2 => "Uniswap V2".to_string(),
+            SwapProtocol::V3 { fee } => format!("Uniswap V3 {}%", *fee as f64 / V3_FEE_UNITS_PER_PERCENT),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapContracts {
+    pub weth: Address,
+    pub v2_router: Option<Address>,
+    pub v3_router: Option<Address>,
+    pub v3_quoter: Option<Address>,
+}
+
+impl SwapContracts {
+    // NOTE from https://docs.uniswap.org/contracts/v2/reference/smart-contracts/v2-deployments
+    pub fn for_chain(chain: EthChain) -> Option<Self> {
+        let (weth, v2_router, v3_router, v3_quoter) = match chain {
+            EthChain::EthereumMainnet => ("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                Some("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D"), V3_SWAP_ROUTER, V3_QUOTER),
+            EthChain::EthereumSepolia => ("0xfFf9976782d46CC05630D1f6eBAb18b2324d6B14",
+                Some("0xeE567Fe1712Faf6149d80dA1E6934E354124CfE3"),
+                "0x3bFA4769FB09eefC5a80d6E87c3B9C650f7Ae48E", "0xEd1f6473345F45b75F8179591dd5bA1888cf2FB3"),
+            EthChain::OptimismMainnet => ("0x4200000000000000000000000000000000000006",
+                Some("0x4A7b5Da61326A6379179b40d00F57E5bbDC962c2"), V3_SWAP_ROUTER, V3_QUOTER),
+            EthChain::ArbitrumMainnet => ("0x82aF49447D8a07e3bd95BD0d56f35241523fBab1",
+                Some("0x4752ba5DBc23f44D87826276BF6Fd6b1C372aD24"), V3_SWAP_ROUTER, V3_QUOTER),
+            EthChain::ArbitrumSepolia => ("0x980B62Da83eFf3D4576C647993b0c1D7faf17c73", None,
+                "0x101F443B4d1b059569D643917553c771E1b9663E", "0x2779a0CC1c3e0E44D2542EC3e79e3864Ae93Ef0B"),
+            EthChain::OptimismSepolia => return None,
+        };
+        Some(Self {
+            weth: weth.parse().unwrap(),
+            v2_router: v2_router.map(|router| router.parse().unwrap()),
+            v3_router: Some(v3_router.parse().unwrap()),
+            v3_quoter: Some(v3_quoter.parse().unwrap()),
+        })
+    }
+
+    pub fn protocols(&self) -> Vec<SwapProtocol> {
+        let mut protocols = Vec::new();
+        if self.v2_router.is_some() {
+            protocols.push(SwapProtocol::V2);
+        }
+        if self.v3_router.is_some() && self.v3_quoter.is_some() {
+            protocols.extend(V3_FEE_TIERS.iter().map(|fee| SwapProtocol::V3 { fee: *fee }));
+        }
+        protocols
+    }
+
+    pub fn router(&self, protocol: SwapProtocol) -> anyhow::Result<Address> {
+        match protocol {
+            SwapProtocol::V2 => self.v2_router,
+            SwapProtocol::V3 { .. } => self.v3_router,
+        }.ok_or_else(|| anyhow::anyhow!(ERR_NO_ROUTER))
+    }
+}
+
+// ETH is swapped as WETH, routers wrap and unwrap it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapToken {
+    pub symbol: String,
+    pub address: Address,
+    pub decimals: u16,
+    pub is_eth: bool,
+}
+
+impl SwapToken {
+    pub fn eth(contracts: &SwapContracts) -> Self {
+        Self { symbol: "ETH".to_string(), address: contracts.weth, decimals: ETH_DECIMALS, is_eth: true }
+    }
+
+    pub fn erc20(symbol: &str, address: Address, decimals: u16) -> Self {
+        Self { symbol: symbol.to_string(), address, decimals, is_eth: false }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwapQuote {
+    pub protocol: SwapProtocol,
+    pub amount_in: Amount,
+    pub amount_out: Amount,
+    pub price_impact: f64, // Share of the price lost to the trade size, 0.01 is 1%
+}
+
+impl SwapQuote {
+    pub fn min_amount_out(&self, slippage_bps: u32) -> Amount {
+        let raw = self.amount_out.raw * (BASIS_POINTS - slippage_bps.min(BASIS_POINTS)) / BASIS_POINTS;
+        Amount::new(raw, self.amount_out.decimals)
+    }
+}
+
+// History details of a swap, the input is the amount and currency of the transaction
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SwapResult {
+    pub protocol: SwapProtocol,
+    pub currency_out: String,
+    pub token_out: Address, // WETH when ETH is received
+    pub amount_out: Amount, // Quoted until the receipt shows the received amount
+    pub amount_out_min: Amount,
+}
+
+impl SwapResult {
+    // Tokens are transferred to the owner, ETH is the WETH the router unwraps
+    pub fn apply_logs(&mut self, owner: Address, logs: &[Log]) {
+        let withdrawal_topic = ethabi::Contract::load(WETH_ABI).ok()
+            .and_then(|contract| contract.event("Withdrawal").ok().map(|event| event.signature()));
+        let received = logs.iter()
+            .filter(|log| log.address == self.token_out)
+            .filter_map(|log| if self.currency_out == "ETH" {
+                (log.topics.first() == withdrawal_topic.as_ref() && log.data.0.len() == 32).then(|| U256::from_big_endian(&log.data.0))
+            } else {
+                erc20::decode_transfer_log(log).ok().filter(|transfer| transfer.to == owner).map(|transfer| transfer.value)
+            })
+            .fold(U256::zero(), |sum, value| sum.saturating_add(value));
+
+        if !received.is_zero() {
+            self.amount_out = Amount::new(received, self.amount_out.decimals);
+        }
+    }
+}
+
+// Percent with up to two decimals to basis points
+pub fn parse_slippage(text: &str) -> anyhow::Result<u32> {
+    let slippage = Amount::parse(text.trim(), SLIPPAGE_DECIMALS).map_err(|_| anyhow::anyhow!(ERR_INVALID_SLIPPAGE))?;
+    if slippage.raw > MAX_SLIPPAGE_BPS.into() {
+        return Err(anyhow::anyhow!(ERR_INVALID_SLIPPAGE));
+    }
+    Ok(slippage.raw.as_u32())
+}
+
+pub fn spot_amount(amount_in: U256) -> U256 {
+    (amount_in / SPOT_QUOTE_DIVISOR).max(U256::one())
+}
+
+// Execution price of the trade against the price of a small trade on the same pool
+pub fn price_impact(amount_in: U256, amount_out: U256, spot_in: U256, spot_out: U256) -> f64 {
+    let to_f64 = |value: U256| value.to_string().parse::<f64>().unwrap_or_default();
+    if amount_in.is_zero() || spot_out.is_zero() {
+        return 0.0;
+    }
+    let execution = to_f64(amount_out) / to_f64(amount_in);
+    let spot = to_f64(spot_out) / to_f64(spot_in);
+    (1.0 - execution / spot).max(0.0)
+}
+
+// Quoter call for the protocol, returns the contract and its calldata
+pub fn encode_quote(contracts: &SwapContracts, protocol: SwapProtocol, token_in: &SwapToken, token_out: &SwapToken, amount_in: U256) -> anyhow::Result<(Address, Vec<u8>)> {
+    match protocol {
+        SwapProtocol::V2 => {
+            let data = ethabi::Contract::load(V2_ROUTER_ABI)?.function("getAmountsOut")?.encode_input(&[
+                ethabi::Token::Uint(amount_in),
+                ethabi::Token::Array(vec![ethabi::Token::Address(token_in.address), ethabi::Token::Address(token_out.address)]),
+            ])?;
+            Ok((contracts.router(protocol)?, data))
+        },
+        SwapProtocol::V3 { fee } => {
+            let quoter = contracts.v3_quoter.ok_or_else(|| anyhow::anyhow!(ERR_NO_ROUTER))?;
+            let data = ethabi::Contract::load(V3_QUOTER_ABI)?.function("quoteExactInputSingle")?.encode_input(&[
+                ethabi::Token::Tuple(vec![
+                    ethabi::Token::Address(token_in.address),
+                    ethabi::Token::Address(token_out.address),
+                    ethabi::Token::Uint(amount_in),
+                    ethabi::Token::Uint(fee.into()),
+                    ethabi::Token::Uint(U256::zero()),
+                ]),
+            ])?;
+            Ok((quoter, data))
+        },
+    }
+}
+
+pub fn decode_quote(protocol: SwapProtocol, data: &[u8]) -> anyhow::Result<U256> {
+    let amount_out = match protocol {
+        SwapProtocol::V2 => {
+            let outputs = ethabi::Contract::load(V2_ROUTER_ABI)?.function("getAmountsOut")?.decode_output(data)?;
+            match outputs.into_iter().next() {
+                Some(ethabi::Token::Array(amounts)) => amounts.last().cloned().and_then(|amount| amount.into_uint()),
+                _ => None,
+            }
+        },
+        SwapProtocol::V3 { .. } => ethabi::Contract::load(V3_QUOTER_ABI)?.function("quoteExactInputSingle")?
+            .decode_output(data)?
+            .into_iter().next()
+            .and_then(|amount| amount.into_uint()),
+    };
+    amount_out.ok_or_else(|| anyhow::anyhow!(ERR_INVALID_QUOTE))
+}
+
+// Exact amount only, so the router is never left with a standing allowance
+pub fn approval_request(owner: Address, chain: EthChain, token_in: &SwapToken, spender: Address, amount: Amount) -> anyhow::Result<TransactionRequest> {
+    Ok(TransactionRequest {
+        from: owner,
+        to: token_in.address,
+        amount: Amount::zero(ETH_DECIMALS),
+        currency: "ETH".to_string(),
+        chain,
+        fees: None,
+        data: Some(erc20::encode_approve(spender, amount.raw)?),
+    })
+}
+
+pub fn swap_request(
+    owner: Address,
+    chain: EthChain,
+    token_in: &SwapToken,
+    token_out: &SwapToken,
+    quote: &SwapQuote,
+    amount_out_min: Amount,
+    deadline: i64,
+) -> anyhow::Result<TransactionRequest> {
+    let contracts = SwapContracts::for_chain(chain).ok_or_else(|| anyhow::anyhow!(ERR_NO_SWAP_ON_CHAIN))?;
+    let amount_in = quote.amount_in.raw;
+    let deadline = U256::from(deadline.max(0));
+
+    let data = match quote.protocol {
+        SwapProtocol::V2 => {
+            let path = ethabi::Token::Array(vec![ethabi::Token::Address(token_in.address), ethabi::Token::Address(token_out.address)]);
+            let router = ethabi::Contract::load(V2_ROUTER_ABI)?;
+            if token_in.is_eth {
+                router.function("swapExactETHForTokens")?.encode_input(&[
+                    ethabi::Token::Uint(amount_out_min.raw), path, ethabi::Token::Address(owner), ethabi::Token::Uint(deadline),
+                ])?
+            } else {
+                let function = if token_out.is_eth { "swapExactTokensForETH" } else { "swapExactTokensForTokens" };
+                router.function(function)?.encode_input(&[
+                    ethabi::Token::Uint(amount_in), ethabi::Token::Uint(amount_out_min.raw), path,
+                    ethabi::Token::Address(owner), ethabi::Token::Uint(deadline),
+                ])?
+            }
+        },
+        SwapProtocol::V3 { fee } => {
+            let router = ethabi::Contract::load(V3_ROUTER_ABI)?;
+            let recipient = if token_out.is_eth { Address::from_low_u64_be(ROUTER_ADDRESS_THIS) } else { owner };
+            let mut calls = vec![router.function("exactInputSingle")?.encode_input(&[
+                ethabi::Token::Tuple(vec![
+                    ethabi::Token::Address(token_in.address),
+                    ethabi::Token::Address(token_out.address),
+                    ethabi::Token::Uint(fee.into()),
+                    ethabi::Token::Address(recipient),
+                    ethabi::Token::Uint(amount_in),
+                    ethabi::Token::Uint(amount_out_min.raw),
+                    ethabi::Token::Uint(U256::zero()),
+                ]),
+            ])?];
+            if token_out.is_eth {
+                calls.push(router.function("unwrapWETH9")?.encode_input(&[
+                    ethabi::Token::Uint(amount_out_min.raw), ethabi::Token::Address(owner),
+                ])?);
+            }
+            router.function("multicall")?.encode_input(&[
+                ethabi::Token::Uint(deadline),
+                ethabi::Token::Array(calls.into_iter().map(ethabi::Token::Bytes).collect()),
+            ])?
+        },
+    };
+
+    Ok(TransactionRequest {
+        from: owner,
+        to: contracts.router(quote.protocol)?,
+        amount: if token_in.is_eth { quote.amount_in } else { Amount::zero(ETH_DECIMALS) },
+        currency: "ETH".to_string(),
+        chain,
+        fees: None,
+        data: Some(data),
+    })
+}
@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::{ethabi, signing::keccak256, types::{Address, Log, H256, U256}};
+    use crate::core::{
+        amount::Amount, eth_chain::EthChain,
+        swap::{self, SwapContracts, SwapProtocol, SwapQuote, SwapResult, SwapToken}
+    };
+
+    const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const DAI: &str = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
+
+    fn log(address: Address, signature: &str, topics: Vec<H256>, data: Vec<ethabi::Token>) -> Log {
+        let mut all_topics = vec![H256::from(keccak256(signature.as_bytes()))];
+        all_topics.extend(topics);
+        Log {
+            address,
+            topics: all_topics,
+            data: ethabi::encode(&data).into(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        }
+    }
+
+    fn tokens(token_in: &str, token_out: &str) -> (SwapToken, SwapToken) {
+        let contracts = SwapContracts::for_chain(EthChain::EthereumMainnet).unwrap();
+        let token = |symbol: &str| match symbol {
+            "ETH" => SwapToken::eth(&contracts),
+            "USDC" => SwapToken::erc20("USDC", USDC.parse().unwrap(), 6),
+            _ => SwapToken::erc20("DAI", DAI.parse().unwrap(), 18),
+        };
+        (token(token_in), token(token_out))
+    }
+
+    #[test_case("0.5", Some(50))]
+    #[test_case(" 1 ", Some(100))]
+    #[test_case("0.05", Some(5))]
+    #[test_case("50", Some(5_000))]
+    #[test_case("50.01", None)]
+    #[test_case("0.001", None)]
+    #[test_case("-1", None)]
+    #[test_case("abc", None)]
+    fn test_parse_slippage(text: &str, expected: Option<u32>) {
+        assert_eq!(swap::parse_slippage(text).ok(), expected);
+    }
+
+    #[test_case(1_000_000, 50, 995_000)]
+    #[test_case(1_000_000, 0, 1_000_000)]
+    #[test_case(999, 100, 989)]
+    #[test_case(1_000_000, 20_000, 0)]
+    fn test_min_amount_out(amount_out: u64, slippage_bps: u32, expected: u64) {
+        let quote = SwapQuote {
+            protocol: SwapProtocol::V2,
+            amount_in: Amount::zero(18),
+            amount_out: Amount::new(amount_out.into(), 6),
+            price_impact: 0.0,
+        };
+        assert_eq!(quote.min_amount_out(slippage_bps), Amount::new(expected.into(), 6));
+    }
+
+    #[test_case(1_000_000, 1_990_000, 1_000, 2_000, 0.005)]
+    #[test_case(1_000_000, 2_000_000, 1_000, 2_000, 0.0)]
+    #[test_case(1_000_000, 2_100_000, 1_000, 2_000, 0.0)]
+    #[test_case(0, 0, 1, 0, 0.0)]
+    fn test_price_impact(amount_in: u64, amount_out: u64, spot_in: u64, spot_out: u64, expected: f64) {
+        let impact = swap::price_impact(amount_in.into(), amount_out.into(), spot_in.into(), spot_out.into());
+        assert!((impact - expected).abs() < 1e-9, "{} != {}", impact, expected);
+    }
+
+    #[test_case(EthChain::EthereumMainnet, Some(vec![SwapProtocol::V2, SwapProtocol::V3 { fee: 500 }, SwapProtocol::V3 { fee: 3000 }, SwapProtocol::V3 { fee: 10_000 }]))]
+    #[test_case(EthChain::ArbitrumSepolia, Some(vec![SwapProtocol::V3 { fee: 500 }, SwapProtocol::V3 { fee: 3000 }, SwapProtocol::V3 { fee: 10_000 }]))]
+    #[test_case(EthChain::OptimismSepolia, None)]
+    fn test_protocols(chain: EthChain, expected: Option<Vec<SwapProtocol>>) {
+        assert_eq!(SwapContracts::for_chain(chain).map(|contracts| contracts.protocols()), expected);
+    }
+
+    #[test_case(SwapProtocol::V2, "Uniswap V2")]
+    #[test_case(SwapProtocol::V3 { fee: 500 }, "Uniswap V3 0.05%")]
+    #[test_case(SwapProtocol::V3 { fee: 3000 }, "Uniswap V3 0.3%")]
+    #[test_case(SwapProtocol::V3 { fee: 10_000 }, "Uniswap V3 1%")]
+    fn test_protocol_display_name(protocol: SwapProtocol, expected: &str) {
+        assert_eq!(protocol.get_display_name(), expected);
+    }
+
+    #[test_case(SwapProtocol::V2, "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D", "d06ca61f", ethabi::Token::Array(vec![ethabi::Token::Uint(1_000.into()), ethabi::Token::Uint(2_500.into())]))]
+    #[test_case(SwapProtocol::V3 { fee: 500 }, "0x61fFE014bA17989E743c5F6cB21bF9697530B21e", "c6a5026a", ethabi::Token::Uint(2_500.into()))]
+    fn test_quote(protocol: SwapProtocol, quoter: &str, selector: &str, amount_out: ethabi::Token) -> anyhow::Result<()> {
+        let contracts = SwapContracts::for_chain(EthChain::EthereumMainnet).unwrap();
+        let (token_in, token_out) = tokens("ETH", "USDC");
+
+        let (address, data) = swap::encode_quote(&contracts, protocol, &token_in, &token_out, 1_000.into())?;
+        assert_eq!(address, quoter.parse()?);
+        assert_eq!(hex::encode(&data[..4]), selector);
+
+        // NOTE: the V3 quoter also returns the price after the swap, ticks crossed and gas estimate
+        let mut output = vec![amount_out];
+        if let SwapProtocol::V3 { .. } = protocol {
+            output.extend([ethabi::Token::Uint(1.into()), ethabi::Token::Uint(2.into()), ethabi::Token::Uint(3.into())]);
+        }
+        assert_eq!(swap::decode_quote(protocol, &ethabi::encode(&output))?, 2_500.into());
+        assert!(swap::decode_quote(protocol, &[]).is_err());
+        Ok(())
+    }
+
+    #[test_case(SwapProtocol::V2, "ETH", "USDC", "7ff36ab5", true)]
+    #[test_case(SwapProtocol::V2, "USDC", "ETH", "18cbafe5", false)]
+    #[test_case(SwapProtocol::V2, "USDC", "DAI", "38ed1739", false)]
+    #[test_case(SwapProtocol::V3 { fee: 500 }, "ETH", "USDC", "5ae401dc", true)]
+    #[test_case(SwapProtocol::V3 { fee: 3000 }, "DAI", "ETH", "5ae401dc", false)]
+    fn test_swap_request(protocol: SwapProtocol, symbol_in: &str, symbol_out: &str, selector: &str, with_value: bool) -> anyhow::Result<()> {
+        let owner = Address::from_low_u64_be(1);
+        let (token_in, token_out) = tokens(symbol_in, symbol_out);
+        let quote = SwapQuote {
+            protocol,
+            amount_in: Amount::new(1_000.into(), token_in.decimals),
+            amount_out: Amount::new(2_000.into(), token_out.decimals),
+            price_impact: 0.0,
+        };
+
+        let request = swap::swap_request(owner, EthChain::EthereumMainnet, &token_in, &token_out, &quote, quote.min_amount_out(50), 1_700_000_000)?;
+        let contracts = SwapContracts::for_chain(EthChain::EthereumMainnet).unwrap();
+        assert_eq!(request.to, contracts.router(protocol)?);
+        assert_eq!(request.currency, "ETH");
+        assert_eq!(request.amount.raw, if with_value { 1_000.into() } else { U256::zero() });
+
+        let data = request.data.unwrap();
+        assert_eq!(hex::encode(&data[..4]), selector);
+        // NOTE: the ETH output of V3 is unwrapped by a second call of the multicall
+        let unwraps = hex::encode(&data).contains("49404b7c");
+        assert_eq!(unwraps, matches!(protocol, SwapProtocol::V3 { .. }) && token_out.is_eth);
+        Ok(())
+    }
+
+    #[test]
+    fn test_approval_request() -> anyhow::Result<()> {
+        let owner = Address::from_low_u64_be(1);
+        let router = Address::from_low_u64_be(2);
+        let (token_in, _) = tokens("USDC", "ETH");
+
+        let request = swap::approval_request(owner, EthChain::EthereumMainnet, &token_in, router, Amount::new(1_000.into(), 6))?;
+        assert_eq!(request.to, token_in.address);
+        assert!(request.amount.is_zero());
+        let data = request.data.unwrap();
+        assert_eq!(hex::encode(&data[..4]), "095ea7b3");
+        assert_eq!(U256::from_big_endian(&data[36..68]), 1_000.into());
+        Ok(())
+    }
+
+    #[test_case("USDC", 1_950)]
+    #[test_case("ETH", 1_980)]
+    fn test_apply_logs(currency_out: &str, expected: u64) {
+        let owner = Address::from_low_u64_be(1);
+        let router = Address::from_low_u64_be(2);
+        let (_, token_out) = tokens("DAI", currency_out);
+        let mut result = SwapResult {
+            protocol: SwapProtocol::V3 { fee: 500 },
+            currency_out: token_out.symbol.clone(),
+            token_out: token_out.address,
+            amount_out: Amount::new(2_000.into(), token_out.decimals),
+            amount_out_min: Amount::new(1_900.into(), token_out.decimals),
+        };
+
+        let transfer = |to: Address, value: u64| log(token_out.address, "Transfer(address,address,uint256)",
+            vec![H256::from(router), H256::from(to)], vec![ethabi::Token::Uint(value.into())]);
+        let logs = vec![
+            transfer(owner, 1_950),
+            transfer(router, 1_980),
+            log(token_out.address, "Withdrawal(address,uint256)", vec![H256::from(router)], vec![ethabi::Token::Uint(1_980.into())]),
+            log(Address::from_low_u64_be(3), "Transfer(address,address,uint256)",
+                vec![H256::from(router), H256::from(owner)], vec![ethabi::Token::Uint(5.into())]),
+        ];
+
+        result.apply_logs(owner, &logs);
+        assert_eq!(result.amount_out, Amount::new(expected.into(), token_out.decimals));
+
+        // NOTE: the quoted amount is kept without matching logs
+        result.apply_logs(owner, &[]);
+        assert_eq!(result.amount_out, Amount::new(expected.into(), token_out.decimals));
+    }
+}
@@ -7,6 +7,9 @@ use super::eth_chain::EthChain;
 pub struct TokenOnChainData {
     pub contract_address: Address,
     pub decimals: u16,
+    // Chainlink USD feed, tokens without one have no price
+    #[serde(default)]
+    pub price_feed: Option<Address>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -34,6 +37,7 @@ impl Token {
             TokenOnChainData {
                 contract_address,
                 decimals,
+                price_feed: None,
             },
         );
         self
@@ -1,34 +1,143 @@
-use web3::types::{Address, H256, U64};
-use super::eth_chain::EthChain;
+use web3::types::{Address, TransactionReceipt, H256, U256, U64};
+use super::{amount::Amount, eth_chain::EthChain, eth_utils, fees::GasFees, revert, swap::SwapResult};
+
+pub const EIP1559_TRANSACTION_TYPE: u64 = 2;
+
+// Number of confirmations after which a transaction is no longer tracked
+pub const FINAL_CONFIRMATIONS: u64 = 12;
 
 pub struct TransactionRequest {
     pub from: Address,
     pub to: Address,
-    pub amount: f64,
+    pub amount: Amount,
     pub currency: String,
     pub chain: EthChain,
+    pub fees: Option<GasFees>, // Estimated with normal tier if not set
+    pub data: Option<Vec<u8>>, // Contract call sent to `to` with `amount` ETH attached
 }
 
-#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum TransactionStatus {
     Pending,
     Successed,
     Failed,
+    Dropped,
+    Replaced,
 }
 
-#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct TransactionResult {
     pub hash: H256,
     pub block_number: Option<U64>,
     pub from: Option<Address>,
     pub to: Option<Address>,
-    pub amount: f64,
-    pub fee: f64,
+    pub amount: Amount,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    pub fee: Amount,
     pub chain: EthChain,
     pub status: TransactionStatus,
+    #[serde(default)]
+    pub nonce: Option<U256>,
+    #[serde(default)]
+    pub gas_used: Option<U256>,
+    #[serde(default)]
+    pub confirmations: u64,
+    #[serde(default)]
+    pub replaces: Option<H256>,
+    #[serde(default)]
+    pub replaced_by: Option<H256>,
+    #[serde(default)]
+    pub swap: Option<SwapResult>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransactionReplacement {
+    SpeedUp,
+    Cancel,
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum TransactionFees {
-    Estimated { currency: String, amount: f64 },
+    Estimated { currency: String, fees: GasFees },
     NotEnoughFunds { currency: String },
+    Reverted { reason: String },
+    RpcFailure { error: String },
+}
+
+impl TransactionFees {
+    // Classify a failed eth_call or eth_estimateGas
+    pub fn from_rpc_error(message: &str, data: Option<&serde_json::Value>, currency: &str) -> Self {
+        if message.to_lowercase().contains("insufficient funds") {
+            return TransactionFees::NotEnoughFunds { currency: currency.to_string() };
+        }
+
+        let revert_data = data
+            .and_then(|data| data.as_str())
+            .and_then(|data| hex::decode(data.trim_start_matches("0x")).ok());
+        if let Some(reason) = revert_data.as_deref().and_then(revert::decode_revert_data) {
+            return TransactionFees::Reverted { reason };
+        }
+
+        match message.strip_prefix("execution reverted") {
+            Some(reason) => {
+                let reason = reason.trim_start_matches(':').trim();
+                TransactionFees::Reverted { reason: if reason.is_empty() { "no reason given".to_string() } else { reason.to_string() } }
+            },
+            None => TransactionFees::RpcFailure { error: message.to_string() },
+        }
+    }
+
+    pub fn into_estimated(self) -> anyhow::Result<GasFees> {
+        match self {
+            TransactionFees::Estimated { fees, .. } => Ok(fees),
+            TransactionFees::NotEnoughFunds { currency } => Err(anyhow::anyhow!("Not enough funds ({})", currency)),
+            TransactionFees::Reverted { reason } => Err(anyhow::anyhow!("Transaction reverts: {}", reason)),
+            TransactionFees::RpcFailure { error } => Err(anyhow::anyhow!("Failed to estimate fees: {}", error)),
+        }
+    }
+}
+
+impl TransactionResult {
+    pub fn is_replaceable(&self) -> bool {
+        self.status == TransactionStatus::Pending && self.nonce.is_some() && self.replaced_by.is_none()
+    }
+
+    pub fn is_tracked(&self) -> bool {
+        match self.status {
+            TransactionStatus::Pending => true,
+            TransactionStatus::Successed | TransactionStatus::Failed => self.confirmations < FINAL_CONFIRMATIONS,
+            TransactionStatus::Dropped | TransactionStatus::Replaced => false,
+        }
+    }
+
+    pub fn apply_receipt(&mut self, receipt: &TransactionReceipt, latest_block: U64) {
+        self.status = match receipt.status {
+            Some(status) if status.is_zero() => TransactionStatus::Failed,
+            _ => TransactionStatus::Successed,
+        };
+        self.block_number = receipt.block_number;
+        self.gas_used = receipt.gas_used;
+        if let (Some(gas_used), Some(gas_price)) = (receipt.gas_used, receipt.effective_gas_price) {
+            self.fee = eth_utils::wei_to_eth(gas_used * gas_price);
+        }
+        self.confirmations = receipt.block_number.map_or(0, |block_number| {
+            latest_block.saturating_sub(block_number).as_u64() + 1
+        });
+        if let (Some(swap), Some(from), TransactionStatus::Successed) = (&mut self.swap, self.from, self.status) {
+            swap.apply_logs(from, &receipt.logs);
+        }
+    }
+
+    // Called when the node doesn't know the transaction anymore
+    pub fn apply_missing(&mut self, account_nonce: U256) {
+        self.status = match self.nonce {
+            Some(nonce) if nonce < account_nonce => TransactionStatus::Replaced,
+            _ => TransactionStatus::Dropped,
+        };
+    }
+}
+
+fn default_currency() -> String {
+    "ETH".to_string()
 }
@@ -0,0 +1,87 @@
+use super::transaction::TransactionResult;
+
+const TAGS_SEPARATOR: char = ',';
+const TAG_QUERY_PREFIX: char = '#';
+
+// Why a transaction happened, kept apart from the on-chain facts of `TransactionResult`
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TransactionLabel {
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub category: String,
+}
+
+impl TransactionLabel {
+    pub fn new(note: &str, tags: &str, category: &str) -> Self {
+        Self {
+            note: note.trim().to_string(),
+            tags: parse_tags(tags),
+            category: category.trim().to_string(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.note.is_empty() && self.tags.is_empty() && self.category.is_empty()
+    }
+
+    pub fn tags_str(&self) -> String {
+        self.tags.join(", ")
+    }
+
+    // Short form for lists, e.g. "[Salary] #work #2024 March payout"
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.category.is_empty() {
+            parts.push(format!("[{}]", self.category));
+        }
+        parts.extend(self.tags.iter().map(|tag| format!("{}{}", TAG_QUERY_PREFIX, tag)));
+        if !self.note.is_empty() {
+            parts.push(self.note.clone());
+        }
+        parts.join(" ")
+    }
+
+    // "#tag" matches the exact tag, any other text is searched in the note, tags and category
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        if let Some(tag) = query.strip_prefix(TAG_QUERY_PREFIX) {
+            return self.tags.iter().any(|other| other.to_lowercase() == tag);
+        }
+        self.note.to_lowercase().contains(&query)
+            || self.category.to_lowercase().contains(&query)
+            || self.tags.iter().any(|tag| tag.to_lowercase().contains(&query))
+    }
+}
+
+// Comma separated, duplicates are dropped ignoring case
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(TAGS_SEPARATOR).map(str::trim).filter(|tag| !tag.is_empty()) {
+        let tag = tag.trim_start_matches(TAG_QUERY_PREFIX).to_string();
+        if !tag.is_empty() && !tags.iter().any(|other| other.eq_ignore_ascii_case(&tag)) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+// Filter for the history, the label is searched along with the currency, chain and addresses
+pub fn matches(transaction: &TransactionResult, label: Option<&TransactionLabel>, query: &str) -> bool {
+    let query = query.trim();
+    if query.is_empty() || label.is_some_and(|label| label.matches(query)) {
+        return true;
+    }
+    if query.starts_with(TAG_QUERY_PREFIX) {
+        return false;
+    }
+
+    let query = query.to_lowercase();
+    transaction.currency.to_lowercase().contains(&query)
+        || transaction.chain.get_display_name().to_lowercase().contains(&query)
+        || [transaction.from, transaction.to].iter().flatten()
+            .any(|address| format!("{:?}", address).contains(&query))
+        || format!("{:?}", transaction.hash).contains(&query)
+}
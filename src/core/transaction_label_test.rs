@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{Address, H256};
+    use crate::core::{
+        amount::Amount, eth_chain::EthChain,
+        transaction::{TransactionResult, TransactionStatus},
+        transaction_label::{self, TransactionLabel}
+    };
+
+    fn transaction() -> TransactionResult {
+        TransactionResult {
+            hash: H256::from_low_u64_be(0xabc),
+            block_number: None,
+            from: Some(Address::from_low_u64_be(12)),
+            to: Some(Address::from_low_u64_be(0xb0b)),
+            amount: Amount::parse("100", 6).unwrap(),
+            currency: "USDC".to_string(),
+            fee: Amount::zero(18),
+            chain: EthChain::ArbitrumMainnet,
+            status: TransactionStatus::Successed,
+            nonce: None,
+            gas_used: None,
+            confirmations: 1,
+            replaces: None,
+            replaced_by: None,
+            swap: None,
+        }
+    }
+
+    #[test_case("work, 2024 ,#Work,, taxes", &["work", "2024", "taxes"]; "trimmed and deduplicated")]
+    #[test_case(" , #", &[]; "empty")]
+    fn test_parse_tags(text: &str, expected: &[&str]) {
+        assert_eq!(transaction_label::parse_tags(text), expected);
+    }
+
+    #[test]
+    fn test_label() {
+        let label = TransactionLabel::new(" March payout ", "work, 2024", " Salary ");
+        assert_eq!(label.summary(), "[Salary] #work #2024 March payout");
+        assert_eq!(label.tags_str(), "work, 2024");
+        assert!(!label.is_empty());
+        assert!(TransactionLabel::new(" ", ",", "").is_empty());
+    }
+
+    #[test_case("", true; "empty query")]
+    #[test_case("payout", true; "note")]
+    #[test_case("SALARY", true; "category")]
+    #[test_case("#work", true; "exact tag")]
+    #[test_case("#wor", false; "partial tag")]
+    #[test_case("usdc", true; "currency")]
+    #[test_case("arbitrum", true; "chain")]
+    #[test_case("b0b", true; "counterparty")]
+    #[test_case("rent", false; "nothing")]
+    fn test_matches(query: &str, expected: bool) {
+        let label = TransactionLabel::new("March payout", "work", "Salary");
+        assert_eq!(transaction_label::matches(&transaction(), Some(&label), query), expected);
+    }
+}
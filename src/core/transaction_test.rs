@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+    use web3::types::{TransactionReceipt, U256, U64};
+    use crate::core::{amount::Amount, eth_chain::EthChain, transaction::{TransactionFees, TransactionResult, TransactionStatus}};
+
+    fn pending_transaction() -> TransactionResult {
+        TransactionResult {
+            hash: web3::types::H256::from_low_u64_be(1),
+            block_number: None,
+            from: Some(web3::types::Address::from_low_u64_be(12)),
+            to: Some(web3::types::Address::from_low_u64_be(13)),
+            amount: Amount::from_wei(U256::exp10(18)),
+            currency: "ETH".to_string(),
+            fee: Amount::from_wei(U256::zero()),
+            chain: EthChain::EthereumMainnet,
+            status: TransactionStatus::Pending,
+            nonce: Some(5.into()),
+            gas_used: None,
+            confirmations: 0,
+            replaces: None,
+            replaced_by: None,
+            swap: None,
+        }
+    }
+
+    #[test_case(Some(1), TransactionStatus::Successed)]
+    #[test_case(Some(0), TransactionStatus::Failed)]
+    #[test_case(None, TransactionStatus::Successed)]
+    fn test_apply_receipt(receipt_status: Option<u64>, expected: TransactionStatus) {
+        let mut transaction = pending_transaction();
+
+        let receipt = TransactionReceipt {
+            block_number: Some(100.into()),
+            gas_used: Some(21000.into()),
+            effective_gas_price: Some(U256::exp10(9)),
+            status: receipt_status.map(U64::from),
+            ..Default::default()
+        };
+        transaction.apply_receipt(&receipt, 104.into());
+
+        assert_eq!(transaction.status, expected);
+        assert_eq!(transaction.block_number, Some(100.into()));
+        assert_eq!(transaction.gas_used, Some(21000.into()));
+        assert_eq!(transaction.fee.to_string(), "0.000021");
+        assert_eq!(transaction.confirmations, 5);
+        assert!(transaction.is_tracked());
+
+        transaction.apply_receipt(&receipt, 200.into());
+        assert!(!transaction.is_tracked());
+    }
+
+    #[test_case(5, TransactionStatus::Dropped)]
+    #[test_case(6, TransactionStatus::Replaced)]
+    fn test_apply_missing(account_nonce: u64, expected: TransactionStatus) {
+        let mut transaction = pending_transaction();
+        transaction.apply_missing(account_nonce.into());
+        assert_eq!(transaction.status, expected);
+        assert!(!transaction.is_tracked());
+    }
+
+    const ERROR_STRING_DATA: &str = "0x08c379a0\
+        0000000000000000000000000000000000000000000000000000000000000020\
+        0000000000000000000000000000000000000000000000000000000000000004\
+        6f6f707300000000000000000000000000000000000000000000000000000000";
+
+    #[test_case("insufficient funds for gas * price + value", None,
+        TransactionFees::NotEnoughFunds { currency: "ETH".to_string() }; "insufficient funds")]
+    #[test_case("execution reverted", Some(ERROR_STRING_DATA),
+        TransactionFees::Reverted { reason: "oops".to_string() }; "revert data")]
+    #[test_case("execution reverted: oops", None,
+        TransactionFees::Reverted { reason: "oops".to_string() }; "revert message")]
+    #[test_case("execution reverted", None,
+        TransactionFees::Reverted { reason: "no reason given".to_string() }; "no reason")]
+    #[test_case("header not found", None,
+        TransactionFees::RpcFailure { error: "header not found".to_string() }; "rpc failure")]
+    fn test_fees_from_rpc_error(message: &str, data: Option<&str>, expected: TransactionFees) {
+        let data = data.map(|data| serde_json::Value::String(data.to_string()));
+        assert_eq!(TransactionFees::from_rpc_error(message, data.as_ref(), "ETH"), expected);
+    }
+}
@@ -0,0 +1,120 @@
+use web3::{
+    api::Accounts,
+    signing::{Key, SecretKey, SecretKeyRef},
+    types::{Address, Bytes, TransactionParameters, H256, U256},
+};
+
+use super::{amount::{Amount, ETH_DECIMALS}, eth_chain::EthChain, fees::GasFees, transaction::EIP1559_TRANSACTION_TYPE};
+
+const ERR_CHAIN_ID_MISMATCH: &str = "Transaction chain id doesn't match its chain";
+const ERR_WRONG_SIGNER: &str = "Transaction must be signed by its sender";
+
+// Everything an offline vault needs to sign a transaction without touching the network
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UnsignedTransaction {
+    pub chain: EthChain,
+    pub chain_id: u64,
+    pub from: Address,
+    pub nonce: U256,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub gas_limit: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    // Charged on top of the gas by OP stack chains
+    #[serde(default)]
+    pub l1_data_fee: U256,
+    // What the transfer means for the user, checked against the payload before signing
+    pub recipient: Address,
+    pub amount: Amount,
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RawSignedTransaction {
+    pub chain: EthChain,
+    pub chain_id: u64,
+    pub from: Address,
+    pub nonce: U256,
+    pub recipient: Address,
+    pub amount: Amount,
+    pub currency: String,
+    pub hash: H256,
+    pub raw_transaction: Bytes,
+}
+
+impl UnsignedTransaction {
+    pub fn new(transaction: TransactionParameters, chain: EthChain, from: Address, fees: GasFees) -> Self {
+        Self {
+            chain,
+            chain_id: chain.get_chain_id(),
+            from,
+            nonce: transaction.nonce.unwrap_or_default(),
+            to: transaction.to.unwrap_or_default(),
+            value: transaction.value,
+            data: transaction.data,
+            gas_limit: fees.gas_limit,
+            max_fee_per_gas: fees.prices.max_fee_per_gas,
+            max_priority_fee_per_gas: fees.prices.max_priority_fee_per_gas,
+            l1_data_fee: fees.charged_l1_fee(),
+            recipient: transaction.to.unwrap_or_default(),
+            amount: Amount::zero(ETH_DECIMALS),
+            currency: String::new(),
+        }
+    }
+
+    pub fn with_transfer(mut self, recipient: Address, amount: Amount, currency: &str) -> Self {
+        self.recipient = recipient;
+        self.amount = amount;
+        self.currency = currency.to_string();
+        self
+    }
+
+    pub fn max_cost(&self) -> U256 {
+        self.gas_limit * self.max_fee_per_gas + self.l1_data_fee
+    }
+
+    pub fn to_parameters(&self) -> TransactionParameters {
+        TransactionParameters {
+            nonce: Some(self.nonce),
+            to: Some(self.to),
+            gas: self.gas_limit,
+            value: self.value,
+            data: self.data.clone(),
+            chain_id: Some(self.chain_id),
+            transaction_type: Some(EIP1559_TRANSACTION_TYPE.into()),
+            max_fee_per_gas: Some(self.max_fee_per_gas),
+            max_priority_fee_per_gas: Some(self.max_priority_fee_per_gas),
+            ..Default::default()
+        }
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.chain_id != self.chain.get_chain_id() {
+            return Err(anyhow::anyhow!(ERR_CHAIN_ID_MISMATCH));
+        }
+        Ok(())
+    }
+
+    // NOTE: all the parameters are set, so signing doesn't send any requests through the transport
+    pub async fn sign<T: web3::Transport>(&self, accounts: Accounts<T>, secret_key: &SecretKey) -> anyhow::Result<RawSignedTransaction> {
+        self.validate()?;
+        if SecretKeyRef::new(secret_key).address() != self.from {
+            return Err(anyhow::anyhow!(ERR_WRONG_SIGNER));
+        }
+
+        let signed = accounts.sign_transaction(self.to_parameters(), secret_key).await?;
+        Ok(RawSignedTransaction {
+            chain: self.chain,
+            chain_id: self.chain_id,
+            from: self.from,
+            nonce: self.nonce,
+            recipient: self.recipient,
+            amount: self.amount,
+            currency: self.currency.clone(),
+            hash: signed.transaction_hash,
+            raw_transaction: signed.raw_transaction,
+        })
+    }
+}
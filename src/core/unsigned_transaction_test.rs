@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use web3::{
+        signing::{Key, SecretKey, SecretKeyRef},
+        transports::test::TestTransport,
+        types::{Address, TransactionParameters},
+    };
+    use crate::core::{
+        amount::Amount,
+        eth_chain::EthChain,
+        fees::{GasFees, GasPrices},
+        unsigned_transaction::UnsignedTransaction
+    };
+
+    fn unsigned_transaction(from: Address) -> UnsignedTransaction {
+        let transaction = TransactionParameters {
+            nonce: Some(3.into()),
+            to: Some(Address::from_low_u64_be(1)),
+            value: 1000.into(),
+            ..Default::default()
+        };
+        let fees = GasFees::new(21000.into(), GasPrices::with_priority_fee(100.into(), 1.into()));
+        UnsignedTransaction::new(transaction, EthChain::EthereumSepolia, from, fees)
+            .with_transfer(Address::from_low_u64_be(1), Amount::from_wei(1000.into()), "ETH")
+    }
+
+    #[test]
+    fn test_json_roundtrip() -> anyhow::Result<()> {
+        let transaction = unsigned_transaction(Address::from_low_u64_be(2));
+
+        let json = serde_json::to_string(&transaction)?;
+        let restored: UnsignedTransaction = serde_json::from_str(&json)?;
+
+        assert_eq!(restored, transaction);
+        assert_eq!(restored.chain_id, 11155111);
+        assert_eq!(restored.max_cost(), (21000 * 201).into());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sign_offline() -> anyhow::Result<()> {
+        let secret_key = SecretKey::from_slice(&[1u8; 32])?;
+        let from = SecretKeyRef::new(&secret_key).address();
+        let transaction = unsigned_transaction(from);
+
+        let transport = TestTransport::default();
+        let signed = transaction.sign(web3::Web3::new(transport.clone()).accounts(), &secret_key).await?;
+
+        let expected = web3::Web3::new(TestTransport::default()).accounts()
+            .sign_transaction(transaction.to_parameters(), &secret_key).await?;
+        assert_eq!(signed.hash, expected.transaction_hash);
+        assert_eq!(signed.raw_transaction, expected.raw_transaction);
+        assert_eq!(signed.raw_transaction.0[0], 2);
+        assert_eq!(signed.nonce, 3.into());
+
+        transport.assert_no_more_requests();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_refuse_to_sign() -> anyhow::Result<()> {
+        let secret_key = SecretKey::from_slice(&[1u8; 32])?;
+        let from = SecretKeyRef::new(&secret_key).address();
+        let accounts = web3::Web3::new(TestTransport::default()).accounts();
+
+        // Another sender
+        let transaction = unsigned_transaction(Address::from_low_u64_be(2));
+        assert!(transaction.sign(accounts.clone(), &secret_key).await.is_err());
+
+        // Tampered chain id
+        let transaction = UnsignedTransaction { chain_id: 1, ..unsigned_transaction(from) };
+        assert!(transaction.sign(accounts, &secret_key).await.is_err());
+        Ok(())
+    }
+}
@@ -27,6 +27,9 @@ impl Cipher {
     }
 
     pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        if ciphertext.len() < 12 {
+            return Err(anyhow!("Decryption error: ciphertext is too short"));
+        }
         let (nonce, ciphertext) = ciphertext.split_at(12);
         let plaintext = self.cipher.decrypt(Nonce::from_slice(nonce), ciphertext)
             .map_err(|e| anyhow!("Decryption error: {:?}", e))?;
@@ -72,4 +72,16 @@ impl Db {
             }
         }).collect()
     }
+
+    // Same as scan_prefix, with the keys to write the values back
+    pub fn scan_prefix_entries<V>(&self, prefix: &[u8], encrypted: bool) -> Result<Vec<(Vec<u8>, V)>>
+    where V: for<'de> Deserialize<'de> {
+        self.db.scan_prefix(prefix).map(|result| {
+            let (key, mut value) = result?;
+            if encrypted {
+                value = self.cipher.decrypt(&value)?.into();
+            }
+            Ok((key.to_vec(), serde_json::from_slice(&value)?))
+        }).collect()
+    }
 }
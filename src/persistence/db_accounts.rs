@@ -1,5 +1,5 @@
 use crate::core::{key_pair::KeyPair, seed_phrase::SeedPhrase};
-use super::db::Db;
+use super::{cipher::Cipher, db::Db};
 
 const ROOT_KEYPAIR: &[u8] = b"root_keypair";
 const ROOT_SEED_PHRASE: &[u8] = b"root_seed_phrase";
@@ -44,4 +44,11 @@ impl Db {
         }
         Err(anyhow::anyhow!(ERR_KEYPAIR_NOT_FOUND))
     }
+
+    // The keypair only decrypts with the password the vault was opened with
+    pub fn verify_password(&self, password: &str) -> anyhow::Result<bool> {
+        let encrypted_keypair = self.get_raw_bytes(ROOT_KEYPAIR, false)?
+            .ok_or_else(|| anyhow::anyhow!(ERR_KEYPAIR_NOT_FOUND))?;
+        Ok(Cipher::new_from_password(password).decrypt(&encrypted_keypair).is_ok())
+    }
 }
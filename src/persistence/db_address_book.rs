@@ -0,0 +1,27 @@
+use web3::types::Address;
+
+use super::db::Db;
+use crate::core::address_book::Contact;
+
+const ADDRESS_BOOK: &[u8] = b"address_book";
+
+impl Db {
+    pub fn save_contact(&self, contact: &Contact) -> anyhow::Result<()> {
+        self.upsert(&contact_id(contact.address), contact, true)
+    }
+
+    pub fn remove_contact(&self, address: Address) -> anyhow::Result<()> {
+        self.remove(&contact_id(address))?;
+        Ok(())
+    }
+
+    pub fn get_contacts(&self) -> anyhow::Result<Vec<Contact>> {
+        self.scan_prefix(ADDRESS_BOOK, 0, usize::MAX, true)
+    }
+}
+
+fn contact_id(address: Address) -> Vec<u8> {
+    let mut key = ADDRESS_BOOK.to_vec();
+    key.extend_from_slice(address.as_bytes());
+    key
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use web3::types::Address;
+    use crate::core::{address_book::Contact, eth_chain::EthChain};
+    use super::super::db::Db;
+
+    fn create_test_db() -> anyhow::Result<Db> {
+        let db_name = format!("test_raclette_address_book_db_{}", uuid::Uuid::new_v4());
+        let mut path = std::env::temp_dir();
+        path.push(db_name);
+
+        let config = sled::Config::new().temporary(true).path(path);
+        let db = config.open()?;
+
+        Db::open(db, "12345678")
+    }
+
+    #[test]
+    fn test_address_book_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        assert!(db.get_contacts()?.is_empty());
+
+        let alice = Contact {
+            chains: vec![EthChain::EthereumMainnet],
+            ..Contact::new("Alice", Address::from_low_u64_be(1))
+        };
+        let bob = Contact::new("Bob", Address::from_low_u64_be(2));
+        db.save_contact(&alice)?;
+        db.save_contact(&bob)?;
+        assert_eq!(db.get_contacts()?, vec![alice.clone(), bob.clone()]);
+
+        // Same address updates the contact
+        let alice = Contact { notes: "Friend".to_string(), ..alice };
+        db.save_contact(&alice)?;
+        assert_eq!(db.get_contacts()?, vec![alice.clone(), bob.clone()]);
+
+        db.remove_contact(bob.address)?;
+        assert_eq!(db.get_contacts()?, vec![alice]);
+
+        // Stored encrypted
+        let raw = db.get_raw_bytes(&[b"address_book".as_slice(), Address::from_low_u64_be(1).as_bytes()].concat(), false)?;
+        assert!(!String::from_utf8_lossy(&raw.unwrap()).contains("Alice"));
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use web3::types::{Address, U64};
+
+use super::db::Db;
+use crate::core::eth_chain::EthChain;
+
+const APPROVED_PAIRS: &[u8] = b"approved_pairs";
+const APPROVAL_CHECKPOINTS: &[u8] = b"approval_checkpoint";
+
+impl Db {
+    // Token and spender pairs found in the approval logs scanned so far
+    pub fn save_approved_pairs(&self, account: Address, chain: EthChain, pairs: &[(Address, Address)]) -> anyhow::Result<()> {
+        self.upsert(&approval_id(APPROVED_PAIRS, account, chain), &pairs, false)
+    }
+
+    pub fn get_approved_pairs(&self, account: Address, chain: EthChain) -> anyhow::Result<Vec<(Address, Address)>> {
+        Ok(self.get(&approval_id(APPROVED_PAIRS, account, chain), false)?.unwrap_or_default())
+    }
+
+    // Next block to be scanned for approvals
+    pub fn save_approval_checkpoint(&self, account: Address, chain: EthChain, block_number: U64) -> anyhow::Result<()> {
+        self.upsert(&approval_id(APPROVAL_CHECKPOINTS, account, chain), &block_number, false)
+    }
+
+    pub fn get_approval_checkpoint(&self, account: Address, chain: EthChain) -> anyhow::Result<Option<U64>> {
+        self.get(&approval_id(APPROVAL_CHECKPOINTS, account, chain), false)
+    }
+}
+
+fn approval_id(prefix: &[u8], account: Address, chain: EthChain) -> Vec<u8> {
+    let mut key = prefix.to_vec();
+    key.extend_from_slice(account.as_bytes());
+    key.extend_from_slice(&chain.get_chain_id().to_be_bytes());
+    key
+}
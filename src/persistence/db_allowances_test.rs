@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use web3::types::Address;
+    use crate::core::eth_chain::EthChain;
+    use super::super::db::Db;
+
+    fn create_test_db() -> anyhow::Result<Db> {
+        let db_name = format!("test_raclette_allowances_db_{}", uuid::Uuid::new_v4());
+        let mut path = std::env::temp_dir();
+        path.push(db_name);
+
+        let config = sled::Config::new().temporary(true).path(path);
+        let db = config.open()?;
+
+        Db::open(db, "12345678")
+    }
+
+    #[test]
+    fn test_approvals_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        let account = Address::from_low_u64_be(1);
+        let chain = EthChain::EthereumMainnet;
+        assert!(db.get_approved_pairs(account, chain)?.is_empty());
+        assert_eq!(db.get_approval_checkpoint(account, chain)?, None);
+
+        let pairs = vec![(Address::from_low_u64_be(0xc0), Address::from_low_u64_be(0x5e))];
+        db.save_approved_pairs(account, chain, &pairs)?;
+        db.save_approval_checkpoint(account, chain, 100_000.into())?;
+        assert_eq!(db.get_approved_pairs(account, chain)?, pairs);
+        assert_eq!(db.get_approval_checkpoint(account, chain)?, Some(100_000.into()));
+
+        // Separate per account and chain
+        assert!(db.get_approved_pairs(Address::from_low_u64_be(2), chain)?.is_empty());
+        assert_eq!(db.get_approval_checkpoint(account, EthChain::OptimismMainnet)?, None);
+        Ok(())
+    }
+}
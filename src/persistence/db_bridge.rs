@@ -0,0 +1,30 @@
+use web3::types::{Address, H256};
+
+use super::db::Db;
+use crate::core::bridge::Withdrawal;
+
+const BRIDGE_WITHDRAWALS: &[u8] = b"bridge_withdrawal";
+
+impl Db {
+    pub fn save_withdrawal(&self, withdrawal: &Withdrawal) -> anyhow::Result<()> {
+        self.upsert(&withdrawal_id(withdrawal.account, withdrawal.tx_hash), withdrawal, false)
+    }
+
+    pub fn get_withdrawals(&self, account: Address) -> anyhow::Result<Vec<Withdrawal>> {
+        let mut withdrawals: Vec<Withdrawal> = self.scan_prefix(&withdrawals_prefix(account), 0, usize::MAX, false)?;
+        withdrawals.sort_by_key(|withdrawal| std::cmp::Reverse(withdrawal.initiated_at));
+        Ok(withdrawals)
+    }
+}
+
+fn withdrawals_prefix(account: Address) -> Vec<u8> {
+    let mut prefix = BRIDGE_WITHDRAWALS.to_vec();
+    prefix.extend_from_slice(account.as_bytes());
+    prefix
+}
+
+fn withdrawal_id(account: Address, tx_hash: H256) -> Vec<u8> {
+    let mut key = withdrawals_prefix(account);
+    key.extend_from_slice(tx_hash.as_bytes());
+    key
+}
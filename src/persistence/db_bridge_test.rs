@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use web3::types::{Address, H256};
+    use crate::core::{bridge::{Withdrawal, WithdrawalStatus}, eth_chain::EthChain};
+    use super::super::db::Db;
+
+    fn create_test_db() -> anyhow::Result<Db> {
+        let db_name = format!("test_raclette_bridge_db_{}", uuid::Uuid::new_v4());
+        let mut path = std::env::temp_dir();
+        path.push(db_name);
+
+        let config = sled::Config::new().temporary(true).path(path);
+        let db = config.open()?;
+
+        Db::open(db, "12345678")
+    }
+
+    #[test]
+    fn test_withdrawals_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        let account = Address::from_low_u64_be(12);
+        let other = Address::from_low_u64_be(13);
+
+        let older = Withdrawal::new(H256::from_low_u64_be(1), EthChain::OptimismMainnet, account, "0.5".parse()?, 100);
+        let mut newer = Withdrawal::new(H256::from_low_u64_be(2), EthChain::ArbitrumMainnet, account, "1".parse()?, 200);
+        db.save_withdrawal(&older)?;
+        db.save_withdrawal(&newer)?;
+        db.save_withdrawal(&Withdrawal::new(H256::from_low_u64_be(3), EthChain::OptimismMainnet, other, "2".parse()?, 300))?;
+
+        // Updates replace the record of the same transaction
+        newer.status = WithdrawalStatus::Finalizable;
+        db.save_withdrawal(&newer)?;
+
+        assert_eq!(db.get_withdrawals(account)?, vec![newer, older]);
+        assert_eq!(db.get_withdrawals(other)?.len(), 1);
+        Ok(())
+    }
+}
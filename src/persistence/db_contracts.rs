@@ -0,0 +1,28 @@
+use web3::types::Address;
+
+use super::db::Db;
+use crate::core::{contract::SavedContract, eth_chain::EthChain};
+
+const CONTRACTS: &[u8] = b"saved_contract";
+
+impl Db {
+    pub fn save_contract(&self, contract: &SavedContract) -> anyhow::Result<()> {
+        self.upsert(&contract_id(contract.chain, contract.address), contract, true)
+    }
+
+    pub fn remove_contract(&self, chain: EthChain, address: Address) -> anyhow::Result<()> {
+        self.remove(&contract_id(chain, address))?;
+        Ok(())
+    }
+
+    pub fn get_contracts(&self) -> anyhow::Result<Vec<SavedContract>> {
+        self.scan_prefix(CONTRACTS, 0, usize::MAX, true)
+    }
+}
+
+fn contract_id(chain: EthChain, address: Address) -> Vec<u8> {
+    let mut key = CONTRACTS.to_vec();
+    key.extend_from_slice(&chain.get_chain_id().to_be_bytes());
+    key.extend_from_slice(address.as_bytes());
+    key
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use web3::types::Address;
+    use crate::core::{contract::SavedContract, eth_chain::EthChain};
+    use super::super::db::Db;
+
+    fn create_test_db() -> anyhow::Result<Db> {
+        let db_name = format!("test_raclette_contracts_db_{}", uuid::Uuid::new_v4());
+        let mut path = std::env::temp_dir();
+        path.push(db_name);
+
+        let config = sled::Config::new().temporary(true).path(path);
+        let db = config.open()?;
+
+        Db::open(db, "12345678")
+    }
+
+    #[test]
+    fn test_contracts_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        assert!(db.get_contracts()?.is_empty());
+
+        let vault = SavedContract {
+            name: "Vault".to_string(),
+            chain: EthChain::EthereumMainnet,
+            address: Address::from_low_u64_be(1),
+            abi: "[]".to_string(),
+        };
+        // Same address on another chain is a separate contract
+        let vault_op = SavedContract { chain: EthChain::OptimismMainnet, ..vault.clone() };
+        db.save_contract(&vault)?;
+        db.save_contract(&vault_op)?;
+        assert_eq!(db.get_contracts()?, vec![vault.clone(), vault_op.clone()]);
+
+        let vault = SavedContract { name: "Main vault".to_string(), ..vault };
+        db.save_contract(&vault)?;
+        assert_eq!(db.get_contracts()?, vec![vault.clone(), vault_op.clone()]);
+
+        db.remove_contract(vault_op.chain, vault_op.address)?;
+        assert_eq!(db.get_contracts()?, vec![vault]);
+
+        // Stored encrypted
+        let key = [b"saved_contract".as_slice(), &1u64.to_be_bytes(), Address::from_low_u64_be(1).as_bytes()].concat();
+        let raw = db.get_raw_bytes(&key, false)?;
+        assert!(!String::from_utf8_lossy(&raw.unwrap()).contains("Main vault"));
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use web3::types::{Address, U256, U64};
+
+use super::db::Db;
+use crate::core::{eth_chain::EthChain, nft::NftHolding};
+
+const NFT_HOLDINGS: &[u8] = b"nft_holding";
+const NFT_CHECKPOINTS: &[u8] = b"nft_checkpoint";
+
+impl Db {
+    pub fn save_nft(&self, account: Address, holding: &NftHolding) -> anyhow::Result<()> {
+        let key = nft_id(account, holding.chain, holding.contract_address, holding.token_id);
+        self.upsert(&key, holding, false)
+    }
+
+    pub fn remove_nft(&self, account: Address, holding: &NftHolding) -> anyhow::Result<()> {
+        self.remove(&nft_id(account, holding.chain, holding.contract_address, holding.token_id))?;
+        Ok(())
+    }
+
+    pub fn get_nft(&self, account: Address, chain: EthChain, contract_address: Address, token_id: U256) -> anyhow::Result<Option<NftHolding>> {
+        self.get(&nft_id(account, chain, contract_address, token_id), false)
+    }
+
+    pub fn get_nfts(&self, account: Address) -> anyhow::Result<Vec<NftHolding>> {
+        let mut prefix = NFT_HOLDINGS.to_vec();
+        prefix.extend_from_slice(account.as_bytes());
+
+        self.scan_prefix(&prefix, 0, usize::MAX, false)
+    }
+
+    // Next block to be scanned by the NFT sync
+    pub fn save_nft_checkpoint(&self, account: Address, chain: EthChain, block_number: U64) -> anyhow::Result<()> {
+        self.upsert(&nft_checkpoint_id(account, chain), &block_number, false)
+    }
+
+    pub fn get_nft_checkpoint(&self, account: Address, chain: EthChain) -> anyhow::Result<Option<U64>> {
+        self.get(&nft_checkpoint_id(account, chain), false)
+    }
+}
+
+fn nft_id(account: Address, chain: EthChain, contract_address: Address, token_id: U256) -> Vec<u8> {
+    let mut token_id_bytes = [0u8; 32];
+    token_id.to_big_endian(&mut token_id_bytes);
+
+    let mut key = NFT_HOLDINGS.to_vec();
+    key.extend_from_slice(account.as_bytes());
+    key.extend_from_slice(&chain.get_chain_id().to_be_bytes());
+    key.extend_from_slice(contract_address.as_bytes());
+    key.extend_from_slice(&token_id_bytes);
+    key
+}
+
+fn nft_checkpoint_id(account: Address, chain: EthChain) -> Vec<u8> {
+    let mut key = NFT_CHECKPOINTS.to_vec();
+    key.extend_from_slice(account.as_bytes());
+    key.extend_from_slice(&chain.get_chain_id().to_be_bytes());
+    key
+}
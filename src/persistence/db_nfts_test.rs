@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use web3::types::{Address, U256};
+    use crate::core::{eth_chain::EthChain, nft::{NftHolding, NftStandard}};
+    use super::super::db::Db;
+
+    fn create_test_db() -> anyhow::Result<Db> {
+        let db_name = format!("test_raclette_nfts_db_{}", uuid::Uuid::new_v4());
+        let mut path = std::env::temp_dir();
+        path.push(db_name);
+
+        let config = sled::Config::new().temporary(true).path(path);
+        let db = config.open()?;
+
+        Db::open(db, "12345678")
+    }
+
+    #[test]
+    fn test_nfts_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        let account = Address::from_low_u64_be(1);
+        let other = Address::from_low_u64_be(2);
+
+        let holding = NftHolding {
+            chain: EthChain::EthereumMainnet,
+            standard: NftStandard::Erc721,
+            contract_address: Address::from_low_u64_be(0xc0),
+            collection: Some("Punks".to_string()),
+            token_id: U256::from(42),
+            amount: U256::one(),
+            metadata_uri: Some("ipfs://punk/42".to_string()),
+        };
+        let other_chain = NftHolding { chain: EthChain::OptimismMainnet, ..holding.clone() };
+        db.save_nft(account, &holding)?;
+        db.save_nft(account, &other_chain)?;
+
+        assert_eq!(db.get_nfts(account)?, vec![holding.clone(), other_chain.clone()]);
+        assert!(db.get_nfts(other)?.is_empty());
+        assert_eq!(db.get_nft(account, EthChain::EthereumMainnet, holding.contract_address, 42.into())?, Some(holding.clone()));
+        assert_eq!(db.get_nft(account, EthChain::EthereumMainnet, holding.contract_address, 43.into())?, None);
+
+        db.remove_nft(account, &holding)?;
+        assert_eq!(db.get_nfts(account)?, vec![other_chain]);
+
+        assert_eq!(db.get_nft_checkpoint(account, EthChain::EthereumMainnet)?, None);
+        db.save_nft_checkpoint(account, EthChain::EthereumMainnet, 100.into())?;
+        assert_eq!(db.get_nft_checkpoint(account, EthChain::EthereumMainnet)?, Some(100.into()));
+        Ok(())
+    }
+}
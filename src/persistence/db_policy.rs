@@ -0,0 +1,63 @@
+use web3::types::{Address, H256};
+
+use super::db::Db;
+use crate::core::policy::{PolicySpend, SpendingPolicy};
+
+const SPENDING_POLICY: &[u8] = b"spending_policy";
+const POLICY_SPENDS: &[u8] = b"policy_spend";
+const POLICY_RECIPIENTS: &[u8] = b"policy_recipient";
+
+impl Db {
+    pub fn save_spending_policy(&self, policy: &SpendingPolicy) -> anyhow::Result<()> {
+        self.upsert(SPENDING_POLICY, policy, true)
+    }
+
+    pub fn get_spending_policy(&self) -> anyhow::Result<Option<SpendingPolicy>> {
+        self.get(SPENDING_POLICY, true)
+    }
+
+    pub fn save_policy_spend(&self, tx_hash: H256, spend: &PolicySpend) -> anyhow::Result<()> {
+        self.upsert(&policy_spend_id(spend.timestamp, tx_hash), spend, true)
+    }
+
+    // Spends with the hash of their transaction
+    // NOTE: keys start with the timestamp, so the spends come in order
+    pub fn get_policy_spends(&self, since: i64) -> anyhow::Result<Vec<(H256, PolicySpend)>> {
+        let spends: Vec<(Vec<u8>, PolicySpend)> = self.scan_prefix_entries(POLICY_SPENDS, true)?;
+        Ok(spends.into_iter()
+            .filter(|(_, spend)| spend.timestamp >= since)
+            .map(|(key, spend)| (H256::from_slice(&key[key.len() - H256::len_bytes()..]), spend))
+            .collect())
+    }
+
+    // A replaced transaction is never mined, so its spend is dropped
+    pub fn remove_policy_spend(&self, tx_hash: H256) -> anyhow::Result<()> {
+        let spend = self.get_policy_spends(0)?.into_iter().find(|(hash, _)| *hash == tx_hash);
+        if let Some((_, spend)) = spend {
+            self.remove(&policy_spend_id(spend.timestamp, tx_hash))?;
+        }
+        Ok(())
+    }
+
+    // When the recipient was first used, new recipients wait for the cooling-off
+    pub fn save_recipient_first_seen(&self, recipient: Address, timestamp: i64) -> anyhow::Result<()> {
+        self.upsert(&policy_recipient_id(recipient), &timestamp, true)
+    }
+
+    pub fn get_recipient_first_seen(&self, recipient: Address) -> anyhow::Result<Option<i64>> {
+        self.get(&policy_recipient_id(recipient), true)
+    }
+}
+
+fn policy_spend_id(timestamp: i64, tx_hash: H256) -> Vec<u8> {
+    let mut key = POLICY_SPENDS.to_vec();
+    key.extend_from_slice(&timestamp.max(0).to_be_bytes());
+    key.extend_from_slice(tx_hash.as_bytes());
+    key
+}
+
+fn policy_recipient_id(recipient: Address) -> Vec<u8> {
+    let mut key = POLICY_RECIPIENTS.to_vec();
+    key.extend_from_slice(recipient.as_bytes());
+    key
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use web3::types::{Address, H256};
+    use crate::core::{eth_chain::EthChain, policy::{LimitUnit, PolicySpend, SpendingLimit, SpendingPolicy}};
+    use super::super::db::Db;
+
+    fn create_test_db() -> anyhow::Result<Db> {
+        let db_name = format!("test_raclette_policy_db_{}", uuid::Uuid::new_v4());
+        let mut path = std::env::temp_dir();
+        path.push(db_name);
+
+        let config = sled::Config::new().temporary(true).path(path);
+        let db = config.open()?;
+
+        Db::open(db, "12345678")
+    }
+
+    #[test]
+    fn test_spending_policy_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        assert_eq!(db.get_spending_policy()?, None);
+
+        let policy = SpendingPolicy {
+            limits: vec![SpendingLimit {
+                currency: "ETH".to_string(),
+                unit: LimitUnit::Token,
+                per_transaction: Some("0.5".parse()?),
+                daily: None,
+            }],
+            recipients: vec![Address::from_low_u64_be(1)],
+            chains: vec![EthChain::OptimismMainnet],
+            cooling_off_hours: 24,
+        };
+        db.save_spending_policy(&policy)?;
+        assert_eq!(db.get_spending_policy()?, Some(policy));
+        Ok(())
+    }
+
+    #[test]
+    fn test_policy_spends_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+
+        let spend = |timestamp: i64| PolicySpend {
+            timestamp,
+            chain: EthChain::EthereumMainnet,
+            currency: "ETH".to_string(),
+            amount: "0.1".parse().unwrap(),
+            usd_value: Some(200.0),
+        };
+        db.save_policy_spend(H256::from_low_u64_be(2), &spend(2000))?;
+        db.save_policy_spend(H256::from_low_u64_be(1), &spend(1000))?;
+        db.save_policy_spend(H256::from_low_u64_be(3), &spend(3000))?;
+
+        let timestamps = db.get_policy_spends(2000)?.iter().map(|(_, spend)| spend.timestamp).collect::<Vec<_>>();
+        assert_eq!(timestamps, vec![2000, 3000]);
+        let hashes = db.get_policy_spends(0)?.iter().map(|(hash, _)| hash.to_low_u64_be()).collect::<Vec<_>>();
+        assert_eq!(hashes, vec![1, 2, 3]);
+
+        db.remove_policy_spend(H256::from_low_u64_be(2))?;
+        db.remove_policy_spend(H256::from_low_u64_be(4))?;
+        let hashes = db.get_policy_spends(0)?.iter().map(|(hash, _)| hash.to_low_u64_be()).collect::<Vec<_>>();
+        assert_eq!(hashes, vec![1, 3]);
+        db.save_policy_spend(H256::from_low_u64_be(2), &spend(2000))?;
+
+        // Recipients don't show up as spends
+        let recipient = Address::from_low_u64_be(5);
+        assert_eq!(db.get_recipient_first_seen(recipient)?, None);
+        db.save_recipient_first_seen(recipient, 1500)?;
+        assert_eq!(db.get_recipient_first_seen(recipient)?, Some(1500));
+        assert_eq!(db.get_policy_spends(0)?.len(), 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use web3::types::{Address, H256};
+
+use super::db::Db;
+use crate::core::{eth_chain::EthChain, safe::{SafeProposal, SavedSafe}};
+
+const SAFES: &[u8] = b"safe_wallet";
+const SAFE_PROPOSALS: &[u8] = b"safe_proposal";
+
+impl Db {
+    pub fn save_safe(&self, safe: &SavedSafe) -> anyhow::Result<()> {
+        self.upsert(&safe_id(SAFES, safe.chain, safe.address), safe, false)
+    }
+
+    // Pending proposals go along with the Safe
+    pub fn remove_safe(&self, chain: EthChain, address: Address) -> anyhow::Result<()> {
+        for proposal in self.get_safe_proposals(chain, address)? {
+            self.remove_safe_proposal(&proposal)?;
+        }
+        self.remove(&safe_id(SAFES, chain, address))?;
+        Ok(())
+    }
+
+    pub fn get_safes(&self) -> anyhow::Result<Vec<SavedSafe>> {
+        self.scan_prefix(SAFES, 0, usize::MAX, false)
+    }
+
+    pub fn save_safe_proposal(&self, proposal: &SafeProposal) -> anyhow::Result<()> {
+        self.upsert(&proposal_id(proposal), proposal, false)
+    }
+
+    pub fn remove_safe_proposal(&self, proposal: &SafeProposal) -> anyhow::Result<()> {
+        self.remove(&proposal_id(proposal))?;
+        Ok(())
+    }
+
+    pub fn get_safe_proposal(&self, chain: EthChain, safe: Address, safe_tx_hash: H256) -> anyhow::Result<Option<SafeProposal>> {
+        let mut key = safe_id(SAFE_PROPOSALS, chain, safe);
+        key.extend_from_slice(safe_tx_hash.as_bytes());
+        self.get(&key, false)
+    }
+
+    pub fn get_safe_proposals(&self, chain: EthChain, safe: Address) -> anyhow::Result<Vec<SafeProposal>> {
+        let mut proposals: Vec<SafeProposal> = self.scan_prefix(&safe_id(SAFE_PROPOSALS, chain, safe), 0, usize::MAX, false)?;
+        proposals.sort_by_key(|proposal| proposal.transaction.nonce);
+        Ok(proposals)
+    }
+}
+
+fn safe_id(prefix: &[u8], chain: EthChain, address: Address) -> Vec<u8> {
+    let mut key = prefix.to_vec();
+    key.extend_from_slice(&chain.get_chain_id().to_be_bytes());
+    key.extend_from_slice(address.as_bytes());
+    key
+}
+
+fn proposal_id(proposal: &SafeProposal) -> Vec<u8> {
+    let mut key = safe_id(SAFE_PROPOSALS, proposal.transaction.chain, proposal.transaction.safe);
+    key.extend_from_slice(proposal.safe_tx_hash.as_bytes());
+    key
+}
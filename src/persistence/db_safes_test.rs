@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use web3::types::Address;
+    use crate::core::{
+        eth_chain::EthChain,
+        safe::{SafeInfo, SafeProposal, SafeTransaction, SavedSafe}
+    };
+    use super::super::db::Db;
+
+    fn create_test_db() -> anyhow::Result<Db> {
+        let db_name = format!("test_raclette_safes_db_{}", uuid::Uuid::new_v4());
+        let mut path = std::env::temp_dir();
+        path.push(db_name);
+
+        let config = sled::Config::new().temporary(true).path(path);
+        let db = config.open()?;
+
+        Db::open(db, "12345678")
+    }
+
+    #[test]
+    fn test_safes_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        assert!(db.get_safes()?.is_empty());
+
+        let treasury = SavedSafe { chain: EthChain::EthereumMainnet, address: Address::from_low_u64_be(1) };
+        let treasury_op = SavedSafe { chain: EthChain::OptimismMainnet, ..treasury.clone() };
+        db.save_safe(&treasury)?;
+        db.save_safe(&treasury_op)?;
+        db.save_safe(&treasury)?;
+        assert_eq!(db.get_safes()?, vec![treasury.clone(), treasury_op.clone()]);
+
+        let info = SafeInfo {
+            chain: treasury.chain,
+            address: treasury.address,
+            owners: vec![Address::from_low_u64_be(2)],
+            threshold: 1,
+            nonce: 3.into(),
+        };
+        let later = SafeProposal::new(SafeTransaction { nonce: 4.into(), ..SafeTransaction::new(&info, Address::zero(), 1.into(), Vec::new()) });
+        let current = SafeProposal::new(SafeTransaction::new(&info, Address::zero(), 1.into(), Vec::new()));
+        db.save_safe_proposal(&later)?;
+        db.save_safe_proposal(&current)?;
+        assert_eq!(db.get_safe_proposals(info.chain, info.address)?, vec![current.clone(), later.clone()]);
+        assert_eq!(db.get_safe_proposal(info.chain, info.address, later.safe_tx_hash)?, Some(later.clone()));
+        assert!(db.get_safe_proposals(treasury_op.chain, treasury_op.address)?.is_empty());
+
+        db.remove_safe_proposal(&later)?;
+        assert_eq!(db.get_safe_proposals(info.chain, info.address)?, vec![current]);
+
+        // Removing the Safe drops its proposals
+        db.remove_safe(treasury.chain, treasury.address)?;
+        assert_eq!(db.get_safes()?, vec![treasury_op]);
+        assert!(db.get_safe_proposals(info.chain, info.address)?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use super::db::Db;
+
+const SIGN_PASSWORD_REQUIRED: &[u8] = b"sign_password_required";
+
+impl Db {
+    pub fn save_sign_password_required(&self, required: bool) -> anyhow::Result<()> {
+        self.upsert(SIGN_PASSWORD_REQUIRED, &required, true)
+    }
+
+    // Off by default, the review step is always shown anyway
+    // NOTE: stored encrypted, a value that can't be decrypted keeps the password required
+    pub fn is_sign_password_required(&self) -> anyhow::Result<bool> {
+        Ok(self.get(SIGN_PASSWORD_REQUIRED, true).unwrap_or(Some(true)).unwrap_or_default())
+    }
+}
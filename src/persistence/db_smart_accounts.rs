@@ -0,0 +1,45 @@
+use web3::types::Address;
+
+use super::db::Db;
+use crate::core::{eth_chain::EthChain, smart_account::SmartAccount};
+
+const SMART_ACCOUNTS: &[u8] = b"smart_account";
+const BUNDLER_ENDPOINTS: &[u8] = b"bundler_endpoint";
+
+impl Db {
+    pub fn save_smart_account(&self, account: &SmartAccount) -> anyhow::Result<()> {
+        let mut key = smart_accounts_prefix(account.owner);
+        key.extend_from_slice(account.address.as_bytes());
+        self.upsert(&key, account, false)
+    }
+
+    pub fn get_smart_accounts(&self, owner: Address) -> anyhow::Result<Vec<SmartAccount>> {
+        self.scan_prefix(&smart_accounts_prefix(owner), 0, usize::MAX, false)
+    }
+
+    // Bundler URLs are chain specific and may carry an API key
+    pub fn save_bundler_endpoint(&self, chain: EthChain, url: &str) -> anyhow::Result<()> {
+        let key = bundler_endpoint_id(chain);
+        if url.is_empty() {
+            self.remove(&key)?;
+            return Ok(());
+        }
+        self.upsert(&key, &url.to_string(), true)
+    }
+
+    pub fn get_bundler_endpoint(&self, chain: EthChain) -> anyhow::Result<Option<String>> {
+        self.get(&bundler_endpoint_id(chain), true)
+    }
+}
+
+fn smart_accounts_prefix(owner: Address) -> Vec<u8> {
+    let mut key = SMART_ACCOUNTS.to_vec();
+    key.extend_from_slice(owner.as_bytes());
+    key
+}
+
+fn bundler_endpoint_id(chain: EthChain) -> Vec<u8> {
+    let mut key = BUNDLER_ENDPOINTS.to_vec();
+    key.extend_from_slice(&chain.get_chain_id().to_be_bytes());
+    key
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use web3::types::Address;
+    use crate::core::{eth_chain::EthChain, smart_account::SmartAccount};
+    use super::super::db::Db;
+
+    fn create_test_db() -> anyhow::Result<Db> {
+        let db_name = format!("test_raclette_smart_accounts_db_{}", uuid::Uuid::new_v4());
+        let mut path = std::env::temp_dir();
+        path.push(db_name);
+
+        let config = sled::Config::new().temporary(true).path(path);
+        let db = config.open()?;
+
+        Db::open(db, "12345678")
+    }
+
+    #[test]
+    fn test_smart_accounts_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        let owner = Address::from_low_u64_be(1);
+        assert!(db.get_smart_accounts(owner)?.is_empty());
+
+        let account = SmartAccount::new(owner, Address::from_low_u64_be(2));
+        db.save_smart_account(&account)?;
+        db.save_smart_account(&account)?;
+        db.save_smart_account(&SmartAccount::new(Address::from_low_u64_be(3), Address::from_low_u64_be(4)))?;
+        assert_eq!(db.get_smart_accounts(owner)?, vec![account]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundler_endpoints_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        assert_eq!(db.get_bundler_endpoint(EthChain::EthereumSepolia)?, None);
+
+        db.save_bundler_endpoint(EthChain::EthereumSepolia, "http://localhost:4337")?;
+        assert_eq!(db.get_bundler_endpoint(EthChain::EthereumSepolia)?, Some("http://localhost:4337".to_string()));
+        assert_eq!(db.get_bundler_endpoint(EthChain::EthereumMainnet)?, None);
+
+        // An empty URL clears the setting
+        db.save_bundler_endpoint(EthChain::EthereumSepolia, "")?;
+        assert_eq!(db.get_bundler_endpoint(EthChain::EthereumSepolia)?, None);
+        Ok(())
+    }
+}
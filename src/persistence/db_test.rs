@@ -61,4 +61,31 @@ mod tests {
         assert_eq!(retrieved_data, None);
         Ok(())
     }
+
+    #[test]
+    fn test_verify_password() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        assert!(db.verify_password("12345678").is_err()); // No keypair yet
+
+        let seed_phrase = crate::core::seed_phrase::SeedPhrase::generate(crate::core::seed_phrase::WordCount::Words12)?;
+        db.save_keypair(&crate::core::key_pair::KeyPair::from_seed(seed_phrase.to_seed(""))?)?;
+        assert!(db.verify_password("12345678")?);
+        assert!(!db.verify_password("87654321")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign_password_setting() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        assert!(!db.is_sign_password_required()?);
+        db.save_sign_password_required(true)?;
+        assert!(db.is_sign_password_required()?);
+        db.save_sign_password_required(false)?;
+        assert!(!db.is_sign_password_required()?);
+
+        // A plain value written around the cipher doesn't turn the password off
+        db.upsert(b"sign_password_required", &false, false)?;
+        assert!(db.is_sign_password_required()?);
+        Ok(())
+    }
 }
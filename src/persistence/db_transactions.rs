@@ -1,10 +1,12 @@
-
-use web3::types::{Address, H256};
+use std::collections::HashMap;
+use web3::types::{Address, H256, U256, U64};
 
 use super::db::Db;
-use crate::core::transaction::TransactionResult;
+use crate::core::{eth_chain::EthChain, transaction::TransactionResult, transaction_label::TransactionLabel};
 
 const ETH_TRANSACTIONS: &[u8] = b"tx_eth";
+const SYNC_CHECKPOINTS: &[u8] = b"sync_checkpoint";
+const TRANSACTION_LABELS: &[u8] = b"tx_label";
 
 impl Db {
     pub fn save_transaction(&self, account: Address, transaction: &TransactionResult) -> anyhow::Result<()> {
@@ -13,12 +15,80 @@ impl Db {
         Ok(())
     }
 
+    // Further token transfers of a saved transaction, one record per log
+    pub fn save_transaction_log(&self, account: Address, transaction: &TransactionResult, log_index: U256) -> anyhow::Result<()> {
+        let mut key = transaction_synthetic_id(account, transaction.hash);
+        key.extend_from_slice(&log_index.low_u64().to_be_bytes());
+        self.upsert(&key, transaction, false)
+    }
+
+    pub fn get_transaction(&self, account: Address, tx_hash: H256) -> anyhow::Result<Option<TransactionResult>> {
+        let key = transaction_synthetic_id(account, tx_hash);
+        self.get(&key, false)
+    }
+
     pub fn get_transactions(&self, account: Address, cursor: usize, count: usize ) -> anyhow::Result<Vec<TransactionResult>> {
         let mut prefix = ETH_TRANSACTIONS.to_vec();
         prefix.extend_from_slice(account.as_bytes());
 
         self.scan_prefix(&prefix, cursor, count, false)
     }
+
+    // NOTE: labels are private notes, unlike the records themselves they are encrypted
+    pub fn save_transaction_label(&self, account: Address, tx_hash: H256, label: &TransactionLabel) -> anyhow::Result<()> {
+        let key = transaction_label_id(account, tx_hash);
+        if label.is_empty() {
+            self.remove(&key)?;
+            return Ok(());
+        }
+        self.upsert(&key, label, true)
+    }
+
+    pub fn get_transaction_label(&self, account: Address, tx_hash: H256) -> anyhow::Result<Option<TransactionLabel>> {
+        self.get(&transaction_label_id(account, tx_hash), true)
+    }
+
+    pub fn get_transaction_labels(&self, account: Address) -> anyhow::Result<HashMap<H256, TransactionLabel>> {
+        let mut prefix = TRANSACTION_LABELS.to_vec();
+        prefix.extend_from_slice(account.as_bytes());
+
+        let entries = self.scan_prefix_entries::<TransactionLabel>(&prefix, true)?;
+        Ok(entries.into_iter()
+            .map(|(key, label)| (H256::from_slice(&key[prefix.len()..]), label))
+            .collect())
+    }
+
+    // Records written before the exact amounts hold f64 numbers, they are rewritten once
+    // with the decimals of their token, ETH amounts and fees keep 18 decimals
+    pub fn migrate_transaction_amounts<F>(&self, token_decimals: F) -> anyhow::Result<usize>
+    where F: Fn(&str, EthChain) -> Option<u16> {
+        let mut count = 0;
+        for (key, value) in self.scan_prefix_entries::<serde_json::Value>(ETH_TRANSACTIONS, false)? {
+            if !value["amount"].is_number() && !value["fee"].is_number() {
+                continue;
+            }
+
+            let mut transaction: TransactionResult = serde_json::from_value(value)?;
+            if let Some(decimals) = token_decimals(&transaction.currency, transaction.chain) {
+                // NOTE: rescaling must not drop digits, otherwise the amount stays with 18 decimals
+                if let Some(amount) = transaction.amount.rescale(decimals).filter(|amount| *amount == transaction.amount) {
+                    transaction.amount = amount;
+                }
+            }
+            self.upsert(&key, &transaction, false)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    // Next block to be scanned by the history sync
+    pub fn save_sync_checkpoint(&self, account: Address, chain: EthChain, block_number: U64) -> anyhow::Result<()> {
+        self.upsert(&sync_checkpoint_id(account, chain), &block_number, false)
+    }
+
+    pub fn get_sync_checkpoint(&self, account: Address, chain: EthChain) -> anyhow::Result<Option<U64>> {
+        self.get(&sync_checkpoint_id(account, chain), false)
+    }
 }
 
 fn transaction_synthetic_id(account: Address, tx_hash: H256) -> Vec<u8> {
@@ -27,3 +97,17 @@ fn transaction_synthetic_id(account: Address, tx_hash: H256) -> Vec<u8> {
     key.extend_from_slice(tx_hash.as_bytes());
     key
 }
+
+fn transaction_label_id(account: Address, tx_hash: H256) -> Vec<u8> {
+    let mut key = TRANSACTION_LABELS.to_vec();
+    key.extend_from_slice(account.as_bytes());
+    key.extend_from_slice(tx_hash.as_bytes());
+    key
+}
+
+fn sync_checkpoint_id(account: Address, chain: EthChain) -> Vec<u8> {
+    let mut key = SYNC_CHECKPOINTS.to_vec();
+    key.extend_from_slice(account.as_bytes());
+    key.extend_from_slice(&chain.get_chain_id().to_be_bytes());
+    key
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::core::{eth_chain, transaction};
+    use crate::core::{amount::Amount, eth_chain, transaction, transaction_label::TransactionLabel};
     use super::super::db::Db;
 
     fn create_test_db() -> anyhow::Result<Db> {
@@ -25,11 +25,18 @@ mod tests {
             hash: "0x9f3be51fb7b3f83bc7d4a37d3b5f4bb5d4c82b898e8b5c35c6a7ec5e93371c2d".parse()?,
             from: Some(account),
             to: Some(other),
-            amount: 1.0,
-            fee: 0.01,
+            amount: Amount::parse("1", 18)?,
+            currency: "ETH".to_string(),
+            fee: Amount::parse("0.01", 18)?,
             chain: eth_chain::EthChain::EthereumMainnet,
             block_number: Some(18000000.into()),
             status: transaction::TransactionStatus::Successed,
+            nonce: Some(1.into()),
+            gas_used: Some(21000.into()),
+            confirmations: 12,
+            replaces: None,
+            replaced_by: None,
+            swap: None,
         };
         db.save_transaction(account, &first)?;
 
@@ -37,16 +44,23 @@ mod tests {
             hash: "0xb3c4a8ec44b5d8b9925b4cb1fc65666c66d29c07ac1faac5740b227fdbb6f5ed".parse()?,
             from: Some(other),
             to: Some(account),
-            amount: 2.0,
-            fee: 0.02,
+            amount: Amount::parse("2", 18)?,
+            currency: "DAI".to_string(),
+            fee: Amount::parse("0.02", 18)?,
             chain: eth_chain::EthChain::OptimismMainnet,
             block_number: Some(17500000.into()),
             status: transaction::TransactionStatus::Pending,
+            nonce: None,
+            gas_used: None,
+            confirmations: 0,
+            replaces: None,
+            replaced_by: None,
+            swap: None,
         };
         db.save_transaction(account, &second)?;
 
         // Update the transaction to check synthetic key
-        second.amount = 3.0;
+        second.amount = Amount::parse("3", 18)?;
         db.save_transaction(account, &second)?;
 
         let transactions = db.get_transactions(account, 0, 1)?;
@@ -60,6 +74,109 @@ mod tests {
         let transactions = db.get_transactions(account, 2, 3)?;
         assert_eq!(transactions.len(), 0);
 
+        assert_eq!(db.get_transaction(account, second.hash)?, Some(second.clone()));
+        assert_eq!(db.get_transaction(other, first.hash)?, None);
+
+        // Other transfers of the same transaction are kept next to it
+        let refund = transaction::TransactionResult { amount: Amount::parse("1", 18)?, ..second.clone() };
+        db.save_transaction_log(account, &refund, 7.into())?;
+        assert_eq!(db.get_transactions(account, 0, 10)?, vec![first, second.clone(), refund]);
+        assert_eq!(db.get_transaction(account, second.hash)?, Some(second));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_checkpoints() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+
+        let account = web3::types::Address::from_low_u64_be(12);
+        let other = web3::types::Address::from_low_u64_be(13);
+
+        assert_eq!(db.get_sync_checkpoint(account, eth_chain::EthChain::EthereumMainnet)?, None);
+
+        db.save_sync_checkpoint(account, eth_chain::EthChain::EthereumMainnet, 100.into())?;
+        db.save_sync_checkpoint(account, eth_chain::EthChain::OptimismMainnet, 200.into())?;
+        db.save_sync_checkpoint(account, eth_chain::EthChain::EthereumMainnet, 150.into())?;
+
+        assert_eq!(db.get_sync_checkpoint(account, eth_chain::EthChain::EthereumMainnet)?, Some(150.into()));
+        assert_eq!(db.get_sync_checkpoint(account, eth_chain::EthChain::OptimismMainnet)?, Some(200.into()));
+        assert_eq!(db.get_sync_checkpoint(other, eth_chain::EthChain::EthereumMainnet)?, None);
+
+        // Checkpoints don't show up as transactions
+        assert!(db.get_transactions(account, 0, 10)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_transaction_amounts() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        let account = web3::types::Address::from_low_u64_be(12);
+
+        // Records as they were stored with f64 amounts
+        let legacy = |hash: u64, currency: &str, amount: f64| {
+            let mut key = b"tx_eth".to_vec();
+            key.extend_from_slice(account.as_bytes());
+            key.extend_from_slice(web3::types::H256::from_low_u64_be(hash).as_bytes());
+            let value = serde_json::json!({
+                "hash": web3::types::H256::from_low_u64_be(hash),
+                "block_number": null,
+                "from": account,
+                "to": null,
+                "amount": amount,
+                "currency": currency,
+                "fee": 0.000021,
+                "chain": eth_chain::EthChain::EthereumMainnet,
+                "status": "Successed",
+            });
+            db.upsert(&key, &value, false)
+        };
+        legacy(1, "ETH", 0.1)?;
+        legacy(2, "USDC", 2.5)?;
+        legacy(3, "USDC", 0.0000001)?;
+
+        let token_decimals = |currency: &str, _| (currency == "USDC").then_some(6);
+        assert_eq!(db.migrate_transaction_amounts(token_decimals)?, 3);
+        assert_eq!(db.migrate_transaction_amounts(token_decimals)?, 0);
+
+        let transactions = db.get_transactions(account, 0, 10)?;
+        let amounts = transactions.iter()
+            .map(|transaction| (transaction.amount.to_string(), transaction.amount.decimals, transaction.fee.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(amounts, vec![
+            ("0.1".to_string(), 18, "0.000021".to_string()),
+            ("2.5".to_string(), 6, "0.000021".to_string()),
+            ("0.0000001".to_string(), 18, "0.000021".to_string()),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_transaction_labels_db() -> anyhow::Result<()> {
+        let db = create_test_db()?;
+        let account = web3::types::Address::from_low_u64_be(12);
+        let other = web3::types::Address::from_low_u64_be(13);
+        let first = web3::types::H256::from_low_u64_be(1);
+        let second = web3::types::H256::from_low_u64_be(2);
+
+        let label = TransactionLabel::new("Invoice 42", "work", "Income");
+        db.save_transaction_label(account, first, &label)?;
+        db.save_transaction_label(account, second, &TransactionLabel::new("", "gas", ""))?;
+        db.save_transaction_label(other, first, &TransactionLabel::new("Other account", "", ""))?;
+
+        assert_eq!(db.get_transaction_label(account, first)?, Some(label.clone()));
+        // NOTE: labels are encrypted, unlike the transaction records
+        assert!(db.get_raw_bytes(&[b"tx_label".as_slice(), account.as_bytes(), first.as_bytes()].concat(), false)?
+            .is_some_and(|bytes| serde_json::from_slice::<TransactionLabel>(&bytes).is_err()));
+
+        let labels = db.get_transaction_labels(account)?;
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels.get(&first), Some(&label));
+
+        // Empty labels are removed
+        db.save_transaction_label(account, second, &TransactionLabel::default())?;
+        assert_eq!(db.get_transaction_label(account, second)?, None);
+        assert_eq!(db.get_transaction_labels(account)?.len(), 1);
         Ok(())
     }
 }
@@ -3,7 +3,24 @@ mod cipher_test;
 pub mod db;
 mod db_test;
 pub mod db_accounts;
+pub mod db_allowances;
+mod db_allowances_test;
+pub mod db_address_book;
+mod db_address_book_test;
+pub mod db_bridge;
+mod db_bridge_test;
 pub mod db_chains;
+pub mod db_contracts;
+mod db_contracts_test;
+pub mod db_nfts;
+mod db_nfts_test;
+pub mod db_policy;
+mod db_policy_test;
+pub mod db_safes;
+mod db_safes_test;
+pub mod db_smart_accounts;
+mod db_smart_accounts_test;
+pub mod db_settings;
 pub mod db_transactions;
 mod db_transactions_test;
 pub mod manage;
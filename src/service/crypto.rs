@@ -1,9 +1,9 @@
-use std::{collections::HashMap, sync::Arc};
-use tokio::sync::RwLock;
+use std::{collections::HashMap, sync::{atomic::AtomicBool, Arc}};
+use tokio::sync::{Mutex, RwLock};
 use web3::transports::Http;
 
 use crate::{
-    core::{balance::Balances, eth_chain::EthChain, provider::Provider, token::TokenList},
+    core::{balance::Balances, eth_chain::EthChain, nonce::{NonceGap, NonceManager}, provider::Provider, token::TokenList},
     persistence::db::Db
 };
 
@@ -14,6 +14,11 @@ pub struct Crypto {
     pub token_list: TokenList,
     pub providers: HashMap<EthChain, Provider<Http>>,
     pub account_balances: Arc<RwLock<HashMap<web3::types::Address, Balances>>>,
+    pub transactions_updated: Arc<AtomicBool>,
+    pub nonce_manager: Arc<Mutex<NonceManager>>,
+    pub nonce_gaps: Arc<RwLock<HashMap<web3::types::Address, Vec<NonceGap>>>>,
+    pub history_syncing: Arc<AtomicBool>,
+    pub nft_syncing: Arc<AtomicBool>,
 }
 
 impl Crypto {
@@ -25,6 +30,11 @@ impl Crypto {
             token_list,
             providers: HashMap::new(),
             account_balances: Arc::new(RwLock::new(HashMap::new())),
+            transactions_updated: Arc::new(AtomicBool::new(false)),
+            nonce_manager: Arc::new(Mutex::new(NonceManager::new())),
+            nonce_gaps: Arc::new(RwLock::new(HashMap::new())),
+            history_syncing: Arc::new(AtomicBool::new(false)),
+            nft_syncing: Arc::new(AtomicBool::new(false)),
         }
     }
 
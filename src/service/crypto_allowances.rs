@@ -0,0 +1,77 @@
+use web3::types::Address;
+
+use crate::core::{allowance::{self, Allowance}, erc20, eth_chain::EthChain};
+use super::crypto::Crypto;
+
+// NOTE: keep the range small enough for the endpoint's eth_getLogs limits
+const SYNC_BLOCK_RANGE: u64 = 100_000;
+
+impl Crypto {
+    // Live non-zero allowances the account has granted on all active chains
+    pub async fn fetch_allowances(&self, owner: Address) -> anyhow::Result<Vec<Allowance>> {
+        let mut allowances = Vec::new();
+        for chain in self.get_active_networks() {
+            // NOTE: a failing endpoint doesn't hide the allowances on the other chains
+            match self.fetch_chain_allowances(owner, chain).await {
+                Ok(chain_allowances) => allowances.extend(chain_allowances),
+                Err(err) => log::warn!("Failed to fetch allowances on {}: {}", chain.get_display_name(), err),
+            }
+        }
+        Ok(allowances)
+    }
+
+    async fn fetch_chain_allowances(&self, owner: Address, chain: EthChain) -> anyhow::Result<Vec<Allowance>> {
+        let provider = self.providers.get(&chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", chain)))?;
+
+        let tokens = self.token_list.iter()
+            .filter_map(|token| token.get_chain_data(&chain).map(|data| (token.symbol.clone(), data.contract_address, data.decimals)))
+            .collect::<Vec<_>>();
+        if tokens.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Approvals are scanned from the last checkpoint, the pairs found before are kept
+        let latest_block = provider.get_block_number().await?;
+        let contract_addresses = tokens.iter().map(|(_, contract_address, _)| *contract_address).collect::<Vec<_>>();
+        let mut pairs = self.db.get_approved_pairs(owner, chain)?;
+        let mut from_block = self.db.get_approval_checkpoint(owner, chain)?.unwrap_or_default();
+
+        while from_block <= latest_block {
+            let to_block = latest_block.min(from_block + SYNC_BLOCK_RANGE - 1);
+            let approvals = provider.get_approval_logs(contract_addresses.clone(), owner, from_block, to_block).await?
+                .iter()
+                .filter_map(|log| erc20::decode_approval_log(log).ok())
+                .collect::<Vec<_>>();
+            for pair in allowance::approved_pairs(&approvals) {
+                if !pairs.contains(&pair) {
+                    pairs.push(pair);
+                }
+            }
+
+            from_block = to_block + 1;
+            self.db.save_approved_pairs(owner, chain, &pairs)?;
+            self.db.save_approval_checkpoint(owner, chain, from_block)?;
+        }
+
+        let mut allowances = Vec::new();
+        for (contract_address, spender) in pairs {
+            let Some((currency, _, decimals)) = tokens.iter().find(|(_, address, _)| *address == contract_address) else {
+                continue;
+            };
+            let amount = provider.get_allowance(contract_address, owner, spender).await?;
+            if amount.is_zero() {
+                continue;
+            }
+            allowances.push(Allowance {
+                chain,
+                currency: currency.clone(),
+                contract_address,
+                spender,
+                amount,
+                decimals: *decimals,
+            });
+        }
+        Ok(allowances)
+    }
+}
@@ -1,6 +1,8 @@
 use crate::core::{balance::{Balance, Balances}, eth_chain::EthChain};
 use super::crypto::Crypto;
 
+const ETH: &str = "ETH";
+
 const BALANCES_FETCH_PROVIDER_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
 
 impl Crypto {
@@ -9,6 +11,51 @@ impl Crypto {
         provider.get_eth_usd_rate().await
     }
 
+    // Tokens are priced by their Chainlink feed on the chain, the others have no USD price
+    pub async fn get_currency_usd_rate(&self, chain: EthChain, currency: &str) -> Option<f64> {
+        if currency == ETH {
+            return self.get_eth_usd_rate(chain).await.ok();
+        }
+        let feed = self.get_price_feed(chain, currency)?;
+        let provider = self.providers.get(&chain)?;
+        match provider.get_usd_rate(feed).await {
+            Ok(rate) => Some(rate),
+            Err(err) => {
+                log::warn!("Failed to get price of {} on {}: {}", currency, chain, err);
+                None
+            },
+        }
+    }
+
+    // Same as above, with the rate at the timestamp
+    pub async fn get_currency_usd_rate_at(&self, chain: EthChain, currency: &str, timestamp: i64) -> Option<f64> {
+        let provider = self.providers.get(&chain)?;
+        let rate = if currency == ETH {
+            provider.get_eth_usd_rate_at(timestamp as u64).await
+        } else {
+            provider.get_usd_rate_at(self.get_price_feed(chain, currency)?, timestamp as u64).await
+        };
+        match rate {
+            Ok(rate) => Some(rate),
+            Err(err) => {
+                log::warn!("Failed to get price of {} at {} on {}: {}", currency, timestamp, chain, err);
+                None
+            },
+        }
+    }
+
+    fn get_price_feed(&self, chain: EthChain, currency: &str) -> Option<web3::types::Address> {
+        self.token_list.iter()
+            .find(|token| token.symbol == currency)
+            .and_then(|token| token.get_chain_data(&chain))
+            .and_then(|data| data.price_feed)
+    }
+
+    pub fn has_price_feed(&self, currency: &str) -> bool {
+        currency == ETH || self.token_list.iter()
+            .any(|token| token.symbol == currency && token.chain_data.values().any(|data| data.price_feed.is_some()))
+    }
+
     pub async fn get_balances(&self, account: web3::types::Address) -> Option<Balances> {
         let balances = self.account_balances.read().await;
         if let Some(balance) = balances.get(&account) {
@@ -17,6 +64,21 @@ impl Crypto {
         None
     }
 
+    pub async fn get_chain_currencies(&self, account: web3::types::Address, chain: EthChain) -> Vec<String> {
+        let mut currencies = vec![ETH.to_string()];
+        if let Some(balances) = self.get_balances(account).await {
+            for balance in balances {
+                if balance.currency == ETH {
+                    continue;
+                }
+                if balance.chain_values.get(&chain).is_some_and(|value| !value.value.is_zero()) {
+                    currencies.push(balance.currency);
+                }
+            }
+        }
+        currencies
+    }
+
     pub async fn fetch_balances(&self, accounts: Vec<web3::types::Address>) {
         let account_balances = self.account_balances.clone();
         let providers = self.providers.clone();
@@ -0,0 +1,72 @@
+use web3::{signing::SecretKey, types::{Address, U256}};
+
+use crate::core::{balance::{Balance, Balances}, batch::{self, BatchEstimate, BatchPayment, BatchResult}, eth_chain::EthChain, fees::FeeTier, transaction::TransactionFees};
+use super::crypto::Crypto;
+
+impl Crypto {
+    // Per-payment fees and a check that the balances cover all payments with their fees
+    pub async fn estimate_batch(&self, from: Address, payments: &[BatchPayment]) -> BatchEstimate {
+        let mut fees = Vec::new();
+        let mut chain_fees: Vec<(EthChain, U256)> = Vec::new();
+        for payment in payments {
+            let payment_fees = self.estimate_transaction_fees(payment.to_request(from), FeeTier::Normal).await
+                .unwrap_or_else(|err| TransactionFees::RpcFailure { error: err.to_string() });
+            if let TransactionFees::Estimated { fees, .. } = &payment_fees {
+                match chain_fees.iter_mut().find(|(chain, _)| *chain == payment.chain) {
+                    Some((_, total)) => *total += fees.max_cost(),
+                    None => chain_fees.push((payment.chain, fees.max_cost())),
+                }
+            }
+            fees.push(payment_fees);
+        }
+
+        let totals = batch::totals(payments);
+        let mut shortfalls = Vec::new();
+        let mut balances: Balances = Vec::new();
+        let mut chains: Vec<EthChain> = Vec::new();
+        for total in &totals {
+            if !chains.contains(&total.chain) {
+                chains.push(total.chain);
+            }
+        }
+        for chain in chains {
+            match self.fetch_chain_balances(from, chain).await {
+                Ok(chain_balances) => balances = Balance::extend_balances(balances, &chain_balances),
+                Err(err) => shortfalls.push(format!("Failed to fetch balances on {}: {}", chain.get_display_name(), err)),
+            }
+        }
+        shortfalls.extend(batch::find_shortfalls(&totals, &chain_fees, &balances));
+
+        BatchEstimate { fees, chain_fees, shortfalls }
+    }
+
+    // NOTE: payments are sent one by one, so the nonce manager hands out sequential nonces
+    pub async fn send_batch(&self, from: Address, payments: &[BatchPayment], estimate: &BatchEstimate, secret_key: &SecretKey) -> Vec<BatchResult> {
+        let mut results = Vec::new();
+        for (payment, payment_fees) in payments.iter().zip(estimate.fees.iter()) {
+            let mut request = payment.to_request(from);
+            if let TransactionFees::Estimated { fees, .. } = payment_fees {
+                request.fees = Some(*fees);
+            }
+
+            let result = match self.send_transaction(request, secret_key).await {
+                Ok(transaction) => Ok(transaction.hash),
+                Err(err) => {
+                    log::warn!("Batch payment in row {} failed: {}", payment.row, err);
+                    Err(err.to_string())
+                }
+            };
+            results.push(BatchResult { payment: payment.clone(), result });
+        }
+        results
+    }
+
+    async fn fetch_chain_balances(&self, account: Address, chain: EthChain) -> anyhow::Result<Balances> {
+        let provider = self.providers.get(&chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", chain)))?;
+
+        let eth_balance = provider.get_eth_balance(account).await?;
+        let token_balances = provider.get_token_balances(account, &self.token_list).await?;
+        Ok(Balance::extend_balances(vec![eth_balance], &token_balances))
+    }
+}
@@ -0,0 +1,90 @@
+use std::{collections::HashMap, sync::atomic::Ordering};
+use web3::{transports::Http, types::{Address, H256, U64}};
+
+use crate::core::{
+    amount::Amount, bridge::{self, BridgeContracts, Withdrawal, WithdrawalStatus},
+    eth_chain::EthChain, provider::Provider
+};
+use super::crypto::Crypto;
+
+impl Crypto {
+    // Called once the withdrawal transaction is sent, the message is picked up from its receipt
+    pub fn record_withdrawal(&self, account: Address, chain: EthChain, tx_hash: H256, amount: Amount) -> anyhow::Result<()> {
+        let withdrawal = Withdrawal::new(tx_hash, chain, account, amount, chrono::Utc::now().timestamp());
+        self.db.save_withdrawal(&withdrawal)?;
+        self.transactions_updated.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn get_withdrawals(&self, account: Address) -> anyhow::Result<Vec<Withdrawal>> {
+        self.db.get_withdrawals(account)
+    }
+
+    pub async fn track_withdrawals(&self, account: Address) {
+        let db = self.db.clone();
+        let providers = self.providers.clone();
+        let transactions_updated = self.transactions_updated.clone();
+
+        tokio::spawn(async move {
+            let withdrawals = match db.get_withdrawals(account) {
+                Ok(withdrawals) => withdrawals,
+                Err(err) => {
+                    log::error!("Failed to load withdrawals for tracking: {}", err);
+                    return;
+                }
+            };
+
+            let now = chrono::Utc::now().timestamp();
+            for mut withdrawal in withdrawals.into_iter().filter(|withdrawal| withdrawal.is_tracked()) {
+                match track_withdrawal(&providers, &mut withdrawal, now).await {
+                    Ok(false) => {},
+                    Ok(true) => {
+                        if let Err(err) = db.save_withdrawal(&withdrawal) {
+                            log::error!("Failed to save tracked withdrawal {:?}: {}", withdrawal.tx_hash, err);
+                            continue;
+                        }
+                        transactions_updated.store(true, Ordering::Relaxed);
+                    },
+                    Err(err) => log::warn!("Failed to track withdrawal {:?}: {}", withdrawal.tx_hash, err),
+                }
+            }
+        });
+    }
+}
+
+// Returns true if the withdrawal has changed
+// NOTE: both the L2 and its L1 have to be active networks to follow the whole withdrawal
+async fn track_withdrawal(providers: &HashMap<EthChain, Provider<Http>>, withdrawal: &mut Withdrawal, now: i64) -> anyhow::Result<bool> {
+    let mut changed = false;
+
+    if withdrawal.message.is_none() {
+        let Some(l2_provider) = providers.get(&withdrawal.chain) else {
+            return Ok(false);
+        };
+        let Some(receipt) = l2_provider.get_transaction_receipt(withdrawal.tx_hash).await? else {
+            return Ok(false);
+        };
+
+        if receipt.status == Some(U64::zero()) {
+            withdrawal.status = WithdrawalStatus::Failed;
+            return Ok(true);
+        }
+        withdrawal.l2_block = receipt.block_number;
+        withdrawal.message = bridge::decode_withdrawal_message(withdrawal.chain, &receipt.logs)?;
+        if withdrawal.message.is_none() {
+            log::warn!("Withdrawal {:?} has no L2 to L1 message", withdrawal.tx_hash);
+            withdrawal.status = WithdrawalStatus::Failed;
+        }
+        changed = true;
+    }
+    if withdrawal.status == WithdrawalStatus::Failed {
+        return Ok(changed);
+    }
+
+    let l1_chain = BridgeContracts::for_chain(withdrawal.chain).map(|contracts| contracts.l1_chain);
+    let Some(l1_provider) = l1_chain.and_then(|chain| providers.get(&chain)) else {
+        return Ok(changed);
+    };
+    let progress = l1_provider.get_withdrawal_progress(withdrawal).await?;
+    Ok(withdrawal.apply_progress(&progress, now) || changed)
+}
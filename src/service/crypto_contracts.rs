@@ -0,0 +1,12 @@
+use web3::types::Address;
+
+use crate::core::eth_chain::EthChain;
+use super::crypto::Crypto;
+
+impl Crypto {
+    pub async fn call_contract(&self, chain: EthChain, from: Address, contract_address: Address, data: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        let provider = self.providers.get(&chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", chain)))?;
+        provider.call_contract(from, contract_address, data).await
+    }
+}
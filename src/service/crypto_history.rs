@@ -0,0 +1,210 @@
+use std::{collections::HashMap, sync::atomic::Ordering};
+use web3::{transports::Http, types::{Address, H256, U256, U64}};
+
+use crate::core::{
+    amount::{Amount, ETH_DECIMALS}, erc20, eth_chain::EthChain, provider::Provider, token::TokenList,
+    history_export::{self, ExportFormat, HistoryFilter, HistoryRecord},
+    transaction::{TransactionResult, TransactionStatus}
+};
+use crate::persistence::db::Db;
+use super::crypto::Crypto;
+
+// NOTE: endpoints limit the blocks or the results of eth_getLogs, rejected ranges are halved down to the minimum
+const SYNC_BLOCK_RANGE_MAX: u64 = 1_000_000;
+const SYNC_BLOCK_RANGE_MIN: u64 = 1_000;
+const SYNC_REQUESTS_PER_PASS: usize = 20;
+
+impl Crypto {
+    // Scan token transfers from the last checkpoint, so incoming transfers show up in the history
+    pub async fn sync_history(&self, account: Address) {
+        if self.history_syncing.swap(true, Ordering::Relaxed) {
+            return; // Previous pass is still running
+        }
+
+        let db = self.db.clone();
+        let providers = self.providers.clone();
+        let token_list = self.token_list.clone();
+        let transactions_updated = self.transactions_updated.clone();
+        let history_syncing = self.history_syncing.clone();
+
+        tokio::spawn(async move {
+            for (chain, provider) in &providers {
+                match sync_chain_history(&db, provider, &token_list, account).await {
+                    Ok(0) => {},
+                    Ok(_) => transactions_updated.store(true, Ordering::Relaxed),
+                    Err(err) => log::warn!("Failed to sync history on {}: {}", chain, err),
+                }
+            }
+            history_syncing.store(false, Ordering::Relaxed);
+        });
+    }
+
+    // Mined transactions within the filter, oldest first, valued with the rates at their block
+    // Returns the number of exported transactions
+    pub async fn export_history(&self, account: Address, filter: &HistoryFilter, path: &std::path::Path) -> anyhow::Result<usize> {
+        let labels = self.db.get_transaction_labels(account)?;
+        let transactions = self.db.get_transactions(account, 0, usize::MAX)?;
+        let mut blocks: HashMap<(EthChain, U64), (i64, Option<f64>)> = HashMap::new();
+        let mut token_rates: HashMap<(EthChain, U64, String), Option<f64>> = HashMap::new();
+        let mut records = Vec::new();
+
+        for transaction in transactions.iter().filter(|transaction| filter.includes_chain(transaction.chain)) {
+            let Some(block_number) = transaction.block_number else {
+                continue;
+            };
+            if !matches!(transaction.status, TransactionStatus::Successed | TransactionStatus::Failed) {
+                continue;
+            }
+
+            let (timestamp, eth_usd_rate) = match blocks.get(&(transaction.chain, block_number)) {
+                Some(block) => *block,
+                None => {
+                    let provider = self.providers.get(&transaction.chain).ok_or_else(||
+                        anyhow::anyhow!(format!("No provider for chain {}", transaction.chain)))?;
+                    let timestamp = provider.get_block_timestamp(block_number).await? as i64;
+                    // NOTE: without a rate the fiat columns stay empty, the rest of the history is still useful
+                    let eth_usd_rate = if filter.includes_time(timestamp) {
+                        provider.get_eth_usd_rate_at(timestamp as u64).await
+                            .inspect_err(|err| log::warn!("Failed to get ETH rate at block {} on {}: {}", block_number, transaction.chain, err))
+                            .ok()
+                    } else {
+                        None
+                    };
+                    blocks.insert((transaction.chain, block_number), (timestamp, eth_usd_rate));
+                    (timestamp, eth_usd_rate)
+                }
+            };
+            if !filter.includes_time(timestamp) {
+                continue;
+            }
+            let currency_usd_rate = if history_export::is_eth_priced(&transaction.currency) {
+                eth_usd_rate
+            } else {
+                let key = (transaction.chain, block_number, transaction.currency.clone());
+                match token_rates.get(&key) {
+                    Some(rate) => *rate,
+                    None => {
+                        let rate = self.get_currency_usd_rate_at(transaction.chain, &transaction.currency, timestamp).await;
+                        token_rates.insert(key, rate);
+                        rate
+                    }
+                }
+            };
+            records.push(HistoryRecord::new(account, transaction, timestamp, eth_usd_rate, currency_usd_rate, labels.get(&transaction.hash)));
+        }
+        records.sort_by_key(|record| record.timestamp);
+
+        let text = match ExportFormat::from_path(path) {
+            ExportFormat::Csv => history_export::to_csv(&records),
+            ExportFormat::Json => history_export::to_json(&records)?,
+        };
+        std::fs::write(path, text)?;
+        Ok(records.len())
+    }
+}
+
+// Returns the number of new transactions
+async fn sync_chain_history(db: &Db, provider: &Provider<Http>, token_list: &TokenList, account: Address) -> anyhow::Result<usize> {
+    let chain = provider.chain;
+    let contract_addresses = token_list.iter()
+        .filter_map(|token| token.get_chain_data(&chain).map(|data| data.contract_address))
+        .collect::<Vec<_>>();
+    if contract_addresses.is_empty() {
+        return Ok(0);
+    }
+
+    let latest_block = provider.get_block_number().await?;
+    let mut from_block = match db.get_sync_checkpoint(account, chain)? {
+        Some(checkpoint) => checkpoint,
+        None => chain.get_history_start_block().into(),
+    };
+    let mut block_range = SYNC_BLOCK_RANGE_MAX;
+    let mut count = 0;
+
+    for _ in 0..SYNC_REQUESTS_PER_PASS {
+        if from_block > latest_block {
+            break;
+        }
+        let to_block = latest_block.min(from_block + block_range - 1);
+
+        let logs = match provider.get_transfer_logs(contract_addresses.clone(), account, from_block, to_block).await {
+            Ok(logs) => logs,
+            Err(err) if block_range > SYNC_BLOCK_RANGE_MIN => {
+                log::debug!("Splitting blocks {}-{} on {}: {}", from_block, to_block, chain, err);
+                block_range /= 2;
+                continue;
+            },
+            Err(err) => return Err(err),
+        };
+
+        // NOTE: a transaction can move several tokens, its transfers are grouped by the transaction hash
+        let mut transactions: Vec<(H256, Vec<(U256, TransactionResult)>)> = Vec::new();
+        for log in logs {
+            let transfer = match erc20::decode_transfer_log(&log) {
+                Ok(transfer) => transfer,
+                Err(err) => {
+                    log::warn!("Skipping transfer log on {}: {}", chain, err);
+                    continue;
+                }
+            };
+            let Some(transaction) = to_transaction_result(&transfer, token_list, chain, latest_block) else {
+                continue;
+            };
+            let log_index = log.log_index.unwrap_or_default();
+            match transactions.iter_mut().find(|(hash, _)| *hash == transaction.hash) {
+                Some((_, transfers)) => transfers.push((log_index, transaction)),
+                None => transactions.push((transaction.hash, vec![(log_index, transaction)])),
+            }
+        }
+
+        for (hash, transfers) in transactions {
+            // NOTE: locally sent transactions have more details, and a transaction's logs are all in one block range
+            if db.get_transaction(account, hash)?.is_some() {
+                continue;
+            }
+            // The first transfer is the transaction record, saved last so an interrupted pass redoes the others
+            let Some(((_, first), others)) = transfers.split_first() else {
+                continue;
+            };
+            for (log_index, transaction) in others {
+                db.save_transaction_log(account, transaction, *log_index)?;
+            }
+            db.save_transaction(account, first)?;
+            count += transfers.len();
+        }
+
+        from_block = to_block + 1;
+        db.save_sync_checkpoint(account, chain, from_block)?;
+        block_range = (block_range * 2).min(SYNC_BLOCK_RANGE_MAX);
+    }
+
+    Ok(count)
+}
+
+fn to_transaction_result(transfer: &erc20::TokenTransfer, token_list: &TokenList, chain: EthChain, latest_block: U64) -> Option<TransactionResult> {
+    let (token, token_chain_data) = token_list.iter().find_map(|token| {
+        token.get_chain_data(&chain)
+            .filter(|data| data.contract_address == transfer.contract_address)
+            .map(|data| (token, data))
+    })?;
+
+    Some(TransactionResult {
+        hash: transfer.transaction_hash?,
+        block_number: transfer.block_number,
+        from: Some(transfer.from),
+        to: Some(transfer.to),
+        amount: Amount::new(transfer.value, token_chain_data.decimals),
+        currency: token.symbol.clone(),
+        fee: Amount::zero(ETH_DECIMALS),
+        chain,
+        status: TransactionStatus::Successed,
+        nonce: None,
+        gas_used: None,
+        confirmations: transfer.block_number.map_or(0, |block_number| {
+            latest_block.saturating_sub(block_number).as_u64() + 1
+        }),
+        replaces: None,
+        replaced_by: None,
+        swap: None,
+    })
+}
@@ -0,0 +1,103 @@
+use std::sync::atomic::Ordering;
+use web3::{transports::Http, types::{Address, U256}};
+
+use crate::core::{nft::{self, NftHolding}, provider::Provider};
+use crate::persistence::db::Db;
+use super::crypto::Crypto;
+
+// NOTE: same limits as the token history sync
+const SYNC_BLOCK_RANGE: u64 = 100_000;
+const SYNC_RANGES_PER_PASS: usize = 10;
+
+impl Crypto {
+    // Rebuild NFT holdings from transfer logs since the last checkpoint
+    pub async fn sync_nfts(&self, account: Address) {
+        if self.nft_syncing.swap(true, Ordering::Relaxed) {
+            return; // Previous pass is still running
+        }
+
+        let db = self.db.clone();
+        let providers = self.providers.clone();
+        let transactions_updated = self.transactions_updated.clone();
+        let nft_syncing = self.nft_syncing.clone();
+
+        tokio::spawn(async move {
+            for (chain, provider) in &providers {
+                match sync_chain_nfts(&db, provider, account).await {
+                    Ok(0) => {},
+                    Ok(_) => transactions_updated.store(true, Ordering::Relaxed),
+                    Err(err) => log::warn!("Failed to sync NFTs on {}: {}", chain, err),
+                }
+            }
+            nft_syncing.store(false, Ordering::Relaxed);
+        });
+    }
+
+    pub fn get_nfts(&self, account: Address) -> anyhow::Result<Vec<NftHolding>> {
+        let active_networks = self.get_active_networks();
+        Ok(self.db.get_nfts(account)?.into_iter()
+            .filter(|holding| active_networks.contains(&holding.chain))
+            .collect())
+    }
+}
+
+// Returns the number of changed holdings
+async fn sync_chain_nfts(db: &Db, provider: &Provider<Http>, account: Address) -> anyhow::Result<usize> {
+    let chain = provider.chain;
+    let latest_block = provider.get_block_number().await?;
+    let mut from_block = db.get_nft_checkpoint(account, chain)?.unwrap_or_default();
+    let mut count = 0;
+
+    for _ in 0..SYNC_RANGES_PER_PASS {
+        if from_block > latest_block {
+            break;
+        }
+        let to_block = latest_block.min(from_block + SYNC_BLOCK_RANGE - 1);
+
+        // NOTE: logs come from several filters, so restore the chain order before applying them
+        let mut logs = provider.get_nft_transfer_logs(account, from_block, to_block).await?;
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        for log in logs {
+            let transfers = match nft::decode_transfer_log(&log) {
+                Ok(transfers) => transfers,
+                Err(err) => {
+                    log::warn!("Skipping NFT transfer log on {}: {}", chain, err);
+                    continue;
+                }
+            };
+
+            for transfer in transfers {
+                let mut holding = match db.get_nft(account, chain, transfer.contract_address, transfer.token_id)? {
+                    Some(holding) => holding,
+                    None => {
+                        let (collection, metadata_uri) = provider.get_nft_metadata(
+                            transfer.standard, transfer.contract_address, transfer.token_id).await?;
+                        NftHolding {
+                            chain,
+                            standard: transfer.standard,
+                            contract_address: transfer.contract_address,
+                            collection,
+                            token_id: transfer.token_id,
+                            amount: U256::zero(),
+                            metadata_uri,
+                        }
+                    }
+                };
+
+                holding.apply_transfer(account, &transfer);
+                if holding.amount.is_zero() {
+                    db.remove_nft(account, &holding)?;
+                } else {
+                    db.save_nft(account, &holding)?;
+                }
+                count += 1;
+            }
+        }
+
+        from_block = to_block + 1;
+        db.save_nft_checkpoint(account, chain, from_block)?;
+    }
+
+    Ok(count)
+}
@@ -0,0 +1,105 @@
+use std::sync::atomic::Ordering;
+use web3::{signing::SecretKey, transports::Http, types::TransactionParameters};
+
+use crate::core::{
+    fees::FeeTier,
+    provider::Provider,
+    review::TransactionReview,
+    transaction::{TransactionRequest, TransactionResult},
+    unsigned_transaction::{RawSignedTransaction, UnsignedTransaction}
+};
+use super::{crypto::Crypto, crypto_transactions::to_transaction_result_impl};
+
+const ERR_NO_TRANSACTION_FOUND: &str = "No transaction found";
+const ERR_PAYLOAD_MISMATCH: &str = "Transaction payload doesn't match the described transfer";
+const ERR_HASH_MISMATCH: &str = "Broadcasted transaction hash doesn't match the signed one";
+
+impl Crypto {
+    // Online, watch-only side: fill everything the offline vault can't look up
+    pub async fn prepare_unsigned_transaction(&self, request: TransactionRequest) -> anyhow::Result<UnsignedTransaction> {
+        let provider = self.providers.get(&request.chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", request.chain)))?;
+
+        let transaction = self.build_transaction_parameters(&request)?;
+        let fees = match request.fees {
+            Some(fees) => fees,
+            None => provider.estimate_transaction_fees(transaction.clone(), request.from, FeeTier::Normal).await?.into_estimated()?,
+        };
+
+        // NOTE: the nonce isn't taken, because the transaction may never be signed
+        let pending_nonce = provider.get_pending_transaction_count(request.from).await?;
+        let nonce = self.nonce_manager.lock().await.reconcile(request.from, request.chain, pending_nonce);
+
+        let transaction = TransactionParameters { nonce: Some(nonce), ..transaction };
+        Ok(UnsignedTransaction::new(transaction, request.chain, request.from, fees)
+            .with_transfer(request.to, request.amount, &request.currency))
+    }
+
+    // Offline vault side, the imported transaction is reviewed like any other before signing
+    // NOTE: without a connection the review just has no USD prices
+    pub async fn prepare_offline_review(&self, transaction: UnsignedTransaction) -> anyhow::Result<TransactionReview> {
+        self.verify_unsigned_payload(&transaction)?;
+        // NOTE: policy violations are shown before the user goes through the details
+        self.check_spending_policy(transaction.from, transaction.chain, transaction.to, transaction.value, &transaction.data.0).await?;
+        self.review_transaction(transaction).await
+    }
+
+    // Offline vault side, no requests are sent
+    pub async fn sign_unsigned_transaction(&self, transaction: &UnsignedTransaction, secret_key: &SecretKey) -> anyhow::Result<RawSignedTransaction> {
+        self.verify_unsigned_payload(transaction)?;
+        let spend = self.check_spending_policy(transaction.from, transaction.chain, transaction.to, transaction.value, &transaction.data.0).await?;
+
+        let provider = match self.providers.get(&transaction.chain) {
+            Some(provider) => provider.clone(),
+            None => {
+                // NOTE: the transport is never used, the vault may have this network disabled
+                let transport = Http::new(&transaction.chain.finalize_endpoint_url(&self.endpoint_url))?;
+                Provider::new(transport, transaction.chain)?
+            }
+        };
+        let signed = transaction.sign(provider.web3.accounts(), secret_key).await?;
+        self.record_spend(signed.hash, &spend);
+        Ok(signed)
+    }
+
+    // The described transfer must be exactly what the payload does
+    fn verify_unsigned_payload(&self, transaction: &UnsignedTransaction) -> anyhow::Result<()> {
+        let expected = self.build_transaction_parameters(&TransactionRequest {
+            from: transaction.from,
+            to: transaction.recipient,
+            amount: transaction.amount,
+            currency: transaction.currency.clone(),
+            chain: transaction.chain,
+            fees: None,
+            data: None,
+        })?;
+        if expected.to != Some(transaction.to) || expected.value != transaction.value || expected.data != transaction.data {
+            return Err(anyhow::anyhow!(ERR_PAYLOAD_MISMATCH));
+        }
+        Ok(())
+    }
+
+    // Online side again: broadcast what the vault signed and track it as usual
+    pub async fn broadcast_signed_transaction(&self, transaction: &RawSignedTransaction) -> anyhow::Result<TransactionResult> {
+        let provider = self.providers.get(&transaction.chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", transaction.chain)))?;
+
+        let tx_hash = provider.send_raw_transaction(transaction.raw_transaction.clone()).await?;
+        if tx_hash != transaction.hash {
+            return Err(anyhow::anyhow!(ERR_HASH_MISMATCH));
+        }
+        let tx = provider.get_transaction(tx_hash).await?
+            .ok_or_else(|| anyhow::anyhow!(ERR_NO_TRANSACTION_FOUND))?;
+
+        let result = TransactionResult {
+            to: Some(transaction.recipient),
+            amount: transaction.amount,
+            currency: transaction.currency.clone(),
+            ..to_transaction_result_impl(&tx, transaction.chain)
+        };
+        self.db.save_transaction(transaction.from, &result)?;
+        self.transactions_updated.store(true, Ordering::Relaxed);
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,117 @@
+use web3::types::{Address, H256, U256};
+
+use crate::core::{
+    eth_chain::EthChain,
+    policy::{self, LimitUnit, PolicySpend, PolicyTransfer, SpendingPolicy, DAILY_WINDOW_SECONDS},
+    transaction::TransactionStatus
+};
+use super::crypto::Crypto;
+
+const ERR_WRONG_PASSWORD_PROVIDED: &str = "Wrong password provided";
+
+impl Crypto {
+    pub fn get_spending_policy(&self) -> anyhow::Result<SpendingPolicy> {
+        Ok(self.db.get_spending_policy()?.unwrap_or_default())
+    }
+
+    // NOTE: the password is checked here, so no caller can change the policy without it
+    pub fn save_spending_policy(&self, policy: &SpendingPolicy, password: &str) -> anyhow::Result<()> {
+        if !self.db.verify_password(password)? {
+            return Err(anyhow::anyhow!(ERR_WRONG_PASSWORD_PROVIDED));
+        }
+        policy.validate()?;
+        // NOTE: USD limits of tokens without a price feed could never be checked
+        if let Some(limit) = policy.limits.iter().find(|limit| limit.unit == LimitUnit::Usd && !self.has_price_feed(&limit.currency)) {
+            return Err(anyhow::anyhow!("{} has no USD price feed, limit it in tokens instead", limit.currency));
+        }
+
+        // Allowlisted recipients are new from now on, unless they are known already
+        let now = chrono::Utc::now().timestamp();
+        for recipient in &policy.recipients {
+            if self.db.get_recipient_first_seen(*recipient)?.is_none() {
+                self.db.save_recipient_first_seen(*recipient, now)?;
+            }
+        }
+        self.db.save_spending_policy(policy)
+    }
+
+    // Fails with the violated rule, the returned spend is recorded once the transaction is sent
+    // NOTE: checks what actually gets signed, so token calls are limited by the tokens they move
+    pub async fn check_spending_policy(&self, from: Address, chain: EthChain, to: Address, value: U256, data: &[u8]) -> anyhow::Result<PolicySpend> {
+        self.check_policy(from, chain, to, value, data, None).await
+    }
+
+    // Same as above, but the original transaction's spend is replaced rather than added to
+    pub async fn check_replacement_policy(&self, from: Address, chain: EthChain, to: Address, value: U256, data: &[u8], original: H256) -> anyhow::Result<PolicySpend> {
+        self.check_policy(from, chain, to, value, data, Some(original)).await
+    }
+
+    async fn check_policy(&self, from: Address, chain: EthChain, to: Address, value: U256, data: &[u8], replaces: Option<H256>) -> anyhow::Result<PolicySpend> {
+        let mut policy = self.get_spending_policy()?;
+        let now = chrono::Utc::now().timestamp();
+
+        let transfer = self.decode_transfer(chain, to, value, data);
+        let usd_value = if policy.has_usd_limits(&transfer.currency) {
+            self.get_currency_usd_rate(chain, &transfer.currency).await.map(|rate| transfer.amount.to_f64() * rate)
+        } else {
+            None
+        };
+        let spend = PolicySpend { timestamp: now, chain, currency: transfer.currency, amount: transfer.amount, usd_value };
+
+        // NOTE: transfers to the own account, like cancellations, don't leave the vault
+        if transfer.recipient == from {
+            policy.recipients.clear();
+            policy.cooling_off_hours = 0;
+        }
+
+        let first_seen = if policy.cooling_off_hours > 0 {
+            self.recipient_first_seen(from, transfer.recipient, now)?
+        } else {
+            None
+        };
+        let history = self.db.get_policy_spends(now - DAILY_WINDOW_SECONDS)?.into_iter()
+            .filter(|(tx_hash, _)| Some(*tx_hash) != replaces)
+            .map(|(_, spend)| spend)
+            .collect::<Vec<_>>();
+        policy.check(&spend, transfer.recipient, &history, first_seen)?;
+        Ok(spend)
+    }
+
+    // What the call pays and to whom, with listed tokens resolved to their symbol
+    pub fn decode_transfer(&self, chain: EthChain, to: Address, value: U256, data: &[u8]) -> PolicyTransfer {
+        policy::decode_transfer(to, value, data, |contract| self.token_list.iter()
+            .find_map(|token| token.get_chain_data(&chain)
+                .filter(|data| data.contract_address == contract)
+                .map(|data| (token.symbol.clone(), data.decimals))))
+    }
+
+    pub fn record_spend(&self, tx_hash: H256, spend: &PolicySpend) {
+        if let Err(err) = self.db.save_policy_spend(tx_hash, spend) {
+            log::error!("Failed to record spend of {}: {:?}", tx_hash, err);
+        }
+    }
+
+    pub fn record_replacement_spend(&self, original: H256, tx_hash: H256, spend: &PolicySpend) {
+        if let Err(err) = self.db.remove_policy_spend(original) {
+            log::error!("Failed to remove spend of {}: {:?}", original, err);
+        }
+        self.record_spend(tx_hash, spend);
+    }
+
+    // Recipients already paid before are known, others are remembered on the first attempt
+    fn recipient_first_seen(&self, account: Address, recipient: Address, now: i64) -> anyhow::Result<Option<i64>> {
+        let paid = self.db.get_transactions(account, 0, usize::MAX)?.iter().any(|transaction|
+            transaction.from == Some(account) && transaction.to == Some(recipient) && transaction.status == TransactionStatus::Successed);
+        if paid {
+            return Ok(None);
+        }
+
+        match self.db.get_recipient_first_seen(recipient)? {
+            Some(first_seen) => Ok(Some(first_seen)),
+            None => {
+                self.db.save_recipient_first_seen(recipient, now)?;
+                Ok(Some(now))
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use web3::{signing::SecretKey, types::Address};
+
+use crate::core::{
+    contract, nonce::NonceGap, review::TransactionReview,
+    transaction::{TransactionReplacement, TransactionRequest, TransactionResult},
+    unsigned_transaction::UnsignedTransaction
+};
+use super::crypto::Crypto;
+
+const ERR_NONCE_CHANGED: &str = "Another transaction was sent since the review, please review again";
+
+impl Crypto {
+    // Fill the transaction completely, so the review shows exactly what gets signed
+    pub async fn prepare_review(&self, request: TransactionRequest) -> anyhow::Result<TransactionReview> {
+        let transaction = self.prepare_unsigned_transaction(request).await?;
+        // NOTE: policy violations are shown before the user goes through the details
+        self.check_spending_policy(transaction.from, transaction.chain, transaction.to, transaction.value, &transaction.data.0).await?;
+        self.review_transaction(transaction).await
+    }
+
+    pub async fn prepare_replacement_review(&self, original: &TransactionResult, replacement: TransactionReplacement) -> anyhow::Result<TransactionReview> {
+        let transaction = self.prepare_replacement(original, replacement).await?;
+        self.check_replacement_policy(transaction.from, transaction.chain, transaction.to, transaction.value, &transaction.data.0, original.hash).await?;
+        self.review_transaction(transaction).await
+    }
+
+    pub async fn prepare_gap_fill_review(&self, account: Address, gap: NonceGap) -> anyhow::Result<TransactionReview> {
+        let transaction = self.prepare_gap_fill(account, gap).await?;
+        self.check_spending_policy(transaction.from, transaction.chain, transaction.to, transaction.value, &transaction.data.0).await?;
+        self.review_transaction(transaction).await
+    }
+
+    // Names, prices and the decoded call shown next to the transaction
+    pub async fn review_transaction(&self, transaction: UnsignedTransaction) -> anyhow::Result<TransactionReview> {
+        let contacts = self.db.get_contacts()?;
+        let contact_name = |address| contacts.iter()
+            .find(|contact| contact.address == address)
+            .map(|contact| contact.name.clone());
+
+        let eth_usd_rate = self.get_eth_usd_rate(transaction.chain).await.ok();
+        let currency_usd_rate = if transaction.currency == "ETH" {
+            eth_usd_rate
+        } else {
+            self.get_currency_usd_rate(transaction.chain, &transaction.currency).await
+        };
+
+        let saved_abis = self.db.get_contracts()?.iter()
+            .filter(|saved| saved.chain == transaction.chain && saved.address == transaction.to)
+            .filter_map(|saved| contract::load_abi(&saved.abi).ok())
+            .collect::<Vec<_>>();
+        let call = contract::decode_known_call(&transaction.data.0, &saved_abis);
+
+        Ok(TransactionReview {
+            from_name: contact_name(transaction.from),
+            to_name: contact_name(transaction.recipient),
+            eth_usd_rate,
+            currency_usd_rate,
+            call,
+            transaction,
+        })
+    }
+
+    pub async fn send_reviewed_transaction(&self, review: &TransactionReview, secret_key: &SecretKey) -> anyhow::Result<TransactionResult> {
+        let transaction = &review.transaction;
+        let provider = self.providers.get(&transaction.chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", transaction.chain)))?;
+        let spend = self.check_spending_policy(transaction.from, transaction.chain, transaction.to, transaction.value, &transaction.data.0).await?;
+
+        // NOTE: the reviewed nonce must still be the next one, otherwise the review is stale
+        let pending_nonce = provider.get_pending_transaction_count(transaction.from).await?;
+        let nonce = self.nonce_manager.lock().await.take_nonce(transaction.from, transaction.chain, pending_nonce);
+        if nonce != transaction.nonce {
+            self.nonce_manager.lock().await.release(transaction.from, transaction.chain, nonce);
+            return Err(anyhow::anyhow!(ERR_NONCE_CHANGED));
+        }
+
+        let result = match transaction.sign(provider.web3.accounts(), secret_key).await {
+            Ok(signed) => self.broadcast_signed_transaction(&signed).await,
+            Err(err) => Err(err),
+        };
+        match &result {
+            Ok(sent) => self.record_spend(sent.hash, &spend),
+            Err(_) => self.nonce_manager.lock().await.release(transaction.from, transaction.chain, nonce),
+        }
+        result
+    }
+}
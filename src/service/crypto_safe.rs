@@ -0,0 +1,81 @@
+use web3::{signing::SecretKey, types::{Address, U256}};
+
+use crate::core::{
+    eth_chain::EthChain,
+    safe::{self, SafeInfo, SafeProposal, SafeTransaction, SavedSafe}
+};
+use super::crypto::Crypto;
+
+const ERR_OTHER_SAFE: &str = "The file is for another Safe";
+
+impl Crypto {
+    pub async fn get_safe_info(&self, chain: EthChain, address: Address) -> anyhow::Result<SafeInfo> {
+        let provider = self.providers.get(&chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", chain)))?;
+        let owners = provider.call_contract(Address::zero(), address, safe::encode_getter("getOwners")?).await?;
+        let threshold = provider.call_contract(Address::zero(), address, safe::encode_getter("getThreshold")?).await?;
+        let nonce = provider.call_contract(Address::zero(), address, safe::encode_getter("nonce")?).await?;
+        safe::decode_info(chain, address, &owners, &threshold, &nonce)
+    }
+
+    // Reads the Safe first, so only a working Safe contract is saved
+    pub async fn add_safe(&self, chain: EthChain, address: Address) -> anyhow::Result<SafeInfo> {
+        let info = self.get_safe_info(chain, address).await?;
+        self.db.save_safe(&SavedSafe { chain, address })?;
+        Ok(info)
+    }
+
+    // Proposal for the current nonce, the ones below it are executed or replaced and dropped
+    pub fn get_safe_proposal(&self, info: &SafeInfo) -> anyhow::Result<Option<SafeProposal>> {
+        let mut current = None;
+        for proposal in self.db.get_safe_proposals(info.chain, info.address)? {
+            if proposal.transaction.nonce < info.nonce {
+                self.db.remove_safe_proposal(&proposal)?;
+            } else if proposal.transaction.nonce == info.nonce {
+                current = Some(proposal);
+            }
+        }
+        Ok(current)
+    }
+
+    // Replaces the proposal for the same nonce, only one of them could be executed anyway
+    pub fn propose_safe_transaction(&self, info: &SafeInfo, to: Address, value: U256, data: Vec<u8>) -> anyhow::Result<SafeProposal> {
+        if let Some(current) = self.get_safe_proposal(info)? {
+            self.db.remove_safe_proposal(&current)?;
+        }
+        let proposal = SafeProposal::new(SafeTransaction::new(info, to, value, data));
+        self.db.save_safe_proposal(&proposal)?;
+        Ok(proposal)
+    }
+
+    pub fn sign_safe_proposal(&self, info: &SafeInfo, proposal: &SafeProposal, secret_key: &SecretKey) -> anyhow::Result<SafeProposal> {
+        let mut proposal = proposal.clone();
+        proposal.sign(info, secret_key)?;
+        self.db.save_safe_proposal(&proposal)?;
+        Ok(proposal)
+    }
+
+    pub fn export_safe_proposal(&self, proposal: &SafeProposal, path: &std::path::Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(proposal)?)?;
+        Ok(())
+    }
+
+    // Signatures of co-signers are merged into the stored proposal of the same transaction
+    pub fn import_safe_proposal(&self, info: &SafeInfo, path: &std::path::Path) -> anyhow::Result<SafeProposal> {
+        let imported: SafeProposal = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        imported.validate()?;
+        if imported.transaction.safe != info.address || imported.transaction.chain != info.chain {
+            return Err(anyhow::anyhow!(ERR_OTHER_SAFE));
+        }
+
+        let proposal = match self.db.get_safe_proposal(info.chain, info.address, imported.safe_tx_hash)? {
+            Some(mut stored) => {
+                stored.merge(&imported)?;
+                stored
+            },
+            None => imported,
+        };
+        self.db.save_safe_proposal(&proposal)?;
+        Ok(proposal)
+    }
+}
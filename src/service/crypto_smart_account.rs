@@ -0,0 +1,120 @@
+use std::sync::atomic::Ordering;
+use web3::{signing::SecretKey, transports::Http, types::{Address, H256, U256}};
+
+use crate::core::{
+    bundler::Bundler,
+    eth_chain::EthChain,
+    fees::FeeTier,
+    provider::Provider,
+    review::TransactionReview,
+    smart_account::{self, SmartAccount, UserOperation, UserOperationReceipt},
+    unsigned_transaction::UnsignedTransaction
+};
+use super::crypto::Crypto;
+
+const ERR_NO_BUNDLER: &str = "No bundler endpoint configured for the chain";
+
+impl Crypto {
+    // Counterfactual address from the factory, the account is deployed with its first operation
+    pub async fn create_smart_account(&self, owner: Address, chain: EthChain) -> anyhow::Result<SmartAccount> {
+        let provider = self.get_provider(chain)?;
+        let factory = smart_account::SIMPLE_ACCOUNT_FACTORY.parse()?;
+        let response = provider.call_contract(Address::zero(), factory, smart_account::encode_get_address(owner, U256::zero())?).await?;
+        let account = SmartAccount::new(owner, smart_account::decode_get_address(&response)?);
+
+        self.db.save_smart_account(&account)?;
+        self.transactions_updated.store(true, Ordering::Relaxed);
+        Ok(account)
+    }
+
+    pub fn get_smart_accounts(&self, owner: Address) -> anyhow::Result<Vec<SmartAccount>> {
+        self.db.get_smart_accounts(owner)
+    }
+
+    pub async fn is_smart_account_deployed(&self, account: &SmartAccount, chain: EthChain) -> anyhow::Result<bool> {
+        Ok(!self.get_provider(chain)?.get_code(account.address).await?.is_empty())
+    }
+
+    pub fn get_bundler_endpoint(&self, chain: EthChain) -> anyhow::Result<Option<String>> {
+        self.db.get_bundler_endpoint(chain)
+    }
+
+    pub fn save_bundler_endpoint(&self, chain: EthChain, url: &str) -> anyhow::Result<()> {
+        if !url.is_empty() {
+            Http::new(url)?;
+        }
+        self.db.save_bundler_endpoint(chain, url)
+    }
+
+    // Unsigned operation calling the target through the account, with gas estimated by the bundler
+    pub async fn prepare_user_operation(&self, account: &SmartAccount, chain: EthChain, to: Address, value: U256, data: Vec<u8>) -> anyhow::Result<UserOperation> {
+        let provider = self.get_provider(chain)?;
+        let bundler = self.get_bundler(account, chain)?;
+        bundler.verify_entry_point().await?;
+
+        let nonce = smart_account::decode_get_nonce(&provider.call_contract(
+            Address::zero(), account.entry_point, smart_account::encode_get_nonce(account.address)?).await?)?;
+        let deployed = self.is_smart_account_deployed(account, chain).await?;
+        let prices = provider.get_gas_prices(FeeTier::Normal).await?;
+
+        let mut operation = UserOperation::new(account, nonce, deployed, smart_account::encode_execute(to, value, data)?, prices)?;
+        let gas = bundler.estimate_user_operation_gas(&operation).await?;
+        operation.apply_gas(&gas);
+        Ok(operation)
+    }
+
+    // The operation shown as the call the account makes, fees are paid by the account
+    pub async fn prepare_user_operation_review(&self, account: &SmartAccount, chain: EthChain, operation: &UserOperation) -> anyhow::Result<TransactionReview> {
+        let (to, value, data) = smart_account::decode_execute(&operation.call_data.0)?;
+        // NOTE: policy violations are shown before the user goes through the details
+        self.check_spending_policy(account.address, chain, to, value, &data).await?;
+
+        let transfer = self.decode_transfer(chain, to, value, &data);
+        self.review_transaction(UnsignedTransaction {
+            chain,
+            chain_id: chain.get_chain_id(),
+            from: account.address,
+            nonce: operation.nonce,
+            to,
+            value,
+            data: data.into(),
+            gas_limit: operation.call_gas_limit + operation.verification_gas_limit + operation.pre_verification_gas,
+            max_fee_per_gas: operation.max_fee_per_gas,
+            max_priority_fee_per_gas: operation.max_priority_fee_per_gas,
+            l1_data_fee: U256::zero(),
+            recipient: transfer.recipient,
+            amount: transfer.amount,
+            currency: transfer.currency,
+        }).await
+    }
+
+    pub async fn send_user_operation(&self, account: &SmartAccount, chain: EthChain, operation: &UserOperation, secret_key: &SecretKey) -> anyhow::Result<H256> {
+        self.get_provider(chain)?.verify_chain_id().await?;
+        let (to, value, data) = smart_account::decode_execute(&operation.call_data.0)?;
+        let spend = self.check_spending_policy(account.address, chain, to, value, &data).await?;
+
+        let mut operation = operation.clone();
+        operation.sign(account.entry_point, chain.get_chain_id(), secret_key)?;
+        let user_op_hash = self.get_bundler(account, chain)?.send_user_operation(&operation).await?;
+        self.record_spend(user_op_hash, &spend);
+        Ok(user_op_hash)
+    }
+
+    pub async fn get_user_operation_receipt(&self, account: &SmartAccount, chain: EthChain, user_op_hash: H256) -> anyhow::Result<Option<UserOperationReceipt>> {
+        let receipt = self.get_bundler(account, chain)?.get_user_operation_receipt(user_op_hash).await?;
+        if receipt.is_some() {
+            self.transactions_updated.store(true, Ordering::Relaxed);
+        }
+        Ok(receipt)
+    }
+
+    fn get_provider(&self, chain: EthChain) -> anyhow::Result<&Provider<Http>> {
+        self.providers.get(&chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", chain)))
+    }
+
+    fn get_bundler(&self, account: &SmartAccount, chain: EthChain) -> anyhow::Result<Bundler<Http>> {
+        let url = self.db.get_bundler_endpoint(chain)?.ok_or_else(|| anyhow::anyhow!(ERR_NO_BUNDLER))?;
+        Ok(Bundler::new(Http::new(&url)?, account.entry_point))
+    }
+}
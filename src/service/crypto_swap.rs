@@ -0,0 +1,90 @@
+use std::sync::atomic::Ordering;
+use web3::types::{Address, H256, U256};
+
+use crate::core::{
+    amount::Amount, eth_chain::EthChain,
+    swap::{self, SwapContracts, SwapQuote, SwapResult, SwapToken}
+};
+use super::crypto::Crypto;
+
+const ERR_NO_SWAP_ON_CHAIN: &str = "Swaps are not available on this chain";
+const ERR_SAME_TOKEN: &str = "Select two different tokens";
+const ERR_NO_ROUTE: &str = "No pool found for the pair";
+const ERR_NO_TRANSACTION_FOUND: &str = "No transaction found";
+
+impl Crypto {
+    // ETH first, then the listed tokens deployed on the chain
+    pub fn get_swap_tokens(&self, chain: EthChain) -> Vec<SwapToken> {
+        let Some(contracts) = SwapContracts::for_chain(chain) else {
+            return Vec::new();
+        };
+        let mut tokens = vec![SwapToken::eth(&contracts)];
+        tokens.extend(self.token_list.iter().filter_map(|token| token.get_chain_data(&chain)
+            .map(|data| SwapToken::erc20(&token.symbol, data.contract_address, data.decimals))));
+        tokens
+    }
+
+    // Best output over the V2 pair and the V3 fee tiers, pools that don't exist fail to quote and are skipped
+    pub async fn quote_swap(&self, chain: EthChain, token_in: &SwapToken, token_out: &SwapToken, amount_in: Amount) -> anyhow::Result<SwapQuote> {
+        let provider = self.providers.get(&chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", chain)))?;
+        let contracts = SwapContracts::for_chain(chain).ok_or_else(|| anyhow::anyhow!(ERR_NO_SWAP_ON_CHAIN))?;
+        if token_in.address == token_out.address {
+            return Err(anyhow::anyhow!(ERR_SAME_TOKEN));
+        }
+
+        let quote = |protocol, amount: U256| async move {
+            let (quoter, data) = swap::encode_quote(&contracts, protocol, token_in, token_out, amount)?;
+            let output = provider.call_contract(Address::zero(), quoter, data).await?;
+            swap::decode_quote(protocol, &output)
+        };
+
+        let mut best: Option<(swap::SwapProtocol, U256)> = None;
+        for protocol in contracts.protocols() {
+            match quote(protocol, amount_in.raw).await {
+                Ok(amount_out) if best.is_none_or(|(_, best_out)| amount_out > best_out) => best = Some((protocol, amount_out)),
+                Ok(_) => {},
+                Err(err) => log::debug!("No {} quote on {}: {}", protocol.get_display_name(), chain, err),
+            }
+        }
+        let (protocol, amount_out) = best.filter(|(_, amount_out)| !amount_out.is_zero())
+            .ok_or_else(|| anyhow::anyhow!(ERR_NO_ROUTE))?;
+
+        let spot_in = swap::spot_amount(amount_in.raw);
+        let spot_out = quote(protocol, spot_in).await?;
+        Ok(SwapQuote {
+            protocol,
+            amount_in,
+            amount_out: Amount::new(amount_out, token_out.decimals),
+            price_impact: swap::price_impact(amount_in.raw, amount_out, spot_in, spot_out),
+        })
+    }
+
+    // The router can spend ETH sent along without approval
+    pub async fn get_swap_allowance(&self, owner: Address, chain: EthChain, token_in: &SwapToken, spender: Address) -> anyhow::Result<U256> {
+        if token_in.is_eth {
+            return Ok(U256::MAX);
+        }
+        let provider = self.providers.get(&chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", chain)))?;
+        provider.get_allowance(token_in.address, owner, spender).await
+    }
+
+    // Turns the sent router call into one swap entry of the history
+    pub fn record_swap(&self, account: Address, tx_hash: H256, token_in: &SwapToken, token_out: &SwapToken, quote: &SwapQuote, amount_out_min: Amount) -> anyhow::Result<()> {
+        let mut transaction = self.db.get_transaction(account, tx_hash)?
+            .ok_or_else(|| anyhow::anyhow!(ERR_NO_TRANSACTION_FOUND))?;
+        transaction.amount = quote.amount_in;
+        transaction.currency = token_in.symbol.clone();
+        transaction.swap = Some(SwapResult {
+            protocol: quote.protocol,
+            currency_out: token_out.symbol.clone(),
+            token_out: token_out.address,
+            amount_out: quote.amount_out,
+            amount_out_min,
+        });
+        self.db.save_transaction(account, &transaction)?;
+        self.transactions_updated.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+}
@@ -34,4 +34,14 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_has_price_feed() -> anyhow::Result<()> {
+        let crypto = Crypto::new(Arc::new(create_test_db()?), "http://localhost:8545");
+        assert!(crypto.has_price_feed("ETH"));
+        assert!(crypto.has_price_feed("USDC"));
+        assert!(!crypto.has_price_feed("PEPE"));
+        assert!(!crypto.has_price_feed("UNKNOWN"));
+        Ok(())
+    }
 }
@@ -0,0 +1,135 @@
+use std::{collections::{HashMap, HashSet}, sync::atomic::Ordering};
+use web3::{transports::Http, types::{Address, H256, U256}};
+
+use crate::core::{
+    eth_chain::EthChain, nonce::{self, NonceGap}, provider::Provider,
+    transaction::{TransactionResult, TransactionStatus}
+};
+use crate::persistence::db::Db;
+use super::crypto::Crypto;
+
+const ERR_NO_SENDER: &str = "Transaction has no sender";
+
+impl Crypto {
+    pub async fn track_transactions(&self, account: Address) {
+        let db = self.db.clone();
+        let providers = self.providers.clone();
+        let transactions_updated = self.transactions_updated.clone();
+        let nonce_manager = self.nonce_manager.clone();
+        let nonce_gaps = self.nonce_gaps.clone();
+
+        tokio::spawn(async move {
+            let transactions = match db.get_transactions(account, 0, usize::MAX) {
+                Ok(transactions) => transactions,
+                Err(err) => {
+                    log::error!("Failed to load transactions for tracking: {}", err);
+                    return;
+                }
+            };
+
+            let mut replaced = HashSet::new();
+            let mut pending_nonces: HashMap<EthChain, Vec<U256>> = HashMap::new();
+            for mut transaction in transactions.into_iter().filter(|tx| tx.is_tracked()) {
+                if replaced.contains(&transaction.hash) {
+                    continue;
+                }
+
+                let provider = match providers.get(&transaction.chain) {
+                    Some(provider) => provider,
+                    None => continue,
+                };
+
+                match track_transaction(provider, &mut transaction).await {
+                    Ok(false) => {},
+                    Ok(true) => {
+                        if let (TransactionStatus::Dropped, Some(nonce)) = (transaction.status, transaction.nonce) {
+                            nonce_manager.lock().await.release(account, transaction.chain, nonce);
+                        }
+                        if let Err(err) = db.save_transaction(account, &transaction) {
+                            log::error!("Failed to save tracked transaction {:?}: {}", transaction.hash, err);
+                            continue;
+                        }
+                        transactions_updated.store(true, Ordering::Relaxed);
+
+                        // Whichever of the linked transactions got mined, the others are replaced
+                        if transaction.status == TransactionStatus::Successed || transaction.status == TransactionStatus::Failed {
+                            for linked in [transaction.replaces, transaction.replaced_by].into_iter().flatten() {
+                                if let Err(err) = mark_replaced(&db, account, linked) {
+                                    log::error!("Failed to mark transaction {:?} as replaced: {}", linked, err);
+                                }
+                                replaced.insert(linked);
+                            }
+                        }
+                    },
+                    Err(err) => {
+                        log::warn!("Failed to track transaction {:?}: {}", transaction.hash, err);
+                    }
+                }
+
+                if let (TransactionStatus::Pending, Some(nonce)) = (transaction.status, transaction.nonce) {
+                    if transaction.from == Some(account) {
+                        pending_nonces.entry(transaction.chain).or_default().push(nonce);
+                    }
+                }
+            }
+
+            let mut gaps = Vec::new();
+            for (chain, pending_nonces) in pending_nonces {
+                let Some(provider) = providers.get(&chain) else {
+                    continue;
+                };
+                match provider.get_transaction_count(account).await {
+                    Ok(account_nonce) => gaps.extend(nonce::find_nonce_gaps(chain, account_nonce, &pending_nonces)),
+                    Err(err) => log::warn!("Failed to get nonce for {} on {}: {}", account, chain, err),
+                }
+            }
+
+            let mut nonce_gaps = nonce_gaps.write().await;
+            if nonce_gaps.get(&account).cloned().unwrap_or_default() != gaps {
+                nonce_gaps.insert(account, gaps);
+                transactions_updated.store(true, Ordering::Relaxed);
+            }
+        });
+    }
+
+    pub async fn get_nonce_gaps(&self, account: Address) -> Vec<NonceGap> {
+        self.nonce_gaps.read().await.get(&account).cloned().unwrap_or_default()
+    }
+
+    pub fn take_transactions_updated(&self) -> bool {
+        self.transactions_updated.swap(false, Ordering::Relaxed)
+    }
+}
+
+// Returns true if the transaction has changed
+async fn track_transaction(provider: &Provider<Http>, transaction: &mut TransactionResult) -> anyhow::Result<bool> {
+    let original = transaction.clone();
+
+    if let Some(receipt) = provider.get_transaction_receipt(transaction.hash).await? {
+        let latest_block = provider.get_block_number().await?;
+        transaction.apply_receipt(&receipt, latest_block);
+    } else if transaction.status == TransactionStatus::Pending {
+        if provider.get_transaction(transaction.hash).await?.is_none() {
+            let sender = transaction.from.ok_or_else(|| anyhow::anyhow!(ERR_NO_SENDER))?;
+            let account_nonce = provider.get_transaction_count(sender).await?;
+            transaction.apply_missing(account_nonce);
+        }
+    } else {
+        // NOTE: receipt is gone after a reorg, so the transaction is pending again
+        transaction.status = TransactionStatus::Pending;
+        transaction.block_number = None;
+        transaction.confirmations = 0;
+    }
+
+    Ok(*transaction != original)
+}
+
+fn mark_replaced(db: &Db, account: Address, tx_hash: H256) -> anyhow::Result<()> {
+    if let Some(mut transaction) = db.get_transaction(account, tx_hash)? {
+        if transaction.status == TransactionStatus::Pending {
+            transaction.status = TransactionStatus::Replaced;
+            db.save_transaction(account, &transaction)?;
+        }
+    }
+    Ok(())
+}
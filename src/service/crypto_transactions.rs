@@ -1,63 +1,264 @@
-use web3::{signing::SecretKey, types::TransactionParameters};
+use std::sync::atomic::Ordering;
+use web3::{signing::SecretKey, types::{Address, TransactionParameters, H256, U256}};
 
-use crate::core::{eth_chain, eth_utils, transaction::*};
+use crate::core::{
+    amount::{Amount, ETH_DECIMALS}, erc20, eth_chain::EthChain, eth_utils, fees::{FeeTier, GasFees, GasPrices}, nonce::NonceGap,
+    transaction::*, transaction_label::TransactionLabel, unsigned_transaction::UnsignedTransaction
+};
 use super::crypto::Crypto;
 
 const ERR_NO_TRANSACTION_FOUND: &str = "No transaction found";
+const ERR_TRANSACTION_NOT_REPLACEABLE: &str = "Transaction is no longer pending";
+const ERR_CALL_WITH_TOKENS: &str = "Only ETH can be attached to a contract call";
+
+const TRANSFER_GAS_LIMIT: u64 = 21000;
 
 impl Crypto {
-    pub async fn estimate_transaction_fees(&self, request: TransactionRequest) -> anyhow::Result<TransactionFees> {
-        if request.currency != "ETH" { // TODO: token transactions
-            return Err(anyhow::anyhow!("Non-ETH transactions are not supported yet"));
-        }
+    pub async fn estimate_transaction_fees(&self, request: TransactionRequest, tier: FeeTier) -> anyhow::Result<TransactionFees> {
+        let provider = self.providers.get(&request.chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", request.chain)))?;
+
+        let transaction = self.build_transaction_parameters(&request)?;
+        provider.estimate_transaction_fees(transaction, request.from, tier).await
+    }
 
-        let provider = self.providers.get(&request.chain).ok_or_else(|| 
+    pub async fn send_transaction(&self, request: TransactionRequest, secret_key: &SecretKey) -> anyhow::Result<TransactionResult> {
+        let provider = self.providers.get(&request.chain).ok_or_else(||
             anyhow::anyhow!(format!("No provider for chain {}", request.chain)))?;
 
-        let transaction = TransactionParameters {
-            to: Some(request.to),
-            value: eth_utils::eth_to_wei(request.amount),
-            ..Default::default()
+        let transaction = self.build_transaction_parameters(&request)?;
+        let to = transaction.to.unwrap_or_default();
+        let spend = self.check_spending_policy(request.from, request.chain, to, transaction.value, &transaction.data.0).await?;
+        let fees = match request.fees {
+            Some(fees) => fees,
+            None => provider.estimate_transaction_fees(transaction.clone(), request.from, FeeTier::Normal).await?.into_estimated()?,
         };
 
-        provider.estimate_transaction_fees(transaction, request.from).await
+        let pending_nonce = provider.get_pending_transaction_count(request.from).await?;
+        let nonce = self.nonce_manager.lock().await.take_nonce(request.from, request.chain, pending_nonce);
+        let transaction = TransactionParameters { nonce: Some(nonce), ..transaction };
+
+        let tx_hash = match provider.send_transaction(transaction, request.from, fees, secret_key).await {
+            Ok(tx_hash) => tx_hash,
+            Err(err) => {
+                self.nonce_manager.lock().await.release(request.from, request.chain, nonce);
+                return Err(err);
+            }
+        };
+        self.record_spend(tx_hash, &spend);
+        let tx = provider.get_transaction(tx_hash).await?
+            .ok_or_else(|| anyhow::anyhow!(ERR_NO_TRANSACTION_FOUND))?;
+
+        let transaction = to_transaction_result(&tx, &request);
+        self.db.save_transaction(request.from, &transaction)?;
+        self.transactions_updated.store(true, Ordering::Relaxed);
+
+        Ok(transaction)
     }
 
-    pub async fn send_transaction(&self, request: TransactionRequest, secret_key: &SecretKey) -> anyhow::Result<TransactionResult> {
-        if request.currency != "ETH" { // TODO: token transactions
-            return Err(anyhow::anyhow!("Non-ETH transactions are not supported yet"));
+    // Same nonce as the pending transaction with bumped fees, reviewed before it is signed
+    pub async fn prepare_replacement(&self, original: &TransactionResult, replacement: TransactionReplacement) -> anyhow::Result<UnsignedTransaction> {
+        let provider = self.providers.get(&original.chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", original.chain)))?;
+
+        let sender = original.from.ok_or_else(|| anyhow::anyhow!(ERR_TRANSACTION_NOT_REPLACEABLE))?;
+        let tx = provider.get_transaction(original.hash).await?
+            .ok_or_else(|| anyhow::anyhow!(ERR_NO_TRANSACTION_FOUND))?;
+        if !original.is_replaceable() || tx.block_number.is_some() {
+            return Err(anyhow::anyhow!(ERR_TRANSACTION_NOT_REPLACEABLE));
         }
 
-        let provider = self.providers.get(&request.chain).ok_or_else(|| 
-            anyhow::anyhow!(format!("No provider for chain {}", request.chain)))?;
+        let (transaction, gas_limit) = match replacement {
+            TransactionReplacement::SpeedUp => (TransactionParameters {
+                nonce: Some(tx.nonce),
+                to: tx.to,
+                value: tx.value,
+                data: tx.input.clone(),
+                ..Default::default()
+            }, tx.gas),
+            TransactionReplacement::Cancel => (TransactionParameters {
+                nonce: Some(tx.nonce),
+                to: Some(sender),
+                value: U256::zero(),
+                ..Default::default()
+            }, TRANSFER_GAS_LIMIT.into()),
+        };
+
+        let current_prices = provider.get_gas_prices(FeeTier::Fast).await?;
+        let original_prices = GasPrices {
+            base_fee_per_gas: current_prices.base_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas.or(tx.gas_price).unwrap_or_default(),
+            max_fee_per_gas: tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default(),
+        };
+        let fees = GasFees::new(gas_limit, current_prices.bump_for_replacement(&original_prices));
+
+        let transaction = UnsignedTransaction::new(transaction, original.chain, sender, fees);
+        Ok(match replacement {
+            TransactionReplacement::SpeedUp =>
+                transaction.with_transfer(original.to.unwrap_or_default(), original.amount, &original.currency),
+            TransactionReplacement::Cancel => transaction.with_transfer(sender, Amount::zero(ETH_DECIMALS), "ETH"),
+        })
+    }
+
+    // Sends the reviewed replacement, the original transaction is marked as replaced
+    pub async fn replace_transaction(&self, original: &TransactionResult, replacement: TransactionReplacement, transaction: &UnsignedTransaction, secret_key: &SecretKey) -> anyhow::Result<TransactionResult> {
+        let provider = self.providers.get(&original.chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", original.chain)))?;
+
+        // NOTE: the original may have been mined while the replacement was reviewed
+        let tx = provider.get_transaction(original.hash).await?
+            .ok_or_else(|| anyhow::anyhow!(ERR_NO_TRANSACTION_FOUND))?;
+        if !original.is_replaceable() || tx.block_number.is_some() || tx.nonce != transaction.nonce {
+            return Err(anyhow::anyhow!(ERR_TRANSACTION_NOT_REPLACEABLE));
+        }
+
+        let sender = transaction.from;
+        let spend = self.check_replacement_policy(sender, original.chain, transaction.to, transaction.value, &transaction.data.0, original.hash).await?;
+        let signed = transaction.sign(provider.web3.accounts(), secret_key).await?;
+        let tx_hash = provider.send_raw_transaction(signed.raw_transaction).await?;
+        self.record_replacement_spend(original.hash, tx_hash, &spend);
+        let tx = provider.get_transaction(tx_hash).await?
+            .ok_or_else(|| anyhow::anyhow!(ERR_NO_TRANSACTION_FOUND))?;
+
+        let mut result = match replacement {
+            TransactionReplacement::SpeedUp => TransactionResult {
+                to: original.to,
+                amount: original.amount,
+                currency: original.currency.clone(),
+                swap: original.swap.clone(),
+                ..to_transaction_result_impl(&tx, original.chain)
+            },
+            TransactionReplacement::Cancel => to_transaction_result_impl(&tx, original.chain),
+        };
+        result.replaces = Some(original.hash);
+        self.db.save_transaction(sender, &result)?;
+
+        // NOTE: a sped up transaction is the same payment, so it keeps the label
+        if replacement == TransactionReplacement::SpeedUp {
+            if let Some(label) = self.db.get_transaction_label(sender, original.hash)? {
+                self.db.save_transaction_label(sender, result.hash, &label)?;
+            }
+        }
+
+        let mut original = original.clone();
+        original.replaced_by = Some(result.hash);
+        self.db.save_transaction(sender, &original)?;
+        self.transactions_updated.store(true, Ordering::Relaxed);
+
+        Ok(result)
+    }
+
+    // Empty transfer to self on the missing nonce, so the stuck transactions can be mined
+    pub async fn prepare_gap_fill(&self, account: Address, gap: NonceGap) -> anyhow::Result<UnsignedTransaction> {
+        let provider = self.providers.get(&gap.chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", gap.chain)))?;
 
         let transaction = TransactionParameters {
-            to: Some(request.to),
-            value: eth_utils::eth_to_wei(request.amount),
+            nonce: Some(gap.nonce),
+            to: Some(account),
+            value: U256::zero(),
             ..Default::default()
         };
+        let prices = provider.get_gas_prices(FeeTier::Normal).await?;
+        let fees = GasFees::new(TRANSFER_GAS_LIMIT.into(), prices);
+
+        Ok(UnsignedTransaction::new(transaction, gap.chain, account, fees)
+            .with_transfer(account, Amount::zero(ETH_DECIMALS), "ETH"))
+    }
 
-        let tx_hash = provider.send_transaction(transaction, request.from, &secret_key).await?;
+    pub async fn fill_nonce_gap(&self, gap: NonceGap, transaction: &UnsignedTransaction, secret_key: &SecretKey) -> anyhow::Result<TransactionResult> {
+        let provider = self.providers.get(&gap.chain).ok_or_else(||
+            anyhow::anyhow!(format!("No provider for chain {}", gap.chain)))?;
+
+        let account = transaction.from;
+        let spend = self.check_spending_policy(account, gap.chain, transaction.to, transaction.value, &transaction.data.0).await?;
+        let signed = transaction.sign(provider.web3.accounts(), secret_key).await?;
+        let tx_hash = provider.send_raw_transaction(signed.raw_transaction).await?;
+        self.record_spend(tx_hash, &spend);
         let tx = provider.get_transaction(tx_hash).await?
             .ok_or_else(|| anyhow::anyhow!(ERR_NO_TRANSACTION_FOUND))?;
 
-        let transaction = to_transaction_result(&tx, request.chain);
-        self.db.save_transaction(request.from, &transaction)?;
+        let result = to_transaction_result_impl(&tx, gap.chain);
+        self.db.save_transaction(account, &result)?;
 
-        Ok(transaction)
+        if let Some(gaps) = self.nonce_gaps.write().await.get_mut(&account) {
+            gaps.retain(|other| *other != gap);
+        }
+        self.transactions_updated.store(true, Ordering::Relaxed);
+
+        Ok(result)
+    }
+
+    pub fn save_transaction_label(&self, account: Address, tx_hash: H256, label: &TransactionLabel) -> anyhow::Result<()> {
+        self.db.save_transaction_label(account, tx_hash, label)?;
+        self.transactions_updated.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn migrate_transactions(&self) -> anyhow::Result<usize> {
+        self.db.migrate_transaction_amounts(|currency, chain| self.token_list.iter()
+            .find(|token| token.symbol == currency)
+            .and_then(|token| token.get_chain_data(&chain))
+            .map(|data| data.decimals))
+    }
+
+    pub(super) fn build_transaction_parameters(&self, request: &TransactionRequest) -> anyhow::Result<TransactionParameters> {
+        if let Some(data) = &request.data {
+            if request.currency != "ETH" {
+                return Err(anyhow::anyhow!(ERR_CALL_WITH_TOKENS));
+            }
+            return Ok(TransactionParameters {
+                to: Some(request.to),
+                value: eth_utils::eth_to_wei(&request.amount)?,
+                data: data.clone().into(),
+                ..Default::default()
+            });
+        }
+
+        if request.currency == "ETH" {
+            return Ok(TransactionParameters {
+                to: Some(request.to),
+                value: eth_utils::eth_to_wei(&request.amount)?,
+                ..Default::default()
+            });
+        }
+
+        let token = self.token_list.iter().find(|token| token.symbol == request.currency)
+            .ok_or_else(|| anyhow::anyhow!("Unknown token {}", request.currency))?;
+        let token_chain_data = token.get_chain_data(&request.chain)
+            .ok_or_else(|| anyhow::anyhow!("Token {} is not available on {}", token.symbol, request.chain))?;
+
+        let amount = request.amount.to_decimals(token_chain_data.decimals)?;
+        let data = erc20::encode_transfer(request.to, amount)?;
+
+        Ok(TransactionParameters {
+            to: Some(token_chain_data.contract_address),
+            data: data.into(),
+            ..Default::default()
+        })
     }
 }
 
-fn to_transaction_result(transaction: &web3::types::Transaction, chain: eth_chain::EthChain) -> TransactionResult {
-    let status = if transaction.block_number.is_some() {
-        TransactionStatus::Successed
-    } else {
-        TransactionStatus::Pending
-    };
+fn to_transaction_result(transaction: &web3::types::Transaction, request: &TransactionRequest) -> TransactionResult {
+    let result = to_transaction_result_impl(transaction, request.chain);
+    if request.currency == "ETH" {
+        return result;
+    }
+
+    // NOTE: token transfers are sent to the contract, so the recipient and amount come from the request
+    TransactionResult {
+        to: Some(request.to),
+        amount: request.amount,
+        currency: request.currency.clone(),
+        ..result
+    }
+}
 
+pub(super) fn to_transaction_result_impl(transaction: &web3::types::Transaction, chain: EthChain) -> TransactionResult {
+    // NOTE: fee is an upper bound until the receipt is tracked
     let fee = transaction
         .gas_price
-        .map_or(0.0, |gas_price| eth_utils::wei_to_eth(transaction.gas * gas_price));
+        .map_or(Amount::zero(ETH_DECIMALS), |gas_price| eth_utils::wei_to_eth(transaction.gas * gas_price));
 
     TransactionResult {
         hash: transaction.hash,
@@ -65,8 +266,15 @@ fn to_transaction_result(transaction: &web3::types::Transaction, chain: eth_chai
         from: transaction.from,
         to: transaction.to,
         amount: eth_utils::wei_to_eth(transaction.value),
+        currency: "ETH".to_string(),
         fee,
         chain,
-        status,
+        status: TransactionStatus::Pending,
+        nonce: Some(transaction.nonce),
+        gas_used: None,
+        confirmations: 0,
+        replaces: None,
+        replaced_by: None,
+        swap: None,
     }
-}
\ No newline at end of file
+}
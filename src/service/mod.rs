@@ -3,4 +3,17 @@ mod session_test;
 pub mod crypto;
 pub mod crypto_balances;
 pub mod crypto_transactions;
+pub mod crypto_tracker;
+pub mod crypto_history;
+pub mod crypto_offline;
+pub mod crypto_batch;
+pub mod crypto_allowances;
+pub mod crypto_nfts;
+pub mod crypto_contracts;
+pub mod crypto_review;
+pub mod crypto_policy;
+pub mod crypto_bridge;
+pub mod crypto_swap;
+pub mod crypto_safe;
+pub mod crypto_smart_account;
 mod crypto_test;
@@ -56,6 +56,13 @@ impl Session {
         Self::remove_account(self.account)
     }
 
+    pub fn verify_password(&self, password: &str) -> anyhow::Result<()> {
+        if !self.db.verify_password(password)? {
+            return Err(anyhow::anyhow!(ERR_WRONG_PASSWORD_PROVIDED));
+        }
+        Ok(())
+    }
+
     pub fn get_secret_key(&self) -> anyhow::Result<SecretKey> {
         let keypair = self.db.get_keypair()?;
         SecretKey::from_slice(keypair.secret_key.as_slice())
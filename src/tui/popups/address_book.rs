@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use ratatui::{
+    crossterm::event::{Event, KeyCode},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame
+};
+
+use crate::core::{address_book::{self, Contact}, eth_chain::{self, EthChain}, eth_utils};
+use crate::service::session::Session;
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Address Book";
+const DEFAULT_EXPORT_FILE: &str = "address_book.json";
+
+pub struct Popup {
+    session: Session,
+    contacts: Vec<Contact>,
+    selected: Option<usize>,
+    chains: Vec<EthChain>,
+    info: Option<String>,
+    error: Option<String>,
+
+    name: controls::Input,
+    ens: controls::Input,
+    address: controls::Input,
+    notes: controls::Input,
+    chains_button: controls::MenuButton<EthChain>,
+    file: controls::Input,
+    import_button: controls::Button,
+    export_button: controls::Button,
+    back_button: controls::Button,
+    delete_button: controls::Button,
+    new_button: controls::Button,
+    save_button: controls::Button,
+}
+
+impl Popup {
+    pub fn new(session: Session) -> Self {
+        let name = controls::Input::new("Name");
+        let ens = controls::Input::new("ENS name (optional)");
+        let address = controls::Input::new("Address")
+            .with_regex(regex::Regex::new(r"^$|^0(x[0-9a-fA-F]*)?$").unwrap());
+        let notes = controls::Input::new("Notes");
+        let chain_options: HashMap<EthChain, String> = eth_chain::MAINNET_CHAINS.iter()
+            .chain(eth_chain::TESTNET_CHAINS.iter())
+            .map(|chain| (*chain, chain.get_display_name().to_string()))
+            .collect();
+        let chains_button = controls::MenuButton::new("Any chain", Some('c'), chain_options);
+        let file = controls::Input::new("Import/export file (.json or .csv)");
+        let import_button = controls::Button::new("Import", Some('i'));
+        let export_button = controls::Button::new("Export", Some('x'));
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let delete_button = controls::Button::new("Delete", Some('d')).warning().disable();
+        let new_button = controls::Button::new("New", Some('n'));
+        let save_button = controls::Button::new("Save", Some('s'));
+
+        let mut popup = Self {
+            session,
+            contacts: Vec::new(),
+            selected: None,
+            chains: Vec::new(),
+            info: None,
+            error: None,
+            name,
+            ens,
+            address,
+            notes,
+            chains_button,
+            file,
+            import_button,
+            export_button,
+            back_button,
+            delete_button,
+            new_button,
+            save_button,
+        };
+        popup.load_contacts();
+        popup.update_chain_options();
+        popup
+    }
+
+    // Prefill the form with an address, e.g. taken from the transaction history
+    pub fn with_address(mut self, address: web3::types::Address) -> Self {
+        match self.contacts.iter().position(|contact| contact.address == address) {
+            Some(index) => self.select(Some(index)),
+            None => self.address.value = format!("{:?}", address).into(),
+        }
+        self
+    }
+
+    fn load_contacts(&mut self) {
+        self.contacts = self.session.db.get_contacts().unwrap_or_else(|err| {
+            log::error!("Failed to load contacts: {:?}", err);
+            Vec::new()
+        });
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|index| *index < self.contacts.len());
+        self.delete_button.disabled = self.selected.is_none();
+
+        let contact = self.selected.map(|index| self.contacts[index].clone());
+        self.name.value = contact.as_ref().map(|contact| contact.name.clone()).unwrap_or_default().into();
+        self.ens.value = contact.as_ref().and_then(|contact| contact.ens.clone()).unwrap_or_default().into();
+        self.address.value = contact.as_ref().map(|contact| format!("{:?}", contact.address)).unwrap_or_default().into();
+        self.notes.value = contact.as_ref().map(|contact| contact.notes.clone()).unwrap_or_default().into();
+        self.chains = contact.map(|contact| contact.chains).unwrap_or_default();
+        self.update_chain_options();
+    }
+
+    fn update_chain_options(&mut self) {
+        // NOTE: labels are updated in place to keep the menu order stable
+        for (chain, label) in self.chains_button.menu.options.iter_mut() {
+            let mark = if self.chains.contains(chain) { "[x]" } else { "[ ]" };
+            *label = format!("{} {}", mark, chain.get_display_name());
+        }
+        self.chains_button.button.label = match self.chains.len() {
+            0 => "Any chain".to_string(),
+            1 => self.chains[0].get_display_name().to_string(),
+            count => format!("{} chains", count),
+        };
+    }
+
+    fn toggle_chain(&mut self, chain: EthChain) {
+        match self.chains.iter().position(|other| *other == chain) {
+            Some(index) => { self.chains.remove(index); },
+            None => self.chains.push(chain),
+        }
+        self.update_chain_options();
+    }
+
+    fn save(&mut self) -> anyhow::Result<()> {
+        let address = eth_utils::str_to_eth_address(&self.address.value)?;
+        let ens = self.ens.value.trim().to_string();
+        let contact = Contact {
+            ens: Some(ens).filter(|ens| !ens.is_empty()),
+            chains: self.chains.clone(),
+            notes: self.notes.value.trim().to_string(),
+            ..Contact::new(self.name.value.trim(), address)
+        };
+        contact.validate()?;
+
+        // NOTE: contacts are keyed by address, so changing it replaces the old entry
+        if let Some(old) = self.selected.map(|index| &self.contacts[index]) {
+            if old.address != contact.address {
+                self.session.db.remove_contact(old.address)?;
+            }
+        }
+        self.session.db.save_contact(&contact)?;
+
+        self.load_contacts();
+        let index = self.contacts.iter().position(|other| other.address == contact.address);
+        self.select(index);
+        self.info = Some(format!("Saved {}", contact.name));
+        Ok(())
+    }
+
+    fn delete(&mut self) -> anyhow::Result<()> {
+        if let Some(contact) = self.selected.map(|index| self.contacts[index].clone()) {
+            self.session.db.remove_contact(contact.address)?;
+            self.load_contacts();
+            self.select(None);
+            self.info = Some(format!("Deleted {}", contact.name));
+        }
+        Ok(())
+    }
+
+    fn file_path(&self) -> anyhow::Result<std::path::PathBuf> {
+        if self.file.value.trim().is_empty() {
+            return Ok(crate::utils::export_path(DEFAULT_EXPORT_FILE)?);
+        }
+        Ok(std::path::PathBuf::from(self.file.value.trim()))
+    }
+
+    fn is_csv(path: &std::path::Path) -> bool {
+        path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("csv"))
+    }
+
+    fn import(&mut self) -> anyhow::Result<()> {
+        let path = self.file_path()?;
+        let text = std::fs::read_to_string(&path)?;
+        let contacts = if Self::is_csv(&path) {
+            address_book::from_csv(&text)?
+        } else {
+            address_book::from_json(&text)?
+        };
+
+        for contact in &contacts {
+            self.session.db.save_contact(contact)?;
+        }
+        self.load_contacts();
+        self.select(None);
+        self.info = Some(format!("Imported {} contacts", contacts.len()));
+        Ok(())
+    }
+
+    fn export(&mut self) -> anyhow::Result<()> {
+        let path = self.file_path()?;
+        let text = if Self::is_csv(&path) {
+            address_book::to_csv(&self.contacts)
+        } else {
+            serde_json::to_string_pretty(&self.contacts)?
+        };
+
+        std::fs::write(&path, text)?;
+        self.info = Some(format!("Exported to {}", path.display()));
+        Ok(())
+    }
+
+    fn set_result(&mut self, result: anyhow::Result<()>) {
+        match result {
+            Ok(()) => self.error = None,
+            Err(err) => {
+                self.info = None;
+                self.error = Some(err.to_string());
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(chains_event) = self.chains_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(chain) = chains_event {
+                self.toggle_chain(chain);
+            }
+            return Ok(false);
+        }
+        if controls::handle_scoped_event(&mut [
+            &mut self.name, &mut self.ens, &mut self.address, &mut self.notes, &mut self.file], &event).is_some() {
+            return Ok(false);
+        }
+
+        if let Event::Key(key_event) = &event {
+            match key_event.code {
+                KeyCode::Up => {
+                    self.select(Some(self.selected.map_or(0, |index| index.saturating_sub(1))));
+                    return Ok(false);
+                },
+                KeyCode::Down => {
+                    self.select(Some(self.selected.map_or(0, |index| index + 1).min(self.contacts.len().saturating_sub(1))));
+                    return Ok(false);
+                },
+                _ => {}
+            }
+        }
+
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.new_button.handle_event(&event) {
+            self.select(None);
+            self.info = None;
+            self.error = None;
+        } else if let Some(()) = self.save_button.handle_event(&event) {
+            let result = self.save();
+            self.set_result(result);
+        } else if let Some(()) = self.delete_button.handle_event(&event) {
+            let result = self.delete();
+            self.set_result(result);
+        } else if let Some(()) = self.import_button.handle_event(&event) {
+            let result = self.import();
+            self.set_result(result);
+        } else if let Some(()) = self.export_button.handle_event(&event) {
+            let result = self.export();
+            self.set_result(result);
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {}
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(0),                            // Contacts
+                Constraint::Length(controls::INPUT_HEIGHT),     // Name & ENS
+                Constraint::Length(controls::INPUT_HEIGHT),     // Address
+                Constraint::Length(controls::INPUT_HEIGHT),     // Notes & chains
+                Constraint::Length(controls::INPUT_HEIGHT),     // File
+                Constraint::Length(1),                          // Info & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        // Contacts
+        let contacts_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+        let contacts_area = contacts_block.inner(content_layout[0]);
+        frame.render_widget(contacts_block, content_layout[0]);
+
+        let lines = if self.contacts.is_empty() {
+            vec![Line::styled("No contacts yet", Style::default().fg(Color::Gray))]
+        } else {
+            self.contacts.iter().enumerate().map(|(index, contact)| {
+                let style = if Some(index) == self.selected {
+                    Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Yellow)
+                };
+                let ens = contact.ens.as_ref().map(|ens| format!(" ({})", ens)).unwrap_or_default();
+                Line::styled(format!("{}{} {:?}", contact.name, ens, contact.address), style)
+            }).collect()
+        };
+        // NOTE: keep the selected contact visible
+        let offset = self.selected.unwrap_or_default().saturating_sub(contacts_area.height.saturating_sub(1) as usize);
+        frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), contacts_area);
+
+        // Form
+        let half_layout = |area: Rect| Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+
+        let name_layout = half_layout(content_layout[1]);
+        self.name.render(frame, name_layout[0]);
+        self.ens.render(frame, name_layout[1]);
+
+        self.address.render(frame, content_layout[2]);
+
+        let notes_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(content_layout[3]);
+        self.notes.render(frame, notes_layout[0]);
+
+        let file_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(10), Constraint::Length(10)])
+            .split(content_layout[4]);
+        self.file.render(frame, file_layout[0]);
+        self.import_button.render(frame, file_layout[1]);
+        self.export_button.render(frame, file_layout[2]);
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else {
+            self.info.as_ref().map(|info| Paragraph::new(info.clone()).style(Style::default().fg(Color::Yellow)))
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.alignment(Alignment::Left),
+                content_layout[5].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+            ])
+            .split(content_layout[6]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.delete_button.render(frame, buttons_layout[1]);
+        self.new_button.render(frame, buttons_layout[2]);
+        self.save_button.render(frame, buttons_layout[3]);
+
+        // NOTE: the chains menu should be rendered last to be on top
+        self.chains_button.render(frame, notes_layout[1]);
+    }
+}
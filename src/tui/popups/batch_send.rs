@@ -0,0 +1,281 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+
+use crate::core::{batch::{self, BatchEstimate, BatchPayment, BatchResult, BatchRow}, eth_utils, transaction::TransactionFees};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Batch Payments";
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+
+    rows: Vec<BatchRow>,
+    estimate: Option<BatchEstimate>,
+    results: Vec<BatchResult>,
+    info: Option<String>,
+    error: Option<String>,
+    password_required: bool,
+    confirming: bool,
+
+    path: controls::Input,
+    password: controls::Input,
+    load_button: controls::Button,
+    back_button: controls::Button,
+    send_button: controls::Button,
+}
+
+impl Popup {
+    pub fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let password_required = session.db.is_sign_password_required().unwrap_or_else(|err| {
+            log::error!("Failed to load sign settings: {:?}", err);
+            true
+        });
+
+        let path = controls::Input::new("Enter CSV path (address,amount,currency,chain)");
+        let password = controls::Input::new("Enter password to sign").masked();
+        let load_button = controls::Button::new("Load", Some('l'));
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let send_button = controls::Button::new("Send All", Some('s')).disable();
+
+        Self {
+            session,
+            crypto,
+            rows: Vec::new(),
+            estimate: None,
+            results: Vec::new(),
+            info: None,
+            error: None,
+            password_required,
+            confirming: false,
+            path,
+            password,
+            load_button,
+            back_button,
+            send_button,
+        }
+    }
+
+    fn payments(&self) -> Vec<BatchPayment> {
+        self.rows.iter().filter_map(|row| row.payment.as_ref().ok().cloned()).collect()
+    }
+
+    fn is_valid(&self) -> bool {
+        !self.rows.is_empty() && self.rows.iter().all(|row| row.payment.is_ok())
+    }
+
+    async fn load(&mut self) -> anyhow::Result<()> {
+        self.rows = Vec::new();
+        self.estimate = None;
+        self.results = Vec::new();
+        self.info = None;
+
+        let content = std::fs::read_to_string(self.path.value.as_str())?;
+        self.rows = batch::parse_csv(&content)?;
+        if !self.is_valid() {
+            return Ok(());
+        }
+
+        let crypto = self.crypto.lock().await.clone();
+        self.estimate = Some(crypto.estimate_batch(self.session.account, &self.payments()).await);
+        Ok(())
+    }
+
+    async fn send(&mut self) -> anyhow::Result<()> {
+        if self.password_required {
+            self.session.verify_password(&self.password.value)?;
+        }
+        let Some(estimate) = self.estimate.take() else {
+            return Ok(());
+        };
+        self.confirming = false;
+        self.password.value = String::new().into();
+
+        let secret_key = self.session.get_secret_key()?;
+        let crypto = self.crypto.lock().await.clone();
+        self.results = crypto.send_batch(self.session.account, &self.payments(), &estimate, &secret_key).await;
+
+        let path = crate::utils::export_path(&format!("batch_result_{}.csv", chrono::Utc::now().format("%Y%m%d_%H%M%S")))?;
+        std::fs::write(&path, batch::results_to_csv(&self.results))?;
+
+        let sent = self.results.iter().filter(|result| result.result.is_ok()).count();
+        self.info = Some(format!("Sent {} of {}, results saved to {}", sent, self.results.len(), path.display()));
+        Ok(())
+    }
+
+    fn summary_lines(&self) -> Vec<Line<'_>> {
+        let yellow = Style::default().fg(Color::Yellow);
+        let red = Style::default().fg(Color::Red);
+
+        if !self.results.is_empty() {
+            return self.results.iter().map(|result| match &result.result {
+                Ok(hash) => Line::styled(format!("Row {}: {:?}", result.payment.row, hash), yellow),
+                Err(error) => Line::styled(format!("Row {}: {}", result.payment.row, error), red),
+            }).collect();
+        }
+
+        let mut lines = Vec::new();
+        let invalid = self.rows.iter().filter_map(|row| row.payment.as_ref().err().map(|error| (row.row, error)));
+        for (row, error) in invalid {
+            lines.push(Line::styled(format!("Row {}: {}", row, error), red));
+        }
+        if self.rows.is_empty() || !lines.is_empty() {
+            return lines;
+        }
+
+        let payments = self.payments();
+        lines.push(Line::styled(format!("{} payments", payments.len()), yellow));
+        for total in batch::totals(&payments) {
+            lines.push(Line::styled(format!("Total: {} {} on {}",
+                total.amount, total.currency, total.chain.get_display_name()), yellow));
+        }
+
+        let Some(estimate) = &self.estimate else {
+            return lines;
+        };
+        for (chain, fees) in &estimate.chain_fees {
+            lines.push(Line::styled(format!("Fees: max {:.6} ETH on {}",
+                eth_utils::wei_to_eth(*fees), chain.get_display_name()), yellow));
+        }
+        for (payment, fees) in payments.iter().zip(estimate.fees.iter()) {
+            let error = match fees {
+                TransactionFees::Estimated { .. } => continue,
+                TransactionFees::NotEnoughFunds { currency } => format!("not enough funds ({})", currency),
+                TransactionFees::Reverted { reason } => format!("transaction reverts: {}", reason),
+                TransactionFees::RpcFailure { error } => format!("RPC failure: {}", error),
+            };
+            lines.push(Line::styled(format!("Row {}: {}", payment.row, error), red));
+        }
+        for shortfall in &estimate.shortfalls {
+            lines.push(Line::styled(shortfall.clone(), red));
+        }
+        lines
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        // NOTE: every payment is signed at once, so the totals and fees are confirmed first
+        if self.confirming {
+            if self.password_required && controls::handle_scoped_event(&mut [&mut self.password], &event).is_some() {
+                self.error = None;
+                return Ok(false);
+            }
+            if let Some(()) = self.back_button.handle_event(&event) {
+                self.confirming = false;
+                self.password.value = String::new().into();
+                self.info = None;
+                return Ok(false);
+            }
+            if let Some(()) = self.send_button.handle_event(&event) {
+                self.send_button.disabled = true;
+                self.error = self.send().await.err().map(|err| err.to_string());
+            }
+            return Ok(false);
+        }
+
+        let input_event = controls::handle_scoped_event(&mut [&mut self.path], &event);
+        if let Some(input_event) = input_event {
+            if let controls::InputEvent::FocusFinished = input_event {
+                self.error = self.load().await.err().map(|err| err.to_string());
+            }
+            return Ok(false);
+        }
+        if let Some(()) = self.load_button.handle_event(&event) {
+            self.error = self.load().await.err().map(|err| err.to_string());
+            return Ok(false);
+        }
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.send_button.handle_event(&event) {
+            self.confirming = true;
+            self.error = None;
+            self.info = Some("Check the totals and fees above, then confirm to sign all payments".to_string());
+            return Ok(false);
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        // NOTE: the batch is confirmed once, so it can't be sent again without reloading
+        self.send_button.disabled = !self.is_valid() || !self.estimate.as_ref().is_some_and(|estimate| estimate.is_ready()) ||
+            (self.confirming && self.password_required && self.password.value.is_empty());
+        self.send_button.label = if self.confirming { "Confirm & Send".to_string() } else { "Send All".to_string() };
+        self.back_button.label = if self.confirming { "Cancel".to_string() } else { "Back".to_string() };
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let password_height = if self.confirming && self.password_required { controls::INPUT_HEIGHT } else { 0 };
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(controls::INPUT_HEIGHT),     // Path
+                Constraint::Fill(0),                            // Summary
+                Constraint::Length(2),                          // Info & errors
+                Constraint::Length(password_height),            // Password
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let path_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(10),
+            ])
+            .split(content_layout[0]);
+
+        self.path.render(frame, path_layout[0]);
+        self.load_button.render(frame, path_layout[1]);
+
+        let summary = Paragraph::new(self.summary_lines())
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(summary, content_layout[1].inner(Margin { vertical: 1, horizontal: 1 }));
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else {
+            self.info.as_ref().map(|info| Paragraph::new(info.clone()).style(Style::default().fg(Color::Yellow)))
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.wrap(Wrap { trim: true }),
+                content_layout[2].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        if password_height > 0 {
+            self.password.render(frame, content_layout[3]);
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(70),
+            ])
+            .split(content_layout[4]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.send_button.render(frame, buttons_layout[1]);
+    }
+}
@@ -0,0 +1,331 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+
+use crate::core::{
+    amount::{Amount, ETH_DECIMALS}, bridge::{self, BridgeContracts, Rollup, Withdrawal},
+    eth_chain::EthChain, eth_utils, fees::FeeTier, transaction::{TransactionFees, TransactionRequest}
+};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Bridge";
+const DEPOSIT_INDEX: usize = 0;
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    chain: Option<EthChain>,
+    withdrawals: Vec<Withdrawal>,
+
+    fees: Option<TransactionFees>,
+    info: Option<String>,
+    error: Option<String>,
+    review: Option<super::transaction_review::Popup>,
+
+    chain_button: controls::MenuButton<EthChain>,
+    direction_switch: controls::MultiSwitch,
+    amount: controls::Input,
+    back_button: controls::Button,
+    send_button: controls::Button,
+}
+
+impl Popup {
+    pub async fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let crypto_lock = crypto.lock().await.clone();
+        let active_networks = crypto_lock.get_active_networks();
+        let chain_options = active_networks.iter()
+            .filter(|chain| Rollup::for_chain(**chain).is_some())
+            .map(|chain| (*chain, chain.get_display_name().to_string()))
+            .collect::<std::collections::HashMap<_, _>>();
+        let info = chain_options.is_empty()
+            .then(|| "Activate Optimism or Arbitrum networks to bridge".to_string());
+
+        let mut chain_button = controls::MenuButton::new("L2 Chain", Some('c'), chain_options);
+        chain_button.button.disabled = info.is_some();
+        let direction_switch = controls::MultiSwitch::new(vec![
+            controls::Button::new("Deposit", Some('d')),
+            controls::Button::new("Withdraw", Some('w')),
+        ]);
+        let amount = controls::Input::new("Enter amount ETH to bridge")
+            .with_regex(regex::Regex::new(r"^(0(\.\d*)?|[1-9]\d*(\.\d*)?)?$").unwrap());
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let send_button = controls::Button::new("Sign Transaction", Some('s')).disable();
+
+        let mut popup = Self {
+            session,
+            crypto,
+            chain: None,
+            withdrawals: Vec::new(),
+            fees: None,
+            info,
+            error: None,
+            review: None,
+            chain_button,
+            direction_switch,
+            amount,
+            back_button,
+            send_button,
+        };
+        popup.load_withdrawals(&crypto_lock);
+        popup
+    }
+
+    fn load_withdrawals(&mut self, crypto: &Crypto) {
+        self.withdrawals = crypto.get_withdrawals(self.session.account).unwrap_or_else(|err| {
+            log::error!("Failed to load withdrawals: {:?}", err);
+            Vec::new()
+        });
+    }
+
+    fn is_deposit(&self) -> bool {
+        self.direction_switch.active_index == DEPOSIT_INDEX
+    }
+
+    fn amount_value(&self) -> Option<Amount> {
+        Amount::parse(&self.amount.value, ETH_DECIMALS).ok().filter(|amount| !amount.is_zero())
+    }
+
+    fn assembly_transaction_request(&self) -> anyhow::Result<TransactionRequest> {
+        let chain = self.chain.ok_or_else(|| anyhow::anyhow!("Select the L2 chain"))?;
+        let amount = self.amount_value().ok_or_else(|| anyhow::anyhow!("Invalid amount"))?;
+
+        let mut request = if self.is_deposit() {
+            bridge::deposit_request(self.session.account, chain, amount)?
+        } else {
+            bridge::withdrawal_request(self.session.account, chain, amount)?
+        };
+        request.fees = match &self.fees {
+            Some(TransactionFees::Estimated { fees, .. }) => Some(*fees),
+            _ => None,
+        };
+        Ok(request)
+    }
+
+    async fn review_transaction(&mut self) -> anyhow::Result<()> {
+        let request = self.assembly_transaction_request()?;
+        self.review = Some(super::transaction_review::Popup::new(self.session.clone(), self.crypto.clone(), request).await);
+        Ok(())
+    }
+
+    async fn on_sent(&mut self, tx_hash: web3::types::H256) {
+        let (Some(chain), Some(amount)) = (self.chain, self.amount_value()) else {
+            return;
+        };
+        let crypto = self.crypto.lock().await.clone();
+        if self.is_deposit() {
+            self.info = Some(format!("Deposit sent, {} ETH arrives on {} in a few minutes", amount, chain.get_display_name()));
+        } else {
+            match crypto.record_withdrawal(self.session.account, chain, tx_hash, amount) {
+                Ok(()) => self.info = Some("Withdrawal initiated, you will be told when it needs an action".to_string()),
+                Err(err) => {
+                    log::error!("Failed to record withdrawal {:?}: {:?}", tx_hash, err);
+                    self.error = Some(format!("Failed to record withdrawal: {}", err));
+                }
+            }
+            self.load_withdrawals(&crypto);
+        }
+        self.amount.value = String::new().into();
+        self.fees = None;
+    }
+
+    fn details_lines(&self) -> Vec<Line<'_>> {
+        let yellow = Style::default().fg(Color::Yellow);
+        let red = Style::default().fg(Color::Red);
+        let mut lines = Vec::new();
+
+        if let Some(contracts) = self.chain.and_then(BridgeContracts::for_chain) {
+            let chain = self.chain.unwrap_or(contracts.l1_chain);
+            let route = if self.is_deposit() {
+                format!("{} -> {}, credited in a few minutes", contracts.l1_chain.get_display_name(), chain.get_display_name())
+            } else {
+                format!("{} -> {}, has to be finalized on L1 after the challenge period", chain.get_display_name(),
+                    contracts.l1_chain.get_display_name())
+            };
+            lines.push(Line::styled(route, yellow));
+        }
+
+        match &self.fees {
+            Some(TransactionFees::Estimated { currency, fees }) => {
+                lines.push(Line::styled(format!("Fees: min {:.6} {}, max {:.6} {}",
+                    eth_utils::wei_to_eth(fees.min_cost()), currency, eth_utils::wei_to_eth(fees.max_cost()), currency), yellow));
+                if fees.has_l1_data_fee() {
+                    lines.push(Line::styled(format!("L2 execution fee {:.6} {}, L1 data fee {:.6} {}",
+                        eth_utils::wei_to_eth(fees.l2_min_cost()), currency, eth_utils::wei_to_eth(fees.l1_min_cost()), currency), yellow));
+                }
+            },
+            Some(TransactionFees::NotEnoughFunds { currency }) =>
+                lines.push(Line::styled(format!("Not enough funds ({})", currency), red)),
+            Some(TransactionFees::Reverted { reason }) =>
+                lines.push(Line::styled(format!("Transaction reverts: {}", reason), red)),
+            Some(TransactionFees::RpcFailure { error }) =>
+                lines.push(Line::styled(format!("RPC failure: {}", error), red)),
+            None => lines.push(Line::styled("Fees: ---", Style::default().fg(Color::Gray))),
+        }
+        lines
+    }
+
+    fn withdrawal_lines(&self) -> Vec<Line<'_>> {
+        if self.withdrawals.is_empty() {
+            return vec![Line::styled("No withdrawals", Style::default().fg(Color::Gray))];
+        }
+        self.withdrawals.iter().map(|withdrawal| {
+            let date = chrono::DateTime::from_timestamp(withdrawal.initiated_at, 0)
+                .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            let mut status = withdrawal.status.get_display_name().to_string();
+            if let Some(finalizable_at) = withdrawal.finalizable_at.filter(|_| !withdrawal.needs_action() && withdrawal.is_tracked()) {
+                let date = chrono::DateTime::from_timestamp(finalizable_at, 0)
+                    .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                status = format!("{}, finalizable after {}", status, date);
+            }
+            let style = if withdrawal.needs_action() {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            Line::styled(format!("{} {} ETH from {}: {}", date, withdrawal.amount, withdrawal.chain.get_display_name(), status), style)
+        }).collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(review) = &mut self.review {
+            if review.handle_event(event).await? {
+                let sent = review.sent();
+                self.review = None;
+                if let Some(tx_hash) = sent {
+                    self.on_sent(tx_hash).await;
+                }
+            }
+            return Ok(false);
+        }
+
+        if let Some(chain_event) = self.chain_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(chain) = chain_event {
+                self.chain = Some(chain);
+                self.chain_button.button.label = chain.get_display_name().to_string();
+                self.fees = None;
+            }
+            return Ok(false);
+        }
+        if self.direction_switch.handle_event(&event).is_some() {
+            self.fees = None;
+            return Ok(false);
+        }
+        if controls::handle_scoped_event(&mut [&mut self.amount], &event).is_some() {
+            self.fees = None;
+            self.info = None;
+            self.error = None;
+            return Ok(false);
+        }
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.send_button.handle_event(&event) {
+            self.error = self.review_transaction().await.err().map(|err| err.to_string());
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        if let Some(review) = &mut self.review {
+            review.update().await;
+            return;
+        }
+
+        self.amount.color = if self.amount.value.is_empty() || self.amount_value().is_some() { Color::Yellow } else { Color::Red };
+
+        if self.fees.is_none() {
+            if let Ok(request) = self.assembly_transaction_request() {
+                let crypto = self.crypto.lock().await.clone();
+                self.fees = Some(crypto.estimate_transaction_fees(request, FeeTier::Normal).await
+                    .unwrap_or_else(|err| TransactionFees::RpcFailure { error: err.to_string() }));
+            }
+        }
+        self.send_button.disabled = !matches!(self.fees, Some(TransactionFees::Estimated { .. }));
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(review) = &mut self.review {
+            review.render(frame, area);
+            return;
+        }
+
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Chain & direction
+                Constraint::Length(controls::INPUT_HEIGHT),     // Amount
+                Constraint::Length(3),                          // Details
+                Constraint::Fill(0),                            // Withdrawals
+                Constraint::Length(1),                          // Info & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let top_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(content_layout[0]);
+        self.direction_switch.render(frame, top_layout[1]);
+
+        self.amount.render(frame, content_layout[1]);
+
+        let details = Paragraph::new(self.details_lines())
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(details, content_layout[2].inner(Margin { vertical: 0, horizontal: 1 }));
+
+        let withdrawals_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title("Withdrawals");
+        let withdrawals_area = withdrawals_block.inner(content_layout[3]);
+        frame.render_widget(withdrawals_block, content_layout[3]);
+        frame.render_widget(Paragraph::new(self.withdrawal_lines()), withdrawals_area);
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else {
+            self.info.as_ref().map(|info| Paragraph::new(info.clone()).style(Style::default().fg(Color::Yellow)))
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.alignment(Alignment::Left),
+                content_layout[4].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(70),
+            ])
+            .split(content_layout[5]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.send_button.render(frame, buttons_layout[1]);
+
+        // NOTE: the chain menu should be rendered last to be on top
+        self.chain_button.render(frame, top_layout[0]);
+    }
+}
@@ -0,0 +1,493 @@
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::{Event, KeyCode},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+use web3::ethabi;
+
+use crate::core::{
+    amount::{Amount, ETH_DECIMALS}, contract::{self, SavedContract}, eth_chain::EthChain, eth_utils, fees::FeeTier,
+    transaction::{TransactionFees, TransactionRequest}
+};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Contract";
+const STATUS_HEIGHT: u16 = 4;
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    contracts: Vec<SavedContract>,
+
+    chain: Option<EthChain>,
+    abi: Option<String>,
+    functions: Vec<ethabi::Function>,
+    selected: Option<usize>,
+    fees: Option<TransactionFees>,
+    output: Vec<String>,
+    info: Option<String>,
+    error: Option<String>,
+    review: Option<super::transaction_review::Popup>,
+
+    chain_button: controls::MenuButton<EthChain>,
+    saved_button: controls::MenuButton<usize>,
+    address: controls::Input,
+    abi_path: controls::Input,
+    name: controls::Input,
+    args: Vec<controls::Input>,
+    value: controls::Input,
+    load_button: controls::Button,
+    save_button: controls::Button,
+    delete_button: controls::Button,
+    back_button: controls::Button,
+    call_button: controls::Button,
+    send_button: controls::Button,
+}
+
+impl Popup {
+    pub async fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let contracts = session.db.get_contracts().unwrap_or_else(|err| {
+            log::error!("Failed to load contracts: {:?}", err);
+            Vec::new()
+        });
+
+        let crypto_lock = crypto.lock().await.clone();
+        let chain_options = crypto_lock.get_active_networks().iter().map(|chain| {
+            (*chain, chain.get_display_name().to_string())
+        }).collect();
+
+        let chain_button = controls::MenuButton::new("Select chain", Some('c'), chain_options);
+        let saved_button = controls::MenuButton::new("Saved", Some('o'), HashMap::new());
+        let address = controls::Input::new("Enter contract address")
+            .with_regex(regex::Regex::new(r"^$|^0(x[0-9a-fA-F]*)?$").unwrap());
+        let abi_path = controls::Input::new("Enter ABI JSON path");
+        let name = controls::Input::new("Enter name to save the contract");
+        let value = controls::Input::new("Enter ETH value")
+            .with_regex(regex::Regex::new(r"^(0(\.\d*)?|[1-9]\d*(\.\d*)?)?$").unwrap());
+        let load_button = controls::Button::new("Load", Some('l'));
+        let save_button = controls::Button::new("Save", Some('v')).disable();
+        let delete_button = controls::Button::new("Delete", Some('d')).warning().disable();
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let call_button = controls::Button::new("Call", Some('a')).disable();
+        let send_button = controls::Button::new("Sign Transaction", Some('s')).disable();
+
+        let mut popup = Self {
+            session,
+            crypto,
+            contracts,
+            chain: None,
+            abi: None,
+            functions: Vec::new(),
+            selected: None,
+            fees: None,
+            output: Vec::new(),
+            info: None,
+            error: None,
+            review: None,
+            chain_button,
+            saved_button,
+            address,
+            abi_path,
+            name,
+            args: Vec::new(),
+            value,
+            load_button,
+            save_button,
+            delete_button,
+            back_button,
+            call_button,
+            send_button,
+        };
+        popup.update_saved_options();
+        popup
+    }
+
+    fn update_saved_options(&mut self) {
+        self.saved_button.menu.options = self.contracts.iter().enumerate()
+            .map(|(index, contract)| (index, format!("{} ({})", contract.name, contract.chain.get_display_name())))
+            .collect();
+        self.saved_button.button.disabled = self.contracts.is_empty();
+    }
+
+    fn set_chain(&mut self, chain: EthChain) {
+        self.chain = Some(chain);
+        self.chain_button.button.label = chain.get_display_name().to_string();
+        self.fees = None;
+    }
+
+    fn set_abi(&mut self, abi: String) -> anyhow::Result<()> {
+        self.functions = contract::functions(&contract::load_abi(&abi)?);
+        self.abi = Some(abi);
+        self.select(Some(0));
+        Ok(())
+    }
+
+    fn load_abi_file(&mut self) -> anyhow::Result<()> {
+        let abi = std::fs::read_to_string(self.abi_path.value.as_str())?;
+        self.set_abi(abi)
+    }
+
+    fn open_saved(&mut self, index: usize) -> anyhow::Result<()> {
+        let Some(saved) = self.contracts.get(index).cloned() else {
+            return Ok(());
+        };
+        self.set_chain(saved.chain);
+        self.address.value = format!("{:?}", saved.address).into();
+        self.name.value = saved.name.clone().into();
+        self.abi_path.value = String::new().into();
+        self.set_abi(saved.abi)
+    }
+
+    fn save(&mut self) -> anyhow::Result<()> {
+        let saved = SavedContract {
+            name: self.name.value.trim().to_string(),
+            chain: self.chain.ok_or_else(|| anyhow::anyhow!("Select a chain"))?,
+            address: eth_utils::str_to_eth_address(&self.address.value)?,
+            abi: self.abi.clone().ok_or_else(|| anyhow::anyhow!("Load an ABI first"))?,
+        };
+        if saved.name.is_empty() {
+            return Err(anyhow::anyhow!("Enter a name"));
+        }
+
+        self.session.db.save_contract(&saved)?;
+        self.contracts = self.session.db.get_contracts()?;
+        self.update_saved_options();
+        self.info = Some(format!("Saved {}", saved.name));
+        Ok(())
+    }
+
+    fn saved_index(&self) -> Option<usize> {
+        let address = eth_utils::str_to_eth_address(&self.address.value).ok()?;
+        self.contracts.iter().position(|saved| Some(saved.chain) == self.chain && saved.address == address)
+    }
+
+    fn delete(&mut self) -> anyhow::Result<()> {
+        let Some(saved) = self.saved_index().map(|index| self.contracts[index].clone()) else {
+            return Ok(());
+        };
+        self.session.db.remove_contract(saved.chain, saved.address)?;
+        self.contracts = self.session.db.get_contracts()?;
+        self.update_saved_options();
+        self.info = Some(format!("Deleted {}", saved.name));
+        Ok(())
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|index| *index < self.functions.len());
+        self.args = self.function().map_or(Vec::new(), |function| {
+            function.inputs.iter().map(|param| controls::Input::new(&contract::param_placeholder(param))).collect()
+        });
+        self.value.value = String::new().into();
+        self.fees = None;
+        self.output = Vec::new();
+    }
+
+    fn function(&self) -> Option<&ethabi::Function> {
+        self.selected.and_then(|index| self.functions.get(index))
+    }
+
+    fn is_payable(&self) -> bool {
+        self.function().is_some_and(contract::is_payable)
+    }
+
+    fn encode_call(&self) -> anyhow::Result<Vec<u8>> {
+        let function = self.function().ok_or_else(|| anyhow::anyhow!("Select a function"))?;
+        let args = self.args.iter().map(|arg| arg.value.to_string()).collect::<Vec<_>>();
+        contract::encode_call(function, &args)
+    }
+
+    fn assembly_transaction_request(&self) -> anyhow::Result<TransactionRequest> {
+        let chain = self.chain.ok_or_else(|| anyhow::anyhow!("Select a chain"))?;
+        let to = eth_utils::str_to_eth_address(&self.address.value)?;
+        let amount = if self.is_payable() && !self.value.value.is_empty() {
+            Amount::parse(&self.value.value, ETH_DECIMALS)?
+        } else {
+            Amount::zero(ETH_DECIMALS)
+        };
+        let fees = match &self.fees {
+            Some(TransactionFees::Estimated { fees, .. }) => Some(*fees),
+            _ => None,
+        };
+
+        Ok(TransactionRequest {
+            from: self.session.account,
+            to,
+            amount,
+            currency: "ETH".to_string(),
+            chain,
+            fees,
+            data: Some(self.encode_call()?),
+        })
+    }
+
+    async fn call(&mut self) -> anyhow::Result<()> {
+        let chain = self.chain.ok_or_else(|| anyhow::anyhow!("Select a chain"))?;
+        let address = eth_utils::str_to_eth_address(&self.address.value)?;
+        let data = self.encode_call()?;
+
+        let crypto = self.crypto.lock().await.clone();
+        let output = crypto.call_contract(chain, self.session.account, address, data).await?;
+        if let Some(function) = self.function() {
+            self.output = contract::decode_output(function, &output)?;
+        }
+        Ok(())
+    }
+
+    async fn review_transaction(&mut self) -> anyhow::Result<()> {
+        let request = self.assembly_transaction_request()?;
+        self.review = Some(super::transaction_review::Popup::new(self.session.clone(), self.crypto.clone(), request).await);
+        Ok(())
+    }
+
+    fn function_lines(&self) -> Vec<Line<'_>> {
+        if self.functions.is_empty() {
+            return vec![Line::styled("Load an ABI or open a saved contract", Style::default().fg(Color::Gray))];
+        }
+        self.functions.iter().enumerate().map(|(index, function)| {
+            let style = if Some(index) == self.selected {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            Line::styled(contract::function_label(function), style)
+        }).collect()
+    }
+
+    fn status_lines(&self) -> Vec<Line<'_>> {
+        let yellow = Style::default().fg(Color::Yellow);
+        let red = Style::default().fg(Color::Red);
+
+        let mut lines = self.output.iter().map(|line| Line::styled(line.clone(), yellow)).collect::<Vec<_>>();
+        match &self.fees {
+            Some(TransactionFees::Estimated { currency, fees }) => {
+                lines.push(Line::styled(format!("Fees: min {:.6} {}, max {:.6} {}",
+                    eth_utils::wei_to_eth(fees.min_cost()), currency, eth_utils::wei_to_eth(fees.max_cost()), currency), yellow));
+                if fees.has_l1_data_fee() {
+                    lines.push(Line::styled(format!("L2 execution fee {:.6} {}, L1 data fee {:.6} {}",
+                        eth_utils::wei_to_eth(fees.l2_min_cost()), currency, eth_utils::wei_to_eth(fees.l1_min_cost()), currency), yellow));
+                }
+            },
+            Some(TransactionFees::NotEnoughFunds { currency }) =>
+                lines.push(Line::styled(format!("Not enough funds ({})", currency), red)),
+            Some(TransactionFees::Reverted { reason }) =>
+                lines.push(Line::styled(format!("Transaction reverts: {}", reason), red)),
+            Some(TransactionFees::RpcFailure { error }) =>
+                lines.push(Line::styled(format!("RPC failure: {}", error), red)),
+            None => {},
+        }
+        if let Some(error) = &self.error {
+            lines.push(Line::styled(error.clone(), red));
+        } else if let Some(info) = &self.info {
+            lines.push(Line::styled(info.clone(), yellow));
+        }
+        lines
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(review) = &mut self.review {
+            if review.handle_event(event).await? {
+                if let Some(hash) = review.sent() {
+                    self.info = Some(format!("Transaction sent: {:?}", hash));
+                }
+                self.review = None;
+            }
+            return Ok(false);
+        }
+        if let Some(chain_event) = self.chain_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(chain) = chain_event {
+                self.set_chain(chain);
+            }
+            return Ok(false);
+        }
+        if let Some(saved_event) = self.saved_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(index) = saved_event {
+                self.error = self.open_saved(index).err().map(|err| err.to_string());
+            }
+            return Ok(false);
+        }
+
+        let is_payable = self.is_payable();
+        let input_event = {
+            let mut focusables: Vec<&mut dyn controls::Focusable> = vec![&mut self.address, &mut self.abi_path, &mut self.name];
+            focusables.extend(self.args.iter_mut().map(|arg| arg as &mut dyn controls::Focusable));
+            if is_payable {
+                focusables.push(&mut self.value);
+            }
+            controls::handle_scoped_event(&mut focusables, &event)
+        };
+        if let Some(input_event) = input_event {
+            if let controls::InputEvent::Enter = input_event {
+                if self.abi_path.focused {
+                    self.error = self.load_abi_file().err().map(|err| err.to_string());
+                }
+            }
+            self.fees = None;
+            self.info = None;
+            return Ok(false);
+        }
+
+        if let Event::Key(key_event) = &event {
+            match key_event.code {
+                KeyCode::Up => {
+                    self.select(Some(self.selected.map_or(0, |index| index.saturating_sub(1))));
+                    return Ok(false);
+                },
+                KeyCode::Down => {
+                    self.select(Some(self.selected.map_or(0, |index| index + 1).min(self.functions.len().saturating_sub(1))));
+                    return Ok(false);
+                },
+                _ => {}
+            }
+        }
+
+        if let Some(()) = self.load_button.handle_event(&event) {
+            self.error = self.load_abi_file().err().map(|err| err.to_string());
+            return Ok(false);
+        }
+        if let Some(()) = self.save_button.handle_event(&event) {
+            self.error = self.save().err().map(|err| err.to_string());
+            return Ok(false);
+        }
+        if let Some(()) = self.delete_button.handle_event(&event) {
+            self.error = self.delete().err().map(|err| err.to_string());
+            return Ok(false);
+        }
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.call_button.handle_event(&event) {
+            self.output = Vec::new();
+            self.error = self.call().await.err().map(|err| err.to_string());
+            return Ok(false);
+        }
+        if let Some(()) = self.send_button.handle_event(&event) {
+            self.error = self.review_transaction().await.err().map(|err| err.to_string());
+            return Ok(false);
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        if let Some(review) = &mut self.review {
+            review.update().await;
+            return;
+        }
+
+        self.chain_button.button.color = if self.chain.is_some() { Color::Yellow } else { Color::Red };
+        let address_valid = eth_utils::str_to_eth_address(&self.address.value).is_ok();
+        self.address.color = if self.address.value.is_empty() || address_valid { Color::Yellow } else { Color::Red };
+        self.save_button.disabled = self.abi.is_none() || !address_valid || self.chain.is_none() || self.name.value.trim().is_empty();
+        self.delete_button.disabled = self.saved_index().is_none();
+
+        let is_view = self.function().is_some_and(contract::is_view);
+        self.call_button.disabled = !is_view || !address_valid || self.chain.is_none();
+
+        // NOTE: state-changing functions are estimated as soon as all arguments are valid
+        if !is_view && self.function().is_some() && self.fees.is_none() {
+            if let Ok(request) = self.assembly_transaction_request() {
+                let crypto = self.crypto.lock().await.clone();
+                self.fees = Some(crypto.estimate_transaction_fees(request, FeeTier::Normal).await
+                    .unwrap_or_else(|err| TransactionFees::RpcFailure { error: err.to_string() }));
+            }
+        }
+        self.send_button.disabled = is_view || !matches!(self.fees, Some(TransactionFees::Estimated { .. }));
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(review) = &mut self.review {
+            review.render(frame, area);
+            return;
+        }
+
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let inputs_count = self.args.len() as u16 + if self.is_payable() { 1 } else { 0 };
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(controls::BUTTON_HEIGHT),                // Chain & saved
+                Constraint::Length(controls::INPUT_HEIGHT),                 // Address
+                Constraint::Length(controls::INPUT_HEIGHT),                 // ABI path
+                Constraint::Length(controls::INPUT_HEIGHT),                 // Name
+                Constraint::Fill(1),                                        // Functions
+                Constraint::Length(controls::INPUT_HEIGHT * inputs_count),  // Arguments
+                Constraint::Length(STATUS_HEIGHT),                          // Output, fees & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),                // Buttons
+            ])
+            .split(inner_area);
+
+        let top_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(24)])
+            .split(content_layout[0]);
+
+        self.address.render(frame, content_layout[1]);
+
+        let input_with_button = |area: Rect| Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(10)])
+            .split(area);
+        let abi_layout = input_with_button(content_layout[2]);
+        self.abi_path.render(frame, abi_layout[0]);
+        self.load_button.render(frame, abi_layout[1]);
+        let name_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(10), Constraint::Length(10)])
+            .split(content_layout[3]);
+        self.name.render(frame, name_layout[0]);
+        self.save_button.render(frame, name_layout[1]);
+        self.delete_button.render(frame, name_layout[2]);
+
+        let functions_area = content_layout[4].inner(Margin { vertical: 0, horizontal: 1 });
+        let offset = self.selected.unwrap_or_default().saturating_sub(functions_area.height.saturating_sub(1) as usize);
+        frame.render_widget(Paragraph::new(self.function_lines()).scroll((offset as u16, 0)), functions_area);
+
+        let args_area = content_layout[5];
+        let mut y = args_area.y;
+        for arg in self.args.iter_mut() {
+            arg.render(frame, Rect { y, height: controls::INPUT_HEIGHT, ..args_area });
+            y += controls::INPUT_HEIGHT;
+        }
+        if self.is_payable() {
+            self.value.render(frame, Rect { y, height: controls::INPUT_HEIGHT, ..args_area });
+        }
+
+        let status = Paragraph::new(self.status_lines())
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(status, content_layout[6].inner(Margin { vertical: 0, horizontal: 1 }));
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(25),
+                Constraint::Percentage(25),
+                Constraint::Percentage(50),
+            ])
+            .split(content_layout[7]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.call_button.render(frame, buttons_layout[1]);
+        self.send_button.render(frame, buttons_layout[2]);
+
+        // NOTE: menus are rendered last to be on top
+        self.saved_button.render(frame, top_layout[1]);
+        self.chain_button.render(frame, top_layout[0]);
+    }
+}
@@ -0,0 +1,198 @@
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+
+use crate::core::{eth_chain::EthChain, history_export::HistoryFilter};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Export History";
+const DEFAULT_EXPORT_FILE: &str = "transactions.csv";
+const DESCRIPTION_TEXT: &str = "Mined transactions with their labels. Fiat values use the Chainlink ETH/USD rate at the block of each transaction, tokens without a price feed are left empty.";
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    chains: Vec<EthChain>,
+    info: Option<String>,
+    error: Option<String>,
+
+    from: controls::Input,
+    to: controls::Input,
+    chains_button: controls::MenuButton<EthChain>,
+    file: controls::Input,
+    back_button: controls::Button,
+    export_button: controls::Button,
+}
+
+impl Popup {
+    pub async fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        // NOTE: block dates and rates are read from the network, so only active chains can be exported
+        let chain_options: HashMap<EthChain, String> = crypto.lock().await.get_active_networks().iter()
+            .map(|chain| (*chain, chain.get_display_name().to_string()))
+            .collect();
+
+        let date_regex = regex::Regex::new(r"^[\d-]*$").unwrap();
+        let from = controls::Input::new("From date, YYYY-MM-DD (optional)").with_regex(date_regex.clone());
+        let to = controls::Input::new("To date, YYYY-MM-DD (optional)").with_regex(date_regex);
+        let chains_button = controls::MenuButton::new("All chains", Some('c'), chain_options);
+        let file = controls::Input::new("Export file (.csv or .json)");
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let export_button = controls::Button::new("Export", Some('x'));
+
+        let mut popup = Self {
+            session,
+            crypto,
+            chains: Vec::new(),
+            info: None,
+            error: None,
+            from,
+            to,
+            chains_button,
+            file,
+            back_button,
+            export_button,
+        };
+        popup.update_chain_options();
+        popup
+    }
+
+    fn update_chain_options(&mut self) {
+        // NOTE: labels are updated in place to keep the menu order stable
+        for (chain, label) in self.chains_button.menu.options.iter_mut() {
+            let mark = if self.chains.contains(chain) { "[x]" } else { "[ ]" };
+            *label = format!("{} {}", mark, chain.get_display_name());
+        }
+        self.chains_button.button.label = match self.chains.len() {
+            0 => "All chains".to_string(),
+            1 => self.chains[0].get_display_name().to_string(),
+            count => format!("{} chains", count),
+        };
+    }
+
+    fn toggle_chain(&mut self, chain: EthChain) {
+        match self.chains.iter().position(|other| *other == chain) {
+            Some(index) => { self.chains.remove(index); },
+            None => self.chains.push(chain),
+        }
+        self.update_chain_options();
+    }
+
+    fn file_path(&self) -> anyhow::Result<std::path::PathBuf> {
+        if self.file.value.trim().is_empty() {
+            return Ok(crate::utils::export_path(DEFAULT_EXPORT_FILE)?);
+        }
+        Ok(std::path::PathBuf::from(self.file.value.trim()))
+    }
+
+    async fn export(&mut self) -> anyhow::Result<()> {
+        // NOTE: no selection means every active chain
+        let chains = if self.chains.is_empty() {
+            self.chains_button.menu.options.keys().copied().collect()
+        } else {
+            self.chains.clone()
+        };
+        let filter = HistoryFilter::new(&self.from.value, &self.to.value, chains)?;
+        let path = self.file_path()?;
+
+        let count = self.crypto.lock().await.export_history(self.session.account, &filter, &path).await?;
+        self.info = Some(format!("Exported {} transactions to {}", count, path.display()));
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(chains_event) = self.chains_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(chain) = chains_event {
+                self.toggle_chain(chain);
+            }
+            return Ok(false);
+        }
+        if controls::handle_scoped_event(&mut [&mut self.from, &mut self.to, &mut self.file], &event).is_some() {
+            return Ok(false);
+        }
+
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.export_button.handle_event(&event) {
+            match self.export().await {
+                Ok(()) => self.error = None,
+                Err(err) => {
+                    log::error!("Failed to export history: {:?}", err);
+                    self.info = None;
+                    self.error = Some(err.to_string());
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {}
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(0),                            // Description
+                Constraint::Length(controls::INPUT_HEIGHT),     // Dates
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Chains
+                Constraint::Length(controls::INPUT_HEIGHT),     // File
+                Constraint::Length(2),                          // Info & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let description = Paragraph::new(DESCRIPTION_TEXT)
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center);
+        frame.render_widget(description, content_layout[0].inner(Margin { vertical: 1, horizontal: 1 }));
+
+        let dates_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(content_layout[1]);
+        self.from.render(frame, dates_layout[0]);
+        self.to.render(frame, dates_layout[1]);
+
+        self.file.render(frame, content_layout[3]);
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else {
+            self.info.as_ref().map(|info| Paragraph::new(info.clone()).style(Style::default().fg(Color::Yellow)))
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.wrap(Wrap { trim: true }).alignment(Alignment::Left),
+                content_layout[4].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(content_layout[5]);
+        self.back_button.render(frame, buttons_layout[0]);
+        self.export_button.render(frame, buttons_layout[1]);
+
+        // NOTE: the chains menu should be rendered last to be on top
+        self.chains_button.render(frame, content_layout[2]);
+    }
+}
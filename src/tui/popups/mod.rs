@@ -2,3 +2,17 @@ pub mod networks;
 pub mod transaction_receive;
 pub mod transaction_send;
 pub mod transaction_review;
+pub mod transaction_export;
+pub mod transaction_import;
+pub mod address_book;
+pub mod batch_send;
+pub mod nft_send;
+pub mod contract_call;
+pub mod spending_policy;
+pub mod bridge;
+pub mod transaction_label;
+pub mod history_export;
+pub mod swap;
+pub mod safe;
+pub mod smart_account;
+pub mod sign_password;
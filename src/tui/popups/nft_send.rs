@@ -0,0 +1,235 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+use web3::types::{Address, U256};
+
+use crate::core::{
+    address_book::{self, Contact}, eth_utils, fees::FeeTier,
+    nft::{NftHolding, NftStandard}, transaction::{TransactionFees, TransactionRequest}
+};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Send NFT";
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    holding: NftHolding,
+    contacts: Vec<Contact>,
+
+    fees: Option<TransactionFees>,
+    error: Option<String>,
+    review: Option<super::transaction_review::Popup>,
+
+    to: controls::Input,
+    amount: controls::Input,
+    back_button: controls::Button,
+    send_button: controls::Button,
+}
+
+impl Popup {
+    pub fn new(session: Session, crypto: Arc<Mutex<Crypto>>, holding: NftHolding) -> Self {
+        let contacts = session.db.get_contacts().unwrap_or_else(|err| {
+            log::error!("Failed to load contacts: {:?}", err);
+            Vec::new()
+        });
+
+        let to = controls::Input::new("Enter receiver address or contact name");
+        let mut amount = controls::Input::new("Enter amount")
+            .with_regex(regex::Regex::new(r"^\d*$").unwrap());
+        amount.value = "1".to_string().into();
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let send_button = controls::Button::new("Sign Transaction", Some('s')).disable();
+
+        Self {
+            session,
+            crypto,
+            holding,
+            contacts,
+            fees: None,
+            error: None,
+            review: None,
+            to,
+            amount,
+            back_button,
+            send_button,
+        }
+    }
+
+    fn recipient(&self) -> Option<Address> {
+        match eth_utils::str_to_eth_address(&self.to.value) {
+            Ok(address) => Some(address),
+            Err(_) => address_book::resolve(&self.contacts, &self.to.value)
+                .filter(|contact| contact.is_allowed_on(self.holding.chain))
+                .map(|contact| contact.address),
+        }
+    }
+
+    fn amount_value(&self) -> Option<U256> {
+        U256::from_dec_str(&self.amount.value).ok()
+            .filter(|amount| !amount.is_zero() && *amount <= self.holding.amount)
+    }
+
+    fn assembly_transaction_request(&self) -> anyhow::Result<TransactionRequest> {
+        let to = self.recipient().ok_or_else(|| anyhow::anyhow!("Invalid receiver"))?;
+        let amount = self.amount_value().ok_or_else(|| anyhow::anyhow!("Invalid amount"))?;
+
+        let mut request = self.holding.transfer_request(self.session.account, to, amount)?;
+        request.fees = match &self.fees {
+            Some(TransactionFees::Estimated { fees, .. }) => Some(*fees),
+            _ => None,
+        };
+        Ok(request)
+    }
+
+    async fn review_transaction(&mut self) -> anyhow::Result<()> {
+        let request = self.assembly_transaction_request()?;
+        self.review = Some(super::transaction_review::Popup::new(self.session.clone(), self.crypto.clone(), request).await);
+        Ok(())
+    }
+
+    fn details_lines(&self) -> Vec<Line<'_>> {
+        let yellow = Style::default().fg(Color::Yellow);
+        let mut lines = vec![
+            Line::styled(format!("{} #{}", self.holding.collection_str(), self.holding.token_id), yellow),
+            Line::styled(format!("{} on {}, owned: {}", self.holding.standard.get_display_name(),
+                self.holding.chain.get_display_name(), self.holding.amount), yellow),
+        ];
+
+        let fees_line = match &self.fees {
+            Some(TransactionFees::Estimated { currency, fees }) => Line::styled(format!("Fees: min {:.6} {}, max {:.6} {}",
+                eth_utils::wei_to_eth(fees.min_cost()), currency, eth_utils::wei_to_eth(fees.max_cost()), currency), yellow),
+            Some(TransactionFees::NotEnoughFunds { currency }) =>
+                Line::styled(format!("Not enough funds ({})", currency), Style::default().fg(Color::Red)),
+            Some(TransactionFees::Reverted { reason }) =>
+                Line::styled(format!("Transaction reverts: {}", reason), Style::default().fg(Color::Red)),
+            Some(TransactionFees::RpcFailure { error }) =>
+                Line::styled(format!("RPC failure: {}", error), Style::default().fg(Color::Red)),
+            None => Line::styled("Fees: ---", Style::default().fg(Color::Gray)),
+        };
+        lines.push(fees_line);
+        if let Some(TransactionFees::Estimated { currency, fees }) = &self.fees {
+            if fees.has_l1_data_fee() {
+                lines.push(Line::styled(format!("L2 execution fee {:.6} {}, L1 data fee {:.6} {}",
+                    eth_utils::wei_to_eth(fees.l2_min_cost()), currency, eth_utils::wei_to_eth(fees.l1_min_cost()), currency), yellow));
+            }
+        }
+        lines
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(review) = &mut self.review {
+            if review.handle_event(event).await? {
+                // Close the whole popup once the transaction is out
+                let sent = review.sent().is_some();
+                self.review = None;
+                return Ok(sent);
+            }
+            return Ok(false);
+        }
+
+        // NOTE: ERC-721 tokens are always sent as a whole
+        let input_event = if self.holding.standard == NftStandard::Erc1155 {
+            controls::handle_scoped_event(&mut [&mut self.to, &mut self.amount], &event)
+        } else {
+            controls::handle_scoped_event(&mut [&mut self.to], &event)
+        };
+        if input_event.is_some() {
+            self.fees = None;
+            return Ok(false);
+        }
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.send_button.handle_event(&event) {
+            self.error = self.review_transaction().await.err().map(|err| err.to_string());
+            return Ok(false);
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        if let Some(review) = &mut self.review {
+            review.update().await;
+            return;
+        }
+
+        self.to.color = if self.to.value.is_empty() || self.recipient().is_some() { Color::Yellow } else { Color::Red };
+        self.amount.color = if self.amount_value().is_some() { Color::Yellow } else { Color::Red };
+
+        if self.fees.is_none() {
+            if let Ok(request) = self.assembly_transaction_request() {
+                let crypto = self.crypto.lock().await.clone();
+                self.fees = Some(crypto.estimate_transaction_fees(request, FeeTier::Normal).await
+                    .unwrap_or_else(|err| TransactionFees::RpcFailure { error: err.to_string() }));
+            }
+        }
+        self.send_button.disabled = !matches!(self.fees, Some(TransactionFees::Estimated { .. }));
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(review) = &mut self.review {
+            review.render(frame, area);
+            return;
+        }
+
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),                          // Details
+                Constraint::Length(controls::INPUT_HEIGHT),     // To
+                Constraint::Length(controls::INPUT_HEIGHT),     // Amount
+                Constraint::Fill(0),                            // Info & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let details = Paragraph::new(self.details_lines())
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(details, content_layout[0].inner(Margin { vertical: 0, horizontal: 1 }));
+
+        self.to.render(frame, content_layout[1]);
+        if self.holding.standard == NftStandard::Erc1155 {
+            self.amount.render(frame, content_layout[2]);
+        }
+
+        if let Some(error) = &self.error {
+            let error_label = Paragraph::new(error.clone())
+                .style(Style::default().fg(Color::Red))
+                .wrap(Wrap { trim: true });
+            frame.render_widget(error_label, content_layout[3].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(70),
+            ])
+            .split(content_layout[4]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.send_button.render(frame, buttons_layout[1]);
+    }
+}
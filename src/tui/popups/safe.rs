@@ -0,0 +1,485 @@
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+use web3::{ethabi, types::{Address, H256}};
+
+use crate::core::{
+    amount::{Amount, ETH_DECIMALS}, contract, eth_chain::EthChain, eth_utils,
+    safe::{self, SafeInfo, SafeProposal, SavedSafe}
+};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Safe Multisig";
+const STATUS_HEIGHT: u16 = 2;
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    safes: Vec<SavedSafe>,
+    password_required: bool,
+
+    chain: Option<EthChain>,
+    info: Option<SafeInfo>,
+    proposal: Option<SafeProposal>,
+    // Saved contract ABIs of the Safe's chain, to decode the proposed call
+    saved_abis: Vec<(Address, ethabi::Contract)>,
+    status: Option<String>,
+    error: Option<String>,
+    review: Option<super::transaction_review::Popup>,
+
+    saved_button: controls::MenuButton<usize>,
+    chain_button: controls::MenuButton<EthChain>,
+    address: controls::Input,
+    add_button: controls::Button,
+    remove_button: controls::Button,
+    to: controls::Input,
+    value: controls::Input,
+    data: controls::Input,
+    file: controls::Input,
+    password: controls::Input,
+    back_button: controls::Button,
+    propose_button: controls::Button,
+    sign_button: controls::Button,
+    export_button: controls::Button,
+    import_button: controls::Button,
+    execute_button: controls::Button,
+}
+
+impl Popup {
+    pub async fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let safes = session.db.get_safes().unwrap_or_else(|err| {
+            log::error!("Failed to load safes: {:?}", err);
+            Vec::new()
+        });
+        let password_required = session.db.is_sign_password_required().unwrap_or_else(|err| {
+            log::error!("Failed to load sign settings: {:?}", err);
+            true
+        });
+        let chain_options = crypto.lock().await.get_active_networks().iter()
+            .map(|chain| (*chain, chain.get_display_name().to_string()))
+            .collect();
+
+        let address_regex = regex::Regex::new(r"^$|^0(x[0-9a-fA-F]*)?$").unwrap();
+        let saved_button = controls::MenuButton::new("Saved", Some('o'), HashMap::new());
+        let chain_button = controls::MenuButton::new("Select chain", Some('c'), chain_options);
+        let address = controls::Input::new("Enter Safe address").with_regex(address_regex.clone());
+        let add_button = controls::Button::new("Add", Some('a'));
+        let remove_button = controls::Button::new("Remove", Some('d')).warning().disable();
+        let to = controls::Input::new("Send to").with_regex(address_regex.clone());
+        let value = controls::Input::new("ETH value")
+            .with_regex(regex::Regex::new(r"^(0(\.\d*)?|[1-9]\d*(\.\d*)?)?$").unwrap());
+        let data = controls::Input::new("Call data, hex (optional)").with_regex(address_regex);
+        let file = controls::Input::new("Signatures file (.json)");
+        let password = controls::Input::new("Enter password to sign").masked();
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let propose_button = controls::Button::new("Propose", Some('p')).disable();
+        let sign_button = controls::Button::new("Sign", Some('s')).disable();
+        let export_button = controls::Button::new("Export", Some('x')).disable();
+        let import_button = controls::Button::new("Import", Some('i')).disable();
+        let execute_button = controls::Button::new("Execute", Some('e')).warning().disable();
+
+        let mut popup = Self {
+            session,
+            crypto,
+            safes,
+            password_required,
+            chain: None,
+            info: None,
+            proposal: None,
+            saved_abis: Vec::new(),
+            status: None,
+            error: None,
+            review: None,
+            saved_button,
+            chain_button,
+            address,
+            add_button,
+            remove_button,
+            to,
+            value,
+            data,
+            file,
+            password,
+            back_button,
+            propose_button,
+            sign_button,
+            export_button,
+            import_button,
+            execute_button,
+        };
+        popup.update_saved_options();
+        popup
+    }
+
+    fn update_saved_options(&mut self) {
+        self.saved_button.menu.options = self.safes.iter().enumerate()
+            .map(|(index, safe)| (index, format!("{:?} ({})", safe.address, safe.chain.get_display_name())))
+            .collect();
+        self.saved_button.button.disabled = self.safes.is_empty();
+    }
+
+    fn set_chain(&mut self, chain: EthChain) {
+        self.chain = Some(chain);
+        self.chain_button.button.label = chain.get_display_name().to_string();
+    }
+
+    async fn open(&mut self, chain: EthChain, address: web3::types::Address, add: bool) -> anyhow::Result<()> {
+        self.info = None;
+        self.proposal = None;
+        self.set_chain(chain);
+        self.address.value = format!("{:?}", address).into();
+
+        let crypto = self.crypto.lock().await.clone();
+        let info = if add {
+            let info = crypto.add_safe(chain, address).await?;
+            self.safes = self.session.db.get_safes()?;
+            self.update_saved_options();
+            info
+        } else {
+            crypto.get_safe_info(chain, address).await?
+        };
+        self.proposal = crypto.get_safe_proposal(&info)?;
+        self.saved_abis = self.session.db.get_contracts()?.into_iter()
+            .filter(|saved| saved.chain == chain)
+            .filter_map(|saved| contract::load_abi(&saved.abi).ok().map(|abi| (saved.address, abi)))
+            .collect();
+        self.info = Some(info);
+        Ok(())
+    }
+
+    async fn add(&mut self) -> anyhow::Result<()> {
+        let chain = self.chain.ok_or_else(|| anyhow::anyhow!("Select a chain"))?;
+        let address = eth_utils::str_to_eth_address(&self.address.value)?;
+        self.open(chain, address, true).await?;
+        self.status = Some("Safe added".to_string());
+        Ok(())
+    }
+
+    fn remove(&mut self) -> anyhow::Result<()> {
+        let Some(info) = self.info.take() else {
+            return Ok(());
+        };
+        self.session.db.remove_safe(info.chain, info.address)?;
+        self.safes = self.session.db.get_safes()?;
+        self.update_saved_options();
+        self.proposal = None;
+        self.status = Some(format!("Removed {:?}", info.address));
+        Ok(())
+    }
+
+    async fn propose(&mut self) -> anyhow::Result<()> {
+        let info = self.info.as_ref().ok_or_else(|| anyhow::anyhow!("Open a Safe first"))?;
+        let to = eth_utils::str_to_eth_address(&self.to.value)?;
+        let value = if self.value.value.is_empty() {
+            Amount::zero(ETH_DECIMALS)
+        } else {
+            Amount::parse(&self.value.value, ETH_DECIMALS)?
+        };
+        let data = hex::decode(self.data.value.trim_start_matches("0x"))?;
+
+        let proposal = self.crypto.lock().await.propose_safe_transaction(info, to, value.raw, data)?;
+        self.proposal = Some(proposal);
+        self.status = Some("Proposed, sign it and share the file with the other owners".to_string());
+        Ok(())
+    }
+
+    async fn sign(&mut self) -> anyhow::Result<()> {
+        let (Some(info), Some(proposal)) = (&self.info, &self.proposal) else {
+            return Ok(());
+        };
+        if self.password_required {
+            self.session.verify_password(&self.password.value)?;
+        }
+        let secret_key = self.session.get_secret_key()?;
+        let proposal = self.crypto.lock().await.sign_safe_proposal(info, proposal, &secret_key)?;
+        self.proposal = Some(proposal);
+        self.password.value = String::new().into();
+        self.status = Some("Signed".to_string());
+        Ok(())
+    }
+
+    fn file_path(&self, proposal: &SafeProposal) -> anyhow::Result<std::path::PathBuf> {
+        if self.file.value.trim().is_empty() {
+            let file_name = format!("safe_tx_{}_{}.json", proposal.transaction.chain_id, proposal.transaction.nonce);
+            return Ok(crate::utils::export_path(&file_name)?);
+        }
+        Ok(std::path::PathBuf::from(self.file.value.trim()))
+    }
+
+    async fn export(&mut self) -> anyhow::Result<()> {
+        let Some(proposal) = &self.proposal else {
+            return Ok(());
+        };
+        let path = self.file_path(proposal)?;
+        self.crypto.lock().await.export_safe_proposal(proposal, &path)?;
+        self.status = Some(format!("Exported to {}", path.display()));
+        Ok(())
+    }
+
+    async fn import(&mut self) -> anyhow::Result<()> {
+        let Some(info) = &self.info else {
+            return Ok(());
+        };
+        if self.file.value.trim().is_empty() {
+            return Err(anyhow::anyhow!("Enter the file to import"));
+        }
+        let path = std::path::PathBuf::from(self.file.value.trim());
+        let proposal = self.crypto.lock().await.import_safe_proposal(info, &path)?;
+        self.status = Some(format!("Imported, {} signatures", proposal.signatures.len()));
+        self.proposal = Some(proposal);
+        Ok(())
+    }
+
+    async fn review_execution(&mut self) -> anyhow::Result<()> {
+        let (Some(info), Some(proposal)) = (&self.info, &self.proposal) else {
+            return Ok(());
+        };
+        let request = safe::exec_request(self.session.account, info, proposal)?;
+        self.review = Some(super::transaction_review::Popup::new(self.session.clone(), self.crypto.clone(), request).await);
+        Ok(())
+    }
+
+    fn on_sent(&mut self, tx_hash: H256) {
+        self.status = Some(format!("Execution sent: {:?}", tx_hash));
+    }
+
+    fn details_lines(&self) -> Vec<Line<'_>> {
+        let yellow = Style::default().fg(Color::Yellow);
+        let gray = Style::default().fg(Color::Gray);
+        let green = Style::default().fg(Color::Green);
+
+        let Some(info) = &self.info else {
+            return vec![Line::styled("Add a Safe or open a saved one", gray)];
+        };
+        let mut lines = vec![Line::styled(format!("Threshold {} of {} owners, nonce {}",
+            info.threshold, info.owners.len(), info.nonce), yellow)];
+        for owner in &info.owners {
+            let you = if *owner == self.session.account { " (you)" } else { "" };
+            let signed = self.proposal.as_ref().is_some_and(|proposal| proposal.is_signed_by(*owner));
+            let (mark, style) = if signed { ("[x]", green) } else { ("[ ]", yellow) };
+            lines.push(Line::styled(format!("{} {:?}{}", mark, owner, you), style));
+        }
+
+        match &self.proposal {
+            Some(proposal) => {
+                let transaction = &proposal.transaction;
+                lines.push(Line::styled(format!("Proposal #{}: {} ETH to {:?}", transaction.nonce,
+                    eth_utils::wei_to_eth(transaction.value), transaction.to), yellow));
+
+                // NOTE: owners sign what the call does, so it is shown decoded whenever possible
+                let saved_abis = self.saved_abis.iter()
+                    .filter(|(address, _)| *address == transaction.to)
+                    .map(|(_, abi)| abi.clone())
+                    .collect::<Vec<_>>();
+                if let Some(call) = contract::decode_known_call(&transaction.data.0, &saved_abis) {
+                    lines.push(Line::styled(format!("Function: {}", call.function), yellow));
+                    for arg in &call.args {
+                        lines.push(Line::styled(format!("  {}", arg), yellow));
+                    }
+                } else if !transaction.data.0.is_empty() {
+                    lines.push(Line::styled(format!("Data: unknown call, {} bytes", transaction.data.0.len()),
+                        Style::default().fg(Color::Red)));
+                }
+                lines.push(Line::styled(format!("safeTxHash {:?}, {} of {} signatures", proposal.safe_tx_hash,
+                    proposal.owner_signatures(info).len(), info.threshold), yellow));
+            },
+            None => lines.push(Line::styled(format!("No proposal for nonce {}", info.nonce), gray)),
+        }
+        lines
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(review) = &mut self.review {
+            if review.handle_event(event).await? {
+                let sent = review.sent();
+                self.review = None;
+                if let Some(tx_hash) = sent {
+                    self.on_sent(tx_hash);
+                }
+            }
+            return Ok(false);
+        }
+        if let Some(saved_event) = self.saved_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(index) = saved_event {
+                if let Some(saved) = self.safes.get(index).cloned() {
+                    self.status = None;
+                    self.error = self.open(saved.chain, saved.address, false).await.err().map(|err| err.to_string());
+                }
+            }
+            return Ok(false);
+        }
+        if let Some(chain_event) = self.chain_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(chain) = chain_event {
+                self.set_chain(chain);
+            }
+            return Ok(false);
+        }
+
+        let input_event = {
+            let mut focusables: Vec<&mut dyn controls::Focusable> = vec![
+                &mut self.address, &mut self.to, &mut self.value, &mut self.data, &mut self.file,
+            ];
+            if self.password_required {
+                focusables.push(&mut self.password);
+            }
+            controls::handle_scoped_event(&mut focusables, &event)
+        };
+        if input_event.is_some() {
+            self.status = None;
+            self.error = None;
+            return Ok(false);
+        }
+
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        let result = if let Some(()) = self.add_button.handle_event(&event) {
+            self.add().await
+        } else if let Some(()) = self.remove_button.handle_event(&event) {
+            self.remove()
+        } else if let Some(()) = self.propose_button.handle_event(&event) {
+            self.propose().await
+        } else if let Some(()) = self.sign_button.handle_event(&event) {
+            self.sign().await
+        } else if let Some(()) = self.export_button.handle_event(&event) {
+            self.export().await
+        } else if let Some(()) = self.import_button.handle_event(&event) {
+            self.import().await
+        } else if let Some(()) = self.execute_button.handle_event(&event) {
+            self.review_execution().await
+        } else {
+            return Ok(false);
+        };
+        if let Err(err) = result {
+            log::error!("Safe action failed: {:?}", err);
+            self.status = None;
+            self.error = Some(err.to_string());
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        if let Some(review) = &mut self.review {
+            review.update().await;
+            return;
+        }
+
+        self.chain_button.button.color = if self.chain.is_some() { Color::Yellow } else { Color::Red };
+        let address_valid = eth_utils::str_to_eth_address(&self.address.value).is_ok();
+        self.address.color = if self.address.value.is_empty() || address_valid { Color::Yellow } else { Color::Red };
+        let to_valid = eth_utils::str_to_eth_address(&self.to.value).is_ok();
+        self.to.color = if self.to.value.is_empty() || to_valid { Color::Yellow } else { Color::Red };
+        self.data.color = if hex::decode(self.data.value.trim_start_matches("0x")).is_ok() { Color::Yellow } else { Color::Red };
+
+        let is_owner = self.info.as_ref().is_some_and(|info| info.is_owner(self.session.account));
+        let signed = self.proposal.as_ref().is_some_and(|proposal| proposal.is_signed_by(self.session.account));
+        let executable = self.info.as_ref().zip(self.proposal.as_ref())
+            .is_some_and(|(info, proposal)| proposal.is_executable(info));
+
+        self.add_button.disabled = self.chain.is_none() || !address_valid;
+        self.remove_button.disabled = self.info.is_none();
+        self.propose_button.disabled = self.info.is_none() || !to_valid;
+        self.sign_button.disabled = self.proposal.is_none() || !is_owner || signed;
+        self.export_button.disabled = self.proposal.is_none();
+        self.import_button.disabled = self.info.is_none();
+        self.execute_button.disabled = !executable;
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(review) = &mut self.review {
+            review.render(frame, area);
+            return;
+        }
+
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let password_height = if self.password_required { controls::INPUT_HEIGHT } else { 0 };
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Saved & chain
+                Constraint::Length(controls::INPUT_HEIGHT),     // Safe address
+                Constraint::Fill(1),                            // Owners & proposal
+                Constraint::Length(controls::INPUT_HEIGHT),     // To & value
+                Constraint::Length(controls::INPUT_HEIGHT),     // Data
+                Constraint::Length(controls::INPUT_HEIGHT),     // File
+                Constraint::Length(password_height),            // Password
+                Constraint::Length(STATUS_HEIGHT),              // Status & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let top_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(24)])
+            .split(content_layout[0]);
+
+        let address_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(10), Constraint::Length(10)])
+            .split(content_layout[1]);
+        self.address.render(frame, address_layout[0]);
+        self.add_button.render(frame, address_layout[1]);
+        self.remove_button.render(frame, address_layout[2]);
+
+        let details = Paragraph::new(self.details_lines())
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(details, content_layout[2].inner(Margin { vertical: 0, horizontal: 1 }));
+
+        let transfer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .split(content_layout[3]);
+        self.to.render(frame, transfer_layout[0]);
+        self.value.render(frame, transfer_layout[1]);
+        self.data.render(frame, content_layout[4]);
+        self.file.render(frame, content_layout[5]);
+        if self.password_required {
+            self.password.render(frame, content_layout[6]);
+        }
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else {
+            self.status.as_ref().map(|status| Paragraph::new(status.clone()).style(Style::default().fg(Color::Yellow)))
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.wrap(Wrap { trim: true }).alignment(Alignment::Left),
+                content_layout[7].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 6); 6])
+            .split(content_layout[8]);
+        self.back_button.render(frame, buttons_layout[0]);
+        self.propose_button.render(frame, buttons_layout[1]);
+        self.sign_button.render(frame, buttons_layout[2]);
+        self.export_button.render(frame, buttons_layout[3]);
+        self.import_button.render(frame, buttons_layout[4]);
+        self.execute_button.render(frame, buttons_layout[5]);
+
+        // NOTE: menus are rendered last to be on top
+        self.chain_button.render(frame, top_layout[1]);
+        self.saved_button.render(frame, top_layout[0]);
+    }
+}
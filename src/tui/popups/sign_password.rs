@@ -0,0 +1,116 @@
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+
+use crate::service::session::Session;
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Ask Password To Sign";
+
+// NOTE: turning the password off is protected by the password itself, turning it on is not
+pub struct Popup {
+    session: Session,
+    error: Option<String>,
+
+    password: controls::Input,
+    back_button: controls::Button,
+    disable_button: controls::Button,
+}
+
+impl Popup {
+    pub fn new(session: Session) -> Self {
+        let password = controls::Input::new("Enter password").masked();
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let disable_button = controls::Button::new("Turn Off", Some('t')).warning().disable();
+
+        Self {
+            session,
+            error: None,
+            password,
+            back_button,
+            disable_button,
+        }
+    }
+
+    fn disable(&mut self) -> anyhow::Result<()> {
+        self.session.verify_password(&self.password.value)?;
+        self.session.db.save_sign_password_required(false)
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if controls::handle_scoped_event(&mut [&mut self.password], &event).is_some() {
+            self.error = None;
+            return Ok(false);
+        }
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.disable_button.handle_event(&event) {
+            match self.disable() {
+                Ok(()) => return Ok(true),
+                Err(err) => {
+                    log::warn!("Failed to turn off the sign password: {}", err);
+                    self.password.value = String::new().into();
+                    self.error = Some(err.to_string());
+                },
+            }
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        self.disable_button.disabled = self.password.value.is_empty();
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(0),                            // Explanation
+                Constraint::Length(controls::INPUT_HEIGHT),     // Password
+                Constraint::Length(2),                          // Errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let explanation = Paragraph::new("Transactions will be signed without asking for the password. Enter the password to confirm.")
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center);
+        frame.render_widget(explanation, content_layout[0].inner(Margin { vertical: 1, horizontal: 1 }));
+
+        self.password.render(frame, content_layout[1]);
+
+        if let Some(error) = &self.error {
+            frame.render_widget(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)).wrap(Wrap { trim: true }),
+                content_layout[2].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(70),
+            ])
+            .split(content_layout[3]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.disable_button.render(frame, buttons_layout[1]);
+    }
+}
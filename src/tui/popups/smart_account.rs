@@ -0,0 +1,373 @@
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+use web3::types::H256;
+
+use crate::core::{
+    amount::{Amount, ETH_DECIMALS}, eth_chain::EthChain, eth_utils,
+    smart_account::{SmartAccount, UserOperation}
+};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Smart Account";
+const STATUS_HEIGHT: u16 = 2;
+
+const RECEIPT_POLL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(5);
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+
+    account: Option<SmartAccount>,
+    chain: Option<EthChain>,
+    deployed: Option<bool>,
+    operation: Option<UserOperation>,
+    pending: Option<(EthChain, H256)>,
+    last_poll: Option<tokio::time::Instant>,
+    status: Option<String>,
+    error: Option<String>,
+    review: Option<super::transaction_review::Popup>,
+
+    chain_button: controls::MenuButton<EthChain>,
+    bundler: controls::Input,
+    save_button: controls::Button,
+    create_button: controls::Button,
+    to: controls::Input,
+    value: controls::Input,
+    data: controls::Input,
+    back_button: controls::Button,
+    estimate_button: controls::Button,
+    send_button: controls::Button,
+}
+
+impl Popup {
+    pub async fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let crypto_guard = crypto.lock().await;
+        let account = crypto_guard.get_smart_accounts(session.account).unwrap_or_else(|err| {
+            log::error!("Failed to load smart accounts: {:?}", err);
+            Vec::new()
+        }).into_iter().next();
+        let chain_options = crypto_guard.get_active_networks().iter()
+            .map(|chain| (*chain, chain.get_display_name().to_string()))
+            .collect::<HashMap<_, _>>();
+        drop(crypto_guard);
+
+        let address_regex = regex::Regex::new(r"^$|^0(x[0-9a-fA-F]*)?$").unwrap();
+        let chain_button = controls::MenuButton::new("Select chain", Some('c'), chain_options);
+        let bundler = controls::Input::new("Bundler endpoint URL");
+        let save_button = controls::Button::new("Save", Some('u')).disable();
+        let create_button = controls::Button::new("Create account", Some('a')).disable();
+        let to = controls::Input::new("Send to").with_regex(address_regex.clone());
+        let value = controls::Input::new("ETH value")
+            .with_regex(regex::Regex::new(r"^(0(\.\d*)?|[1-9]\d*(\.\d*)?)?$").unwrap());
+        let data = controls::Input::new("Call data, hex (optional)").with_regex(address_regex);
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let estimate_button = controls::Button::new("Estimate", Some('e')).disable();
+        let send_button = controls::Button::new("Review & Send", Some('s')).warning().disable();
+
+        Self {
+            session,
+            crypto,
+            account,
+            chain: None,
+            deployed: None,
+            operation: None,
+            pending: None,
+            last_poll: None,
+            status: None,
+            error: None,
+            review: None,
+            chain_button,
+            bundler,
+            save_button,
+            create_button,
+            to,
+            value,
+            data,
+            back_button,
+            estimate_button,
+            send_button,
+        }
+    }
+
+    async fn set_chain(&mut self, chain: EthChain) -> anyhow::Result<()> {
+        self.chain = Some(chain);
+        self.chain_button.button.label = chain.get_display_name().to_string();
+        self.operation = None;
+        self.deployed = None;
+
+        let crypto = self.crypto.lock().await.clone();
+        self.bundler.value = crypto.get_bundler_endpoint(chain)?.unwrap_or_default().into();
+        if let Some(account) = &self.account {
+            self.deployed = Some(crypto.is_smart_account_deployed(account, chain).await?);
+        }
+        Ok(())
+    }
+
+    async fn save_bundler(&mut self) -> anyhow::Result<()> {
+        let Some(chain) = self.chain else {
+            return Ok(());
+        };
+        self.crypto.lock().await.save_bundler_endpoint(chain, self.bundler.value.trim())?;
+        self.status = Some(format!("Bundler saved for {}", chain.get_display_name()));
+        Ok(())
+    }
+
+    async fn create(&mut self) -> anyhow::Result<()> {
+        let chain = self.chain.ok_or_else(|| anyhow::anyhow!("Select a chain"))?;
+        let crypto = self.crypto.lock().await.clone();
+        let account = crypto.create_smart_account(self.session.account, chain).await?;
+        self.deployed = Some(crypto.is_smart_account_deployed(&account, chain).await?);
+        self.status = Some(format!("Smart account {:?} added, it's deployed with its first operation", account.address));
+        self.account = Some(account);
+        Ok(())
+    }
+
+    fn call_inputs(&self) -> (String, String, String) {
+        (self.to.value.to_string(), self.value.value.to_string(), self.data.value.to_string())
+    }
+
+    async fn estimate(&mut self) -> anyhow::Result<()> {
+        let (Some(account), Some(chain)) = (&self.account, self.chain) else {
+            return Ok(());
+        };
+        let to = eth_utils::str_to_eth_address(&self.to.value)?;
+        let value = if self.value.value.is_empty() {
+            Amount::zero(ETH_DECIMALS)
+        } else {
+            Amount::parse(&self.value.value, ETH_DECIMALS)?
+        };
+        let data = hex::decode(self.data.value.trim_start_matches("0x"))?;
+
+        let crypto = self.crypto.lock().await.clone();
+        let operation = crypto.prepare_user_operation(account, chain, to, value.raw, data).await?;
+        self.status = Some(format!("Up to {} ETH in fees, paid from the smart account", eth_utils::wei_to_eth(operation.max_cost())));
+        self.operation = Some(operation);
+        Ok(())
+    }
+
+    // NOTE: the operation is signed from the same review as any other transaction
+    async fn review_send(&mut self) {
+        let (Some(account), Some(chain), Some(operation)) = (&self.account, self.chain, &self.operation) else {
+            return;
+        };
+        self.review = Some(super::transaction_review::Popup::new_user_operation(
+            self.session.clone(), self.crypto.clone(), account.clone(), chain, operation.clone()).await);
+    }
+
+    fn on_sent(&mut self, user_op_hash: H256) {
+        let Some(chain) = self.chain else {
+            return;
+        };
+        self.operation = None;
+        self.pending = Some((chain, user_op_hash));
+        self.last_poll = Some(tokio::time::Instant::now());
+        self.status = Some(format!("User operation sent: {:?}, waiting for inclusion", user_op_hash));
+    }
+
+    async fn poll_receipt(&mut self) -> anyhow::Result<()> {
+        let (Some(account), Some((chain, user_op_hash))) = (&self.account, self.pending) else {
+            return Ok(());
+        };
+        let crypto = self.crypto.lock().await.clone();
+        let Some(receipt) = crypto.get_user_operation_receipt(account, chain, user_op_hash).await? else {
+            return Ok(());
+        };
+
+        self.pending = None;
+        // NOTE: the account is deployed even if its call reverts
+        if self.chain == Some(chain) {
+            self.deployed = Some(true);
+        }
+        self.status = Some(if receipt.success {
+            format!("Included in transaction {:?}", receipt.receipt.transaction_hash)
+        } else {
+            format!("Call reverted in transaction {:?}", receipt.receipt.transaction_hash)
+        });
+        Ok(())
+    }
+
+    fn details_lines(&self) -> Vec<Line<'_>> {
+        let yellow = Style::default().fg(Color::Yellow);
+        let gray = Style::default().fg(Color::Gray);
+
+        let Some(account) = &self.account else {
+            return vec![Line::styled("Select a chain and create a SimpleAccount owned by this vault", gray)];
+        };
+        let deployed = match (self.chain, self.deployed) {
+            (Some(chain), Some(true)) => format!("deployed on {}", chain.get_display_name()),
+            (Some(chain), Some(false)) => format!("not deployed on {} yet", chain.get_display_name()),
+            _ => "select a chain".to_string(),
+        };
+        vec![
+            Line::styled(format!("Address {:?}", account.address), yellow),
+            Line::styled(format!("Owner {:?}, {}", account.owner, deployed), yellow),
+            Line::styled(format!("EntryPoint {:?}", account.entry_point), gray),
+        ]
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(review) = &mut self.review {
+            if review.handle_event(event).await? {
+                let sent = review.sent();
+                self.review = None;
+                if let Some(user_op_hash) = sent {
+                    self.on_sent(user_op_hash);
+                }
+            }
+            return Ok(false);
+        }
+        if let Some(chain_event) = self.chain_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(chain) = chain_event {
+                self.status = None;
+                self.error = self.set_chain(chain).await.err().map(|err| err.to_string());
+            }
+            return Ok(false);
+        }
+
+        let call_inputs = self.call_inputs();
+        let input_event = controls::handle_scoped_event(
+            &mut [&mut self.bundler, &mut self.to, &mut self.value, &mut self.data], &event);
+        if input_event.is_some() {
+            // The estimated operation is for the previous call
+            if self.call_inputs() != call_inputs {
+                self.operation = None;
+            }
+            self.status = None;
+            self.error = None;
+            return Ok(false);
+        }
+
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        let result = if let Some(()) = self.save_button.handle_event(&event) {
+            self.save_bundler().await
+        } else if let Some(()) = self.create_button.handle_event(&event) {
+            self.create().await
+        } else if let Some(()) = self.estimate_button.handle_event(&event) {
+            self.estimate().await
+        } else if let Some(()) = self.send_button.handle_event(&event) {
+            self.review_send().await;
+            return Ok(false);
+        } else {
+            return Ok(false);
+        };
+        if let Err(err) = result {
+            log::error!("Smart account action failed: {:?}", err);
+            self.status = None;
+            self.error = Some(err.to_string());
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        if let Some(review) = &mut self.review {
+            review.update().await;
+            return;
+        }
+        if self.pending.is_some() && self.last_poll.is_none_or(|last_poll| last_poll.elapsed() > RECEIPT_POLL_INTERVAL) {
+            self.last_poll = Some(tokio::time::Instant::now());
+            if let Err(err) = self.poll_receipt().await {
+                log::error!("Failed to get user operation receipt: {:?}", err);
+            }
+        }
+
+        self.chain_button.button.color = if self.chain.is_some() { Color::Yellow } else { Color::Red };
+        let to_valid = eth_utils::str_to_eth_address(&self.to.value).is_ok();
+        self.to.color = if self.to.value.is_empty() || to_valid { Color::Yellow } else { Color::Red };
+        self.data.color = if hex::decode(self.data.value.trim_start_matches("0x")).is_ok() { Color::Yellow } else { Color::Red };
+
+        let ready = self.account.is_some() && self.chain.is_some() && self.pending.is_none();
+        self.save_button.disabled = self.chain.is_none();
+        self.create_button.disabled = self.chain.is_none() || self.account.is_some();
+        self.estimate_button.disabled = !ready || !to_valid;
+        self.send_button.disabled = !ready || self.operation.is_none();
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(review) = &mut self.review {
+            review.render(frame, area);
+            return;
+        }
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Chain & create
+                Constraint::Length(controls::INPUT_HEIGHT),     // Bundler
+                Constraint::Fill(1),                            // Account details
+                Constraint::Length(controls::INPUT_HEIGHT),     // To & value
+                Constraint::Length(controls::INPUT_HEIGHT),     // Data
+                Constraint::Length(STATUS_HEIGHT),              // Status & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let top_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(24)])
+            .split(content_layout[0]);
+        self.create_button.render(frame, top_layout[0]);
+
+        let bundler_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(10)])
+            .split(content_layout[1]);
+        self.bundler.render(frame, bundler_layout[0]);
+        self.save_button.render(frame, bundler_layout[1]);
+
+        let details = Paragraph::new(self.details_lines())
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(details, content_layout[2].inner(Margin { vertical: 0, horizontal: 1 }));
+
+        let transfer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .split(content_layout[3]);
+        self.to.render(frame, transfer_layout[0]);
+        self.value.render(frame, transfer_layout[1]);
+        self.data.render(frame, content_layout[4]);
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else {
+            self.status.as_ref().map(|status| Paragraph::new(status.clone()).style(Style::default().fg(Color::Yellow)))
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.wrap(Wrap { trim: true }).alignment(Alignment::Left),
+                content_layout[5].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 3); 3])
+            .split(content_layout[6]);
+        self.back_button.render(frame, buttons_layout[0]);
+        self.estimate_button.render(frame, buttons_layout[1]);
+        self.send_button.render(frame, buttons_layout[2]);
+
+        // NOTE: menus are rendered last to be on top
+        self.chain_button.render(frame, top_layout[1]);
+    }
+}
@@ -0,0 +1,367 @@
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::{Event, KeyCode},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame
+};
+use web3::types::Address;
+
+use crate::core::{
+    address_book::{self, Contact}, amount::Amount, eth_chain::{self, EthChain}, eth_utils,
+    policy::{LimitUnit, SpendingLimit, SpendingPolicy}
+};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Spending Policy";
+
+pub struct Popup {
+    crypto: Arc<Mutex<Crypto>>,
+    contacts: Vec<Contact>,
+    policy: SpendingPolicy,
+    selected: Option<usize>,
+    unit: LimitUnit,
+    info: Option<String>,
+    error: Option<String>,
+
+    currency: controls::Input,
+    unit_button: controls::Button,
+    per_transaction: controls::Input,
+    daily: controls::Input,
+    set_limit_button: controls::Button,
+    remove_limit_button: controls::Button,
+    recipients: controls::Input,
+    chains_button: controls::MenuButton<EthChain>,
+    cooling_off: controls::Input,
+    password: controls::Input,
+    back_button: controls::Button,
+    save_button: controls::Button,
+}
+
+impl Popup {
+    pub async fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let contacts = session.db.get_contacts().unwrap_or_else(|err| {
+            log::error!("Failed to load contacts: {:?}", err);
+            Vec::new()
+        });
+        let policy = crypto.lock().await.get_spending_policy().unwrap_or_else(|err| {
+            log::error!("Failed to load spending policy: {:?}", err);
+            SpendingPolicy::default()
+        });
+
+        let amount_regex = regex::Regex::new(r"^\d*\.?\d*$").unwrap();
+        let currency = controls::Input::new("Currency (e.g. ETH)");
+        let unit_button = controls::Button::new(LimitUnit::Token.get_display_name(), Some('u'));
+        let per_transaction = controls::Input::new("Per transaction").with_regex(amount_regex.clone());
+        let daily = controls::Input::new("Daily").with_regex(amount_regex);
+        let set_limit_button = controls::Button::new("Set limit", Some('l'));
+        let remove_limit_button = controls::Button::new("Remove", Some('r')).disable();
+        let mut recipients = controls::Input::new("Allowed recipients, addresses or contacts separated by commas (any if empty)");
+        recipients.value = policy.recipients.iter()
+            .map(|address| format!("{:?}", address))
+            .collect::<Vec<_>>()
+            .join(", ")
+            .into();
+        let chain_options: HashMap<EthChain, String> = eth_chain::MAINNET_CHAINS.iter()
+            .chain(eth_chain::TESTNET_CHAINS.iter())
+            .map(|chain| (*chain, chain.get_display_name().to_string()))
+            .collect();
+        let chains_button = controls::MenuButton::new("Any chain", Some('c'), chain_options);
+        let mut cooling_off = controls::Input::new("Cooling-off for new recipients, hours (off if empty)")
+            .with_regex(regex::Regex::new(r"^\d*$").unwrap());
+        if policy.cooling_off_hours > 0 {
+            cooling_off.value = policy.cooling_off_hours.to_string().into();
+        }
+        let password = controls::Input::new("Enter password to save").masked();
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let save_button = controls::Button::new("Save", Some('s')).warning().disable();
+
+        let mut popup = Self {
+            crypto,
+            contacts,
+            policy,
+            selected: None,
+            unit: LimitUnit::Token,
+            info: None,
+            error: None,
+            currency,
+            unit_button,
+            per_transaction,
+            daily,
+            set_limit_button,
+            remove_limit_button,
+            recipients,
+            chains_button,
+            cooling_off,
+            password,
+            back_button,
+            save_button,
+        };
+        popup.update_chain_options();
+        popup
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|index| *index < self.policy.limits.len());
+        self.remove_limit_button.disabled = self.selected.is_none();
+
+        let limit = self.selected.map(|index| self.policy.limits[index].clone());
+        let amount_str = |amount: Option<Amount>| amount.map(|amount| amount.to_string()).unwrap_or_default();
+        self.currency.value = limit.as_ref().map(|limit| limit.currency.clone()).unwrap_or_default().into();
+        self.per_transaction.value = amount_str(limit.as_ref().and_then(|limit| limit.per_transaction)).into();
+        self.daily.value = amount_str(limit.as_ref().and_then(|limit| limit.daily)).into();
+        self.set_unit(limit.map_or(LimitUnit::Token, |limit| limit.unit));
+    }
+
+    fn set_unit(&mut self, unit: LimitUnit) {
+        self.unit = unit;
+        self.unit_button.label = unit.get_display_name().to_string();
+    }
+
+    fn update_chain_options(&mut self) {
+        // NOTE: labels are updated in place to keep the menu order stable
+        for (chain, label) in self.chains_button.menu.options.iter_mut() {
+            let mark = if self.policy.chains.contains(chain) { "[x]" } else { "[ ]" };
+            *label = format!("{} {}", mark, chain.get_display_name());
+        }
+        self.chains_button.button.label = match self.policy.chains.len() {
+            0 => "Any chain".to_string(),
+            1 => self.policy.chains[0].get_display_name().to_string(),
+            count => format!("{} chains", count),
+        };
+    }
+
+    fn toggle_chain(&mut self, chain: EthChain) {
+        match self.policy.chains.iter().position(|other| *other == chain) {
+            Some(index) => { self.policy.chains.remove(index); },
+            None => self.policy.chains.push(chain),
+        }
+        self.update_chain_options();
+    }
+
+    fn parse_amount(input: &controls::Input, name: &str) -> anyhow::Result<Option<Amount>> {
+        if input.value.trim().is_empty() {
+            return Ok(None);
+        }
+        input.value.parse::<Amount>().map(Some).map_err(|_| anyhow::anyhow!("Invalid {} limit", name))
+    }
+
+    fn set_limit(&mut self) -> anyhow::Result<()> {
+        let limit = SpendingLimit {
+            currency: self.currency.value.trim().to_uppercase(),
+            unit: self.unit,
+            per_transaction: Self::parse_amount(&self.per_transaction, "per-transaction")?,
+            daily: Self::parse_amount(&self.daily, "daily")?,
+        };
+
+        let mut policy = self.policy.clone();
+        policy.set_limit(limit.clone());
+        policy.validate()?;
+        self.policy = policy;
+
+        let index = self.policy.limits.iter().position(|other| *other == limit);
+        self.select(index);
+        self.info = Some(format!("{} limit set, save to apply", limit.currency));
+        Ok(())
+    }
+
+    fn remove_limit(&mut self) {
+        if let Some(index) = self.selected {
+            let limit = self.policy.limits.remove(index);
+            self.select(None);
+            self.info = Some(format!("{} limit removed, save to apply", limit.currency));
+        }
+    }
+
+    fn parse_recipients(&self) -> anyhow::Result<Vec<Address>> {
+        self.recipients.value.split(',')
+            .map(|recipient| recipient.trim())
+            .filter(|recipient| !recipient.is_empty())
+            .map(|recipient| match eth_utils::str_to_eth_address(recipient) {
+                Ok(address) => Ok(address),
+                Err(_) => address_book::resolve(&self.contacts, recipient)
+                    .map(|contact| contact.address)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown recipient {}", recipient)),
+            })
+            .collect()
+    }
+
+    async fn save(&mut self) -> anyhow::Result<()> {
+        let policy = SpendingPolicy {
+            recipients: self.parse_recipients()?,
+            cooling_off_hours: self.cooling_off.value.trim().parse().unwrap_or_default(),
+            ..self.policy.clone()
+        };
+
+        self.crypto.lock().await.save_spending_policy(&policy, &self.password.value)?;
+        self.policy = policy;
+        self.password.value = String::new().into();
+        self.info = Some("Spending policy saved".to_string());
+        Ok(())
+    }
+
+    fn limit_lines(&self) -> Vec<Line<'_>> {
+        if self.policy.limits.is_empty() {
+            return vec![Line::styled("No spending limits", Style::default().fg(Color::Gray))];
+        }
+        self.policy.limits.iter().enumerate().map(|(index, limit)| {
+            let style = if Some(index) == self.selected {
+                Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            Line::styled(limit.to_string(), style)
+        }).collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(chains_event) = self.chains_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(chain) = chains_event {
+                self.toggle_chain(chain);
+            }
+            return Ok(false);
+        }
+        if controls::handle_scoped_event(&mut [&mut self.currency, &mut self.per_transaction, &mut self.daily,
+            &mut self.recipients, &mut self.cooling_off, &mut self.password], &event).is_some() {
+            self.error = None;
+            return Ok(false);
+        }
+
+        if let Event::Key(key_event) = &event {
+            match key_event.code {
+                KeyCode::Up => {
+                    self.select(Some(self.selected.map_or(0, |index| index.saturating_sub(1))));
+                    return Ok(false);
+                },
+                KeyCode::Down => {
+                    self.select(Some(self.selected.map_or(0, |index| index + 1).min(self.policy.limits.len().saturating_sub(1))));
+                    return Ok(false);
+                },
+                _ => {}
+            }
+        }
+
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.unit_button.handle_event(&event) {
+            self.set_unit(if self.unit == LimitUnit::Token { LimitUnit::Usd } else { LimitUnit::Token });
+        } else if let Some(()) = self.set_limit_button.handle_event(&event) {
+            if let Err(err) = self.set_limit() {
+                self.info = None;
+                self.error = Some(err.to_string());
+            }
+        } else if let Some(()) = self.remove_limit_button.handle_event(&event) {
+            self.remove_limit();
+        } else if let Some(()) = self.save_button.handle_event(&event) {
+            if let Err(err) = self.save().await {
+                log::warn!("Failed to save spending policy: {}", err);
+                self.info = None;
+                self.error = Some(err.to_string());
+            }
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        self.save_button.disabled = self.password.value.is_empty();
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(0),                            // Limits
+                Constraint::Length(controls::INPUT_HEIGHT),     // Limit form
+                Constraint::Length(controls::INPUT_HEIGHT),     // Recipients & chains
+                Constraint::Length(controls::INPUT_HEIGHT),     // Cooling-off & password
+                Constraint::Length(1),                          // Info & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        // Limits
+        let limits_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+        let limits_area = limits_block.inner(content_layout[0]);
+        frame.render_widget(limits_block, content_layout[0]);
+        let offset = self.selected.unwrap_or_default().saturating_sub(limits_area.height.saturating_sub(1) as usize);
+        frame.render_widget(Paragraph::new(self.limit_lines()).scroll((offset as u16, 0)), limits_area);
+
+        let limit_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(20),
+                Constraint::Length(9),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Length(13),
+                Constraint::Length(10),
+            ])
+            .split(content_layout[1]);
+        self.currency.render(frame, limit_layout[0]);
+        self.unit_button.render(frame, limit_layout[1]);
+        self.per_transaction.render(frame, limit_layout[2]);
+        self.daily.render(frame, limit_layout[3]);
+        self.set_limit_button.render(frame, limit_layout[4]);
+        self.remove_limit_button.render(frame, limit_layout[5]);
+
+        let recipients_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .split(content_layout[2]);
+        self.recipients.render(frame, recipients_layout[0]);
+
+        let security_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(content_layout[3]);
+        self.cooling_off.render(frame, security_layout[0]);
+        self.password.render(frame, security_layout[1]);
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else if let Some(info) = &self.info {
+            Some(Paragraph::new(info.clone()).style(Style::default().fg(Color::Yellow)))
+        } else if self.policy.is_empty() {
+            Some(Paragraph::new("No restrictions, every transaction is allowed").style(Style::default().fg(Color::Gray)))
+        } else {
+            None
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.alignment(Alignment::Left),
+                content_layout[4].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(70),
+            ])
+            .split(content_layout[5]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.save_button.render(frame, buttons_layout[1]);
+
+        // NOTE: the chains menu should be rendered last to be on top
+        self.chains_button.render(frame, recipients_layout[1]);
+    }
+}
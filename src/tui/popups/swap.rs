@@ -0,0 +1,420 @@
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+use web3::types::{H256, U256};
+
+use crate::core::{
+    amount::Amount, eth_chain::EthChain,
+    swap::{self, SwapContracts, SwapQuote, SwapToken}, transaction::TransactionRequest
+};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Swap";
+const HIGH_PRICE_IMPACT: f64 = 0.05;
+
+// Swap details fixed when the review opens, recorded once it is sent
+struct PendingSwap {
+    token_in: SwapToken,
+    token_out: SwapToken,
+    quote: SwapQuote,
+    amount_out_min: Amount,
+}
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    chain: Option<EthChain>,
+    tokens: Vec<SwapToken>,
+    token_in: Option<SwapToken>,
+    token_out: Option<SwapToken>,
+
+    quote: Option<Result<SwapQuote, String>>,
+    allowance: Option<U256>,
+    pending_swap: Option<PendingSwap>,
+    info: Option<String>,
+    error: Option<String>,
+    review: Option<super::transaction_review::Popup>,
+
+    chain_button: controls::MenuButton<EthChain>,
+    token_in_button: controls::MenuButton<String>,
+    token_out_button: controls::MenuButton<String>,
+    amount: controls::Input,
+    slippage: controls::Input,
+    back_button: controls::Button,
+    refresh_button: controls::Button,
+    send_button: controls::Button,
+}
+
+impl Popup {
+    pub async fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let chain_options = crypto.lock().await.get_active_networks().iter()
+            .filter(|chain| SwapContracts::for_chain(**chain).is_some())
+            .map(|chain| (*chain, chain.get_display_name().to_string()))
+            .collect::<HashMap<_, _>>();
+        let info = chain_options.is_empty()
+            .then(|| "Activate a network with Uniswap deployments to swap".to_string());
+
+        let mut chain_button = controls::MenuButton::new("Chain", Some('c'), chain_options);
+        chain_button.button.disabled = info.is_some();
+        let mut token_in_button = controls::MenuButton::new("From token", Some('f'), HashMap::new());
+        token_in_button.button.disabled = true;
+        let mut token_out_button = controls::MenuButton::new("To token", Some('t'), HashMap::new());
+        token_out_button.button.disabled = true;
+        let amount = controls::Input::new("Enter amount to swap")
+            .with_regex(regex::Regex::new(r"^(0(\.\d*)?|[1-9]\d*(\.\d*)?)?$").unwrap());
+        let mut slippage = controls::Input::new("Slippage, %")
+            .with_regex(regex::Regex::new(r"^\d*(\.\d{0,2})?$").unwrap());
+        slippage.value = swap::DEFAULT_SLIPPAGE.to_string().into();
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let refresh_button = controls::Button::new("Refresh quote", Some('r'));
+        let send_button = controls::Button::new("Review Swap", Some('s')).disable();
+
+        Self {
+            session,
+            crypto,
+            chain: None,
+            tokens: Vec::new(),
+            token_in: None,
+            token_out: None,
+            quote: None,
+            allowance: None,
+            pending_swap: None,
+            info,
+            error: None,
+            review: None,
+            chain_button,
+            token_in_button,
+            token_out_button,
+            amount,
+            slippage,
+            back_button,
+            refresh_button,
+            send_button,
+        }
+    }
+
+    async fn select_chain(&mut self, chain: EthChain) {
+        self.chain = Some(chain);
+        self.chain_button.button.label = chain.get_display_name().to_string();
+        self.tokens = self.crypto.lock().await.get_swap_tokens(chain);
+
+        let options = self.tokens.iter()
+            .map(|token| (token.symbol.clone(), token.symbol.clone()))
+            .collect::<HashMap<_, _>>();
+        self.token_in_button.menu.options = options.clone();
+        self.token_out_button.menu.options = options;
+        self.token_in_button.button.disabled = false;
+        self.token_out_button.button.disabled = false;
+
+        // NOTE: ETH goes first, so it is the default input
+        self.token_in = self.tokens.first().cloned();
+        self.token_out = None;
+        self.update_token_labels();
+        self.reset_quote();
+    }
+
+    fn select_token(&mut self, symbol: &str, is_input: bool) {
+        let token = self.tokens.iter().find(|token| token.symbol == symbol).cloned();
+        if is_input {
+            self.token_in = token;
+        } else {
+            self.token_out = token;
+        }
+        self.update_token_labels();
+        self.reset_quote();
+    }
+
+    fn update_token_labels(&mut self) {
+        self.token_in_button.button.label = match &self.token_in {
+            Some(token) => format!("From {}", token.symbol),
+            None => "From token".to_string(),
+        };
+        self.token_out_button.button.label = match &self.token_out {
+            Some(token) => format!("To {}", token.symbol),
+            None => "To token".to_string(),
+        };
+    }
+
+    fn reset_quote(&mut self) {
+        self.quote = None;
+        self.allowance = None;
+    }
+
+    fn amount_value(&self) -> Option<Amount> {
+        let decimals = self.token_in.as_ref()?.decimals;
+        Amount::parse(&self.amount.value, decimals).ok().filter(|amount| !amount.is_zero())
+    }
+
+    fn slippage_value(&self) -> Option<u32> {
+        swap::parse_slippage(&self.slippage.value).ok()
+    }
+
+    fn ready_quote(&self) -> Option<&SwapQuote> {
+        self.quote.as_ref().and_then(|quote| quote.as_ref().ok())
+    }
+
+    fn needs_approval(&self) -> bool {
+        match (self.ready_quote(), self.allowance) {
+            (Some(quote), Some(allowance)) => allowance < quote.amount_in.raw,
+            _ => false,
+        }
+    }
+
+    async fn fetch_quote(&mut self) {
+        let (Some(chain), Some(token_in), Some(token_out), Some(amount)) =
+            (self.chain, self.token_in.clone(), self.token_out.clone(), self.amount_value()) else {
+            return;
+        };
+        let crypto = self.crypto.lock().await.clone();
+        let quote = crypto.quote_swap(chain, &token_in, &token_out, amount).await;
+        self.allowance = match &quote {
+            Ok(quote) => {
+                let router = SwapContracts::for_chain(chain).and_then(|contracts| contracts.router(quote.protocol).ok());
+                match router {
+                    Some(router) => crypto.get_swap_allowance(self.session.account, chain, &token_in, router).await
+                        .inspect_err(|err| log::error!("Failed to get swap allowance: {:?}", err))
+                        .ok(),
+                    None => None,
+                }
+            },
+            Err(_) => None,
+        };
+        self.quote = Some(quote.map_err(|err| err.to_string()));
+    }
+
+    fn assembly_transaction_request(&mut self) -> anyhow::Result<TransactionRequest> {
+        let chain = self.chain.ok_or_else(|| anyhow::anyhow!("Select the chain"))?;
+        let (Some(token_in), Some(token_out)) = (self.token_in.clone(), self.token_out.clone()) else {
+            return Err(anyhow::anyhow!("Select the tokens to swap"));
+        };
+        let quote = *self.ready_quote().ok_or_else(|| anyhow::anyhow!("No quote for the swap"))?;
+        let contracts = SwapContracts::for_chain(chain).ok_or_else(|| anyhow::anyhow!("Swaps are not available on this chain"))?;
+
+        if self.needs_approval() {
+            self.pending_swap = None;
+            return swap::approval_request(self.session.account, chain, &token_in, contracts.router(quote.protocol)?, quote.amount_in);
+        }
+
+        let slippage = swap::parse_slippage(&self.slippage.value)?;
+        let amount_out_min = quote.min_amount_out(slippage);
+        let deadline = chrono::Utc::now().timestamp() + swap::SWAP_DEADLINE_SECONDS;
+        let request = swap::swap_request(self.session.account, chain, &token_in, &token_out, &quote, amount_out_min, deadline)?;
+        self.pending_swap = Some(PendingSwap { token_in, token_out, quote, amount_out_min });
+        Ok(request)
+    }
+
+    async fn review_transaction(&mut self) -> anyhow::Result<()> {
+        let request = self.assembly_transaction_request()?;
+        self.review = Some(super::transaction_review::Popup::new(self.session.clone(), self.crypto.clone(), request).await);
+        Ok(())
+    }
+
+    async fn on_sent(&mut self, tx_hash: H256) {
+        let Some(pending) = self.pending_swap.take() else {
+            // NOTE: the allowance is read again once the approval is mined and the quote refreshed
+            self.allowance = None;
+            self.info = Some("Approval sent, refresh the quote once it is confirmed".to_string());
+            return;
+        };
+        let crypto = self.crypto.lock().await.clone();
+        match crypto.record_swap(self.session.account, tx_hash, &pending.token_in, &pending.token_out, &pending.quote, pending.amount_out_min) {
+            Ok(()) => self.info = Some(format!("Swap sent, at least {} {} will be received", pending.amount_out_min, pending.token_out.symbol)),
+            Err(err) => {
+                log::error!("Failed to record swap {:?}: {:?}", tx_hash, err);
+                self.error = Some(format!("Failed to record swap: {}", err));
+            }
+        }
+        self.amount.value = String::new().into();
+        self.reset_quote();
+    }
+
+    fn details_lines(&self) -> Vec<Line<'_>> {
+        let yellow = Style::default().fg(Color::Yellow);
+        let red = Style::default().fg(Color::Red);
+        let gray = Style::default().fg(Color::Gray);
+
+        let (Some(token_in), Some(token_out)) = (&self.token_in, &self.token_out) else {
+            return vec![Line::styled("Select the chain and the tokens to swap", gray)];
+        };
+        let quote = match &self.quote {
+            Some(Ok(quote)) => quote,
+            Some(Err(error)) => return vec![Line::styled(format!("No quote: {}", error), red)],
+            None => return vec![Line::styled("Quote: ---", gray)],
+        };
+
+        let mut lines = vec![
+            Line::styled(format!("Quote: {} {} -> {} {}", quote.amount_in, token_in.symbol, quote.amount_out, token_out.symbol), yellow),
+            Line::styled(format!("Route: {}", quote.protocol.get_display_name()), yellow),
+        ];
+        let impact = format!("Price impact: {:.2}%", quote.price_impact * 100.0);
+        lines.push(if quote.price_impact >= HIGH_PRICE_IMPACT {
+            Line::styled(format!("{}, the pool is too small for the amount", impact), red)
+        } else {
+            Line::styled(impact, yellow)
+        });
+        match self.slippage_value() {
+            Some(slippage) => lines.push(Line::styled(format!("Minimum received: {} {} with {}% slippage",
+                quote.min_amount_out(slippage), token_out.symbol, self.slippage.value.trim()), yellow)),
+            None => lines.push(Line::styled("Slippage should be a percent between 0 and 50", red)),
+        }
+        if self.needs_approval() {
+            lines.push(Line::styled(format!("The router needs an approval to spend {} {}", quote.amount_in, token_in.symbol), yellow));
+        }
+        lines
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(review) = &mut self.review {
+            if review.handle_event(event).await? {
+                let sent = review.sent();
+                self.review = None;
+                if let Some(tx_hash) = sent {
+                    self.on_sent(tx_hash).await;
+                }
+            }
+            return Ok(false);
+        }
+
+        if let Some(chain_event) = self.chain_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(chain) = chain_event {
+                self.select_chain(chain).await;
+            }
+            return Ok(false);
+        }
+        if let Some(token_event) = self.token_in_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(symbol) = token_event {
+                self.select_token(&symbol, true);
+            }
+            return Ok(false);
+        }
+        if let Some(token_event) = self.token_out_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(symbol) = token_event {
+                self.select_token(&symbol, false);
+            }
+            return Ok(false);
+        }
+        if controls::handle_scoped_event(&mut [&mut self.amount, &mut self.slippage], &event).is_some() {
+            // NOTE: slippage only changes the minimum received, the quote stays valid
+            if controls::Focusable::is_focused(&self.amount) {
+                self.reset_quote();
+            }
+            self.info = None;
+            self.error = None;
+            return Ok(false);
+        }
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.refresh_button.handle_event(&event) {
+            self.reset_quote();
+            return Ok(false);
+        }
+        if let Some(()) = self.send_button.handle_event(&event) {
+            self.error = self.review_transaction().await.err().map(|err| err.to_string());
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        if let Some(review) = &mut self.review {
+            review.update().await;
+            return;
+        }
+
+        self.amount.color = if self.amount.value.is_empty() || self.amount_value().is_some() { Color::Yellow } else { Color::Red };
+        self.slippage.color = if self.slippage_value().is_some() { Color::Yellow } else { Color::Red };
+
+        if self.quote.is_none() {
+            self.fetch_quote().await;
+        }
+
+        self.send_button.label = match &self.token_in {
+            Some(token) if self.needs_approval() => format!("Approve {}", token.symbol),
+            _ => "Review Swap".to_string(),
+        };
+        self.send_button.disabled = self.ready_quote().is_none() || self.allowance.is_none() || self.slippage_value().is_none();
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(review) = &mut self.review {
+            review.render(frame, area);
+            return;
+        }
+
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Chain & tokens
+                Constraint::Length(controls::INPUT_HEIGHT),     // Amount & slippage
+                Constraint::Fill(0),                            // Details
+                Constraint::Length(1),                          // Info & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let top_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(34), Constraint::Percentage(33), Constraint::Percentage(33)])
+            .split(content_layout[0]);
+
+        let amount_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .split(content_layout[1]);
+        self.amount.render(frame, amount_layout[0]);
+        self.slippage.render(frame, amount_layout[1]);
+
+        let details = Paragraph::new(self.details_lines())
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(details, content_layout[2].inner(Margin { vertical: 1, horizontal: 1 }));
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else {
+            self.info.as_ref().map(|info| Paragraph::new(info.clone()).style(Style::default().fg(Color::Yellow)))
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.alignment(Alignment::Left),
+                content_layout[3].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(25),
+                Constraint::Percentage(35),
+                Constraint::Percentage(40),
+            ])
+            .split(content_layout[4]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.refresh_button.render(frame, buttons_layout[1]);
+        self.send_button.render(frame, buttons_layout[2]);
+
+        // NOTE: the menus should be rendered last to be on top
+        self.token_out_button.render(frame, top_layout[2]);
+        self.token_in_button.render(frame, top_layout[1]);
+        self.chain_button.render(frame, top_layout[0]);
+    }
+}
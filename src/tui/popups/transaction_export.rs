@@ -0,0 +1,121 @@
+use copypasta::{ClipboardContext, ClipboardProvider};
+use qrcode::render::unicode;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame
+};
+
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const QR_CODE_QUIET_ZONE: u16 = 8;
+
+pub struct Popup {
+    title: String,
+    payload: String,
+    path: std::path::PathBuf,
+    back_button: controls::Button,
+    copy_button: controls::Button,
+    copied: bool,
+}
+
+impl Popup {
+    pub fn new(title: &str, payload: String, path: std::path::PathBuf) -> Self {
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let copy_button = controls::Button::new("Copy To Clipboard", Some('c'));
+
+        Self {
+            title: title.to_string(),
+            payload,
+            path,
+            back_button,
+            copy_button,
+            copied: false,
+        }
+    }
+
+    fn generate_qr_code(&self, max_width: u16) -> Option<String> {
+        let qr_code = qrcode::QrCode::new(&self.payload).ok()?;
+        if qr_code.width() as u16 + QR_CODE_QUIET_ZONE > max_width {
+            return None;
+        }
+        Some(qr_code
+            .render::<unicode::Dense1x2>()  // Use dense Unicode characters
+            .dark_color(unicode::Dense1x2::Dark)
+            .light_color(unicode::Dense1x2::Light)
+            .build())
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.copy_button.handle_event(&event) {
+            let mut ctx = ClipboardContext::new().unwrap();
+            ctx.set_contents(self.payload.clone()).unwrap();
+            self.copied = true;
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {}
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(self.title.clone());
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),  // margin
+                Constraint::Length(2),  // Path
+                Constraint::Length(1),  // Copied
+                Constraint::Fill(0),    // QR code
+                Constraint::Length(controls::BUTTON_HEIGHT),
+            ])
+            .split(inner_area);
+
+        let path_paragraph = Paragraph::new(format!("Saved to {}", self.path.display()))
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true });
+        frame.render_widget(path_paragraph, content_layout[1]);
+
+        if self.copied {
+            let copied_paragraph = Paragraph::new("Copied!")
+                .style(Style::default().fg(Color::Yellow))
+                .alignment(Alignment::Center);
+            frame.render_widget(copied_paragraph, content_layout[2]);
+        }
+
+        let qr_code_paragraph = match self.generate_qr_code(content_layout[3].width) {
+            Some(qr_code_string) => Paragraph::new(qr_code_string)
+                .style(Style::default().fg(Color::Yellow)),
+            None => Paragraph::new("Too large for a QR code, use the file instead")
+                .style(Style::default().fg(Color::Gray)),
+        };
+        frame.render_widget(qr_code_paragraph.alignment(Alignment::Center), content_layout[3]);
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(70),
+            ])
+            .split(content_layout[4]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.copy_button.render(frame, buttons_layout[1]);
+    }
+}
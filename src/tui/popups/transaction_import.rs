@@ -0,0 +1,279 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame
+};
+
+use crate::core::{eth_utils, unsigned_transaction::{RawSignedTransaction, UnsignedTransaction}};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const ERR_OTHER_ACCOUNT: &str = "Transaction is sent from another account";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ImportMode {
+    Sign,      // Offline vault signs an exported unsigned transaction
+    Broadcast, // Online machine sends a transaction signed by the vault
+}
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    mode: ImportMode,
+
+    unsigned: Option<UnsignedTransaction>,
+    signed: Option<RawSignedTransaction>,
+    info: Option<String>,
+    error: Option<String>,
+
+    path: controls::Input,
+    load_button: controls::Button,
+    back_button: controls::Button,
+    action_button: controls::Button,
+    review: Option<super::transaction_review::Popup>,
+    export: Option<super::transaction_export::Popup>,
+}
+
+impl Popup {
+    pub fn new(session: Session, crypto: Arc<Mutex<Crypto>>, mode: ImportMode) -> Self {
+        let path = controls::Input::new("Enter transaction file path");
+        let load_button = controls::Button::new("Load", Some('l'));
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let action_button = match mode {
+            ImportMode::Sign => controls::Button::new("Review & Sign", Some('s')),
+            ImportMode::Broadcast => controls::Button::new("Broadcast", Some('s')),
+        }.disable();
+
+        Self {
+            session,
+            crypto,
+            mode,
+            unsigned: None,
+            signed: None,
+            info: None,
+            error: None,
+            path,
+            load_button,
+            back_button,
+            action_button,
+            review: None,
+            export: None,
+        }
+    }
+
+    fn title(&self) -> &str {
+        match self.mode {
+            ImportMode::Sign => "Sign Offline Transaction",
+            ImportMode::Broadcast => "Broadcast Signed Transaction",
+        }
+    }
+
+    fn load(&mut self) -> anyhow::Result<()> {
+        self.unsigned = None;
+        self.signed = None;
+        self.info = None;
+
+        let content = std::fs::read_to_string(self.path.value.as_str())?;
+        let from = match self.mode {
+            ImportMode::Sign => {
+                let transaction: UnsignedTransaction = serde_json::from_str(&content)?;
+                transaction.validate()?;
+                let from = transaction.from;
+                self.unsigned = Some(transaction);
+                from
+            },
+            ImportMode::Broadcast => {
+                let transaction: RawSignedTransaction = serde_json::from_str(&content)?;
+                let from = transaction.from;
+                self.signed = Some(transaction);
+                from
+            },
+        };
+
+        if from != self.session.account {
+            self.unsigned = None;
+            self.signed = None;
+            return Err(anyhow::anyhow!(ERR_OTHER_ACCOUNT));
+        }
+        Ok(())
+    }
+
+    // NOTE: signed from the same review as any other transaction, including its password
+    async fn review_sign(&mut self) {
+        let Some(transaction) = &self.unsigned else {
+            return;
+        };
+        self.review = Some(super::transaction_review::Popup::new_offline(
+            self.session.clone(), self.crypto.clone(), transaction.clone()).await);
+    }
+
+    fn export_signed(&mut self, signed: &RawSignedTransaction) -> anyhow::Result<()> {
+        let payload = serde_json::to_string(signed)?;
+        let path = crate::utils::export_path(&format!("signed_tx_{}_{}.json", signed.chain_id, signed.nonce))?;
+        std::fs::write(&path, &payload)?;
+
+        self.export = Some(super::transaction_export::Popup::new("Signed Transaction", payload, path));
+        Ok(())
+    }
+
+    async fn broadcast(&mut self) -> anyhow::Result<()> {
+        let Some(transaction) = &self.signed else {
+            return Ok(());
+        };
+
+        let result = self.crypto.lock().await.broadcast_signed_transaction(transaction).await?;
+        self.info = Some(format!("Broadcasted {:?}", result.hash));
+        self.signed = None;
+        Ok(())
+    }
+
+    fn summary_lines(&self) -> Vec<String> {
+        if let Some(transaction) = &self.unsigned {
+            return vec![
+                format!("Chain: {} ({})", transaction.chain.get_display_name(), transaction.chain_id),
+                format!("From: {:?}", transaction.from),
+                format!("To: {:?}", transaction.recipient),
+                format!("Amount: {} {}", transaction.amount, transaction.currency),
+                format!("Nonce: {}", transaction.nonce),
+                format!("Gas limit: {}", transaction.gas_limit),
+                format!("Max fee: {:.6} ETH", eth_utils::wei_to_eth(transaction.max_cost())),
+            ];
+        }
+        if let Some(transaction) = &self.signed {
+            return vec![
+                format!("Chain: {} ({})", transaction.chain.get_display_name(), transaction.chain_id),
+                format!("From: {:?}", transaction.from),
+                format!("To: {:?}", transaction.recipient),
+                format!("Amount: {} {}", transaction.amount, transaction.currency),
+                format!("Nonce: {}", transaction.nonce),
+                format!("Hash: {:?}", transaction.hash),
+            ];
+        }
+        Vec::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(review) = &mut self.review {
+            if review.handle_event(event).await? {
+                let signed = review.signed().cloned();
+                self.review = None;
+                if let Some(signed) = signed {
+                    self.error = self.export_signed(&signed).err().map(|err| err.to_string());
+                }
+            }
+            return Ok(false);
+        }
+        if let Some(export) = &mut self.export {
+            if export.handle_event(event).await? {
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+
+        if let Some(input_event) = controls::handle_scoped_event(&mut [&mut self.path], &event) {
+            if let controls::InputEvent::FocusFinished = input_event {
+                self.error = self.load().err().map(|err| err.to_string());
+            }
+            return Ok(false);
+        }
+        if let Some(()) = self.load_button.handle_event(&event) {
+            self.error = self.load().err().map(|err| err.to_string());
+            return Ok(false);
+        }
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.action_button.handle_event(&event) {
+            match self.mode {
+                ImportMode::Sign => self.review_sign().await,
+                ImportMode::Broadcast => self.error = self.broadcast().await.err().map(|err| err.to_string()),
+            }
+            return Ok(false);
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        if let Some(review) = &mut self.review {
+            review.update().await;
+            return;
+        }
+        self.action_button.disabled = self.unsigned.is_none() && self.signed.is_none();
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(review) = &mut self.review {
+            review.render(frame, area);
+            return;
+        }
+        if let Some(export) = &mut self.export {
+            export.render(frame, area);
+            return;
+        }
+
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(self.title().to_string());
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(controls::INPUT_HEIGHT),     // Path
+                Constraint::Fill(0),                            // Summary
+                Constraint::Length(2),                          // Info & errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let path_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(10),
+            ])
+            .split(content_layout[0]);
+
+        self.path.render(frame, path_layout[0]);
+        self.load_button.render(frame, path_layout[1]);
+
+        let margin = Margin { vertical: 1, horizontal: 1 };
+        let summary = Paragraph::new(self.summary_lines().into_iter().map(Line::from).collect::<Vec<_>>())
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Left);
+        frame.render_widget(summary, content_layout[1].inner(margin));
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else {
+            self.info.as_ref().map(|info| Paragraph::new(info.clone()).style(Style::default().fg(Color::Yellow)))
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.wrap(ratatui::widgets::Wrap { trim: true }),
+                content_layout[2].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(70),
+            ])
+            .split(content_layout[3]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.action_button.render(frame, buttons_layout[1]);
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+
+use crate::core::{transaction::TransactionResult, transaction_label::TransactionLabel};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Transaction Label";
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    transaction: TransactionResult,
+    error: Option<String>,
+
+    note: controls::Input,
+    tags: controls::Input,
+    category: controls::Input,
+    back_button: controls::Button,
+    clear_button: controls::Button,
+    save_button: controls::Button,
+}
+
+impl Popup {
+    pub fn new(session: Session, crypto: Arc<Mutex<Crypto>>, transaction: TransactionResult) -> Self {
+        let label = session.db.get_transaction_label(session.account, transaction.hash).unwrap_or_else(|err| {
+            log::error!("Failed to load transaction label: {:?}", err);
+            None
+        }).unwrap_or_default();
+
+        let mut note = controls::Input::new("Note");
+        note.value = label.note.clone().into();
+        let mut tags = controls::Input::new("Tags (comma separated)");
+        tags.value = label.tags_str().into();
+        let mut category = controls::Input::new("Category");
+        category.value = label.category.clone().into();
+
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let clear_button = controls::Button::new("Clear", Some('c')).warning();
+        let save_button = controls::Button::new("Save", Some('s'));
+
+        Self {
+            session,
+            crypto,
+            transaction,
+            error: None,
+            note,
+            tags,
+            category,
+            back_button,
+            clear_button,
+            save_button,
+        }
+    }
+
+    // Returns true when saved and the popup can be closed
+    async fn save(&mut self, label: TransactionLabel) -> bool {
+        let crypto = self.crypto.lock().await;
+        match crypto.save_transaction_label(self.session.account, self.transaction.hash, &label) {
+            Ok(()) => true,
+            Err(err) => {
+                log::error!("Failed to save transaction label: {:?}", err);
+                self.error = Some(err.to_string());
+                false
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if controls::handle_scoped_event(&mut [&mut self.note, &mut self.tags, &mut self.category], &event).is_some() {
+            return Ok(false);
+        }
+
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if let Some(()) = self.clear_button.handle_event(&event) {
+            return Ok(self.save(TransactionLabel::default()).await);
+        }
+        if let Some(()) = self.save_button.handle_event(&event) {
+            let label = TransactionLabel::new(&self.note.value, &self.tags.value, &self.category.value);
+            return Ok(self.save(label).await);
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {}
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(0),                            // Transaction
+                Constraint::Length(controls::INPUT_HEIGHT),     // Note
+                Constraint::Length(controls::INPUT_HEIGHT),     // Tags & category
+                Constraint::Length(1),                          // Errors
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        let transaction = &self.transaction;
+        let summary = Paragraph::new(format!("{} {} on {}\n{:?}",
+            transaction.amount, transaction.currency, transaction.chain.get_display_name(), transaction.hash))
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center);
+        frame.render_widget(summary, content_layout[0].inner(Margin { vertical: 1, horizontal: 1 }));
+
+        self.note.render(frame, content_layout[1]);
+
+        let tags_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(content_layout[2]);
+        self.tags.render(frame, tags_layout[0]);
+        self.category.render(frame, tags_layout[1]);
+
+        if let Some(error) = &self.error {
+            frame.render_widget(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)),
+                content_layout[3].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+                Constraint::Percentage(33),
+            ])
+            .split(content_layout[4]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.clear_button.render(frame, buttons_layout[1]);
+        self.save_button.render(frame, buttons_layout[2]);
+    }
+}
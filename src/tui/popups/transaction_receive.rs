@@ -1,31 +1,59 @@
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::Mutex;
 use copypasta::{ClipboardContext, ClipboardProvider};
 use qrcode::render::unicode;
 use ratatui::{
     crossterm::event::Event,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame
 };
 
+use crate::core::{amount::{Amount, ETH_DECIMALS}, eth_chain::EthChain, payment_uri::PaymentRequest, token::TokenList};
+use crate::service::crypto::Crypto;
 use crate::tui::{widgets::controls, app::AppScreen};
 
 const TITLE: &str = "Receive Crypto";
+const DEFAULT_CURRENCY: &str = "ETH";
 
 pub struct Popup {
     address: web3::types::Address,
+    token_list: TokenList,
+    chain: Option<EthChain>,
+    currency: String,
+    chain_button: controls::MenuButton<EthChain>,
+    currency_button: controls::MenuButton<String>,
+    amount: controls::Input,
     back_button: controls::Button,
     copy_button: controls::Button,
     copied: bool,
 }
 
 impl Popup {
-    pub fn new(address: web3::types::Address) -> Self {
+    pub async fn new(address: web3::types::Address, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let crypto = crypto.lock().await;
+        let chain_options = crypto.get_active_networks().iter()
+            .map(|chain| (*chain, chain.get_display_name().to_string()))
+            .collect();
+        let token_list = crypto.token_list.clone();
+
+        let chain_button = controls::MenuButton::new("Any chain", Some('n'), chain_options);
+        let mut currency_button = controls::MenuButton::new(DEFAULT_CURRENCY, Some('u'), HashMap::new());
+        currency_button.button.disabled = true;
+        let amount = controls::Input::new("Requested amount (optional)")
+            .with_regex(regex::Regex::new(r"^(0(\.\d*)?|[1-9]\d*(\.\d*)?)?$").unwrap());
         let back_button = controls::Button::new("Back", Some('b')).escape();
         let copy_button = controls::Button::new("Copy To Clipboard", Some('c'));
 
         Self {
             address,
+            token_list,
+            chain: None,
+            currency: DEFAULT_CURRENCY.to_string(),
+            chain_button,
+            currency_button,
+            amount,
             back_button,
             copy_button,
             copied: false,
@@ -36,8 +64,60 @@ impl Popup {
         format!("0x{}", hex::encode(self.address.as_bytes()))
     }
 
+    fn set_chain(&mut self, chain: EthChain) {
+        self.chain = Some(chain);
+        self.chain_button.button.label = chain.get_display_name().to_string();
+
+        // NOTE: any listed token could be received, not only the held ones
+        let mut options: HashMap<String, String> = self.token_list.iter()
+            .filter(|token| token.get_chain_data(&chain).is_some())
+            .map(|token| (token.symbol.clone(), token.symbol.clone()))
+            .collect();
+        options.insert(DEFAULT_CURRENCY.to_string(), DEFAULT_CURRENCY.to_string());
+        if !options.contains_key(&self.currency) {
+            self.set_currency(DEFAULT_CURRENCY.to_string());
+        }
+        self.currency_button.menu.options = options;
+        self.currency_button.button.disabled = false;
+    }
+
+    fn set_currency(&mut self, currency: String) {
+        self.currency_button.button.label = currency.clone();
+        self.currency = currency;
+    }
+
+    // Contract address and decimals of the requested currency, None for ETH
+    fn token(&self, chain: EthChain) -> Option<(web3::types::Address, u16)> {
+        self.token_list.iter()
+            .find(|token| token.symbol == self.currency)
+            .and_then(|token| token.get_chain_data(&chain))
+            .map(|data| (data.contract_address, data.decimals))
+    }
+
+    fn decimals(&self) -> u16 {
+        self.chain.and_then(|chain| self.token(chain)).map_or(ETH_DECIMALS, |(_, decimals)| decimals)
+    }
+
+    fn requested_amount(&self) -> Option<Amount> {
+        Amount::parse(&self.amount.value, self.decimals()).ok().filter(|amount| !amount.is_zero())
+    }
+
+    // Bare address unless a chain or an amount is requested, EIP-681 URI otherwise
+    fn payload(&self) -> String {
+        let amount = self.requested_amount();
+        if self.chain.is_none() && amount.is_none() {
+            return self.full_address();
+        }
+        PaymentRequest {
+            recipient: self.address,
+            chain: self.chain,
+            token: self.chain.and_then(|chain| self.token(chain)).map(|(contract_address, _)| contract_address),
+            amount: amount.map(|amount| amount.raw),
+        }.to_string()
+    }
+
     fn generate_qr_code(&self) -> String {
-        let qr_code = qrcode::QrCode::new(self.full_address()).unwrap();
+        let qr_code = qrcode::QrCode::new(self.payload()).unwrap();
         qr_code
             .render::<unicode::Dense1x2>()  // Use dense Unicode characters
             .dark_color(unicode::Dense1x2::Dark)
@@ -49,18 +129,39 @@ impl Popup {
 #[async_trait::async_trait]
 impl AppScreen for Popup {
     async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(chain_event) = self.chain_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(chain) = chain_event {
+                self.set_chain(chain);
+                self.copied = false;
+            }
+            return Ok(false);
+        }
+        if let Some(currency_event) = self.currency_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(currency) = currency_event {
+                self.set_currency(currency);
+                self.copied = false;
+            }
+            return Ok(false);
+        }
+        if controls::handle_scoped_event(&mut [&mut self.amount], &event).is_some() {
+            self.copied = false;
+            return Ok(false);
+        }
         if let Some(()) = self.back_button.handle_event(&event) {
             return Ok(true);
         }
         if let Some(()) = self.copy_button.handle_event(&event) {
             let mut ctx = ClipboardContext::new().unwrap();
-            ctx.set_contents(self.full_address()).unwrap();
+            ctx.set_contents(self.payload()).unwrap();
             self.copied = true;
         }
         Ok(false)
     }
 
-    async fn update(&mut self) {}
+    async fn update(&mut self) {
+        let amount_valid = self.amount.value.is_empty() || self.requested_amount().is_some();
+        self.amount.color = if amount_valid { Color::Yellow } else { Color::Red };
+    }
 
     fn render(&mut self, frame: &mut Frame, area: Rect) {
         frame.render_widget(Clear, area);
@@ -75,31 +176,45 @@ impl AppScreen for Popup {
         let content_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(1),  // margin
-                Constraint::Length(1),  // Address
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Chain & currency
+                Constraint::Length(controls::INPUT_HEIGHT),     // Amount
+                Constraint::Length(2),  // Address or request
                 Constraint::Length(1),  // Copied
                 Constraint::Fill(0),    // QR code
                 Constraint::Length(controls::BUTTON_HEIGHT),
             ])
             .split(inner_area);
 
-        let address_paragraph = Paragraph::new(format!("Address: {}", self.full_address()))
+        let request_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(60),
+                Constraint::Percentage(40),
+            ])
+            .split(content_layout[0]);
+
+        self.amount.render(frame, content_layout[1]);
+
+        let payload = self.payload();
+        let label = if payload == self.full_address() { "Address" } else { "Request" };
+        let address_paragraph = Paragraph::new(format!("{}: {}", label, payload))
             .style(Style::default().fg(Color::Yellow))
-            .alignment(Alignment::Center);
-        frame.render_widget(address_paragraph, content_layout[1]);
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(address_paragraph, content_layout[2]);
 
         if self.copied {
             let copied_paragraph = Paragraph::new("Copied!")
                 .style(Style::default().fg(Color::Yellow))
                 .alignment(Alignment::Center);
-            frame.render_widget(copied_paragraph, content_layout[2]);
+            frame.render_widget(copied_paragraph, content_layout[3]);
         }
 
         let qr_code_string = self.generate_qr_code();
         let qr_code_paragraph = Paragraph::new(qr_code_string)
             .style(Style::default().fg(Color::Yellow))
             .alignment(Alignment::Center);
-        frame.render_widget(qr_code_paragraph, content_layout[3]);
+        frame.render_widget(qr_code_paragraph, content_layout[4]);
 
         let buttons_layout = Layout::default()
             .direction(Direction::Horizontal)
@@ -107,9 +222,13 @@ impl AppScreen for Popup {
                 Constraint::Percentage(30),
                 Constraint::Percentage(70),
             ])
-            .split(content_layout[4]);
+            .split(content_layout[5]);
 
         self.back_button.render(frame, buttons_layout[0]);
         self.copy_button.render(frame, buttons_layout[1]);
+
+        // NOTE: menus are rendered last to be on top
+        self.currency_button.render(frame, request_layout[1]);
+        self.chain_button.render(frame, request_layout[0]);
     }
 }
@@ -0,0 +1,302 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::Event,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame
+};
+use web3::types::{Address, H256};
+
+use crate::core::{
+    eth_chain::EthChain, eth_utils, nonce::NonceGap, review::TransactionReview,
+    smart_account::{SmartAccount, UserOperation},
+    transaction::{TransactionReplacement, TransactionRequest, TransactionResult},
+    unsigned_transaction::{RawSignedTransaction, UnsignedTransaction}
+};
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE: &str = "Review Transaction";
+
+// What signing the reviewed transaction does
+enum ReviewAction {
+    Send,
+    // Offline vault, the caller exports the signed transaction
+    SignOffline,
+    // NOTE: the review shows the call the smart account makes, the operation is what gets signed
+    UserOperation(SmartAccount, Box<UserOperation>),
+    Replace(Box<TransactionResult>, TransactionReplacement),
+    FillGap(NonceGap),
+}
+
+pub struct Popup {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    action: ReviewAction,
+    review: Option<TransactionReview>,
+    password_required: bool,
+    sent: Option<H256>,
+    signed: Option<RawSignedTransaction>,
+    error: Option<String>,
+
+    password: controls::Input,
+    confirm_checkbox: controls::CheckBox,
+    back_button: controls::Button,
+    sign_button: controls::Button,
+}
+
+impl Popup {
+    pub async fn new(session: Session, crypto: Arc<Mutex<Crypto>>, request: TransactionRequest) -> Self {
+        let review = crypto.lock().await.clone().prepare_review(request).await;
+        Self::with_review(session, crypto, review, ReviewAction::Send)
+    }
+
+    pub async fn new_user_operation(session: Session, crypto: Arc<Mutex<Crypto>>, account: SmartAccount, chain: EthChain, operation: UserOperation) -> Self {
+        let review = crypto.lock().await.clone().prepare_user_operation_review(&account, chain, &operation).await;
+        Self::with_review(session, crypto, review, ReviewAction::UserOperation(account, Box::new(operation)))
+    }
+
+    pub async fn new_offline(session: Session, crypto: Arc<Mutex<Crypto>>, transaction: UnsignedTransaction) -> Self {
+        let review = crypto.lock().await.clone().prepare_offline_review(transaction).await;
+        Self::with_review(session, crypto, review, ReviewAction::SignOffline)
+    }
+
+    pub async fn new_replacement(session: Session, crypto: Arc<Mutex<Crypto>>, original: TransactionResult, replacement: TransactionReplacement) -> Self {
+        let review = crypto.lock().await.clone().prepare_replacement_review(&original, replacement).await;
+        Self::with_review(session, crypto, review, ReviewAction::Replace(Box::new(original), replacement))
+    }
+
+    pub async fn new_gap_fill(session: Session, crypto: Arc<Mutex<Crypto>>, gap: NonceGap) -> Self {
+        let review = crypto.lock().await.clone().prepare_gap_fill_review(session.account, gap).await;
+        Self::with_review(session, crypto, review, ReviewAction::FillGap(gap))
+    }
+
+    fn with_review(session: Session, crypto: Arc<Mutex<Crypto>>, review: anyhow::Result<TransactionReview>, action: ReviewAction) -> Self {
+        let password_required = session.db.is_sign_password_required().unwrap_or_else(|err| {
+            log::error!("Failed to load sign settings: {:?}", err);
+            true
+        });
+
+        let (review, error) = match review {
+            Ok(review) => (Some(review), None),
+            Err(err) => {
+                log::warn!("Failed to prepare transaction review: {}", err);
+                (None, Some(err.to_string()))
+            }
+        };
+
+        let password = controls::Input::new("Enter password to sign").masked();
+        let confirm_checkbox = controls::CheckBox::new("I have checked the details above", false, Some('i'));
+        let back_button = controls::Button::new("Back", Some('b')).escape();
+        let sign_label = if matches!(action, ReviewAction::SignOffline) { "Sign" } else { "Sign & Send" };
+        let sign_button = controls::Button::new(sign_label, Some('s')).warning().disable();
+
+        Self {
+            session,
+            crypto,
+            action,
+            review,
+            password_required,
+            sent: None,
+            signed: None,
+            error,
+            password,
+            confirm_checkbox,
+            back_button,
+            sign_button,
+        }
+    }
+
+    // Hash of the sent transaction or user operation, the caller closes its own popup then
+    pub fn sent(&self) -> Option<H256> {
+        self.sent
+    }
+
+    pub fn signed(&self) -> Option<&RawSignedTransaction> {
+        self.signed.as_ref()
+    }
+
+    fn is_done(&self) -> bool {
+        self.sent.is_some() || self.signed.is_some()
+    }
+
+    async fn sign(&mut self) -> anyhow::Result<()> {
+        let Some(review) = &self.review else {
+            return Ok(());
+        };
+        if self.password_required {
+            self.session.verify_password(&self.password.value)?;
+        }
+
+        let secret_key = self.session.get_secret_key()?;
+        let crypto = self.crypto.lock().await.clone();
+        match &self.action {
+            ReviewAction::Send => self.sent = Some(crypto.send_reviewed_transaction(review, &secret_key).await?.hash),
+            ReviewAction::SignOffline => self.signed = Some(crypto.sign_unsigned_transaction(&review.transaction, &secret_key).await?),
+            ReviewAction::UserOperation(account, operation) =>
+                self.sent = Some(crypto.send_user_operation(account, review.transaction.chain, operation, &secret_key).await?),
+            ReviewAction::Replace(original, replacement) =>
+                self.sent = Some(crypto.replace_transaction(original, *replacement, &review.transaction, &secret_key).await?.hash),
+            ReviewAction::FillGap(gap) => self.sent = Some(crypto.fill_nonce_gap(*gap, &review.transaction, &secret_key).await?.hash),
+        }
+        Ok(())
+    }
+
+    fn address_str(address: Address, name: &Option<String>) -> String {
+        match name {
+            Some(name) => format!("{} ({:?})", name, address),
+            None => format!("{:?}", address),
+        }
+    }
+
+    fn usd_str(usd: Option<f64>) -> String {
+        usd.map_or(String::new(), |usd| format!(" ({:.2} USD)", usd))
+    }
+
+    fn review_lines(review: &TransactionReview) -> Vec<Line<'_>> {
+        let yellow = Style::default().fg(Color::Yellow);
+        let bold = yellow.add_modifier(Modifier::BOLD);
+        let transaction = &review.transaction;
+
+        let mut lines = vec![
+            Line::styled(format!("Chain:     {} (chain ID {})", transaction.chain.get_display_name(), transaction.chain_id), yellow),
+            Line::styled(format!("From:      {}", Self::address_str(transaction.from, &review.from_name)), yellow),
+            Line::styled(format!("To:        {}", Self::address_str(transaction.recipient, &review.to_name)), bold),
+            Line::styled(format!("Value:     {} {}{}", transaction.amount, transaction.currency,
+                Self::usd_str(review.amount_usd())), bold),
+        ];
+        if transaction.to != transaction.recipient {
+            lines.push(Line::styled(format!("Contract:  {:?}", transaction.to), yellow));
+        }
+        if let Some(call) = &review.call {
+            lines.push(Line::styled(format!("Function:  {}", call.function), yellow));
+            for arg in &call.args {
+                lines.push(Line::styled(format!("           {}", arg), yellow));
+            }
+        } else if !transaction.data.0.is_empty() {
+            lines.push(Line::styled(format!("Data:      unknown call, {} bytes", transaction.data.0.len()),
+                Style::default().fg(Color::Red)));
+        }
+
+        lines.extend([
+            Line::styled(format!("Nonce:     {}", transaction.nonce), yellow),
+            Line::styled(format!("Gas limit: {}", transaction.gas_limit), yellow),
+            Line::styled(format!("Max fee:   {:.6} ETH{}", eth_utils::wei_to_eth(review.max_fee()),
+                Self::usd_str(review.max_fee_usd())), yellow),
+        ]);
+        if !transaction.l1_data_fee.is_zero() {
+            lines.push(Line::styled(format!("           incl. L1 data fee {:.6} ETH", eth_utils::wei_to_eth(transaction.l1_data_fee)), yellow));
+        }
+        let total = if review.is_native_currency() {
+            format!("Total:     {:.6} ETH{}", eth_utils::wei_to_eth(review.total_eth()), Self::usd_str(review.total_usd()))
+        } else {
+            format!("Total:     {} {} + {:.6} ETH{}", transaction.amount, transaction.currency,
+                eth_utils::wei_to_eth(review.total_eth()), Self::usd_str(review.total_usd()))
+        };
+        lines.push(Line::styled(total, bold));
+        lines
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Popup {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if self.password_required && !self.is_done() {
+            let input_event = controls::handle_scoped_event(&mut [&mut self.password], &event);
+            if input_event.is_some() {
+                self.error = None;
+                return Ok(false);
+            }
+        }
+        if let Some(()) = self.back_button.handle_event(&event) {
+            return Ok(true);
+        }
+        if self.is_done() {
+            return Ok(false);
+        }
+        if self.confirm_checkbox.handle_event(&event).is_some() {
+            return Ok(false);
+        }
+        if let Some(()) = self.sign_button.handle_event(&event) {
+            self.sign_button.disabled = true;
+            if let Err(err) = self.sign().await {
+                log::warn!("Failed to sign reviewed transaction: {}", err);
+                self.error = Some(err.to_string());
+            }
+            return Ok(false);
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        // NOTE: signing needs an explicit confirmation, and the password if the user asked for it
+        self.sign_button.disabled = self.review.is_none() || self.is_done() || !self.confirm_checkbox.toggled ||
+            (self.password_required && self.password.value.is_empty());
+        self.back_button.label = if self.is_done() { "Close".to_string() } else { "Back".to_string() };
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(TITLE);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let password_height = if self.password_required { controls::INPUT_HEIGHT } else { 0 };
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),                            // Details
+                Constraint::Length(2),                          // Status
+                Constraint::Length(controls::CHECKBOX_HEIGHT),  // Confirmation
+                Constraint::Length(password_height),            // Password
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
+            ])
+            .split(inner_area);
+
+        if let Some(review) = &self.review {
+            let details = Paragraph::new(Self::review_lines(review))
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: false });
+            frame.render_widget(details, content_layout[0].inner(Margin { vertical: 1, horizontal: 1 }));
+        }
+
+        let status = if let Some(error) = &self.error {
+            Some(Paragraph::new(error.clone()).style(Style::default().fg(Color::Red)))
+        } else {
+            let done = match self.action {
+                ReviewAction::Send | ReviewAction::FillGap(_) => self.sent.map(|hash| format!("Transaction sent: {:?}", hash)),
+                ReviewAction::Replace(..) => self.sent.map(|hash| format!("Replacement sent: {:?}", hash)),
+                ReviewAction::SignOffline => self.signed.as_ref().map(|signed| format!("Transaction signed: {:?}", signed.hash)),
+                ReviewAction::UserOperation(..) => self.sent.map(|hash| format!("User operation sent: {:?}", hash)),
+            };
+            done.map(|done| Paragraph::new(done).style(Style::default().fg(Color::Yellow)))
+        };
+        if let Some(status) = status {
+            frame.render_widget(status.wrap(Wrap { trim: true }),
+                content_layout[1].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        self.confirm_checkbox.render(frame, content_layout[2]);
+        if self.password_required {
+            self.password.render(frame, content_layout[3]);
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Percentage(70),
+            ])
+            .split(content_layout[4]);
+
+        self.back_button.render(frame, buttons_layout[0]);
+        self.sign_button.render(frame, buttons_layout[1]);
+    }
+}
@@ -1,38 +1,56 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::Mutex;
 use ratatui::{
     crossterm::event::Event,
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Style},
+    text::Line,
     widgets::{Block, Borders, Clear, Paragraph},
     Frame
 };
 
-use crate::core::{eth_chain::EthChain, eth_utils, transaction::{TransactionFees, TransactionRequest}};
+use crate::core::{
+    address_book::{self, Contact}, amount::{Amount, ETH_DECIMALS, USD_DECIMALS}, eth_chain::EthChain, eth_utils, fees::FeeTier,
+    payment_uri::PaymentRequest, transaction::{TransactionFees, TransactionRequest}
+};
 use crate::service::{crypto::Crypto, session::Session};
 use crate::tui::{widgets::controls, app::AppScreen};
 
 const TITLE: &str = "Send Transaction";
+const DEFAULT_CURRENCY: &str = "ETH";
+const CUSTOM_FEE_TIER_INDEX: usize = 3;
+const MAX_CONTACT_OPTIONS: usize = 8;
 
 pub struct Popup {
     session: Session,
     crypto: Arc<Mutex<Crypto>>,
 
     chain: Option<EthChain>,
+    currency: String,
     from: web3::types::Address,
     eth_usd_rate: Option<f64>,
-    amount_value: f64,
+    amount_value: Option<Amount>,
     alt_amount_value: Option<f64>,
     fees: Option<TransactionFees>,
     error: Option<String>,
+    recipient_error: Option<String>,
+    contacts: Vec<Contact>,
+    payment_request: Option<PaymentRequest>,
 
     chain_button: controls::MenuButton<EthChain>,
+    currency_button: controls::MenuButton<String>,
     to: controls::Input,
+    contacts_button: controls::MenuButton<web3::types::Address>,
     amount: controls::Input,
     swap_button: controls::SwapButton,
+    fee_tier_switch: controls::MultiSwitch,
+    priority_fee: controls::Input,
     busy: controls::Busy,
     back_button: controls::Button,
-    send_button: controls::Button
+    export_button: controls::Button,
+    send_button: controls::Button,
+    export: Option<super::transaction_export::Popup>,
+    review: Option<super::transaction_review::Popup>,
 }
 
 impl Popup {
@@ -40,7 +58,7 @@ impl Popup {
         let from = session.account;
         let chain = None;
         let eth_usd_rate = None;
-        let amount_value = 0.0;
+        let amount_value = None;
         let alt_amount_value = None;
         let fees = None;
 
@@ -50,61 +68,232 @@ impl Popup {
         }).collect();
 
         let chain_button = controls::MenuButton::new("Chain", Some('c'), chain_options);
-        let to = controls::Input::new("Enter receiver address")
-            .with_regex(regex::Regex::new(r"^$|^0(x[0-9a-fA-F]*)?$").unwrap());
+        let mut currency_button = controls::MenuButton::new("Currency", Some('u'), HashMap::new());
+        currency_button.button.disabled = true;
+        // NOTE: no regex for the receiver, it could be a contact name or a payment URI as well
+        let to = controls::Input::new("Enter receiver address, contact name or ethereum: URI");
+        let contacts = session.db.get_contacts().unwrap_or_else(|err| {
+            log::error!("Failed to load contacts: {:?}", err);
+            Vec::new()
+        });
+        let contacts_button = controls::MenuButton::new("Contacts", Some('k'), HashMap::new());
         let amount = controls::Input::new("Enter amount ETH to transfer")
             .with_regex(regex::Regex::new(r"^(0(\.\d*)?|[1-9]\d*(\.\d*)?)?$").unwrap());
         let swap_button = controls::SwapButton::new(
             controls::Button::new("ETH", Some('m')),
             controls::Button::new("USD", Some('e'))
         );
+        let mut fee_tier_switch = controls::MultiSwitch::new(vec![
+            controls::Button::new("Slow", Some('l')),
+            controls::Button::new("Normal", Some('n')),
+            controls::Button::new("Fast", Some('f')),
+            controls::Button::new("Custom", Some('t')),
+        ]);
+        fee_tier_switch.set_active(1);
+        let priority_fee = controls::Input::new("Tip, gwei")
+            .with_regex(regex::Regex::new(r"^(0(\.\d*)?|[1-9]\d*(\.\d*)?)?$").unwrap());
         let busy = controls::Busy::new("Loading..");
         let back_button = controls::Button::new("Back", Some('b')).escape();
+        let export_button = controls::Button::new("Export", Some('x'));
         let send_button = controls::Button::new("Sign Transaction", Some('s'));
 
-        Self {
+        let mut popup = Self {
             session,
             crypto,
             chain,
+            currency: DEFAULT_CURRENCY.to_string(),
             from,
             eth_usd_rate,
             amount_value,
             alt_amount_value,
             fees,
             error: None,
+            recipient_error: None,
+            contacts,
+            payment_request: None,
             chain_button,
+            currency_button,
             to,
+            contacts_button,
             amount,
             swap_button,
+            fee_tier_switch,
+            priority_fee,
             busy,
             back_button,
-            send_button
-        }
+            export_button,
+            send_button,
+            export: None,
+            review: None,
+        };
+        popup.update_contact_options();
+        popup
     }
 
     fn assembly_transaction_request(&self) -> Option<TransactionRequest> {
         let chain = self.chain?;
-        let to = eth_utils::str_to_eth_address(&self.to.value).ok()?;
-        if self.amount_value <= 0.0 {
-            return None;
-        }
+        let (to, _) = self.recipient()?;
+        let amount = self.amount_value?;
+
+        let fees = match &self.fees {
+            Some(TransactionFees::Estimated { fees, .. }) => Some(*fees),
+            _ => None,
+        };
 
         Some(TransactionRequest {
-            currency: "ETH".to_string(),
+            currency: self.currency.clone(),
             chain,
             from: self.from,
             to,
-            amount: self.amount_value,
+            amount,
+            fees,
+            data: None,
         })
     }
 
+    // Receiver typed as an address, picked by a contact name or requested by a payment URI
+    fn recipient(&self) -> Option<(web3::types::Address, Option<&Contact>)> {
+        if PaymentRequest::is_payment_uri(&self.to.value) {
+            let address = self.to.value.parse::<PaymentRequest>().ok()?.recipient;
+            return Some((address, self.contacts.iter().find(|contact| contact.address == address)));
+        }
+        match eth_utils::str_to_eth_address(&self.to.value) {
+            Ok(address) => Some((address, self.contacts.iter().find(|contact| contact.address == address))),
+            Err(_) => address_book::resolve(&self.contacts, &self.to.value)
+                .map(|contact| (contact.address, Some(contact))),
+        }
+    }
+
+    fn update_contact_options(&mut self) {
+        let query = if eth_utils::str_to_eth_address(&self.to.value).is_ok() || PaymentRequest::is_payment_uri(&self.to.value) {
+            ""
+        } else {
+            self.to.value.as_str()
+        };
+        self.contacts_button.menu.options = address_book::search(&self.contacts, query).into_iter()
+            .take(MAX_CONTACT_OPTIONS)
+            .map(|contact| (contact.address, contact.name.clone()))
+            .collect();
+        self.contacts_button.button.disabled = self.contacts_button.menu.options.is_empty();
+    }
+
+    fn is_custom_fee_tier(&self) -> bool {
+        self.fee_tier_switch.active_index == CUSTOM_FEE_TIER_INDEX
+    }
+
+    fn fee_tier(&self) -> Option<FeeTier> {
+        match self.fee_tier_switch.active_index {
+            0 => Some(FeeTier::Slow),
+            1 => Some(FeeTier::Normal),
+            2 => Some(FeeTier::Fast),
+            _ => {
+                let wei = eth_utils::gwei_to_wei(&self.priority_fee.value).ok()?;
+                Some(FeeTier::Custom { max_priority_fee_per_gas: wei })
+            }
+        }
+    }
+
+    fn fees_str(&self, wei: web3::types::U256, currency: &str) -> String {
+        let amount = eth_utils::wei_to_eth(wei);
+        match self.eth_usd_rate {
+            Some(eth_usd_rate) => format!("{:.6} {} ({:.2} USD)", amount, currency, amount.to_f64() * eth_usd_rate),
+            None => format!("{:.6} {}", amount, currency),
+        }
+    }
+
+    fn is_native_currency(&self) -> bool {
+        self.currency == DEFAULT_CURRENCY
+    }
+
+    async fn update_currency_options(&mut self, chain: EthChain) {
+        let crypto = self.crypto.lock().await.clone();
+        let currencies = crypto.get_chain_currencies(self.from, chain).await;
+        if !currencies.contains(&self.currency) {
+            self.set_currency(DEFAULT_CURRENCY.to_string());
+        }
+
+        self.currency_button.menu.options = currencies.into_iter()
+            .map(|currency| (currency.clone(), currency))
+            .collect();
+        self.currency_button.button.disabled = false;
+    }
+
+    async fn set_chain(&mut self, chain: EthChain) {
+        self.chain = Some(chain);
+        self.invalidate_amount_and_fees();
+        self.update_currency_options(chain).await;
+
+        // Update USD rate
+        let crypto = self.crypto.lock().await.clone();
+        self.eth_usd_rate = crypto.get_eth_usd_rate(chain).await.ok();
+    }
+
+    // NOTE: a pasted URI arrives key by key, so it's applied again whenever the parsed request changes
+    async fn apply_payment_request(&mut self) -> anyhow::Result<()> {
+        let Ok(request) = self.to.value.parse::<PaymentRequest>() else {
+            return Ok(());
+        };
+        if self.payment_request.as_ref() == Some(&request) {
+            return Ok(());
+        }
+        self.payment_request = Some(request.clone());
+
+        if let Some(chain) = request.chain {
+            if !self.chain_button.menu.options.contains_key(&chain) {
+                return Err(anyhow::anyhow!("{} is not an active network", chain.get_display_name()));
+            }
+            if self.chain != Some(chain) {
+                self.set_chain(chain).await;
+            }
+        }
+
+        let decimals = match request.token {
+            Some(contract_address) => {
+                let chain = self.chain.ok_or_else(|| anyhow::anyhow!("Select chain for the requested token"))?;
+                let token_list = self.crypto.lock().await.token_list.clone();
+                let (symbol, decimals) = token_list.iter().find_map(|token| token.get_chain_data(&chain)
+                        .filter(|data| data.contract_address == contract_address)
+                        .map(|data| (token.symbol.clone(), data.decimals)))
+                    .ok_or_else(|| anyhow::anyhow!("Unknown token {:?} on {}", contract_address, chain.get_display_name()))?;
+                self.set_currency(symbol);
+                decimals
+            },
+            None => {
+                self.set_currency(DEFAULT_CURRENCY.to_string());
+                ETH_DECIMALS
+            },
+        };
+
+        if let Some(amount) = request.amount {
+            if self.swap_button.state {
+                self.swap_button.swap();
+            }
+            self.amount.value = Amount::new(amount, decimals).to_string().into();
+        }
+        Ok(())
+    }
+
+    fn set_currency(&mut self, currency: String) {
+        self.amount.placeholder = format!("Enter amount {} to transfer", currency);
+        self.swap_button.first.label = currency.clone();
+        self.currency = currency;
+
+        // NOTE: USD conversion is only available for the native currency
+        if !self.is_native_currency() && self.swap_button.state {
+            self.swap_button.swap();
+        }
+        self.swap_button.first.disabled = !self.is_native_currency();
+        self.invalidate_amount_and_fees();
+    }
+
     fn invalidate_amount_and_fees(&mut self) {
-        self.amount_value = 0.0;
+        self.amount_value = None;
         self.alt_amount_value = None;
         self.fees = None;
     }
 
-    async fn send_transaction(&mut self) {
+    // NOTE: nothing is signed here, the review popup does it after confirmation
+    async fn review_transaction(&mut self) {
         let transaction_request = match self.assembly_transaction_request() {
             Some(request) => request,
             None => {
@@ -113,44 +302,90 @@ impl Popup {
             }
         };
 
+        self.error = None;
+        self.review = Some(super::transaction_review::Popup::new(
+            self.session.clone(), self.crypto.clone(), transaction_request).await);
+    }
+
+    // Unsigned transaction for the offline vault
+    async fn export_transaction(&mut self) -> anyhow::Result<()> {
+        let transaction_request = self.assembly_transaction_request()
+            .ok_or_else(|| anyhow::anyhow!("Invalid transaction params"))?;
+
         let crypto = self.crypto.lock().await.clone();
-        let secret_key = self.session.get_secret_key().unwrap();
+        let transaction = crypto.prepare_unsigned_transaction(transaction_request).await?;
 
-        match crypto.send_transaction(transaction_request, &secret_key).await {
-            Ok(_) => {
-                // TODO: Transaction result screen
-                self.error = None;
-            },
-            Err(err) => {
-                log::warn!("Failed to send transaction: {}", err);
-                self.error = Some(err.to_string());
-            }
-        }
+        let payload = serde_json::to_string(&transaction)?;
+        let path = crate::utils::export_path(&format!("unsigned_tx_{}_{}.json", transaction.chain_id, transaction.nonce))?;
+        std::fs::write(&path, &payload)?;
+
+        self.export = Some(super::transaction_export::Popup::new("Unsigned Transaction", payload, path));
+        Ok(())
     }
 }
 
 #[async_trait::async_trait]
 impl AppScreen for Popup {
     async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
-        if let Some(_) = controls::handle_scoped_event(&mut[&mut self.to, &mut self.amount], &event) {
+        if let Some(export) = &mut self.export {
+            if export.handle_event(event).await? {
+                self.export = None;
+            }
+            return Ok(false);
+        }
+        if let Some(review) = &mut self.review {
+            if review.handle_event(event).await? {
+                // Close the whole send popup once the transaction is out
+                let sent = review.sent().is_some();
+                self.review = None;
+                return Ok(sent);
+            }
+            return Ok(false);
+        }
+
+        let scoped_event = if self.is_custom_fee_tier() {
+            controls::handle_scoped_event(&mut[&mut self.to, &mut self.amount, &mut self.priority_fee], &event)
+        } else {
+            controls::handle_scoped_event(&mut[&mut self.to, &mut self.amount], &event)
+        };
+        if scoped_event.is_some() {
             self.invalidate_amount_and_fees();
+            self.update_contact_options();
+            if PaymentRequest::is_payment_uri(&self.to.value) {
+                self.error = self.apply_payment_request().await.err().map(|err| err.to_string());
+            }
+            return Ok(false);
+        }
+        if let Some(contacts_event) = self.contacts_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(address) = contacts_event {
+                self.to.value = format!("{:?}", address).into();
+                self.invalidate_amount_and_fees();
+            }
+            return Ok(false);
+        }
+        if self.fee_tier_switch.handle_event(&event).is_some() {
+            self.priority_fee.focused = false;
+            self.fees = None;
             return Ok(false);
         }
         if let Some(chain_event) = self.chain_button.handle_event(&event) {
             if let controls::MenuEvent::Selected(chain) = chain_event {
-                self.chain = Some(chain);
-                self.invalidate_amount_and_fees();
-
-                // Update USD rate
-                let crypto = self.crypto.lock().await.clone();
-                self.eth_usd_rate = crypto.get_eth_usd_rate(chain).await.ok();
+                self.set_chain(chain).await;
+            }
+            return Ok(false);
+        }
+        if let Some(currency_event) = self.currency_button.handle_event(&event) {
+            if let controls::MenuEvent::Selected(currency) = currency_event {
+                self.set_currency(currency);
             }
             return Ok(false);
         }
         if let Some(_) = self.swap_button.handle_event(&event) {
-            if let Some(alt_amount_value) = self.alt_amount_value {
-                self.amount_value = alt_amount_value;
-                self.amount.value = format!("{}", self.amount_value).into();
+            // NOTE: the converted float is cut to the currency decimals, the amount must parse back exactly
+            let decimals = if self.swap_button.state { USD_DECIMALS } else { ETH_DECIMALS };
+            if let Some(alt_amount) = self.alt_amount_value.and_then(|value| Amount::from_f64(value, decimals).ok()) {
+                self.amount.value = alt_amount.to_string().into();
+                self.amount_value = Some(alt_amount);
             }
             self.alt_amount_value = None;
             return Ok(false);
@@ -158,15 +393,26 @@ impl AppScreen for Popup {
         if let Some(()) = self.back_button.handle_event(&event) {
             return Ok(true);
         }
+        if let Some(()) = self.export_button.handle_event(&event) {
+            if let Err(err) = self.export_transaction().await {
+                log::warn!("Failed to export transaction: {}", err);
+                self.error = Some(err.to_string());
+            }
+            return Ok(false);
+        }
         if let Some(()) = self.send_button.handle_event(&event) {
-            self.send_button.disabled = true;
-            self.send_transaction().await;
+            self.review_transaction().await;
             return Ok(false);
         }
         Ok(false)
     }
 
     async fn update(&mut self) {
+        if let Some(review) = &mut self.review {
+            review.update().await;
+            return;
+        }
+
         let mut is_ready = true;
 
         // Validate chain
@@ -180,37 +426,57 @@ impl AppScreen for Popup {
             is_ready &= false;
         }
 
-        // Validate receiver address
-        let to = eth_utils::str_to_eth_address(&self.to.value);
-        let address_valid = to.is_ok();
+        // Currency
+        if self.currency_button.button.disabled {
+            self.currency_button.button.label = "Select chain first".to_string();
+        } else {
+            self.currency_button.button.label = self.currency.clone();
+        }
+
+        // Validate receiver address and the contact's allowed chains
+        let recipient = self.recipient();
+        let recipient_error = match (&recipient, self.chain) {
+            (Some((_, Some(contact))), Some(chain)) if !contact.is_allowed_on(chain) =>
+                Some(format!("{} is not allowed on {}", contact.name, chain.get_display_name())),
+            _ => None,
+        };
+        let address_valid = recipient.is_some() && recipient_error.is_none();
+        self.recipient_error = recipient_error;
         self.to.color = if address_valid || self.to.value.is_empty() { Color::Yellow } else { Color::Red };
         is_ready &= address_valid;
 
         // Validate amount
-        self.amount_value = self.amount.value.parse::<f64>().unwrap_or(0.0);
-        let amount_valid = self.amount_value > 0.0;
+        // NOTE: typed decimals are kept as is, the token decimals are checked when the transaction is built
+        self.amount_value = self.amount.value.parse::<Amount>().ok().filter(|amount| !amount.is_zero());
+        let amount_valid = self.amount_value.is_some();
         self.amount.color = if amount_valid || self.amount.value.is_empty() { Color::Yellow } else { Color::Red };
         is_ready &= amount_valid;
 
+        // Validate custom fee tip
+        let fee_tier = self.fee_tier();
+        self.priority_fee.color = if fee_tier.is_some() || self.priority_fee.value.is_empty() { Color::Yellow } else { Color::Red };
+
         // Calc fees
         if self.fees.is_none() && amount_valid && address_valid {
-            if let Some(transaction_request) = self.assembly_transaction_request() {
-                let crypto = self.crypto.lock().await.clone();
-                self.fees = crypto.estimate_transaction_fees(transaction_request).await.ok();
-            } else {
-                self.fees = None;
+            match (self.assembly_transaction_request(), fee_tier) {
+                (Some(transaction_request), Some(fee_tier)) => {
+                    let crypto = self.crypto.lock().await.clone();
+                    self.fees = Some(crypto.estimate_transaction_fees(transaction_request, fee_tier).await
+                        .unwrap_or_else(|err| TransactionFees::RpcFailure { error: err.to_string() }));
+                },
+                _ => self.fees = None,
             }
         }
-        is_ready &= self.fees.is_some();
+        is_ready &= matches!(self.fees, Some(TransactionFees::Estimated { .. }));
 
         // Calc alt amount
-        if amount_valid && self.alt_amount_value.is_none() {
+        if let (Some(amount), true) = (self.amount_value, self.alt_amount_value.is_none() && self.is_native_currency()) {
             if let Some(eth_usd_rate) = self.eth_usd_rate {
                 // TODO: Wai ot eth, delecgate to service
                 self.alt_amount_value = Some(if self.swap_button.state {
-                     self.amount_value * eth_usd_rate
+                     amount.to_f64() * eth_usd_rate
                 } else {
-                    self.amount_value / eth_usd_rate
+                    amount.to_f64() / eth_usd_rate
                 });
             } else {
                 self.alt_amount_value = None;
@@ -218,9 +484,19 @@ impl AppScreen for Popup {
         }
 
         self.send_button.disabled = !is_ready;
+        self.export_button.disabled = !is_ready;
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(export) = &mut self.export {
+            export.render(frame, area);
+            return;
+        }
+        if let Some(review) = &mut self.review {
+            review.render(frame, area);
+            return;
+        }
+
         frame.render_widget(Clear, area);
 
         let block = Block::default()
@@ -230,15 +506,21 @@ impl AppScreen for Popup {
         let inner_area = block.inner(area);
         frame.render_widget(block, area);
 
+        // NOTE: rollups show the L2 execution and the L1 data fee apart
+        let l1_fee_lines = match &self.fees {
+            Some(TransactionFees::Estimated { fees, .. }) if fees.has_l1_data_fee() => 2,
+            _ => 0,
+        };
         let content_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(controls::BUTTON_HEIGHT),    // Currency
                 Constraint::Length(controls::BUTTON_HEIGHT),    // Chain
+                Constraint::Length(controls::BUTTON_HEIGHT),    // Currency
                 Constraint::Length(controls::BUTTON_HEIGHT),    // From
                 Constraint::Length(controls::BUTTON_HEIGHT),    // To
                 Constraint::Length(controls::INPUT_HEIGHT),     // Amount
-                Constraint::Length(controls::BUTTON_HEIGHT),    // Fees
+                Constraint::Length(controls::SWITCH_HEIGHT),    // Fee tier
+                Constraint::Length(controls::INPUT_HEIGHT + 1 + l1_fee_lines), // Fees
                 Constraint::Fill(controls::BUTTON_HEIGHT),      // Error
                 Constraint::Length(controls::BUTTON_HEIGHT),    // Buttons
             ])
@@ -253,33 +535,29 @@ impl AppScreen for Popup {
 
         let label_margin = Margin { vertical: 1, horizontal: 1 };
 
-        // Currency
-        let currency_layout = Layout::default()
+        // Chain
+        let chain_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(row_constraints)
             .split(content_layout[0]);
 
-        let currency_label = Paragraph::new("Currency")
-            .style(Style::default().fg(Color::Yellow))
-            .alignment(Alignment::Left);
-        frame.render_widget(currency_label, currency_layout[1].inner(label_margin));
-
-        let currency = Paragraph::new("ETH")
+        let chain_label = Paragraph::new("Chain")
             .style(Style::default().fg(Color::Yellow))
             .alignment(Alignment::Left);
-        frame.render_widget(currency, currency_layout[2].inner(label_margin));
+        frame.render_widget(chain_label, chain_layout[1].inner(label_margin));
+        // NOTE: Chain should be rendered last to ensure it's on top
 
-        // Chain
-        let chain_layout = Layout::default()
+        // Currency
+        let currency_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(row_constraints)
             .split(content_layout[1]);
 
-        let chain_label = Paragraph::new("Chain")
+        let currency_label = Paragraph::new("Currency")
             .style(Style::default().fg(Color::Yellow))
             .alignment(Alignment::Left);
-        frame.render_widget(chain_label, chain_layout[1].inner(label_margin));
-        // NOTE: Chain should be rendered last to ensure it's on top
+        frame.render_widget(currency_label, currency_layout[1].inner(label_margin));
+        // NOTE: Currency should be rendered after other rows, but before chain
 
         // From
         let from_layout = Layout::default()
@@ -303,12 +581,20 @@ impl AppScreen for Popup {
             .constraints(row_constraints)
             .split(content_layout[3]);
 
-        let to_label = Paragraph::new("To")
+        let to_label = Paragraph::new(match self.recipient() {
+                Some((_, Some(contact))) => contact.name.clone(),
+                _ => "To".to_string(),
+            })
             .style(Style::default().fg(Color::Yellow))
             .alignment(Alignment::Left);
         frame.render_widget(to_label, to_layout[1].inner(label_margin));
 
-        self.to.render(frame, to_layout[2]);
+        let to_input_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Length(12)])
+            .split(to_layout[2]);
+        self.to.render(frame, to_input_layout[0]);
+        // NOTE: Contacts should be rendered after other rows, but before chain and currency
 
         // Amount
         let amount_layout = Layout::default()
@@ -341,25 +627,65 @@ impl AppScreen for Popup {
                 .style(Style::default().fg(Color::Yellow))
                 .alignment(Alignment::Left);
             frame.render_widget(alt_amount_label, alt_layout);
-        } else if self.amount_value > 0.0 {
+        } else if self.amount_value.is_some() {
             self.busy.render(frame, alt_layout);
         }
 
-        // Fees
-        let fees_layout = Layout::default()
+        // Fee tier
+        let fee_tier_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(row_constraints)
             .split(content_layout[5]);
 
+        let fee_tier_label = Paragraph::new("Speed")
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Left);
+        frame.render_widget(fee_tier_label, fee_tier_layout[1].inner(label_margin));
+
+        self.fee_tier_switch.render(frame, fee_tier_layout[2]);
+
+        // Fees
+        let fees_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(if self.is_custom_fee_tier() {
+                vec![
+                    Constraint::Percentage(2),
+                    Constraint::Percentage(21),     // Label
+                    Constraint::Percentage(20),     // Custom tip input
+                    Constraint::Percentage(55),     // Fees value
+                    Constraint::Percentage(2)]
+            } else {
+                vec![
+                    Constraint::Percentage(2),
+                    Constraint::Percentage(21),     // Label
+                    Constraint::Percentage(0),
+                    Constraint::Percentage(75),     // Fees value
+                    Constraint::Percentage(2)]
+            })
+            .split(content_layout[6]);
+
         let fees_label = Paragraph::new("Fees")
             .style(Style::default().fg(Color::Yellow))
             .alignment(Alignment::Left);
         frame.render_widget(fees_label, fees_layout[1].inner(label_margin));
 
+        if self.is_custom_fee_tier() {
+            let priority_fee_area = Rect { height: controls::INPUT_HEIGHT, ..fees_layout[2] };
+            self.priority_fee.render(frame, priority_fee_area);
+        }
+
         let fees_value = if let Some(fees) = &self.fees {
             match fees {
-                TransactionFees::Estimated { currency, amount } => {
-                    Paragraph::new(format!("{} {}", amount, currency))
+                TransactionFees::Estimated { currency, fees } => {
+                    let mut lines: Vec<Line> = vec![
+                        format!("min {}", self.fees_str(fees.min_cost(), currency)).into(),
+                        format!("max {}", self.fees_str(fees.max_cost(), currency)).into(),
+                    ];
+                    if fees.has_l1_data_fee() {
+                        lines.push(format!("L2 execution fee {}", self.fees_str(fees.l2_min_cost(), currency)).into());
+                        lines.push(format!("L1 data fee {}", self.fees_str(fees.l1_min_cost(), currency)).into());
+                    }
+                    Paragraph::new(lines)
                         .style(Style::default().fg(Color::Yellow))
                         .alignment(Alignment::Left)
                 },
@@ -367,6 +693,18 @@ impl AppScreen for Popup {
                     Paragraph::new(format!("Not enough funds ({})", currency))
                         .style(Style::default().fg(Color::Red))
                         .alignment(Alignment::Left)
+                },
+                TransactionFees::Reverted { reason } => {
+                    Paragraph::new(format!("Transaction reverts: {}", reason))
+                        .style(Style::default().fg(Color::Red))
+                        .alignment(Alignment::Left)
+                        .wrap(ratatui::widgets::Wrap { trim: true })
+                },
+                TransactionFees::RpcFailure { error } => {
+                    Paragraph::new(format!("RPC failure: {}", error))
+                        .style(Style::default().fg(Color::Red))
+                        .alignment(Alignment::Left)
+                        .wrap(ratatui::widgets::Wrap { trim: true })
                 }
             }
         } else {
@@ -374,29 +712,33 @@ impl AppScreen for Popup {
                 .style(Style::default().fg(Color::Gray))
                 .alignment(Alignment::Left)
         };
-        frame.render_widget(fees_value, fees_layout[2].inner(label_margin));
+        frame.render_widget(fees_value, fees_layout[3].inner(Margin { vertical: 1, horizontal: 1 }));
 
         // Error
-        if let Some(error_text) = &self.error {
+        if let Some(error_text) = self.error.as_ref().or(self.recipient_error.as_ref()) {
             let error_label = Paragraph::new(error_text.clone())
                 .style(Style::default().fg(Color::Red))
                 .alignment(Alignment::Left);
-            frame.render_widget(error_label, content_layout[6].inner(label_margin));
+            frame.render_widget(error_label, content_layout[7].inner(label_margin));
         }
 
-        // Chains menu
+        // Contacts, currencies & chains menus
+        self.contacts_button.render(frame, to_input_layout[1]);
+        self.currency_button.render(frame, currency_layout[2]);
         self.chain_button.render(frame, chain_layout[2]);
 
         // Buttons
         let buttons_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
+                Constraint::Percentage(25),
                 Constraint::Percentage(30),
-                Constraint::Percentage(70),
+                Constraint::Percentage(45),
             ])
-            .split(content_layout[7]);
+            .split(content_layout[8]);
 
         self.back_button.render(frame, buttons_layout[0]);
-        self.send_button.render(frame, buttons_layout[1]);
+        self.export_button.render(frame, buttons_layout[1]);
+        self.send_button.render(frame, buttons_layout[2]);
     }
 }
@@ -10,6 +10,8 @@ mod account_import_finalize;
 mod porfolio;
 mod porfolio_accounts;
 mod porfolio_transactions;
+mod porfolio_allowances;
+mod porfolio_nfts;
 mod account_delete;
 mod mnemonic_access;
 mod mnemonic_delete;
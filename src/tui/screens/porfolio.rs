@@ -12,21 +12,43 @@ use crate::tui::{widgets::controls, app::{AppCommand, AppScreen}};
 const POPUP_WIDTH: u16 = 60;
 const POPUP_HEIGHT: u16 = 30;
 
+const TRACKING_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(15);
+
 #[derive(Clone, PartialEq, Eq, Hash)]
 enum ManageOption {
     Networks,
+    SignOffline,
+    BroadcastSigned,
+    BatchPayments,
+    ExportHistory,
+    Bridge,
+    Swap,
+    Safe,
+    SmartAccount,
+    Contracts,
+    AddressBook,
+    SignPassword,
+    SpendingPolicy,
     AccessMnemonic,
     DeleteAccount,
 }
 
 pub trait PorfolioPage: AppScreen {
     fn on_networks_change(&mut self);
+    fn on_transactions_change(&mut self);
+    fn take_popup(&mut self) -> Option<Box<dyn AppScreen + Send>>;
+
+    // While the page edits text, its keys must not trigger the screen hotkeys
+    fn is_editing(&self) -> bool {
+        false
+    }
 }
 
 pub struct Screen {
     command_tx: mpsc::Sender<AppCommand>,
     session: Session,
     crypto: Arc<Mutex<Crypto>>,
+    last_tracking: Option<tokio::time::Instant>,
 
     page_switch: controls::MultiSwitch,
     page: Option<Box<dyn PorfolioPage + Send>>,
@@ -45,11 +67,18 @@ impl Screen {
 
         let mut crypto: Crypto = Crypto::new(session.db.clone(), &endpoint_url);
         crypto.load_active_networks().expect("Failed to load active networks");
+        match crypto.migrate_transactions() {
+            Ok(0) => {},
+            Ok(count) => log::info!("Migrated amounts of {} stored transactions", count),
+            Err(err) => log::error!("Failed to migrate stored transactions: {:?}", err),
+        }
         let crypto = Arc::new(Mutex::new(crypto));
 
         let page_switch = controls::MultiSwitch::new(vec![
             controls::Button::new("Accounts", Some('a')),
             controls::Button::new("Transactions", Some('t')),
+            controls::Button::new("Allowances", Some('l')),
+            controls::Button::new("NFTs", Some('n')),
             controls::Button::new("Charts", Some('c')).disable(),
             controls::Button::new("Settings", Some('s')).disable(),
         ]);
@@ -63,6 +92,18 @@ impl Screen {
             manage_options.insert(ManageOption::AccessMnemonic, "Access mnemonic".to_string());
         }
         manage_options.insert(ManageOption::Networks, "Networks".to_string());
+        manage_options.insert(ManageOption::SignOffline, "Sign offline transaction".to_string());
+        manage_options.insert(ManageOption::BroadcastSigned, "Broadcast signed transaction".to_string());
+        manage_options.insert(ManageOption::BatchPayments, "Batch payments".to_string());
+        manage_options.insert(ManageOption::ExportHistory, "Export history".to_string());
+        manage_options.insert(ManageOption::Bridge, "Bridge".to_string());
+        manage_options.insert(ManageOption::Swap, "Swap".to_string());
+        manage_options.insert(ManageOption::Safe, "Safe multisig".to_string());
+        manage_options.insert(ManageOption::SmartAccount, "Smart account".to_string());
+        manage_options.insert(ManageOption::AddressBook, "Address book".to_string());
+        manage_options.insert(ManageOption::Contracts, "Contracts".to_string());
+        manage_options.insert(ManageOption::SignPassword, sign_password_label(&session));
+        manage_options.insert(ManageOption::SpendingPolicy, "Spending policy".to_string());
         manage_options.insert(ManageOption::DeleteAccount, "Delete Account".to_string());
         let manage_button = controls::MenuButton::new(
             "Manage", Some('m'), manage_options).keep_above();
@@ -74,6 +115,7 @@ impl Screen {
             command_tx,
             session,
             crypto,
+            last_tracking: None,
             page_switch,
             page,
             quit_button,
@@ -89,6 +131,24 @@ impl Screen {
             page.on_networks_change();
         }
     }
+
+    async fn track_transactions(&mut self) {
+        let crypto = self.crypto.lock().await;
+
+        if self.last_tracking.is_none() || self.last_tracking.unwrap().elapsed() > TRACKING_INTERVAL {
+            crypto.track_transactions(self.session.account).await;
+            crypto.sync_history(self.session.account).await;
+            crypto.sync_nfts(self.session.account).await;
+            crypto.track_withdrawals(self.session.account).await;
+            self.last_tracking = Some(tokio::time::Instant::now());
+        }
+
+        if crypto.take_transactions_updated() {
+            if let Some(page) = &mut self.page {
+                page.on_transactions_change();
+            }
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -100,12 +160,21 @@ impl AppScreen for Screen {
                     self.popup = None;
                     // TODO: check if networks have changed indeed
                     self.on_networks_change();
+                    self.manage_button.menu.options.insert(ManageOption::SignPassword, sign_password_label(&self.session));
                     return Ok(true);
                 }
             }
             return Ok(false);
         }
 
+        if let Some(page) = self.page.as_mut().filter(|page| page.is_editing()) {
+            if let Ok(true) = page.handle_event(event).await {
+                self.popup = page.take_popup();
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+
         if let Some(manage_event) = self.manage_button.handle_event(&event) {
             if let controls::MenuEvent::Selected(manage_option) = manage_event {
                 match manage_option {
@@ -113,6 +182,72 @@ impl AppScreen for Screen {
                         self.popup = Some(Box::new(super::super::popups::networks::Popup::new(self.crypto.clone())));
                         return Ok(true);
                     },
+                    ManageOption::SignOffline | ManageOption::BroadcastSigned => {
+                        let mode = if manage_option == ManageOption::SignOffline {
+                            super::super::popups::transaction_import::ImportMode::Sign
+                        } else {
+                            super::super::popups::transaction_import::ImportMode::Broadcast
+                        };
+                        self.popup = Some(Box::new(super::super::popups::transaction_import::Popup::new(
+                            self.session.clone(), self.crypto.clone(), mode)));
+                        return Ok(true);
+                    },
+                    ManageOption::BatchPayments => {
+                        self.popup = Some(Box::new(super::super::popups::batch_send::Popup::new(
+                            self.session.clone(), self.crypto.clone())));
+                        return Ok(true);
+                    },
+                    ManageOption::ExportHistory => {
+                        let popup = super::super::popups::history_export::Popup::new(self.session.clone(), self.crypto.clone()).await;
+                        self.popup = Some(Box::new(popup));
+                        return Ok(true);
+                    },
+                    ManageOption::Bridge => {
+                        let popup = super::super::popups::bridge::Popup::new(self.session.clone(), self.crypto.clone()).await;
+                        self.popup = Some(Box::new(popup));
+                        return Ok(true);
+                    },
+                    ManageOption::Swap => {
+                        let popup = super::super::popups::swap::Popup::new(self.session.clone(), self.crypto.clone()).await;
+                        self.popup = Some(Box::new(popup));
+                        return Ok(true);
+                    },
+                    ManageOption::Safe => {
+                        let popup = super::super::popups::safe::Popup::new(self.session.clone(), self.crypto.clone()).await;
+                        self.popup = Some(Box::new(popup));
+                        return Ok(true);
+                    },
+                    ManageOption::SmartAccount => {
+                        let popup = super::super::popups::smart_account::Popup::new(self.session.clone(), self.crypto.clone()).await;
+                        self.popup = Some(Box::new(popup));
+                        return Ok(true);
+                    },
+                    ManageOption::Contracts => {
+                        let popup = super::super::popups::contract_call::Popup::new(self.session.clone(), self.crypto.clone()).await;
+                        self.popup = Some(Box::new(popup));
+                        return Ok(true);
+                    },
+                    ManageOption::SignPassword => {
+                        let required = self.session.db.is_sign_password_required().unwrap_or(true);
+                        if required {
+                            self.popup = Some(Box::new(super::super::popups::sign_password::Popup::new(self.session.clone())));
+                            return Ok(true);
+                        }
+                        if let Err(err) = self.session.db.save_sign_password_required(true) {
+                            log::error!("Failed to save sign settings: {:?}", err);
+                        }
+                        self.manage_button.menu.options.insert(ManageOption::SignPassword, sign_password_label(&self.session));
+                        return Ok(true);
+                    },
+                    ManageOption::SpendingPolicy => {
+                        let popup = super::super::popups::spending_policy::Popup::new(self.session.clone(), self.crypto.clone()).await;
+                        self.popup = Some(Box::new(popup));
+                        return Ok(true);
+                    },
+                    ManageOption::AddressBook => {
+                        self.popup = Some(Box::new(super::super::popups::address_book::Popup::new(self.session.clone())));
+                        return Ok(true);
+                    },
                     ManageOption::AccessMnemonic => {
                         self.command_tx.send(AppCommand::SwitchScreen(Box::new(
                             super::mnemonic_access::Screen::new(self.command_tx.clone(), self.session.clone())
@@ -141,7 +276,17 @@ impl AppScreen for Screen {
                 },
                 1 => {
                     self.page = Some(Box::new(
-                        super::porfolio_transactions::Page::new(self.session.clone())
+                        super::porfolio_transactions::Page::new(self.session.clone(), self.crypto.clone())
+                    ));
+                },
+                2 => {
+                    self.page = Some(Box::new(
+                        super::porfolio_allowances::Page::new(self.session.clone(), self.crypto.clone())
+                    ));
+                },
+                3 => {
+                    self.page = Some(Box::new(
+                        super::porfolio_nfts::Page::new(self.session.clone(), self.crypto.clone())
                     ));
                 },
                 _ => {} // TODO: other pages
@@ -152,13 +297,15 @@ impl AppScreen for Screen {
         if let Some(page) = &mut self.page {
             if let Ok(ok) = page.handle_event(event.clone()).await {
                 if ok {
+                    self.popup = page.take_popup();
                     return Ok(true);
                 }
             }
         }
 
         if let Some(()) = self.receive_button.handle_event(&event) {
-            self.popup = Some(Box::new(super::super::popups::transaction_receive::Popup::new(self.session.account)));
+            let popup = super::super::popups::transaction_receive::Popup::new(self.session.account, self.crypto.clone()).await;
+            self.popup = Some(Box::new(popup));
             return Ok(true);
         }
 
@@ -181,6 +328,8 @@ impl AppScreen for Screen {
             popup.update().await;
         }
 
+        self.track_transactions().await;
+
         if let Some(page) = &mut self.page {
             page.update().await;
         }
@@ -231,3 +380,8 @@ impl AppScreen for Screen {
         }
     }
 }
+
+fn sign_password_label(session: &Session) -> String {
+    let required = session.db.is_sign_password_required().unwrap_or(true);
+    format!("Ask password to sign: {}", if required { "on" } else { "off" })
+}
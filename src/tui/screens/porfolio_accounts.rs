@@ -12,11 +12,13 @@ use crate::tui::{widgets::{controls, account}, app::AppScreen};
 
 const SUMMARY_HEIGHT: u16 = 2;
 const SUMMARY_TEXT: &str = "Summary balance";
+const SMART_ACCOUNT_NAME: &str = "Smart Account";
 
 const UPDATE_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(10);
 
 pub struct Page {
     crypto: Arc<Mutex<Crypto>>,
+    owner: web3::types::Address,
     last_update: Option<tokio::time::Instant>,
 
     accounts: Vec<account::AccountDisplay>,
@@ -32,6 +34,7 @@ impl Page {
 
         Self {
             crypto,
+            owner: session.account,
             last_update: None,
             accounts,
             busy,
@@ -73,6 +76,21 @@ impl AppScreen for Page {
 
     async fn update(&mut self) {
         let crypto = self.crypto.lock().await;
+        // Smart accounts owned by the vault key are shown after it
+        // NOTE: only loaded on a full refresh, creating an account resets the page through the transactions change
+        if self.last_update.is_none() {
+            let smart_accounts = crypto.get_smart_accounts(self.owner).unwrap_or_else(|err| {
+                log::error!("Failed to load smart accounts: {:?}", err);
+                Vec::new()
+            });
+            for smart_account in smart_accounts {
+                if self.accounts.iter().all(|account| account.address != smart_account.address) {
+                    let mut account = account::AccountDisplay::new(smart_account.address);
+                    account.name = SMART_ACCOUNT_NAME.to_string();
+                    self.accounts.push(account);
+                }
+            }
+        }
 
         for account in &mut self.accounts {
             account.balances = crypto.get_balances(account.address).await;
@@ -134,4 +152,12 @@ impl super::porfolio::PorfolioPage for Page {
     fn on_networks_change(&mut self) {
         self.last_update = None;
     }
+
+    fn on_transactions_change(&mut self) {
+        self.last_update = None;
+    }
+
+    fn take_popup(&mut self) -> Option<Box<dyn AppScreen + Send>> {
+        None
+    }
 }
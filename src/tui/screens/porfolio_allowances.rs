@@ -0,0 +1,214 @@
+use std::sync::Arc;
+use tokio::{sync::Mutex, task::JoinHandle};
+use ratatui::{
+    crossterm::event::{Event, KeyCode},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph, Frame
+};
+
+use crate::core::allowance::Allowance;
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE_HEIGHT: u16 = 2;
+const STATUS_HEIGHT: u16 = 1;
+
+const TITLE_TEXT: &str = "Token Allowances";
+
+pub struct Page {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    update: bool,
+    fetching: Option<JoinHandle<anyhow::Result<Vec<Allowance>>>>,
+    allowances: Vec<Allowance>,
+    selected: Option<usize>,
+    error: Option<String>,
+    popup: Option<Box<dyn AppScreen + Send>>,
+
+    busy: controls::Busy,
+    refresh_button: controls::Button,
+    revoke_button: controls::Button,
+}
+
+impl Page {
+    pub fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let busy = controls::Busy::new("Loading..");
+        let refresh_button = controls::Button::new("Refresh", Some('f'));
+        let revoke_button = controls::Button::new("Revoke", Some('v')).warning().disable();
+
+        Self {
+            session,
+            crypto,
+            update: true,
+            fetching: None,
+            allowances: Vec::new(),
+            selected: None,
+            error: None,
+            popup: None,
+            busy,
+            refresh_button,
+            revoke_button,
+        }
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|index| *index < self.allowances.len());
+        self.revoke_button.disabled = self.selected.is_none();
+    }
+
+    // Revoke goes through the review popup like any other transaction
+    async fn revoke_selected(&mut self) -> anyhow::Result<()> {
+        let Some(allowance) = self.selected.map(|index| self.allowances[index].clone()) else {
+            return Ok(());
+        };
+
+        let request = allowance.revoke_request(self.session.account)?;
+        self.popup = Some(Box::new(crate::tui::popups::transaction_review::Popup::new(
+            self.session.clone(), self.crypto.clone(), request).await));
+        Ok(())
+    }
+
+    fn allowance_line(&self, index: usize, allowance: &Allowance) -> Line<'_> {
+        let style = if Some(index) == self.selected {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+        let amount_style = if allowance.is_unlimited() {
+            style.fg(if Some(index) == self.selected { Color::Black } else { Color::Red }).add_modifier(Modifier::BOLD)
+        } else {
+            style
+        };
+
+        Line::from(vec![
+            Span::styled(format!("{} on {}: {:?} can spend ",
+                allowance.currency, allowance.chain.get_display_name(), allowance.spender), style),
+            Span::styled(allowance.amount_str(), amount_style),
+        ])
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Page {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(()) = self.refresh_button.handle_event(&event) {
+            self.update = true;
+            return Ok(true);
+        }
+        if let Some(()) = self.revoke_button.handle_event(&event) {
+            if let Err(err) = self.revoke_selected().await {
+                log::error!("Failed to revoke allowance: {:?}", err);
+                self.error = Some(err.to_string());
+            }
+            return Ok(true);
+        }
+
+        if let Event::Key(key_event) = &event {
+            match key_event.code {
+                KeyCode::Up => {
+                    self.select(Some(self.selected.map_or(0, |index| index.saturating_sub(1))));
+                    return Ok(true);
+                },
+                KeyCode::Down => {
+                    self.select(Some(self.selected.map_or(0, |index| index + 1).min(self.allowances.len().saturating_sub(1))));
+                    return Ok(true);
+                },
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        if self.update && self.fetching.is_none() {
+            // NOTE: scanning approval logs takes a while, so it runs in the background
+            let crypto = self.crypto.lock().await.clone();
+            let account = self.session.account;
+            self.fetching = Some(tokio::spawn(async move { crypto.fetch_allowances(account).await }));
+            self.update = false;
+        }
+
+        if !self.fetching.as_ref().is_some_and(|fetching| fetching.is_finished()) {
+            return;
+        }
+        let Some(fetching) = self.fetching.take() else {
+            return;
+        };
+        match fetching.await {
+            Ok(Ok(allowances)) => {
+                self.allowances = allowances;
+                self.error = None;
+            },
+            Ok(Err(err)) => self.error = Some(err.to_string()),
+            Err(err) => self.error = Some(err.to_string()),
+        }
+        self.select(self.selected);
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(TITLE_HEIGHT),
+                Constraint::Fill(0),    // Fill height for allowances
+                Constraint::Length(STATUS_HEIGHT),
+                Constraint::Length(controls::BUTTON_HEIGHT),
+            ])
+            .split(area);
+
+        let title = Paragraph::new(TITLE_TEXT)
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        frame.render_widget(title, content_layout[0]);
+
+        let list_area = content_layout[1].inner(Margin { vertical: 0, horizontal: 1 });
+        if self.fetching.is_some() {
+            self.busy.render(frame, Rect { height: 1, ..list_area });
+        } else {
+            let lines = if self.allowances.is_empty() {
+                vec![Line::styled("No active allowances", Style::default().fg(Color::Gray))]
+            } else {
+                self.allowances.iter().enumerate()
+                    .map(|(index, allowance)| self.allowance_line(index, allowance))
+                    .collect()
+            };
+            let offset = self.selected.unwrap_or_default().saturating_sub(list_area.height.saturating_sub(1) as usize);
+            frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), list_area);
+        }
+
+        if let Some(error_text) = &self.error {
+            let error_label = Paragraph::new(error_text.clone())
+                .style(Style::default().fg(Color::Red))
+                .alignment(Alignment::Left);
+            frame.render_widget(error_label, content_layout[2].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(14),
+                Constraint::Length(14),
+            ])
+            .split(content_layout[3]);
+
+        self.refresh_button.render(frame, buttons_layout[1]);
+        self.revoke_button.render(frame, buttons_layout[2]);
+    }
+}
+
+impl super::porfolio::PorfolioPage for Page {
+    fn on_networks_change(&mut self) {
+        self.update = true;
+    }
+
+    fn on_transactions_change(&mut self) {
+        self.update = true;
+    }
+
+    fn take_popup(&mut self) -> Option<Box<dyn AppScreen + Send>> {
+        self.popup.take()
+    }
+}
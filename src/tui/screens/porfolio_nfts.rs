@@ -0,0 +1,174 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ratatui::{
+    crossterm::event::{Event, KeyCode},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::Paragraph, Frame
+};
+
+use crate::core::nft::NftHolding;
+use crate::service::{crypto::Crypto, session::Session};
+use crate::tui::{widgets::controls, app::AppScreen};
+
+const TITLE_HEIGHT: u16 = 2;
+const STATUS_HEIGHT: u16 = 1;
+
+const TITLE_TEXT: &str = "NFTs";
+
+pub struct Page {
+    session: Session,
+    crypto: Arc<Mutex<Crypto>>,
+    update: bool,
+    holdings: Vec<NftHolding>,
+    selected: Option<usize>,
+    error: Option<String>,
+    popup: Option<Box<dyn AppScreen + Send>>,
+
+    send_button: controls::Button,
+}
+
+impl Page {
+    pub fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
+        let send_button = controls::Button::new("Send", Some('e')).disable();
+
+        Self {
+            session,
+            crypto,
+            update: true,
+            holdings: Vec::new(),
+            selected: None,
+            error: None,
+            popup: None,
+            send_button,
+        }
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|index| *index < self.holdings.len());
+        self.send_button.disabled = self.selected.is_none();
+    }
+
+    fn holding_lines(&self, index: usize, holding: &NftHolding) -> Vec<Line<'_>> {
+        let style = if Some(index) == self.selected {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+
+        let amount = if holding.amount > 1.into() { format!(" x{}", holding.amount) } else { String::new() };
+        vec![
+            Line::styled(format!("{} #{}{} ({} on {})", holding.collection_str(), holding.token_id, amount,
+                holding.standard.get_display_name(), holding.chain.get_display_name()), style),
+            Line::styled(format!("  {}", holding.metadata_uri.as_deref().unwrap_or("No metadata")),
+                Style::default().fg(Color::Gray)),
+        ]
+    }
+}
+
+#[async_trait::async_trait]
+impl AppScreen for Page {
+    async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(()) = self.send_button.handle_event(&event) {
+            if let Some(holding) = self.selected.map(|index| self.holdings[index].clone()) {
+                self.popup = Some(Box::new(crate::tui::popups::nft_send::Popup::new(
+                    self.session.clone(), self.crypto.clone(), holding)));
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+
+        if let Event::Key(key_event) = &event {
+            match key_event.code {
+                KeyCode::Up => {
+                    self.select(Some(self.selected.map_or(0, |index| index.saturating_sub(1))));
+                    return Ok(true);
+                },
+                KeyCode::Down => {
+                    self.select(Some(self.selected.map_or(0, |index| index + 1).min(self.holdings.len().saturating_sub(1))));
+                    return Ok(true);
+                },
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    async fn update(&mut self) {
+        if !self.update {
+            return;
+        }
+        self.update = false;
+
+        let crypto = self.crypto.lock().await.clone();
+        match crypto.get_nfts(self.session.account) {
+            Ok(holdings) => {
+                self.holdings = holdings;
+                self.error = None;
+            },
+            Err(err) => self.error = Some(err.to_string()),
+        }
+        self.select(self.selected);
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let content_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(TITLE_HEIGHT),
+                Constraint::Fill(0),    // Fill height for holdings
+                Constraint::Length(STATUS_HEIGHT),
+                Constraint::Length(controls::BUTTON_HEIGHT),
+            ])
+            .split(area);
+
+        let title = Paragraph::new(TITLE_TEXT)
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center);
+        frame.render_widget(title, content_layout[0]);
+
+        let list_area = content_layout[1].inner(Margin { vertical: 0, horizontal: 1 });
+        let lines = if self.holdings.is_empty() {
+            vec![Line::styled("No NFTs found yet", Style::default().fg(Color::Gray))]
+        } else {
+            self.holdings.iter().enumerate()
+                .flat_map(|(index, holding)| self.holding_lines(index, holding))
+                .collect()
+        };
+        // NOTE: two lines per holding
+        let offset = (self.selected.unwrap_or_default() * 2 + 1).saturating_sub(list_area.height.saturating_sub(1) as usize);
+        frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), list_area);
+
+        if let Some(error_text) = &self.error {
+            let error_label = Paragraph::new(error_text.clone())
+                .style(Style::default().fg(Color::Red))
+                .alignment(Alignment::Left);
+            frame.render_widget(error_label, content_layout[2].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(14),
+            ])
+            .split(content_layout[3]);
+
+        self.send_button.render(frame, buttons_layout[1]);
+    }
+}
+
+impl super::porfolio::PorfolioPage for Page {
+    fn on_networks_change(&mut self) {
+        self.update = true;
+    }
+
+    fn on_transactions_change(&mut self) {
+        self.update = true;
+    }
+
+    fn take_popup(&mut self) -> Option<Box<dyn AppScreen + Send>> {
+        self.popup.take()
+    }
+}
@@ -1,46 +1,179 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
 use ratatui::{
-    crossterm::event::Event,
+    crossterm::event::{Event, KeyCode, MouseButton, MouseEventKind},
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Style},
     widgets::Paragraph, Frame
 };
 
-use crate::service::session::Session;
+use crate::core::{nonce::NonceGap, transaction::TransactionReplacement, transaction_label};
+use crate::service::{crypto::Crypto, session::Session};
 use crate::tui::{widgets::{controls, transaction}, app::AppScreen};
 
 const TITLE_HEIGHT: u16 = 2;
+const STATUS_HEIGHT: u16 = 1;
 const TRANSACTIONAS_PER_PAGE: usize = 10;
 
 const TITLE_TEXT: &str = "Transactions";
+const FILTER_PLACEHOLDER: &str = "Filter (f) by note, #tag, category, token or address";
+const NO_MATCHES_TEXT: &str = "No transactions match the filter";
 
 pub struct Page {
     session: Session,
+    crypto: Arc<Mutex<Crypto>>,
     update: bool,
     cursor: usize,
+    selected: Option<usize>,
+    gaps: Vec<NonceGap>,
+    withdrawal_actions: Vec<String>,
 
     transactions: Vec<transaction::TransactionDisplay>,
     scroll: controls::Scroll,
+    filter: controls::Input,
+    label_button: controls::Button,
+    speed_up_button: controls::Button,
+    cancel_button: controls::Button,
+    fill_gap_button: controls::Button,
+    add_contact_button: controls::Button,
+    popup: Option<Box<dyn AppScreen + Send>>,
 }
 
 impl Page {
-    pub fn new(session: Session) -> Self {
+    pub fn new(session: Session, crypto: Arc<Mutex<Crypto>>) -> Self {
         let transactions = Vec::new();
 
         let scroll = controls::Scroll::new();
+        let filter = controls::Input::new(FILTER_PLACEHOLDER);
+        let label_button = controls::Button::new("Edit label", Some('e')).disable();
+        let speed_up_button = controls::Button::new("Speed up", Some('p')).disable();
+        let cancel_button = controls::Button::new("Cancel", Some('x')).warning().disable();
+        let fill_gap_button = controls::Button::new("Fill gap", Some('g')).disable();
+        let add_contact_button = controls::Button::new("Add contact", Some('k')).disable();
 
         Self {
             session,
+            crypto,
             update: true,
             cursor: 0,
+            selected: None,
+            gaps: Vec::new(),
+            withdrawal_actions: Vec::new(),
             transactions,
-            scroll
+            scroll,
+            filter,
+            label_button,
+            speed_up_button,
+            cancel_button,
+            fill_gap_button,
+            add_contact_button,
+            popup: None,
         }
     }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|index| *index < self.transactions.len());
+        for (i, transaction) in self.transactions.iter_mut().enumerate() {
+            transaction.selected = Some(i) == self.selected;
+        }
+
+        let replaceable = self.selected
+            .map(|index| &self.transactions[index])
+            .is_some_and(|tx| tx.is_outgoing() && tx.transaction().is_replaceable());
+        self.speed_up_button.disabled = !replaceable;
+        self.cancel_button.disabled = !replaceable;
+        self.add_contact_button.disabled = self.selected_counterparty().is_none();
+        self.label_button.disabled = self.selected.is_none();
+    }
+
+    fn selected_counterparty(&self) -> Option<web3::types::Address> {
+        let tx = &self.transactions[self.selected?];
+        if tx.is_outgoing() { tx.transaction().to } else { tx.transaction().from }
+    }
+
+    // NOTE: replacements are signed like any other transaction, after the review
+    async fn replace_selected(&mut self, replacement: TransactionReplacement) {
+        let Some(index) = self.selected else {
+            return;
+        };
+        let original = self.transactions[index].transaction().clone();
+        self.popup = Some(Box::new(crate::tui::popups::transaction_review::Popup::new_replacement(
+            self.session.clone(), self.crypto.clone(), original, replacement).await));
+    }
+
+    async fn fill_first_gap(&mut self) {
+        let Some(gap) = self.gaps.first().copied() else {
+            return;
+        };
+        self.popup = Some(Box::new(crate::tui::popups::transaction_review::Popup::new_gap_fill(
+            self.session.clone(), self.crypto.clone(), gap).await));
+    }
 }
 
 #[async_trait::async_trait]
 impl AppScreen for Page {
     async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+        if let Some(input_event) = controls::handle_scoped_event(&mut [&mut self.filter], &event) {
+            if let controls::InputEvent::Input(_) = input_event {
+                self.select(None);
+                self.update = true;
+            }
+            return Ok(false);
+        }
+
+        if let Some(()) = self.label_button.handle_event(&event) {
+            if let Some(index) = self.selected {
+                self.popup = Some(Box::new(crate::tui::popups::transaction_label::Popup::new(
+                    self.session.clone(), self.crypto.clone(), self.transactions[index].transaction().clone())));
+            }
+            return Ok(true);
+        }
+        if let Some(()) = self.speed_up_button.handle_event(&event) {
+            self.replace_selected(TransactionReplacement::SpeedUp).await;
+            return Ok(true);
+        }
+        if let Some(()) = self.cancel_button.handle_event(&event) {
+            self.replace_selected(TransactionReplacement::Cancel).await;
+            return Ok(true);
+        }
+        if let Some(()) = self.fill_gap_button.handle_event(&event) {
+            self.fill_first_gap().await;
+            return Ok(true);
+        }
+        if let Some(()) = self.add_contact_button.handle_event(&event) {
+            if let Some(address) = self.selected_counterparty() {
+                self.popup = Some(Box::new(crate::tui::popups::address_book::Popup::new(
+                    self.session.clone()).with_address(address)));
+            }
+            return Ok(true);
+        }
+
+        match &event {
+            Event::Key(key_event) => match key_event.code {
+                KeyCode::Up => {
+                    self.select(Some(self.selected.map_or(0, |index| index.saturating_sub(1))));
+                    return Ok(true);
+                },
+                KeyCode::Down => {
+                    self.select(Some(self.selected.map_or(0, |index| index + 1).min(self.transactions.len().saturating_sub(1))));
+                    return Ok(true);
+                },
+                KeyCode::Char('f') => {
+                    controls::Focusable::set_focused(&mut self.filter, true);
+                    return Ok(false);
+                },
+                _ => {}
+            },
+            Event::Mouse(mouse_event) if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(index) = self.transactions.iter()
+                    .position(|tx| tx.contains(mouse_event.column, mouse_event.row)) {
+                    self.select(Some(index));
+                    return Ok(true);
+                }
+            },
+            _ => {}
+        }
+
         self.scroll.handle_event(&event);
         Ok(false)
     }
@@ -50,17 +183,48 @@ impl AppScreen for Page {
             return;
         }
 
-        let transactions = self.session.db.get_transactions(self.session.account, self.cursor, TRANSACTIONAS_PER_PAGE)
+        let account = self.session.account;
+        let labels = self.session.db.get_transaction_labels(account).unwrap_or_else(|err| {
+            log::error!("Failed to fetch transaction labels: {:?}", err);
+            Default::default()
+        });
+
+        // NOTE: filtered history is searched as a whole, then shown as a single page
+        let query = self.filter.value.trim().to_string();
+        let (cursor, count) = if query.is_empty() { (self.cursor, TRANSACTIONAS_PER_PAGE) } else { (0, usize::MAX) };
+        let transactions = self.session.db.get_transactions(account, cursor, count)
             .unwrap_or_else(|err| {
                 log::error!("Failed to fetch transactions: {:?}", err);
                 Vec::new() // Empty transactions on error
             }
         );
 
-        self.transactions = transactions.into_iter().map(|tx| {
-            transaction::TransactionDisplay::new(tx, transaction::TransactionDisplayType::Incoming)
+        self.transactions = transactions.into_iter()
+            .filter(|tx| transaction_label::matches(tx, labels.get(&tx.hash), &query))
+            .take(TRANSACTIONAS_PER_PAGE)
+            .map(|tx| {
+            let transaction_type = if tx.swap.is_some() {
+                transaction::TransactionDisplayType::Swap
+            } else if tx.from == Some(account) {
+                transaction::TransactionDisplayType::Outgoing
+            } else {
+                transaction::TransactionDisplayType::Incoming
+            };
+            let label = labels.get(&tx.hash).cloned();
+            transaction::TransactionDisplay::new(tx, transaction_type).with_label(label)
         }).collect();
 
+        self.gaps = self.crypto.lock().await.get_nonce_gaps(account).await;
+        self.fill_gap_button.disabled = self.gaps.is_empty();
+
+        let withdrawals = self.session.db.get_withdrawals(account).unwrap_or_else(|err| {
+            log::error!("Failed to fetch withdrawals: {:?}", err);
+            Vec::new()
+        });
+        self.withdrawal_actions = withdrawals.iter().filter_map(|withdrawal| withdrawal.action_str()).collect();
+
+        // NOTE: keep selection on the same position after reload
+        self.select(self.selected);
         self.update = false;
     }
 
@@ -69,7 +233,10 @@ impl AppScreen for Page {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(TITLE_HEIGHT),
+                Constraint::Length(controls::INPUT_HEIGHT),
                 Constraint::Fill(0),    // Fill height for trasnactions
+                Constraint::Length(STATUS_HEIGHT),
+                Constraint::Length(controls::BUTTON_HEIGHT),
             ])
             .split(area);
 
@@ -78,10 +245,12 @@ impl AppScreen for Page {
             .alignment(Alignment::Center);
         frame.render_widget(title, content_layout[0]);
 
+        self.filter.render(frame, content_layout[1].inner(Margin { vertical: 0, horizontal: 1 }));
+
         let transactions_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints(self.transactions.iter().map(|_| Constraint::Fill(1)).collect::<Vec<_>>().as_slice())
-        .split(content_layout[1].inner(Margin {
+        .constraints(self.transactions.iter().map(|tx| Constraint::Length(tx.implicit_height() as u16)).collect::<Vec<_>>().as_slice())
+        .split(content_layout[2].inner(Margin {
             vertical: 0,
             horizontal: 1,
         }));
@@ -95,7 +264,36 @@ impl AppScreen for Page {
         }
 
         self.scroll.total = total_content_height;
-        self.scroll.render(frame, content_layout[1]);
+        self.scroll.render(frame, content_layout[2]);
+
+        let status_label = self.gaps.first().map(|gap| Paragraph::new(format!(
+            "Nonce {} is missing on {}, later transactions are stuck", gap.nonce, gap.chain.get_display_name())))
+            .or_else(|| self.withdrawal_actions.first().map(|action| Paragraph::new(action.clone())))
+            .or_else(|| (self.transactions.is_empty() && !self.filter.value.trim().is_empty())
+                .then(|| Paragraph::new(NO_MATCHES_TEXT)))
+            .map(|label| label.style(Style::default().fg(Color::Yellow)));
+        if let Some(status_label) = status_label {
+            frame.render_widget(status_label.alignment(Alignment::Left),
+                content_layout[3].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
+
+        let buttons_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(16),
+                Constraint::Length(16),
+                Constraint::Length(14),
+                Constraint::Length(14),
+                Constraint::Length(14),
+            ])
+            .split(content_layout[4]);
+
+        self.label_button.render(frame, buttons_layout[1]);
+        self.add_contact_button.render(frame, buttons_layout[2]);
+        self.fill_gap_button.render(frame, buttons_layout[3]);
+        self.speed_up_button.render(frame, buttons_layout[4]);
+        self.cancel_button.render(frame, buttons_layout[5]);
     }
 }
 
@@ -103,4 +301,16 @@ impl super::porfolio::PorfolioPage for Page {
     fn on_networks_change(&mut self) {
         self.update = true;
     }
+
+    fn on_transactions_change(&mut self) {
+        self.update = true;
+    }
+
+    fn take_popup(&mut self) -> Option<Box<dyn AppScreen + Send>> {
+        self.popup.take()
+    }
+
+    fn is_editing(&self) -> bool {
+        controls::Focusable::is_focused(&self.filter)
+    }
 }
@@ -1,13 +1,15 @@
 use ratatui::{
-    layout::{Alignment, Margin, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Position, Rect},
     style::{Color, Style},
-    widgets::Paragraph,
+    symbols,
+    widgets::{Block, Borders, Paragraph},
     Frame
 };
 
-use crate::core::transaction::TransactionResult;
+use crate::core::{transaction::{TransactionResult, TransactionStatus}, transaction_label::TransactionLabel};
 
 const TRANSACTION_HEIGHT: usize = 3;
+const LABEL_HEIGHT: usize = 1;
 
 pub enum TransactionDisplayType {
     Incoming,
@@ -16,43 +18,126 @@ pub enum TransactionDisplayType {
 }
 
 pub struct TransactionDisplay {
+    pub selected: bool,
     transaction: TransactionResult,
     transaction_type: TransactionDisplayType,
+    label: Option<TransactionLabel>,
+    area: Rect,
 }
 
 impl TransactionDisplay {
     pub fn new(transaction: TransactionResult, transaction_type: TransactionDisplayType) -> Self {
         Self {
+            selected: false,
             transaction,
             transaction_type,
+            label: None,
+            area: Rect::default(),
         }
     }
 
+    pub fn with_label(mut self, label: Option<TransactionLabel>) -> Self {
+        self.label = label;
+        self
+    }
+
+    pub fn transaction(&self) -> &TransactionResult {
+        &self.transaction
+    }
+
+    pub fn is_outgoing(&self) -> bool {
+        matches!(self.transaction_type, TransactionDisplayType::Outgoing | TransactionDisplayType::Swap)
+    }
+
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        self.area.contains(Position { x: column, y: row })
+    }
+
     pub fn implicit_height(&self) -> usize {
-        TRANSACTION_HEIGHT
+        if self.label.is_some() { TRANSACTION_HEIGHT + LABEL_HEIGHT } else { TRANSACTION_HEIGHT }
     }
 
     pub fn get_transaction_str(&self) -> String {
         let amount = self.transaction.amount;
-        let currency = "ETH"; // TODO: different blockchains & tokens
+        let currency = &self.transaction.currency;
         let from = self.transaction.from.unwrap_or_default();
         let to = self.transaction.to.unwrap_or_default();
 
         match self.transaction_type {
-            TransactionDisplayType::Incoming => 
+            TransactionDisplayType::Incoming =>
                 format!("↓ Received {} {} from {}", amount, currency, from),
-            TransactionDisplayType::Outgoing => 
+            TransactionDisplayType::Outgoing =>
                 format!("↑ Sent {} {} to {}", amount, currency, to),
-            TransactionDisplayType::Swap => 
-                format!("↕ Swap {} {} from {} to {}", amount, currency, from, to),
+            TransactionDisplayType::Swap => match &self.transaction.swap {
+                Some(swap) => format!("↕ Swapped {} {} for {} {} on {}", amount, currency,
+                    swap.amount_out, swap.currency_out, swap.protocol.get_display_name()),
+                None => format!("↕ Swap {} {} from {} to {}", amount, currency, from, to),
+            },
+        }
+    }
+
+    pub fn get_status_str(&self) -> String {
+        match self.transaction.status {
+            TransactionStatus::Pending => "Pending".to_string(),
+            TransactionStatus::Successed => format!("Confirmed ({})", self.transaction.confirmations),
+            TransactionStatus::Failed => format!("Failed ({})", self.transaction.confirmations),
+            TransactionStatus::Dropped => "Dropped".to_string(),
+            TransactionStatus::Replaced => "Replaced".to_string(),
+        }
+    }
+
+    fn get_status_color(&self) -> Color {
+        match self.transaction.status {
+            TransactionStatus::Pending => Color::Yellow,
+            TransactionStatus::Successed => Color::Green,
+            TransactionStatus::Failed | TransactionStatus::Dropped => Color::Red,
+            TransactionStatus::Replaced => Color::Gray,
         }
     }
 
     pub fn render(&mut self, frame: &mut Frame, area: Rect) {
-        let inner = area.inner(Margin { vertical: 1, horizontal: 1 });
+        self.area = area; // Store the transaction's area for mouse handling
+
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(self.transaction.chain.get_display_name().to_string());
+        if self.selected {
+            block = block.border_set(symbols::border::THICK);
+        }
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
 
-        let paragraph = Paragraph::new(self.get_transaction_str())
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Fill(1)])
+            .split(inner_area);
+
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Fill(3),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ])
+            .split(rows[0]);
+
+        let transaction_label = Paragraph::new(self.get_transaction_str())
             .style(Style::default().fg(Color::Yellow))
             .alignment(Alignment::Left);
+        frame.render_widget(transaction_label, layout[1]);
+
+        let status_label = Paragraph::new(self.get_status_str())
+            .style(Style::default().fg(self.get_status_color()))
+            .alignment(Alignment::Right);
+        frame.render_widget(status_label, layout[2]);
+
+        if let Some(label) = &self.label {
+            let label_text = Paragraph::new(label.summary())
+                .style(Style::default().fg(Color::Gray))
+                .alignment(Alignment::Left);
+            frame.render_widget(label_text, rows[1].inner(Margin { vertical: 0, horizontal: 1 }));
+        }
     }
 }
@@ -16,3 +16,15 @@ pub fn app_data_path() -> std::io::Result<std::path::PathBuf> {
         }
     }
 }
+
+// Where exported files go, so the user can easily find and move them
+pub fn export_path(file_name: &str) -> std::io::Result<std::path::PathBuf> {
+    let dir = if cfg!(test) {
+        std::env::temp_dir()
+    } else if let Some(user_dirs) = directories::UserDirs::new() {
+        user_dirs.download_dir().unwrap_or(user_dirs.home_dir()).to_path_buf()
+    } else {
+        app_data_path()?
+    };
+    Ok(dir.join(file_name))
+}